@@ -0,0 +1,16 @@
+-- 第3波：伏击（启用lua特性时代替默认的生成方式）
+-- spawn(name, count) 按敌人定义名称（或carrier）从编队生成敌人
+-- wait(secs) 等待，banner(text) 显示横幅，music(path) 切换背景音乐（nil停止）
+banner("AMBUSH")
+music("music_void.wav")
+
+for i = 1, 3 do
+  spawn("Scout", 2)
+  wait(1.5)
+end
+
+wait(2)
+banner("GUNSHIPS")
+spawn("Gunner", 2)
+wait(4)
+spawn("carrier")
@@ -0,0 +1,260 @@
+use crate::AppState;
+use crate::wave::{Wave, WavePhase};
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+
+// 手柄摇杆的死区（摇杆偏移小于该值时视为居中）
+const STICK_DEAD_ZONE: f32 = 0.2;
+
+/// 玩家动作 - 玩法和菜单只读取动作，不关心由哪种设备触发
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    Fire,              // 开火
+    Bomb,              // 引爆炸弹
+    Dash,              // 冲刺
+    Secondary,         // 发射副武器
+    Slot(u8),          // 数字槽位1~4（切换武器，商店开放时购买商品）
+    Pause,             // 暂停/离开
+    MenuConfirm,       // 确认（开始游戏、商店中开始下一波）
+    PracticeSpawn(u8), // 练习模式生成槽位0~5（普通敌人、精英、复合敌人、母舰、编队、首领）
+    PracticeClear,     // 练习模式清除全部敌人
+    ToggleAutoFire,    // 循环切换连发模式
+    ToggleAssist,      // 开关辅助模式
+    ToggleGhost,       // 开关幽灵回放
+}
+
+/// 动作状态资源 - 本帧的移动方向和各动作的按下状态，由各设备的适配系统汇总而成
+#[derive(Resource, Clone, Default)]
+pub struct ActionState {
+    pub move_axis: Vec2,          // 移动方向（长度不超过1）
+    buttons: ButtonInput<Action>, // 各动作的按下状态
+    held: Vec<Action>,            // 本帧各设备按住的动作
+    tapped: Vec<Action>,          // 本帧各设备按下后又松开的动作
+}
+
+impl ActionState {
+    /// 动作是否按住
+    pub fn pressed(&self, action: Action) -> bool {
+        self.buttons.pressed(action)
+    }
+
+    /// 动作是否刚刚按下
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.buttons.just_pressed(action)
+    }
+
+    /// 设备适配：按住动作
+    pub fn hold(&mut self, action: Action) {
+        self.held.push(action);
+    }
+
+    /// 设备适配：本帧按下又松开了动作（按下仍会被读到）
+    pub fn tap(&mut self, action: Action) {
+        self.tapped.push(action);
+    }
+
+    /// 设备适配：叠加移动方向
+    pub fn push_axis(&mut self, axis: Vec2) {
+        self.move_axis += axis;
+    }
+
+    /// 开始新的一帧：清空刚按下/刚松开的状态和各设备的输入
+    fn begin(&mut self) {
+        self.buttons.clear();
+        self.held.clear();
+        self.tapped.clear();
+        self.move_axis = Vec2::ZERO;
+    }
+
+    /// 结束一帧：按各设备的输入更新动作状态，限制移动方向的长度
+    fn finish(&mut self) {
+        for &action in &self.held {
+            self.buttons.press(action);
+        }
+        for &action in &self.tapped {
+            if !self.held.contains(&action) {
+                self.buttons.press(action);
+                self.buttons.release(action);
+            }
+        }
+        let released: Vec<Action> = self
+            .buttons
+            .get_pressed()
+            .filter(|action| !self.held.contains(action))
+            .copied()
+            .collect();
+        for action in released {
+            self.buttons.release(action);
+        }
+        self.move_axis = self.move_axis.clamp_length_max(1.);
+    }
+
+    /// 并入一帧的动作状态：补上这一帧按下的动作，松开已不再按住的动作，移动方向取最新的一帧
+    pub fn merge(&mut self, frame: &ActionState) {
+        for &action in frame.buttons.get_just_pressed() {
+            self.buttons.press(action);
+        }
+        let released: Vec<Action> = self
+            .buttons
+            .get_pressed()
+            .filter(|action| !frame.pressed(**action))
+            .copied()
+            .collect();
+        for action in released {
+            self.buttons.release(action);
+        }
+        self.move_axis = frame.move_axis;
+    }
+
+    /// 清除刚按下/刚松开的状态，只保留按住的动作
+    pub fn clear_just(&mut self) {
+        self.buttons.clear();
+    }
+
+    /// 屏蔽本帧的全部动作和移动方向（按住的动作在屏蔽结束后重新按下）
+    pub fn suppress(&mut self) {
+        self.buttons.reset_all();
+        self.move_axis = Vec2::ZERO;
+    }
+}
+
+/// 动作系统集合 - 设备适配系统在这里把设备输入转换为动作，改写设备输入的系统（如聊天输入）需排在它之前
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ActionSystem;
+
+/// 设备适配系统集合 - 在动作状态开始新的一帧之后、汇总之前运行，其他设备（如触屏）的适配系统也加入这里
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ActionAdapters;
+
+/// 自动驾驶适配系统集合 - 在各设备适配之后、汇总之前运行，可以读到本帧玩家已有的输入（辅助模式在玩家移动时让出控制）
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AutopilotAdapters;
+
+/// 动作过滤系统集合 - 在汇总之后运行，按界面状态屏蔽动作（如聊天输入期间），对全部设备和自动驾驶同样生效
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ActionFilters;
+
+/// 输入动作插件 - 每帧由键盘、手柄等设备的适配系统汇总为动作状态，玩法系统只读取动作
+pub struct ActionPlugin;
+
+impl Plugin for ActionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActionState>()
+            .configure_sets(
+                PreUpdate,
+                (
+                    ActionSystem.after(InputSystem),
+                    ActionAdapters.in_set(ActionSystem),
+                    AutopilotAdapters.in_set(ActionSystem).after(ActionAdapters),
+                    ActionFilters
+                        .in_set(ActionSystem)
+                        .after(action_finish_system),
+                ),
+            )
+            // 读取设备输入之后依次：开始新的一帧 → 各设备适配 → 自动驾驶 → 汇总 → 过滤
+            .add_systems(
+                PreUpdate,
+                (
+                    action_begin_system.before(ActionAdapters),
+                    (keyboard_action_system, gamepad_action_system).in_set(ActionAdapters),
+                    action_finish_system.after(AutopilotAdapters),
+                )
+                    .in_set(ActionSystem),
+            );
+    }
+}
+
+/// 动作开始系统
+fn action_begin_system(mut actions: ResMut<ActionState>) {
+    actions.begin();
+}
+
+/// 动作汇总系统
+fn action_finish_system(mut actions: ResMut<ActionState>) {
+    actions.finish();
+}
+
+/// 键盘适配系统 - 方向键移动，空格开火，B炸弹，左Shift冲刺，左Ctrl副武器，1~4数字槽位，Esc暂停，回车确认，
+/// 5~0练习生成，退格键练习清场，F/A/G切换连发、辅助模式和幽灵回放
+fn keyboard_action_system(kb: Res<ButtonInput<KeyCode>>, mut actions: ResMut<ActionState>) {
+    const BINDINGS: [(KeyCode, Action); 20] = [
+        (KeyCode::Space, Action::Fire),
+        (KeyCode::KeyB, Action::Bomb),
+        (KeyCode::ShiftLeft, Action::Dash),
+        (KeyCode::ControlLeft, Action::Secondary),
+        (KeyCode::Digit1, Action::Slot(1)),
+        (KeyCode::Digit2, Action::Slot(2)),
+        (KeyCode::Digit3, Action::Slot(3)),
+        (KeyCode::Digit4, Action::Slot(4)),
+        (KeyCode::Escape, Action::Pause),
+        (KeyCode::Enter, Action::MenuConfirm),
+        (KeyCode::Digit5, Action::PracticeSpawn(0)),
+        (KeyCode::Digit6, Action::PracticeSpawn(1)),
+        (KeyCode::Digit7, Action::PracticeSpawn(2)),
+        (KeyCode::Digit8, Action::PracticeSpawn(3)),
+        (KeyCode::Digit9, Action::PracticeSpawn(4)),
+        (KeyCode::Digit0, Action::PracticeSpawn(5)),
+        (KeyCode::Backspace, Action::PracticeClear),
+        (KeyCode::KeyF, Action::ToggleAutoFire),
+        (KeyCode::KeyA, Action::ToggleAssist),
+        (KeyCode::KeyG, Action::ToggleGhost),
+    ];
+    for (key, action) in BINDINGS {
+        if kb.pressed(key) {
+            actions.hold(action);
+        } else if kb.just_pressed(key) {
+            actions.tap(action);
+        }
+    }
+
+    let axis =
+        |negative: KeyCode, positive: KeyCode| match (kb.pressed(negative), kb.pressed(positive)) {
+            (true, false) => -1.,
+            (false, true) => 1.,
+            _ => 0.,
+        };
+    actions.push_axis(Vec2::new(
+        axis(KeyCode::ArrowLeft, KeyCode::ArrowRight),
+        axis(KeyCode::ArrowDown, KeyCode::ArrowUp),
+    ));
+}
+
+/// 手柄适配系统 - 左摇杆和十字键移动，A开火（菜单和商店中为确认），B炸弹，X冲刺，右扳机副武器，Start暂停，Y练习清场，Select切换连发模式
+fn gamepad_action_system(
+    gamepads: Query<&Gamepad>,
+    state: Res<State<AppState>>,
+    wave: Option<Res<Wave>>,
+    mut actions: ResMut<ActionState>,
+) {
+    // A键只在对局中（商店开放时除外）开火，在菜单和商店中确认，不会在开始下一波时同时开火
+    let shop = wave.is_some_and(|wave| wave.phase == WavePhase::Shop);
+    let south = if *state.get() == AppState::Playing && !shop {
+        Action::Fire
+    } else {
+        Action::MenuConfirm
+    };
+    let bindings: [(GamepadButton, Action); 7] = [
+        (GamepadButton::South, south),
+        (GamepadButton::East, Action::Bomb),
+        (GamepadButton::West, Action::Dash),
+        (GamepadButton::RightTrigger2, Action::Secondary),
+        (GamepadButton::Start, Action::Pause),
+        (GamepadButton::North, Action::PracticeClear),
+        (GamepadButton::Select, Action::ToggleAutoFire),
+    ];
+    for gamepad in &gamepads {
+        for (button, action) in bindings {
+            if gamepad.pressed(button) {
+                actions.hold(action);
+            } else if gamepad.just_pressed(button) {
+                actions.tap(action);
+            }
+        }
+
+        let stick = gamepad.left_stick();
+        if stick.length() > STICK_DEAD_ZONE {
+            actions.push_axis(stick);
+        }
+        actions.push_axis(gamepad.dpad());
+    }
+}
@@ -0,0 +1,82 @@
+use crate::AppState;
+use crate::registry::{AssetRegistry, Tex, asset_fallback_system};
+use bevy::image::TextureAtlasBuilder;
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+// 图集中相邻精灵之间的间隔（像素），避免线性采样时相互渗色
+const ATLAS_PADDING: u32 = 2;
+
+/// 图集插件 - 加载阶段把资源注册表中的玩家、敌人、激光和拾取物精灵打包进同一张图集，打包完成后进入主菜单
+pub struct AtlasPlugin;
+
+impl Plugin for AtlasPlugin {
+    fn build(&self, app: &mut App) {
+        // 加载阶段等待源图加载完成后打包图集
+        app.add_systems(
+            Update,
+            atlas_build_system
+                .after(asset_fallback_system)
+                .run_if(in_state(AppState::Loading)),
+        );
+    }
+}
+
+/// 图集打包系统 - 源图全部加载（或加载失败）后打包成一张图集，记录各精灵的索引并进入主菜单
+fn atlas_build_system(
+    asset_server: Res<AssetServer>,
+    mut images: ResMut<Assets<Image>>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut registry: ResMut<AssetRegistry>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let pending = registry.packed_textures().any(|(_, handle)| {
+        !images.contains(handle) && !asset_server.load_state(handle).is_failed()
+    });
+    if pending {
+        return;
+    }
+
+    if let Some((layout, atlas, atlas_index)) = pack_atlas(&registry, &images) {
+        let atlas = images.add(atlas);
+        let layout = layouts.add(layout);
+        registry.set_atlas(atlas, layout, atlas_index);
+    }
+    next_state.set(AppState::MainMenu);
+}
+
+/// 把资源注册表中需要打包的精灵打包成一张图集，返回图集布局、图集纹理和各精灵的索引
+/// 缺失的精灵共用占位纹理，占位纹理只打包一次
+pub fn pack_atlas(
+    registry: &AssetRegistry,
+    images: &Assets<Image>,
+) -> Option<(TextureAtlasLayout, Image, HashMap<Tex, usize>)> {
+    let mut builder = TextureAtlasBuilder::default();
+    builder.padding(UVec2::splat(ATLAS_PADDING));
+    let mut added = HashSet::new();
+    for (key, handle) in registry.packed_textures() {
+        if !added.insert(handle.id()) {
+            continue;
+        }
+        match images.get(handle) {
+            Some(image) => {
+                builder.add_texture(Some(handle.id()), image);
+            }
+            None => warn!("精灵图加载失败，未打包进图集: {key:?}"),
+        }
+    }
+    let (layout, sources, atlas) = match builder.build() {
+        Ok(result) => result,
+        Err(e) => {
+            warn!("打包精灵图集失败: {e}");
+            return None;
+        }
+    };
+
+    // 记录各精灵在图集中的索引（加载失败的精灵没有索引）
+    let atlas_index = registry
+        .packed_textures()
+        .filter_map(|(key, handle)| sources.texture_index(handle).map(|index| (key, index)))
+        .collect();
+    Some((layout, atlas, atlas_index))
+}
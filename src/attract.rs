@@ -0,0 +1,172 @@
+use crate::config::{GameConfig, LevelScript};
+use crate::game_mode::{GameMode, GameModePlugin};
+use crate::run::GameOverEvent;
+use crate::{AppState, GameSet};
+use bevy::ecs::system::SystemParam;
+use bevy::input::gamepad::GamepadButtonChangedEvent;
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input::mouse::MouseButtonInput;
+use bevy::prelude::*;
+
+// 主菜单无操作多久后开始演示（秒）
+const ATTRACT_IDLE_SECS: f32 = 15.;
+// 一次演示的时长，结束后回到主菜单重新计时（秒）
+const ATTRACT_DEMO_SECS: f32 = 60.;
+
+/// 演示计时资源 - 主菜单的无操作时间和演示已进行的时间
+#[derive(Resource, Default)]
+struct AttractTimer {
+    idle: f32, // 主菜单无操作的时间（秒）
+    demo: f32, // 演示已进行的时间（秒）
+}
+
+/// 演示标题标记
+#[derive(Component)]
+struct AttractTitle;
+
+/// 玩家输入参数 - 读取真实设备的按键事件（不受自动驾驶改写的按键状态影响）
+#[derive(SystemParam)]
+struct PlayerInput<'w, 's> {
+    keys: EventReader<'w, 's, KeyboardInput>,
+    mouse: EventReader<'w, 's, MouseButtonInput>,
+    gamepad: EventReader<'w, 's, GamepadButtonChangedEvent>,
+}
+
+impl PlayerInput<'_, '_> {
+    /// 本帧是否有任意按键、鼠标或手柄按钮被按下
+    fn any_pressed(&mut self) -> bool {
+        // 读完全部事件，避免残留到下一帧
+        let keys = self.keys.read().any(|event| event.state.is_pressed());
+        let mouse = self.mouse.read().any(|event| event.state.is_pressed());
+        let gamepad = self.gamepad.read().any(|event| event.state.is_pressed());
+        keys || mouse || gamepad
+    }
+}
+
+/// 演示模式插件 - 主菜单无操作15秒后，在标题下方进行一局演示（演示模式中由自动驾驶插件接管玩家），任意输入或演示结束后返回主菜单（像街机一样）
+#[derive(Clone)]
+pub struct AttractPlugin;
+
+impl Plugin for AttractPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AttractTimer>()
+            // 创建演示标题（默认隐藏）
+            .add_systems(Startup, attract_title_setup_system)
+            // 进入主菜单时重新计时
+            .add_systems(OnEnter(AppState::MainMenu), attract_reset_system)
+            // 主菜单无操作时开始演示
+            .add_systems(
+                Update,
+                attract_idle_system.run_if(in_state(AppState::MainMenu)),
+            )
+            // 任意输入或演示结束时返回主菜单
+            .add_systems(Update, attract_exit_system.in_set(GameSet::Input))
+            // 只在演示中显示标题
+            .add_systems(Update, attract_title_system.in_set(GameSet::Presentation));
+    }
+}
+
+impl GameModePlugin for AttractPlugin {
+    fn mode(&self) -> GameMode {
+        GameMode::Attract
+    }
+
+    /// 演示中的击毁不计入档案
+    fn counts_score(&self) -> bool {
+        false
+    }
+}
+
+/// 演示标题初始化系统 - 标题和操作提示显示在屏幕上方
+fn attract_title_setup_system(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                top: Val::Percent(20.),
+                justify_content: JustifyContent::Center,
+                ..Default::default()
+            },
+            GlobalZIndex(5),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                AttractTitle,
+                Text::new("RUST INVADERS\nDEMO - press any key"),
+                TextFont {
+                    font_size: 36.,
+                    ..Default::default()
+                },
+                TextColor(Color::srgba(1., 1., 1., 0.85)),
+                TextLayout::new_with_justify(JustifyText::Center),
+                Visibility::Hidden,
+            ));
+        });
+}
+
+/// 计时重置系统
+fn attract_reset_system(mut timer: ResMut<AttractTimer>) {
+    *timer = AttractTimer::default();
+}
+
+/// 无操作计时系统 - 主菜单中有输入时重新计时，无操作15秒后以默认关卡脚本开始演示
+fn attract_idle_system(
+    time: Res<Time>,
+    mut input: PlayerInput,
+    mut timer: ResMut<AttractTimer>,
+    mut mode: ResMut<GameMode>,
+    mut config: ResMut<GameConfig>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if input.any_pressed() {
+        timer.idle = 0.;
+        return;
+    }
+    timer.idle += time.delta_secs();
+    if timer.idle < ATTRACT_IDLE_SECS {
+        return;
+    }
+    info!("主菜单无操作，开始演示");
+    *timer = AttractTimer::default();
+    *mode = GameMode::Attract;
+    config.level = LevelScript::load_default();
+    next_state.set(AppState::Playing);
+}
+
+/// 演示退出系统 - 任意输入或演示结束时结束本局，返回主菜单
+fn attract_exit_system(
+    time: Res<Time>,
+    mut input: PlayerInput,
+    mut timer: ResMut<AttractTimer>,
+    mut mode: ResMut<GameMode>,
+    mut game_over: EventWriter<GameOverEvent>,
+) {
+    if *mode != GameMode::Attract {
+        return;
+    }
+    timer.demo += time.delta_secs();
+    if !input.any_pressed() && timer.demo < ATTRACT_DEMO_SECS {
+        return;
+    }
+    // 演示结束后恢复为无尽模式，避免影响之后的游戏
+    *mode = GameMode::Endless;
+    game_over.send(GameOverEvent::to(AppState::MainMenu));
+}
+
+/// 演示标题显示系统
+fn attract_title_system(
+    mode: Res<GameMode>,
+    state: Res<State<AppState>>,
+    mut query: Query<&mut Visibility, With<AttractTitle>>,
+) {
+    let Ok(mut visibility) = query.get_single_mut() else {
+        return;
+    };
+    let active = *mode == GameMode::Attract && *state.get() == AppState::Playing;
+    visibility.set_if_neq(if active {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    });
+}
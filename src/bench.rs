@@ -0,0 +1,198 @@
+use crate::combat::ExplosionRequested;
+use crate::components::{
+    Enemy, EnemyKind, Explosion, Health, Laser, Movable, SpriteSize, Team, Velocity,
+};
+use crate::pool::BulletPool;
+use crate::registry::{AssetRegistry, Tex};
+use crate::{
+    AppState, ENEMY_HEALTH, ENEMY_LASER_SIZE, ENEMY_SIZE, EnemyCount, GameSet, SPRITE_SCALE,
+    WinSize,
+};
+use bevy::prelude::*;
+use std::time::{Duration, Instant};
+
+// 未指定--seed时基准测试使用的随机种子
+pub const BENCH_SEED: u64 = 1234;
+// 基准场景中的敌人数量
+const BENCH_ENEMIES: usize = 500;
+// 基准场景中的激光数量
+const BENCH_LASERS: usize = 3000;
+// 基准场景中维持的爆炸数量
+const BENCH_EXPLOSIONS: usize = 100;
+// 基准测试的时长（游戏时间，秒）
+const BENCH_SECS: f32 = 10.;
+// 基准场景中敌人和激光的缩放（大量实体时缩小以便铺满屏幕）
+const BENCH_SCALE: f32 = SPRITE_SCALE * 0.3;
+// 激光的飞行速度（速度单位，测试期间大部分激光保持在屏幕内）
+const BENCH_LASER_SPEED: f32 = 0.05;
+
+/// 基准测试资源 - 场景生成时的游戏时间和每帧耗时
+#[derive(Resource)]
+struct BenchRun {
+    started: f32,          // 场景生成时的游戏时间（秒）
+    samples: Vec<f64>,     // 每帧的真实耗时（毫秒）
+    last: Option<Instant>, // 上一帧结束的时刻
+}
+
+/// 基准测试插件（--bench）- 生成确定性的重负载场景（500个敌人、3000束激光、100个爆炸），运行10秒后输出帧时间统计并退出
+/// 窗口模式和无窗口模拟（--bench --headless）均可使用
+pub struct BenchPlugin;
+
+impl Plugin for BenchPlugin {
+    fn build(&self, app: &mut App) {
+        // 窗口模式下资源加载完成后跳过主菜单
+        app.add_systems(OnEnter(AppState::MainMenu), bench_start_system)
+            // 开始游戏时生成基准场景
+            .add_systems(OnEnter(AppState::Playing), bench_scene_system)
+            // 维持爆炸数量
+            .add_systems(
+                FixedUpdate,
+                bench_explosion_system
+                    .before(crate::explosion_spawn_system)
+                    .in_set(GameSet::Spawn),
+            )
+            // 每帧结束时记录帧时间
+            .add_systems(Last, bench_frame_system);
+    }
+}
+
+/// 基准启动系统 - 直接进入游戏
+fn bench_start_system(mut next_state: ResMut<NextState<AppState>>) {
+    next_state.set(AppState::Playing);
+}
+
+/// 基准场景生成系统 - 敌人和激光按网格排列，位置和速度只取决于下标，每次运行完全相同（敌人不开火）
+fn bench_scene_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    win_size: Res<WinSize>,
+    registry: Res<AssetRegistry>,
+    mut pool: ResMut<BulletPool>,
+    mut enemy_count: ResMut<EnemyCount>,
+) {
+    let scale = Vec3::new(BENCH_SCALE, BENCH_SCALE, 1.);
+
+    // 敌人铺满屏幕上半部分
+    for position in grid(BENCH_ENEMIES, win_size.w, win_size.h / 2.) {
+        commands.spawn((
+            registry.sprite(Tex::Enemy),
+            Transform {
+                translation: Vec3::new(position.x, position.y + win_size.h / 4., 10.),
+                scale,
+                ..Default::default()
+            },
+            Enemy,
+            Team::ENEMY,
+            EnemyKind::Basic,
+            SpriteSize::from(ENEMY_SIZE),
+            Health::full(ENEMY_HEALTH),
+        ));
+    }
+    enemy_count.0 += BENCH_ENEMIES as u32; // 计入敌人数量，常规生成暂停
+
+    // 激光铺满全屏，按下标交替上下飞行
+    for (i, position) in grid(BENCH_LASERS, win_size.w, win_size.h).enumerate() {
+        let direction = if i % 2 == 0 { 1. } else { -1. };
+        pool.spawn(
+            &mut commands,
+            (
+                registry.sprite(Tex::EnemyLaser),
+                Transform {
+                    translation: position.extend(0.),
+                    scale,
+                    ..Default::default()
+                },
+                Laser,
+                Team::ENEMY,
+                SpriteSize::from(ENEMY_LASER_SIZE),
+                Movable { auto_despawn: true },
+                Velocity {
+                    x: 0.,
+                    y: direction * BENCH_LASER_SPEED,
+                },
+            ),
+        );
+    }
+
+    commands.insert_resource(BenchRun {
+        started: time.elapsed_secs(),
+        samples: Vec::new(),
+        last: None,
+    });
+    info!(
+        enemies = BENCH_ENEMIES,
+        lasers = BENCH_LASERS,
+        explosions = BENCH_EXPLOSIONS,
+        "基准场景已生成"
+    );
+}
+
+/// 爆炸维持系统 - 爆炸播放完毕后在固定位置补充，场上始终保持100个爆炸
+fn bench_explosion_system(
+    run: Option<Res<BenchRun>>,
+    win_size: Res<WinSize>,
+    query: Query<(), With<Explosion>>,
+    mut explosions: EventWriter<ExplosionRequested>,
+) {
+    if run.is_none() {
+        return;
+    }
+    let missing = BENCH_EXPLOSIONS.saturating_sub(query.iter().count());
+    for position in grid(BENCH_EXPLOSIONS, win_size.w, win_size.h).take(missing) {
+        explosions.send(ExplosionRequested(position.extend(20.)));
+    }
+}
+
+/// 帧时间记录系统 - 测试时长结束后打印平均值和百分位数并退出
+fn bench_frame_system(
+    time: Res<Time>,
+    run: Option<ResMut<BenchRun>>,
+    mut exit_events: EventWriter<AppExit>,
+) {
+    let Some(mut run) = run else {
+        return;
+    };
+    let now = Instant::now();
+    if let Some(last) = run.last {
+        let frame: Duration = now - last;
+        run.samples.push(frame.as_secs_f64() * 1000.);
+    }
+    run.last = Some(now);
+
+    if time.elapsed_secs() - run.started < BENCH_SECS {
+        return;
+    }
+    print_report(&mut run.samples);
+    exit_events.send(AppExit::Success);
+}
+
+/// 打印帧时间统计（毫秒）
+fn print_report(samples: &mut [f64]) {
+    if samples.is_empty() {
+        println!("基准测试没有记录到帧");
+        return;
+    }
+    samples.sort_by(f64::total_cmp);
+    let average = samples.iter().sum::<f64>() / samples.len() as f64;
+    let percentile = |p: f64| samples[((samples.len() - 1) as f64 * p).round() as usize];
+    println!("基准测试完成：{} 帧", samples.len());
+    println!("平均帧时间: {average:.3} ms（{:.1} FPS）", 1000. / average);
+    println!("P50: {:.3} ms", percentile(0.5));
+    println!("P95: {:.3} ms", percentile(0.95));
+    println!("P99: {:.3} ms", percentile(0.99));
+    println!("最长: {:.3} ms", samples[samples.len() - 1]);
+}
+
+/// 把count个点均匀排成铺满width×height区域的网格（区域中心为原点）
+fn grid(count: usize, width: f32, height: f32) -> impl Iterator<Item = Vec2> {
+    let columns = ((count as f32 * width / height).sqrt().ceil() as usize).max(1);
+    let rows = count.div_ceil(columns);
+    let step = Vec2::new(width / columns as f32, height / rows as f32);
+    (0..count).map(move |i| {
+        let (column, row) = ((i % columns) as f32, (i / columns) as f32);
+        Vec2::new(
+            -width / 2. + step.x * (column + 0.5),
+            height / 2. - step.y * (row + 0.5),
+        )
+    })
+}
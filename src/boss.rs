@@ -0,0 +1,461 @@
+use crate::components::{
+    Enemy, EnemyKind, FireCooldown, FirePeriod, Health, Laser, Movable, Player, Resistances,
+    SpriteSize, Team, Velocity,
+};
+use crate::cutscene::{Cutscene, CutsceneStep};
+use crate::enemy::{
+    Armored, DoubleShot, Elite, EnemyPart, PartKind, SpawnEnemyEvent, SpawnKind,
+    enemy_spawner_system, spawn_weak_point,
+};
+use crate::faction_time::{Faction, FactionTime};
+use crate::pool::BulletPool;
+use crate::registry::{AssetRegistry, Tex};
+use crate::rng::GameRng;
+use crate::sim::RollbackAppExt;
+use crate::status::StatusEffects;
+use crate::theme::ThemeAssets;
+use crate::wave::{Wave, WavePhase, WaveWatch};
+use crate::{
+    ENEMY_FIRE_PERIOD, ENEMY_HEALTH, ENEMY_LASER_SIZE, ENEMY_SIZE, EnemyCount, GameSet,
+    SPRITE_SCALE, WinSize,
+};
+use bevy::prelude::*;
+use std::f32::consts::{FRAC_PI_2, TAU};
+use std::time::Duration;
+
+// 首领名称（登场时闪烁显示）
+const BOSS_NAME: &str = "DREADNOUGHT";
+// 首领相对普通敌人的放大倍率
+const BOSS_SCALE: f32 = 2.5;
+// 首领的着色
+const BOSS_TINT: Color = Color::srgb(1., 0.4, 0.4);
+// 首领可抵挡的伤害（普通命中为1）
+const BOSS_ARMOR_HITS: f32 = 40.;
+// 首领的抗性：抵抗动能，惧怕爆炸
+const BOSS_RESISTANCES: Resistances = Resistances {
+    kinetic: 0.5,
+    energy: 1.,
+    explosive: 1.5,
+};
+// 护盾球的抗性：能量护盾几乎免疫能量武器，容易被动能弹击碎
+const BOSS_ORB_RESISTANCES: Resistances = Resistances {
+    kinetic: 1.5,
+    energy: 0.25,
+    explosive: 1.,
+};
+// 击毁首领的额外得分
+const BOSS_BONUS_SCORE: u64 = 2000;
+// 首领登场滑入的时间（秒）
+const BOSS_INTRO_SLIDE_SECS: f32 = 2.;
+// 首领名称闪烁的时间（秒）
+const BOSS_INTRO_NAME_SECS: f32 = 1.5;
+// 登场时屏幕震动的幅度（像素）
+const BOSS_INTRO_SHAKE: f32 = 8.;
+// 首领左右巡航的角速度（弧度/秒）
+const BOSS_SWAY_SPEED: f32 = 0.6;
+// 核心暴露后首领的着色
+const BOSS_EXPOSED_TINT: Color = Color::srgb(1., 0.15, 0.15);
+// 首领炮塔相对首领中心的位置（首领缩放前的像素）
+const BOSS_TURRET_OFFSETS: [Vec2; 3] = [
+    Vec2::new(-ENEMY_SIZE.0 * 0.35, -ENEMY_SIZE.1 * 0.5),
+    Vec2::new(0., -ENEMY_SIZE.1 * 0.5),
+    Vec2::new(ENEMY_SIZE.0 * 0.35, -ENEMY_SIZE.1 * 0.5),
+];
+// 首领炮塔的尺寸（首领缩放前的像素）
+const BOSS_TURRET_SIZE: f32 = 32.;
+// 首领炮塔的颜色
+const BOSS_TURRET_COLOR: Color = Color::srgb(0.55, 0.55, 0.6);
+// 首领炮塔的生命值（每次激光命中扣除1点）
+const BOSS_TURRET_HEALTH: f32 = 8.;
+// 首领炮塔的开火间隔范围（秒）
+const BOSS_TURRET_FIRE_PERIOD: (f32, f32) = (1.5, 3.);
+// 首领炮塔发射的激光速度（速度单位）
+const BOSS_TURRET_LASER_SPEED: f32 = 0.8;
+// 首领核心弱点的伤害倍率
+const BOSS_WEAK_POINT_MULTIPLIER: f32 = 3.;
+// 环绕首领的护盾球数量
+const BOSS_ORB_COUNT: usize = 4;
+// 护盾球的环绕半径（首领缩放前的像素）
+const BOSS_ORB_RADIUS: f32 = ENEMY_SIZE.0 * 0.75;
+// 护盾球的尺寸（首领缩放前的像素）
+const BOSS_ORB_SIZE: f32 = 24.;
+// 护盾球的颜色
+const BOSS_ORB_COLOR: Color = Color::srgba(0.3, 0.9, 1., 0.85);
+// 护盾球的环绕角速度（弧度/秒）
+const BOSS_ORB_SPEED: f32 = 1.2;
+
+/// 首领组件 - 记录左右巡航的相位
+#[derive(Component, Clone)]
+pub struct Boss {
+    sway: f32, // 巡航相位（过场期间不推进）
+}
+
+/// 首领炮塔组件 - 首领的子实体，独立瞄准玩家并按自己的冷却开火
+#[derive(Component, Clone)]
+pub struct BossTurret;
+
+/// 护盾环组件 - 首领的子实体，持续旋转并带动作为其子实体的护盾球环绕首领
+#[derive(Component, Clone)]
+struct OrbRing;
+
+/// 核心护盾组件 - 首领的炮塔全部被击毁前，命中首领核心的激光被吸收
+#[derive(Component, Clone)]
+pub struct CoreShield;
+
+/// 首领插件 - 首领波次开始时生成首领并播放登场过场；首领带有独立开火的炮塔，炮塔全部被击毁后核心才会受到伤害
+pub struct BossPlugin;
+
+impl Plugin for BossPlugin {
+    fn build(&self, app: &mut App) {
+        // 登记参与快照回滚的组件
+        app.rollback_component::<Boss>()
+            .rollback_component::<BossTurret>()
+            .rollback_component::<OrbRing>()
+            .rollback_component::<CoreShield>();
+
+        // 首领波次进入战斗阶段时生成首领
+        app.add_systems(
+            FixedUpdate,
+            boss_spawn_system
+                .before(enemy_spawner_system)
+                .in_set(GameSet::Spawn),
+        )
+        // 登场过场结束后左右巡航，炮塔随首领移动并瞄准玩家，护盾球环绕首领旋转
+        .add_systems(
+            FixedUpdate,
+            (
+                boss_movement_system,
+                boss_turret_aim_system,
+                orb_ring_system,
+            )
+                .chain()
+                .in_set(GameSet::Movement),
+        )
+        // 炮塔全部被击毁时暴露核心
+        .add_systems(FixedUpdate, boss_core_system.in_set(GameSet::Collision))
+        // 炮塔按各自的冷却向玩家开火
+        .add_systems(FixedUpdate, boss_turret_fire_system.in_set(GameSet::Spawn));
+    }
+}
+
+/// 首领登场过场 - 锁定输入，从屏幕上方滑入，闪烁名称并震动屏幕，最后恢复控制
+fn boss_intro(boss: Entity, to: Vec3) -> Vec<CutsceneStep> {
+    vec![
+        CutsceneStep::LockInput,
+        CutsceneStep::SlideTo {
+            entity: boss,
+            to,
+            secs: BOSS_INTRO_SLIDE_SECS,
+        },
+        CutsceneStep::Shake {
+            intensity: BOSS_INTRO_SHAKE,
+            secs: BOSS_INTRO_NAME_SECS,
+        },
+        CutsceneStep::FlashText {
+            text: BOSS_NAME.to_string(),
+            secs: BOSS_INTRO_NAME_SECS,
+        },
+        CutsceneStep::UnlockInput,
+    ]
+}
+
+/// 首领生成系统 - 首领波次进入战斗阶段时发送生成首领的事件（在屏幕上方生成首领，并播放登场过场）
+fn boss_spawn_system(
+    wave: Res<Wave>,
+    mut watch: ResMut<WaveWatch>,
+    mut spawn_events: EventWriter<SpawnEnemyEvent>,
+) {
+    // 只在波次或阶段切换时处理（生成敌人也会修改波次资源）
+    if !watch.changed("boss_spawn", &wave) {
+        return;
+    }
+    if wave.phase != WavePhase::Fighting || !wave.is_boss_wave() {
+        return;
+    }
+    spawn_events.send(SpawnEnemyEvent::new(SpawnKind::Boss));
+}
+
+/// 在屏幕上方生成首领并播放登场过场（带炮塔、护盾环和核心弱点），首领计入敌人数量
+pub fn spawn_boss(
+    commands: &mut Commands,
+    win_size: &WinSize,
+    registry: &AssetRegistry,
+    theme_assets: &ThemeAssets,
+    enemy_count: &mut EnemyCount,
+    cutscene: &mut Cutscene,
+    rng: &mut GameRng,
+) {
+    let scale = SPRITE_SCALE * BOSS_SCALE;
+    let top = win_size.h / 2.;
+    let start = Vec3::new(0., top + ENEMY_SIZE.1 * scale, 10.);
+    let to = Vec3::new(0., top - ENEMY_SIZE.1 * scale, 10.);
+    let fire_period = FirePeriod {
+        min: ENEMY_FIRE_PERIOD.0,
+        max: ENEMY_FIRE_PERIOD.1,
+    };
+    // 首次开火推迟到登场过场结束之后
+    let intro_secs = BOSS_INTRO_SLIDE_SECS + BOSS_INTRO_NAME_SECS;
+    let fire_cooldown = FireCooldown(Timer::from_seconds(
+        intro_secs + fire_period.roll(rng),
+        TimerMode::Once,
+    ));
+
+    let boss = commands
+        .spawn((
+            Sprite {
+                color: BOSS_TINT,
+                ..registry.sprite(theme_assets.enemy(EnemyKind::Boss))
+            },
+            Transform {
+                translation: start,
+                scale: Vec3::new(scale, scale, 1.),
+                ..Default::default()
+            },
+        ))
+        .insert(Enemy) // 标记为敌人实体
+        .insert(Team::ENEMY) // 敌方队伍
+        .insert(EnemyKind::Boss) // 敌人种类
+        .insert(Boss { sway: 0. }) // 首领巡航
+        .insert(SpriteSize::from(ENEMY_SIZE)) // 碰撞尺寸（随缩放放大）
+        .insert(Health::full(ENEMY_HEALTH)) // 装甲之外的生命值
+        .insert(Armored {
+            hits: BOSS_ARMOR_HITS,
+        }) // 需要多次命中才能击毁
+        .insert(BOSS_RESISTANCES) // 各伤害类型的倍率
+        .insert(Elite {
+            bonus: BOSS_BONUS_SCORE,
+        }) // 击毁时的额外得分
+        .insert(DoubleShot) // 每次开火发射两束激光
+        .insert(fire_period) // 开火间隔范围
+        .insert(fire_cooldown) // 开火冷却计时器
+        .insert(CoreShield) // 炮塔全部被击毁前核心免疫伤害
+        .with_children(|parent| {
+            for offset in BOSS_TURRET_OFFSETS {
+                spawn_turret(parent, offset, intro_secs, rng);
+            }
+            spawn_orb_ring(parent);
+            // 核心中央的弱点（核心暴露后命中造成三倍伤害）
+            spawn_weak_point(
+                parent,
+                Vec2::new(0., ENEMY_SIZE.1 * 0.1),
+                ENEMY_SIZE.1 * 0.3,
+                BOSS_WEAK_POINT_MULTIPLIER,
+            );
+        })
+        .id();
+    enemy_count.0 += 1; // 首领计入敌人数量，击毁前本波不会结束
+
+    cutscene.play(boss_intro(boss, to));
+}
+
+/// 生成一座首领炮塔（带有指向玩家的炮管）
+fn spawn_turret(
+    parent: &mut ChildSpawnerCommands,
+    offset: Vec2,
+    intro_secs: f32,
+    rng: &mut GameRng,
+) {
+    let fire_period = FirePeriod {
+        min: BOSS_TURRET_FIRE_PERIOD.0,
+        max: BOSS_TURRET_FIRE_PERIOD.1,
+    };
+    // 首次开火推迟到登场过场结束之后
+    let fire_cooldown = FireCooldown(Timer::from_seconds(
+        intro_secs + fire_period.roll(rng),
+        TimerMode::Once,
+    ));
+    parent
+        .spawn((
+            Sprite::from_color(BOSS_TURRET_COLOR, Vec2::splat(BOSS_TURRET_SIZE)),
+            Transform::from_translation(offset.extend(1.)),
+        ))
+        .insert(BossTurret) // 首领炮塔
+        .insert(EnemyPart {
+            kind: PartKind::Turret,
+        }) // 可被单独击毁的部件
+        .insert(Health::full(BOSS_TURRET_HEALTH)) // 炮塔生命值
+        .insert(SpriteSize(Vec2::splat(BOSS_TURRET_SIZE))) // 碰撞尺寸（随首领缩放放大）
+        .insert(fire_period) // 开火间隔范围
+        .insert(fire_cooldown) // 开火冷却计时器
+        .with_children(|turret| {
+            // 炮管沿炮塔的局部+Y方向伸出
+            turret.spawn((
+                Sprite::from_color(
+                    BOSS_TURRET_COLOR,
+                    Vec2::new(BOSS_TURRET_SIZE / 4., BOSS_TURRET_SIZE * 0.75),
+                ),
+                Transform::from_xyz(0., BOSS_TURRET_SIZE * 0.6, -0.1),
+            ));
+        });
+}
+
+/// 生成护盾环 - 护盾球均匀分布在环上，吸收命中的玩家激光，需逐个击毁
+fn spawn_orb_ring(parent: &mut ChildSpawnerCommands) {
+    parent
+        .spawn((Transform::default(), Visibility::default(), OrbRing))
+        .with_children(|ring| {
+            for i in 0..BOSS_ORB_COUNT {
+                let angle = TAU * i as f32 / BOSS_ORB_COUNT as f32;
+                ring.spawn((
+                    Sprite::from_color(BOSS_ORB_COLOR, Vec2::splat(BOSS_ORB_SIZE)),
+                    Transform::from_translation(
+                        (Vec2::from_angle(angle) * BOSS_ORB_RADIUS).extend(2.),
+                    ),
+                ))
+                .insert(EnemyPart {
+                    kind: PartKind::Orb,
+                }) // 可被单独击毁的部件（吸收命中的激光）
+                .insert(Health::full(PartKind::Orb.health())) // 护盾球生命值
+                .insert(BOSS_ORB_RESISTANCES) // 各伤害类型的倍率
+                .insert(SpriteSize(Vec2::splat(BOSS_ORB_SIZE))); // 碰撞尺寸（随首领缩放放大）
+            }
+        });
+}
+
+/// 首领移动系统 - 过场结束后沿水平方向往返巡航（减速和眩晕状态同样生效）
+fn boss_movement_system(
+    time: Res<Time>,
+    faction_time: Res<FactionTime>,
+    cutscene: Res<Cutscene>,
+    win_size: Res<WinSize>,
+    mut query: Query<(&mut Transform, &mut Boss, Option<&StatusEffects>)>,
+) {
+    if cutscene.active() {
+        return;
+    }
+    let span = win_size.w / 3.;
+    for (mut transform, mut boss, status) in &mut query {
+        boss.sway += BOSS_SWAY_SPEED
+            * faction_time.delta_secs(&time, Faction::Enemy)
+            * StatusEffects::speed_scale(status);
+        transform.translation.x = boss.sway.sin() * span;
+    }
+}
+
+/// 朝向计算 - 把精灵的局部+Y方向转向指定方向的旋转
+fn facing(direction: Vec2) -> Quat {
+    Quat::from_rotation_z(direction.to_angle() - FRAC_PI_2)
+}
+
+/// 炮塔瞄准系统 - 按炮塔的世界位置（首领变换与相对变换合成）把炮管转向玩家
+fn boss_turret_aim_system(
+    boss_query: Query<&Transform, (With<Boss>, Without<BossTurret>)>,
+    player_query: Query<&Transform, (With<Player>, Without<BossTurret>)>,
+    mut turret_query: Query<(&ChildOf, &mut Transform), With<BossTurret>>,
+) {
+    let Ok(player_tf) = player_query.get_single() else {
+        return;
+    };
+    for (child_of, mut turret_tf) in &mut turret_query {
+        let Ok(boss_tf) = boss_query.get(child_of.parent()) else {
+            continue;
+        };
+        let world = boss_tf.transform_point(turret_tf.translation);
+        let direction = (player_tf.translation - world).truncate();
+        if direction != Vec2::ZERO {
+            turret_tf.rotation = facing(direction);
+        }
+    }
+}
+
+/// 护盾环旋转系统 - 护盾球随护盾环绕首领旋转（过场期间同样旋转）
+fn orb_ring_system(
+    time: Res<Time>,
+    faction_time: Res<FactionTime>,
+    mut query: Query<&mut Transform, With<OrbRing>>,
+) {
+    for mut transform in &mut query {
+        transform.rotate_z(BOSS_ORB_SPEED * faction_time.delta_secs(&time, Faction::Enemy));
+    }
+}
+
+/// 炮塔开火系统 - 每座炮塔按自己的冷却计时器向玩家发射激光（过场期间暂停）
+#[allow(clippy::type_complexity)] // 允许复杂的查询类型
+#[allow(clippy::too_many_arguments)] // 开火需要同时访问首领、玩家、炮塔和对象池
+fn boss_turret_fire_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    faction_time: Res<FactionTime>,
+    cutscene: Res<Cutscene>,
+    registry: Res<AssetRegistry>,
+    mut pool: ResMut<BulletPool>,
+    mut rng: ResMut<GameRng>,
+    boss_query: Query<(&Transform, Option<&StatusEffects>), With<Boss>>,
+    player_query: Query<&Transform, With<Player>>,
+    mut turret_query: Query<
+        (&ChildOf, &Transform, &mut FireCooldown, &FirePeriod),
+        With<BossTurret>,
+    >,
+) {
+    if cutscene.active() {
+        return;
+    }
+    let Ok(player_tf) = player_query.get_single() else {
+        return;
+    };
+    for (child_of, turret_tf, mut cooldown, fire_period) in &mut turret_query {
+        // 首领眩晕期间炮塔冷却暂停且不开火
+        let Ok((boss_tf, status)) = boss_query.get(child_of.parent()) else {
+            continue;
+        };
+        if StatusEffects::suppressed(status) {
+            continue;
+        }
+        cooldown.0.tick(faction_time.delta(&time, Faction::Enemy));
+        if !cooldown.0.finished() {
+            continue;
+        }
+
+        // 重新随机下一次开火间隔
+        cooldown
+            .0
+            .set_duration(Duration::from_secs_f32(fire_period.roll(&mut rng)));
+        cooldown.0.reset();
+
+        let world = boss_tf.transform_point(turret_tf.translation);
+        let Some(direction) = (player_tf.translation - world).truncate().try_normalize() else {
+            continue;
+        };
+        let velocity = direction * BOSS_TURRET_LASER_SPEED;
+
+        // 生成炮塔激光（从炮管末端射出）
+        pool.spawn(
+            &mut commands,
+            (
+                registry.sprite(Tex::EnemyLaser),
+                Transform {
+                    translation: (world.truncate() + direction * BOSS_TURRET_SIZE).extend(0.),
+                    rotation: facing(direction),
+                    scale: Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.),
+                },
+            ),
+        )
+        .insert(Laser) // 标记为激光实体
+        .insert(SpriteSize::from(ENEMY_LASER_SIZE)) // 设置激光大小
+        .insert(Team::ENEMY) // 标记为敌方队伍的激光
+        .insert(Movable { auto_despawn: true }) // 可移动且超出屏幕自动回收到对象池
+        .insert(Velocity {
+            x: velocity.x,
+            y: velocity.y,
+        }); // 朝玩家方向飞行
+    }
+}
+
+/// 核心暴露系统 - 炮塔全部被击毁后移除核心护盾，首领变色提示可以攻击核心
+fn boss_core_system(
+    mut commands: Commands,
+    mut boss_query: Query<(Entity, &mut Sprite), (With<Boss>, With<CoreShield>)>,
+    turret_query: Query<&ChildOf, With<BossTurret>>,
+) {
+    for (boss, mut sprite) in &mut boss_query {
+        if turret_query
+            .iter()
+            .any(|child_of| child_of.parent() == boss)
+        {
+            continue;
+        }
+        commands.entity(boss).remove::<CoreShield>();
+        sprite.color = BOSS_EXPOSED_TINT;
+        info!("首领炮塔全部击毁，核心暴露");
+    }
+}
@@ -0,0 +1,160 @@
+use crate::action::{Action, ActionState, AutopilotAdapters};
+use crate::components::{Enemy, Player, Team};
+use crate::game_mode::GameMode;
+use crate::settings::Settings;
+use crate::wave::{Wave, WavePhase};
+use crate::{AppState, WinSize};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+// 开始躲避的威胁距离（威胁在玩家上方多远以内，像素）
+const DODGE_RANGE: f32 = 220.;
+// 威胁与玩家的水平距离小于该值时躲避（像素）
+const DODGE_WIDTH: f32 = 45.;
+// 躲避时与屏幕边缘保持的距离（像素）
+const EDGE_MARGIN: f32 = 60.;
+// 对准后停止移动的水平距离（像素）
+const AIM_DEAD_ZONE: f32 = 10.;
+// 目标进入该水平距离时开火（像素）
+const AIM_WINDOW: f32 = 30.;
+
+/// 自动驾驶方式
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Drive {
+    Off,    // 玩家自己操作
+    Assist, // 辅助：玩家没有按方向键时代为躲避和瞄准开火
+    Full,   // 完全接管：移动、开火并在商店开放时开始下一波
+}
+
+/// 自动驾驶资源 - 控制本帧是否由机器人驾驶玩家
+#[derive(Resource, Default)]
+pub struct Autopilot {
+    pub always: bool, // 始终完全接管（无窗口模拟）
+}
+
+impl Autopilot {
+    /// 始终完全接管的自动驾驶
+    pub fn always() -> Self {
+        Self { always: true }
+    }
+}
+
+/// 机器人观察到的场面 - 玩家、敌人和会击毁玩家的威胁（敌人激光、流星）
+#[derive(SystemParam)]
+struct BotView<'w, 's> {
+    wave: Res<'w, Wave>,
+    win_size: Res<'w, WinSize>,
+    player: Query<'w, 's, &'static Transform, With<Player>>,
+    enemies: Query<'w, 's, &'static Transform, With<Enemy>>,
+    threats: Query<'w, 's, (&'static Transform, &'static Team), (Without<Player>, Without<Enemy>)>,
+}
+
+/// 机器人本帧的操作
+#[derive(Default)]
+struct BotInput {
+    direction: f32,  // 水平移动方向（-1向左，1向右，0不动）
+    fire: bool,      // 是否开火
+    next_wave: bool, // 是否开始下一波（商店开放时）
+}
+
+impl BotView<'_, '_> {
+    /// 根据场面决定操作：优先躲避正上方最近的威胁，否则对准水平距离最近的敌人，对准后开火
+    fn decide(&self) -> BotInput {
+        if self.wave.phase == WavePhase::Shop {
+            return BotInput {
+                next_wave: true,
+                ..Default::default()
+            };
+        }
+        let Ok(player_tf) = self.player.get_single() else {
+            return BotInput::default();
+        };
+        let player = player_tf.translation.truncate();
+
+        // 躲避：正上方最近的威胁，向远离它的一侧移动（靠近屏幕边缘时改向另一侧）
+        let threat = self
+            .threats
+            .iter()
+            .filter(|(_, team)| team.hostile(Team::PLAYER))
+            .map(|(tf, _)| tf.translation.truncate() - player)
+            .filter(|offset| {
+                offset.y > 0. && offset.y < DODGE_RANGE && offset.x.abs() < DODGE_WIDTH
+            })
+            .min_by(|a, b| a.y.total_cmp(&b.y));
+        if let Some(offset) = threat {
+            let mut direction = if offset.x > 0. { -1. } else { 1. };
+            let edge = self.win_size.w / 2. - EDGE_MARGIN;
+            if (player.x + direction * EDGE_MARGIN).abs() > edge {
+                direction = -direction;
+            }
+            return BotInput {
+                direction,
+                ..Default::default()
+            };
+        }
+
+        // 瞄准：水平距离最近的敌人
+        let Some(dx) = self
+            .enemies
+            .iter()
+            .map(|tf| tf.translation.x - player.x)
+            .min_by(|a, b| a.abs().total_cmp(&b.abs()))
+        else {
+            return BotInput::default();
+        };
+        let direction = if dx.abs() <= AIM_DEAD_ZONE {
+            0.
+        } else {
+            dx.signum()
+        };
+        BotInput {
+            direction,
+            fire: dx.abs() <= AIM_WINDOW,
+            next_wave: false,
+        }
+    }
+}
+
+/// 自动驾驶插件 - 机器人代替玩家操作，用于无窗口模拟、主菜单演示和辅助模式（A键切换）
+pub struct BotPlugin;
+
+impl Plugin for BotPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Autopilot>()
+            // 在各设备适配之后、汇总之前写入动作，玩法系统读到的就是机器人的操作
+            .add_systems(PreUpdate, bot_drive_system.in_set(AutopilotAdapters));
+    }
+}
+
+/// 自动驾驶系统 - 作为动作适配，按本帧的决定叠加移动方向、按住开火或确认（玩家同时按住的动作照常生效）
+fn bot_drive_system(
+    mut actions: ResMut<ActionState>,
+    autopilot: Res<Autopilot>,
+    mode: Res<GameMode>,
+    settings: Res<Settings>,
+    state: Res<State<AppState>>,
+    view: BotView,
+) {
+    let drive = if *state.get() != AppState::Playing {
+        Drive::Off
+    } else if autopilot.always || *mode == GameMode::Attract {
+        Drive::Full
+    } else if settings.assist {
+        Drive::Assist
+    } else {
+        Drive::Off
+    };
+    // 辅助模式下玩家正在移动（键盘或手柄）时由玩家操作
+    if drive == Drive::Off || (drive == Drive::Assist && actions.move_axis != Vec2::ZERO) {
+        return;
+    }
+
+    let input = view.decide();
+    actions.push_axis(Vec2::new(input.direction, 0.));
+    if input.fire {
+        actions.hold(Action::Fire);
+    }
+    if input.next_wave && drive == Drive::Full {
+        actions.hold(Action::MenuConfirm);
+    }
+}
@@ -0,0 +1,392 @@
+use crate::config::GameConfig;
+use crate::game_mode::{GameMode, GameModePlugin, ModeOutcome, run_mode_system};
+use crate::launch::LaunchOptions;
+use crate::level::{CampaignLevel, LevelLoader};
+use crate::mods::ModList;
+use crate::profile::Profile;
+use crate::registry::read_asset_text;
+use crate::run::GameOverEvent;
+use crate::sim::RollbackAppExt;
+use crate::wave::{Wave, WavePhase};
+use crate::{AppState, GameStats};
+use bevy::prelude::*;
+use serde::Deserialize;
+
+// 战役关卡文件路径
+const CAMPAIGN_PATH: &str = "assets/campaign.ron";
+// 关卡文件所在目录（相对assets目录）和扩展名
+const LEVELS_DIR: &str = "levels";
+const LEVEL_EXTENSION: &str = "level";
+// 关卡选择使用的按键（第1~8关）
+const LEVEL_KEYS: [KeyCode; 8] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+];
+
+/// 战役关卡顺序 - 战役关卡文件的内容，各关卡.level文件的路径（相对assets目录）
+#[derive(Default, Deserialize)]
+struct CampaignOrder {
+    levels: Vec<String>, // 按解锁顺序排列的关卡文件
+}
+
+impl CampaignOrder {
+    /// 从战役关卡文件读取，文件不存在或格式错误时没有可选关卡
+    fn load() -> Self {
+        match read_asset_text(CAMPAIGN_PATH) {
+            Ok(content) => ron::from_str(&content).unwrap_or_else(|e| {
+                warn!("战役关卡文件格式错误: {e}");
+                Self::default()
+            }),
+            Err(e) => {
+                warn!("读取战役关卡文件失败: {e}");
+                Self::default()
+            }
+        }
+    }
+}
+
+/// 战役资源 - 按解锁顺序排列的全部关卡句柄（关卡内容由关卡加载器读取，dev特性下修改文件后热重载）
+#[derive(Resource)]
+pub struct Campaign(pub Vec<Handle<CampaignLevel>>);
+
+impl FromWorld for Campaign {
+    /// 按战役关卡文件中的顺序加载各关卡文件（模组中的同名文件优先），模组新增的关卡按路径顺序排在最后
+    fn from_world(world: &mut World) -> Self {
+        let mods = world.get_resource::<ModList>().cloned().unwrap_or_default();
+        let mut order = CampaignOrder::load().levels;
+        for (path, _) in mods.files_in(LEVELS_DIR, LEVEL_EXTENSION) {
+            if !order.contains(&path) {
+                order.push(path);
+            }
+        }
+        let asset_server = world.resource::<AssetServer>();
+        let levels = order
+            .iter()
+            .map(|path| asset_server.load(mods.asset_path(path)))
+            .collect();
+        Self(levels)
+    }
+}
+
+impl Campaign {
+    /// 指定下标的关卡（下标越界或关卡尚未加载完成时为None）
+    pub fn level<'a>(
+        &self,
+        levels: &'a Assets<CampaignLevel>,
+        index: usize,
+    ) -> Option<&'a CampaignLevel> {
+        self.0.get(index).and_then(|handle| levels.get(handle))
+    }
+}
+
+/// 战役进行记录资源 - 关卡开始时的死亡次数，用于结算星级
+#[derive(Resource, Clone, Default)]
+struct CampaignRun {
+    deaths_at_start: u32, // 关卡开始时的累计死亡次数
+}
+
+/// 按关卡内的死亡次数结算星级（无死亡3星，最多死亡2次2星，其余1星）
+fn stars_for(deaths: u32) -> u8 {
+    match deaths {
+        0 => 3,
+        1..=2 => 2,
+        _ => 1,
+    }
+}
+
+/// 关卡选择界面根节点标记
+#[derive(Component)]
+struct LevelSelectScreen;
+
+/// 关卡列表文本标记
+#[derive(Component)]
+struct LevelSelectText;
+
+/// 战役插件 - 关卡选择界面、战役关卡的加载与完成结算
+#[derive(Clone)]
+pub struct CampaignPlugin;
+
+impl Plugin for CampaignPlugin {
+    fn build(&self, app: &mut App) {
+        // 注册战役关卡资源类型和.level文件加载器
+        app.init_asset::<CampaignLevel>()
+            .init_asset_loader::<LevelLoader>()
+            // 加载战役关卡（游戏模式资源由游戏玩法插件初始化，无窗口模拟同样可用）
+            .init_resource::<Campaign>()
+            .init_resource::<CampaignRun>()
+            .rollback_resource::<CampaignRun>()
+            // 进入关卡选择时创建界面，离开时销毁
+            .add_systems(OnEnter(AppState::LevelSelect), level_select_setup_system)
+            .add_systems(OnExit(AppState::LevelSelect), level_select_cleanup_system)
+            // 首次进入主菜单后按启动参数直接开始关卡（等待关卡加载完成）
+            .add_systems(
+                Update,
+                launch_level_system.run_if(in_state(AppState::MainMenu)),
+            )
+            // 处理关卡选择按键，关卡文件加载或修改后刷新列表
+            .add_systems(
+                Update,
+                (
+                    level_select_input_system,
+                    level_select_refresh_system.run_if(on_event::<AssetEvent<CampaignLevel>>),
+                )
+                    .run_if(in_state(AppState::LevelSelect)),
+            )
+            // 正在进行的关卡文件被修改时重新载入关卡脚本
+            .add_systems(
+                Update,
+                level_reload_system.run_if(on_event::<AssetEvent<CampaignLevel>>),
+            );
+    }
+}
+
+impl GameModePlugin for CampaignPlugin {
+    fn mode(&self) -> GameMode {
+        GameMode::Campaign(0)
+    }
+
+    /// 清除关卡的最后一波后胜利
+    fn outcome(&self, world: &World) -> Option<ModeOutcome> {
+        let GameMode::Campaign(index) = *world.resource::<GameMode>() else {
+            return None;
+        };
+        let level = world
+            .resource::<Campaign>()
+            .level(world.resource::<Assets<CampaignLevel>>(), index)?;
+        let wave = world.resource::<Wave>();
+        (wave.phase == WavePhase::Shop && wave.number >= level.waves)
+            .then_some(ModeOutcome::Victory)
+    }
+
+    /// 结算星级并返回关卡选择
+    fn finish(&self, _outcome: ModeOutcome, world: &mut World) {
+        run_mode_system(world, campaign_complete_system);
+    }
+}
+
+/// 关卡是否已解锁（第1关默认解锁，其余关卡需要完成前一关）
+fn level_unlocked(profile: &Profile, index: usize) -> bool {
+    index == 0 || profile.level_stars(index - 1) > 0
+}
+
+/// 关卡列表文本 - 全部关卡及其星级（尚未加载完成的关卡显示为Loading）
+fn level_select_text(
+    campaign: &Campaign,
+    levels: &Assets<CampaignLevel>,
+    profile: &Profile,
+) -> String {
+    let mut lines = vec!["CAMPAIGN".to_string(), String::new()];
+    if campaign.0.is_empty() {
+        lines.push("No campaign levels found".to_string());
+    }
+    for i in 0..campaign.0.len().min(LEVEL_KEYS.len()) {
+        let Some(level) = campaign.level(levels, i) else {
+            lines.push(format!("{}: Loading...", i + 1));
+            continue;
+        };
+        let status = if level_unlocked(profile, i) {
+            let stars = profile.level_stars(i) as usize;
+            format!("{}{}", "*".repeat(stars), "-".repeat(3 - stars))
+        } else {
+            "LOCKED".to_string()
+        };
+        lines.push(format!("{}: {}  [{status}]", i + 1, level.name));
+    }
+    lines.push(String::new());
+    lines.push("1-8: play level   Esc: back".to_string());
+    lines.join("\n")
+}
+
+/// 关卡选择初始化系统 - 列出全部关卡及其星级
+fn level_select_setup_system(
+    mut commands: Commands,
+    campaign: Res<Campaign>,
+    levels: Res<Assets<CampaignLevel>>,
+    profile: Res<Profile>,
+) {
+    let text = level_select_text(&campaign, &levels, &profile);
+
+    commands
+        .spawn((
+            LevelSelectScreen,
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            BackgroundColor(Color::srgba(0., 0., 0., 0.9)),
+            GlobalZIndex(10),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                LevelSelectText,
+                Text::new(text),
+                TextFont {
+                    font_size: 20.,
+                    ..Default::default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// 关卡选择销毁系统
+fn level_select_cleanup_system(
+    mut commands: Commands,
+    query: Query<Entity, With<LevelSelectScreen>>,
+) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// 关卡列表刷新系统 - 关卡文件加载完成或被修改后更新列表
+fn level_select_refresh_system(
+    campaign: Res<Campaign>,
+    levels: Res<Assets<CampaignLevel>>,
+    profile: Res<Profile>,
+    mut query: Query<&mut Text, With<LevelSelectText>>,
+) {
+    if let Ok(mut text) = query.get_single_mut() {
+        text.0 = level_select_text(&campaign, &levels, &profile);
+    }
+}
+
+/// 关卡选择按键系统 - 数字键开始已解锁且加载完成的关卡，Esc返回主菜单
+#[allow(clippy::too_many_arguments)] // 开始关卡需要同时设置多个资源
+fn level_select_input_system(
+    kb: Res<ButtonInput<KeyCode>>,
+    campaign: Res<Campaign>,
+    levels: Res<Assets<CampaignLevel>>,
+    profile: Res<Profile>,
+    stats: Res<GameStats>,
+    mut mode: ResMut<GameMode>,
+    mut config: ResMut<GameConfig>,
+    mut run: ResMut<CampaignRun>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if kb.just_pressed(KeyCode::Escape) {
+        next_state.set(AppState::MainMenu);
+        return;
+    }
+
+    let selected = LEVEL_KEYS
+        .iter()
+        .position(|key| kb.just_pressed(*key))
+        .filter(|&index| level_unlocked(&profile, index));
+    let Some((index, level)) =
+        selected.and_then(|index| Some((index, campaign.level(&levels, index)?)))
+    else {
+        return;
+    };
+
+    start_level(index, level, &stats, &mut mode, &mut config, &mut run);
+    next_state.set(AppState::Playing);
+}
+
+/// 载入关卡脚本并记录开始时的死亡次数
+fn start_level(
+    index: usize,
+    level: &CampaignLevel,
+    stats: &GameStats,
+    mode: &mut GameMode,
+    config: &mut GameConfig,
+    run: &mut CampaignRun,
+) {
+    *mode = GameMode::Campaign(index);
+    config.level = level.script.clone();
+    run.deaths_at_start = stats.player_deaths;
+}
+
+/// 启动关卡系统 - 带有--level参数时，首次进入主菜单后等该关卡加载完成直接开始（不检查解锁）
+#[allow(clippy::too_many_arguments)] // 开始关卡需要同时设置多个资源
+fn launch_level_system(
+    options: Res<LaunchOptions>,
+    mut launched: Local<bool>,
+    asset_server: Res<AssetServer>,
+    campaign: Res<Campaign>,
+    levels: Res<Assets<CampaignLevel>>,
+    stats: Res<GameStats>,
+    mut mode: ResMut<GameMode>,
+    mut config: ResMut<GameConfig>,
+    mut run: ResMut<CampaignRun>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let Some(index) = options.level.filter(|_| !*launched) else {
+        return;
+    };
+    let Some(handle) = campaign.0.get(index) else {
+        *launched = true;
+        warn!("启动参数指定的关卡不存在: {}", index + 1);
+        return;
+    };
+    if asset_server.load_state(handle).is_failed() {
+        *launched = true;
+        warn!("启动参数指定的关卡加载失败: {}", index + 1);
+        return;
+    }
+    let Some(level) = levels.get(handle) else {
+        return; // 等待关卡加载完成
+    };
+    *launched = true;
+    start_level(index, level, &stats, &mut mode, &mut config, &mut run);
+    next_state.set(AppState::Playing);
+}
+
+/// 关卡重载系统 - 正在进行的战役关卡文件被修改（dev特性下热重载）时替换关卡脚本，之后的波次使用新的力场和台词
+fn level_reload_system(
+    mode: Res<GameMode>,
+    campaign: Res<Campaign>,
+    levels: Res<Assets<CampaignLevel>>,
+    mut config: ResMut<GameConfig>,
+    mut events: EventReader<AssetEvent<CampaignLevel>>,
+) {
+    let GameMode::Campaign(index) = *mode else {
+        events.clear();
+        return;
+    };
+    let Some(handle) = campaign.0.get(index) else {
+        return;
+    };
+    let modified = events.read().any(|event| event.is_modified(handle.id()));
+    if !modified {
+        return;
+    }
+    let Some(level) = levels.get(handle) else {
+        return;
+    };
+    config.level = level.script.clone();
+    info!(level = %level.name, "重新载入关卡脚本");
+}
+
+/// 战役完成系统 - 结算星级并保存，结束本局后返回关卡选择
+fn campaign_complete_system(
+    mode: Res<GameMode>,
+    campaign: Res<Campaign>,
+    levels: Res<Assets<CampaignLevel>>,
+    run: Res<CampaignRun>,
+    stats: Res<GameStats>,
+    mut profile: ResMut<Profile>,
+    mut game_over: EventWriter<GameOverEvent>,
+) {
+    let GameMode::Campaign(index) = *mode else {
+        return;
+    };
+    let Some(level) = campaign.level(&levels, index) else {
+        return;
+    };
+    let stars = stars_for(stats.player_deaths - run.deaths_at_start);
+    profile.record_level_stars(index, stars);
+    profile.save();
+    info!(level = %level.name, stars, "战役关卡完成");
+    game_over.send(GameOverEvent::to(AppState::LevelSelect));
+}
@@ -0,0 +1,90 @@
+use crate::components::{Explosion, Laser, SpawnTick};
+use crate::config::GameConfig;
+use crate::pool::recycle;
+use crate::settings::Settings;
+use crate::sim::RollbackAppExt;
+use bevy::ecs::query::QueryFilter;
+use bevy::prelude::*;
+
+// 低画质下爆炸上限为配置上限的几分之一
+const LOW_GRAPHICS_EXPLOSION_DIVISOR: usize = 4;
+
+/// 生成计数资源 - 下一个分配的生成序号
+#[derive(Resource, Clone, Default)]
+struct SpawnCounter(u64);
+
+/// 实体数量上限插件 - 激光和爆炸超出配置的上限时清理最早生成的，防止长时间游戏或逻辑错误导致实体无限增长
+/// 游戏中没有粒子实体，压力测试弹幕的数量由压力测试自身限制，都不在此清理
+pub struct CapsPlugin;
+
+impl Plugin for CapsPlugin {
+    fn build(&self, app: &mut App) {
+        // 初始化生成计数资源
+        app.init_resource::<SpawnCounter>()
+            .rollback_resource::<SpawnCounter>()
+            // 每帧末尾：记录新实体的生成序号，再按上限清理
+            .add_systems(
+                Last,
+                (spawn_tick_system, laser_cap_system, explosion_cap_system).chain(),
+            );
+    }
+}
+
+/// 生成序号系统 - 为新生成（或从对象池复用）的激光和爆炸记录生成序号
+fn spawn_tick_system(
+    mut commands: Commands,
+    mut counter: ResMut<SpawnCounter>,
+    query: Query<Entity, Or<(Added<Laser>, Added<Explosion>)>>,
+) {
+    for entity in &query {
+        commands.entity(entity).insert(SpawnTick(counter.0));
+        counter.0 += 1;
+    }
+}
+
+/// 激光上限系统 - 超出上限时回收最早的激光
+fn laser_cap_system(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    query: Query<(Entity, &SpawnTick), With<Laser>>,
+) {
+    for entity in oldest_over_cap(&query, config.tuning.laser_cap) {
+        commands.entity(entity).queue(recycle);
+    }
+}
+
+/// 爆炸上限系统 - 超出上限时销毁最早的爆炸（低画质下上限降低）
+fn explosion_cap_system(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    settings: Res<Settings>,
+    query: Query<(Entity, &SpawnTick), With<Explosion>>,
+) {
+    let cap = if settings.graphics.is_low() {
+        config.tuning.explosion_cap / LOW_GRAPHICS_EXPLOSION_DIVISOR
+    } else {
+        config.tuning.explosion_cap
+    };
+    for entity in oldest_over_cap(&query, cap) {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// 找出超出上限的部分中最早生成的实体
+fn oldest_over_cap<F: QueryFilter>(
+    query: &Query<(Entity, &SpawnTick), F>,
+    cap: usize,
+) -> Vec<Entity> {
+    let count = query.iter().count();
+    if count <= cap {
+        return Vec::new();
+    }
+    let mut entities: Vec<(Entity, SpawnTick)> =
+        query.iter().map(|(entity, tick)| (entity, *tick)).collect();
+    entities.sort_unstable_by_key(|(_, tick)| *tick);
+    entities
+        .into_iter()
+        .take(count - cap)
+        .map(|(entity, _)| entity)
+        .collect()
+}
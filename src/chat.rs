@@ -0,0 +1,278 @@
+use crate::action::{ActionFilters, ActionState, ActionSystem};
+use crate::net::{NetEvent, NetMessage, NetSession};
+use crate::wave::{Wave, WavePhase};
+use crate::{AppState, GameSet};
+use bevy::input::InputSystem;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+// 同时显示的聊天消息数
+const CHAT_LINES: usize = 5;
+// 聊天消息的显示时间（秒）
+const CHAT_SHOW_SECS: f32 = 8.;
+// 显示时间的最后一段逐渐淡出（秒）
+const CHAT_FADE_SECS: f32 = 2.;
+// 一条消息的最大长度（字符）
+const CHAT_MAX_CHARS: usize = 60;
+// 主机在聊天中的显示名
+const HOST_NAME: &str = "P1";
+
+/// 一条聊天消息
+struct ChatLine {
+    from: String, // 发送者
+    text: String, // 内容
+    age: f32,     // 已显示的时间（秒）
+}
+
+/// 聊天资源 - 正在输入的消息和最近收到的消息
+#[derive(Resource, Default)]
+struct Chat {
+    typing: Option<String>,    // 正在输入的消息（未在输入时为None）
+    lines: VecDeque<ChatLine>, // 最近的消息（最新的在最后）
+}
+
+impl Chat {
+    /// 添加一条消息，超出显示数量时丢弃最早的
+    fn push(&mut self, from: String, text: String) {
+        self.lines.push_back(ChatLine {
+            from,
+            text,
+            age: 0.,
+        });
+        if self.lines.len() > CHAT_LINES {
+            self.lines.pop_front();
+        }
+    }
+}
+
+/// 聊天消息文本标记（下标0是最早的一行）
+#[derive(Component)]
+struct ChatLineText(usize);
+
+/// 聊天输入框文本标记
+#[derive(Component)]
+struct ChatInputText;
+
+/// 聊天插件 - 联机对局中按回车输入聊天消息（回车发送、Esc关闭），消息通过联机连接发送，左下角显示最近几条并逐渐淡出；
+/// 输入期间屏蔽全部玩法动作（键盘、手柄和自动驾驶）
+pub struct ChatPlugin;
+
+impl Plugin for ChatPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Chat>()
+            // 创建聊天界面
+            .add_systems(Startup, chat_setup_system)
+            // 在读取设备输入之后、转换为动作之前处理聊天按键，输入期间清空按键状态，玩法系统读不到打字的按键
+            .add_systems(
+                PreUpdate,
+                chat_input_system
+                    .after(InputSystem)
+                    .before(ActionSystem)
+                    .run_if(in_state(AppState::Playing)),
+            )
+            // 输入期间在动作汇总之后屏蔽全部动作，手柄和自动驾驶的输入同样进不了步长输入
+            .add_systems(
+                PreUpdate,
+                chat_action_filter_system
+                    .in_set(ActionFilters)
+                    .run_if(in_state(AppState::Playing)),
+            )
+            // 收发聊天消息
+            .add_systems(Update, chat_net_system.run_if(in_state(AppState::Playing)))
+            // 刷新聊天界面
+            .add_systems(Update, chat_display_system.in_set(GameSet::Presentation));
+    }
+}
+
+/// 聊天界面初始化系统 - 左下角的消息列表和输入框
+fn chat_setup_system(mut commands: Commands) {
+    let font = TextFont {
+        font_size: 16.,
+        ..Default::default()
+    };
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(8.),
+                bottom: Val::Px(160.),
+                flex_direction: FlexDirection::Column,
+                ..Default::default()
+            },
+            GlobalZIndex(6),
+        ))
+        .with_children(|parent| {
+            for index in 0..CHAT_LINES {
+                parent.spawn((ChatLineText(index), Text::new(""), font.clone()));
+            }
+            parent.spawn((
+                ChatInputText,
+                Text::new(""),
+                font.clone(),
+                TextColor(Color::srgb(1., 1., 0.6)),
+                Visibility::Hidden,
+            ));
+        });
+}
+
+/// 聊天按键系统 - 联机对局中回车开始输入（商店开放时回车用于开始下一波），输入中回车发送、Esc关闭、退格删除
+fn chat_input_system(
+    mut typed: EventReader<KeyboardInput>,
+    mut kb: ResMut<ButtonInput<KeyCode>>,
+    mut chat: ResMut<Chat>,
+    mut session: ResMut<NetSession>,
+    wave: Res<Wave>,
+) {
+    if matches!(*session, NetSession::Offline | NetSession::Joining(_)) {
+        chat.typing = None;
+        return;
+    }
+
+    let Some(mut message) = chat.typing.take() else {
+        typed.clear();
+        if kb.just_pressed(KeyCode::Enter) && wave.phase != WavePhase::Shop {
+            chat.typing = Some(String::new());
+            kb.reset_all();
+        }
+        return;
+    };
+
+    let mut send = false;
+    let mut close = false;
+    for event in typed.read().filter(|event| event.state.is_pressed()) {
+        match &event.logical_key {
+            Key::Enter => send = true,
+            Key::Escape => close = true,
+            Key::Backspace => {
+                message.pop();
+            }
+            Key::Character(text) if message.chars().count() < CHAT_MAX_CHARS => {
+                message.extend(text.chars().filter(|c| !c.is_control()));
+            }
+            _ => {}
+        }
+    }
+    // 输入期间直接读取键盘的系统也读不到打字的按键
+    kb.reset_all();
+
+    if send {
+        let text = message.trim().to_string();
+        if !text.is_empty() {
+            send_chat(&mut session, &mut chat, text);
+        }
+    } else if !close {
+        chat.typing = Some(message);
+    }
+}
+
+/// 聊天动作过滤系统 - 输入聊天消息期间屏蔽本帧的全部动作
+fn chat_action_filter_system(chat: Res<Chat>, mut actions: ResMut<ActionState>) {
+    if chat.typing.is_some() {
+        actions.suppress();
+    }
+}
+
+/// 发送聊天消息：主机直接显示并广播，客户端发给主机（由主机转发回来时显示）
+fn send_chat(session: &mut NetSession, chat: &mut Chat, text: String) {
+    match session {
+        NetSession::Offline | NetSession::Joining(_) => {}
+        NetSession::Host(host) => {
+            let from = HOST_NAME.to_string();
+            host.broadcast(&NetMessage::Chat {
+                from: from.clone(),
+                text: text.clone(),
+            });
+            chat.push(from, text);
+        }
+        NetSession::Client(client) => {
+            let message = NetMessage::Chat {
+                from: String::new(),
+                text,
+            };
+            if let Err(e) = client.connection.send(&message) {
+                warn!("发送聊天消息失败: {e}");
+            }
+        }
+    }
+}
+
+/// 聊天网络系统 - 主机收到客户端的消息时填入发送者后转发给全部客户端；客户端显示主机转发的消息，与主机断开时显示提示
+fn chat_net_system(
+    mut events: EventReader<NetEvent>,
+    mut session: ResMut<NetSession>,
+    mut chat: ResMut<Chat>,
+) {
+    for event in events.read() {
+        match (&mut *session, event) {
+            (
+                NetSession::Host(host),
+                NetEvent::Received {
+                    from,
+                    message: NetMessage::Chat { text, .. },
+                },
+            ) => {
+                host.broadcast(&NetMessage::Chat {
+                    from: from.clone(),
+                    text: text.clone(),
+                });
+                chat.push(from.clone(), text.clone());
+            }
+            (
+                NetSession::Client(_),
+                NetEvent::Received {
+                    message: NetMessage::Chat { from, text },
+                    ..
+                },
+            ) => chat.push(from.clone(), text.clone()),
+            (NetSession::Offline, NetEvent::Left(_)) => {
+                chat.push(String::new(), "Host disconnected".to_string());
+            }
+            _ => {}
+        }
+    }
+}
+
+/// 聊天显示系统 - 推进消息的显示时间，最后两秒逐渐淡出；输入中显示输入框
+fn chat_display_system(
+    time: Res<Time>,
+    mut chat: ResMut<Chat>,
+    mut lines: Query<(&ChatLineText, &mut Text, &mut TextColor), Without<ChatInputText>>,
+    mut input: Query<(&mut Text, &mut Visibility), With<ChatInputText>>,
+) {
+    let delta = time.delta_secs();
+    for line in chat.lines.iter_mut() {
+        line.age += delta;
+    }
+    chat.lines.retain(|line| line.age < CHAT_SHOW_SECS);
+
+    // 最新的消息显示在最下面一行
+    let offset = CHAT_LINES - chat.lines.len();
+    for (ChatLineText(index), mut text, mut color) in &mut lines {
+        let line = index.checked_sub(offset).and_then(|i| chat.lines.get(i));
+        let Some(line) = line else {
+            text.0.clear();
+            continue;
+        };
+        text.0 = if line.from.is_empty() {
+            line.text.clone()
+        } else {
+            format!("{}: {}", line.from, line.text)
+        };
+        let alpha = ((CHAT_SHOW_SECS - line.age) / CHAT_FADE_SECS).clamp(0., 1.);
+        color.0 = Color::srgba(1., 1., 1., alpha);
+    }
+
+    let Ok((mut text, mut visibility)) = input.get_single_mut() else {
+        return;
+    };
+    match &chat.typing {
+        Some(message) => {
+            text.0 = format!("> {message}_");
+            visibility.set_if_neq(Visibility::Inherited);
+        }
+        None => {
+            visibility.set_if_neq(Visibility::Hidden);
+        }
+    }
+}
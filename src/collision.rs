@@ -0,0 +1,89 @@
+use crate::components::SpriteSize;
+use bevy::math::Vec3Swizzles;
+use bevy::math::bounding::{Aabb2d, IntersectsVolume};
+use bevy::prelude::{ChildOf, Entity, Query, Transform};
+
+/// 构建精灵的轴对齐包围盒（AABB） - 以位置为中心，尺寸按缩放比例计算
+pub fn sprite_aabb(transform: &Transform, size: &SpriteSize) -> Aabb2d {
+    Aabb2d::new(
+        transform.translation.truncate(),
+        (size.0 * transform.scale.xy()) / 2.,
+    )
+}
+
+/// 判断两个包围盒是否重叠
+pub fn overlaps(a: &Aabb2d, b: &Aabb2d) -> bool {
+    a.intersects(b)
+}
+
+/// 判断两个精灵是否发生碰撞
+pub fn sprites_collide(
+    a_tf: &Transform,
+    a_size: &SpriteSize,
+    b_tf: &Transform,
+    b_size: &SpriteSize,
+) -> bool {
+    overlaps(&sprite_aabb(a_tf, a_size), &sprite_aabb(b_tf, b_size))
+}
+
+/// 计算实体的世界变换 - 沿父实体链逐级合成相对变换（不依赖渲染阶段的GlobalTransform传播，固定步长内即时准确）
+pub fn world_transform(
+    entity: Entity,
+    query: &Query<(&Transform, Option<&ChildOf>)>,
+) -> Option<Transform> {
+    let (transform, child_of) = query.get(entity).ok()?;
+    match child_of {
+        Some(child_of) => {
+            Some(world_transform(child_of.parent(), query)?.mul_transform(*transform))
+        }
+        None => Some(*transform),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::math::{Vec2, Vec3};
+
+    fn sprite_at(x: f32, y: f32, scale: f32) -> Transform {
+        Transform::from_xyz(x, y, 0.).with_scale(Vec3::new(scale, scale, 1.))
+    }
+
+    #[test]
+    fn aabb_uses_scaled_half_size() {
+        let aabb = sprite_aabb(&sprite_at(10., 20., 0.5), &SpriteSize::from((144., 75.)));
+        assert_eq!(aabb.min, Vec2::new(10. - 36., 20. - 18.75));
+        assert_eq!(aabb.max, Vec2::new(10. + 36., 20. + 18.75));
+    }
+
+    #[test]
+    fn overlapping_sprites_collide() {
+        let size = SpriteSize::from((20., 20.));
+        assert!(sprites_collide(
+            &sprite_at(0., 0., 1.),
+            &size,
+            &sprite_at(15., 15., 1.),
+            &size
+        ));
+    }
+
+    #[test]
+    fn distant_sprites_do_not_collide() {
+        let size = SpriteSize::from((20., 20.));
+        assert!(!sprites_collide(
+            &sprite_at(0., 0., 1.),
+            &size,
+            &sprite_at(25., 0., 1.),
+            &size
+        ));
+    }
+
+    #[test]
+    fn scale_shrinks_collision_box() {
+        // 缩放前会重叠，缩放到一半后不再重叠
+        let size = SpriteSize::from((20., 20.));
+        let a = sprite_at(0., 0., 0.5);
+        let b = sprite_at(15., 0., 0.5);
+        assert!(!sprites_collide(&a, &size, &b, &size));
+    }
+}
@@ -0,0 +1,427 @@
+use crate::boss::CoreShield;
+use crate::components::{DamageType, Enemy, EnemyKind, Health, Resistances, Team};
+use crate::enemy::{Armored, Elite, EnemySplitEvent, ScoreValue, Splitting, spawn_crit_flash};
+use crate::faction_time::Faction;
+use crate::registry::Sfx;
+use crate::sound::PlaySound;
+use crate::status::{Inflicts, inflict};
+use crate::{EnemyCount, GameSet, GameStats};
+use bevy::prelude::*;
+
+// 玩家爆炸音效的播放速度（放慢敌人爆炸音效，声音更低沉）
+const PLAYER_DEATH_SOUND_SPEED: f32 = 0.6;
+
+/// 伤害事件 - 激光命中、炸弹和燃烧对敌人造成的一次伤害，由伤害系统统一结算护盾、抗性、装甲和生命值
+#[derive(Event, Clone, Copy, Debug)]
+pub struct DamageEvent {
+    pub target: Entity,             // 受到伤害的敌人
+    pub amount: f32,                // 伤害值（普通命中为1）
+    pub kind: Option<DamageType>,   // 伤害类型（按抗性折算，为None时不折算，如燃烧）
+    pub piercing: bool,             // 是否无视抗性和装甲（如炸弹，核心护盾仍会吸收）
+    pub critical: Option<Vec3>,     // 命中弱点时的命中位置（显示暴击闪光）
+    pub inflicts: Option<Inflicts>, // 附带的状态效果（命中被装甲抵挡时施加）
+}
+
+impl DamageEvent {
+    /// 普通伤害
+    pub fn new(target: Entity, amount: f32, kind: Option<DamageType>) -> Self {
+        Self {
+            target,
+            amount,
+            kind,
+            piercing: false,
+            critical: None,
+            inflicts: None,
+        }
+    }
+
+    /// 直接击毁的伤害（无视抗性和装甲，首领的核心护盾仍会吸收）
+    pub fn lethal(target: Entity) -> Self {
+        Self {
+            piercing: true,
+            ..Self::new(target, f32::INFINITY, None)
+        }
+    }
+}
+
+/// 死亡事件 - 带有队伍的实体生命值耗尽时发送，死亡处理系统按阵营销毁实体、生成爆炸并播放爆炸音效
+#[derive(Event, Clone, Copy, Debug)]
+pub struct DeathEvent {
+    pub entity: Entity,   // 生命值耗尽的实体（死亡处理系统销毁前仍然存在）
+    pub position: Vec3,   // 死亡位置
+    pub faction: Faction, // 所属阵营
+}
+
+/// 击毁事件 - 敌人被击毁时发送，得分统计、掉落、日志和档案都响应该事件
+#[derive(Event, Clone, Copy, Debug)]
+pub struct EntityKilledEvent {
+    pub entity: Entity,  // 被击毁的敌人（已销毁）
+    pub position: Vec3,  // 击毁位置
+    pub kind: EnemyKind, // 敌人种类
+    pub score: u64,      // 击毁得分（含精英额外加分）
+}
+
+/// 爆炸请求事件 - 在指定位置生成爆炸动画
+#[derive(Event, Clone, Copy, Debug)]
+pub struct ExplosionRequested(pub Vec3); // 爆炸位置
+
+/// 战斗插件 - 注册伤害、死亡、击毁和爆炸事件；命中判定只扣除生命值（敌人通过伤害事件），
+/// 玩家和敌人的生命值耗尽后都经由死亡事件统一销毁，击毁后的得分和掉落由各模块响应击毁事件处理
+pub struct CombatPlugin;
+
+impl Plugin for CombatPlugin {
+    fn build(&self, app: &mut App) {
+        // 注册战斗事件
+        app.add_event::<DamageEvent>()
+            .add_event::<DeathEvent>()
+            .add_event::<EntityKilledEvent>()
+            .add_event::<ExplosionRequested>()
+            // 结算本帧的伤害，找出生命值耗尽的实体，记录敌人击毁后统一销毁，再按击毁事件记录得分
+            .add_systems(
+                FixedUpdate,
+                (
+                    damage_system,
+                    health_depletion_system,
+                    enemy_death_system,
+                    death_system,
+                    kill_score_system,
+                )
+                    .chain()
+                    .in_set(GameSet::Spawn),
+            );
+    }
+}
+
+/// 伤害系统 - 核心护盾吸收伤害，伤害按抗性折算后先消耗装甲，装甲不足时扣除生命值
+/// （生命值已耗尽的敌人等待本帧的死亡处理，忽略后续伤害）
+#[allow(clippy::type_complexity)] // 允许复杂的查询类型
+pub fn damage_system(
+    mut commands: Commands,
+    mut damage_events: EventReader<DamageEvent>,
+    mut query: Query<
+        (
+            &mut Health,
+            Option<&mut Armored>,
+            Option<&Resistances>,
+            Has<CoreShield>,
+        ),
+        With<Enemy>,
+    >,
+) {
+    for event in damage_events.read() {
+        let Ok((mut health, armored, resistances, shielded)) = query.get_mut(event.target) else {
+            continue;
+        };
+        if health.current <= 0. {
+            continue;
+        }
+
+        // 核心护盾吸收这次伤害（首领炮塔全部被击毁前，炸弹也无法穿透）
+        if shielded {
+            continue;
+        }
+        if event.piercing {
+            health.current -= event.amount;
+            continue;
+        }
+        let damage = event.kind.map_or(event.amount, |kind| {
+            Resistances::damage(resistances, kind, event.amount)
+        });
+        // 免疫该伤害类型时伤害被抵消
+        if damage <= 0. {
+            continue;
+        }
+        if let Some(impact) = event.critical {
+            spawn_crit_flash(&mut commands, impact);
+        }
+
+        // 装甲词缀抵挡这次伤害（剩余可抵挡的伤害足够时），并承受附带的状态效果
+        if let Some(mut armored) = armored.filter(|armored| armored.hits >= damage) {
+            armored.hits -= damage;
+            if let Some(inflicts) = event.inflicts {
+                commands
+                    .entity(event.target)
+                    .queue(inflict(inflicts.kind, inflicts.secs));
+            }
+            continue;
+        }
+        health.current -= damage;
+    }
+}
+
+/// 生命值耗尽系统 - 玩家和敌人的生命值耗尽时按所属队伍发送死亡事件（敌人部件不带队伍，由部件命中系统单独处理）
+fn health_depletion_system(
+    query: Query<(Entity, &Transform, &Health, &Team)>,
+    mut death_events: EventWriter<DeathEvent>,
+) {
+    for (entity, transform, health, team) in &query {
+        if health.current <= 0. {
+            death_events.send(DeathEvent {
+                entity,
+                position: transform.translation,
+                faction: team.0,
+            });
+        }
+    }
+}
+
+/// 敌人击毁系统 - 敌人死亡时减少敌人数量，发送击毁事件（含精英额外加分），分裂词缀的敌人请求分裂
+fn enemy_death_system(
+    mut enemy_count: ResMut<EnemyCount>,
+    mut death_events: EventReader<DeathEvent>,
+    query: Query<
+        (
+            &EnemyKind,
+            Option<&ScoreValue>,
+            Option<&Elite>,
+            Has<Splitting>,
+        ),
+        With<Enemy>,
+    >,
+    mut killed_events: EventWriter<EntityKilledEvent>,
+    mut split_events: EventWriter<EnemySplitEvent>,
+) {
+    for event in death_events.read() {
+        if event.faction != Faction::Enemy {
+            continue;
+        }
+        let Ok((kind, score, elite, splitting)) = query.get(event.entity) else {
+            continue;
+        };
+        enemy_count.0 -= 1; // 减少敌人数量
+        killed_events.send(EntityKilledEvent {
+            entity: event.entity,
+            position: event.position,
+            kind: *kind,
+            score: ScoreValue::of(score) + elite.map_or(0, |elite| elite.bonus),
+        });
+        if splitting {
+            split_events.send(EnemySplitEvent(event.position));
+        }
+    }
+}
+
+/// 死亡处理系统 - 销毁死亡的实体，在死亡位置生成爆炸并播放爆炸音效（玩家的爆炸音效放慢播放）
+pub fn death_system(
+    mut commands: Commands,
+    mut death_events: EventReader<DeathEvent>,
+    mut explosions: EventWriter<ExplosionRequested>,
+    mut sounds: EventWriter<PlaySound>,
+) {
+    for event in death_events.read() {
+        commands.entity(event.entity).despawn();
+        explosions.send(ExplosionRequested(event.position));
+        let speed = match event.faction {
+            Faction::Player => PLAYER_DEATH_SOUND_SPEED,
+            Faction::Enemy => 1.,
+        };
+        sounds.send(PlaySound::at(Sfx::EnemyExplosion, event.position).with_speed(speed));
+    }
+}
+
+/// 得分系统 - 响应击毁事件记录击毁数量并增加得分
+fn kill_score_system(mut stats: ResMut<GameStats>, mut events: EventReader<EntityKilledEvent>) {
+    for event in events.read() {
+        stats.enemies_killed += 1; // 记录击毁数量
+        stats.score += event.score; // 增加得分（精英额外加分）
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{InputScript, headless_app, run_frames};
+
+    // 结算一次伤害事件所需的帧数（留出固定步长追上的余量）
+    const SETTLE_FRAMES: u32 = 3;
+
+    /// 生成一个带有生命值和得分的敌人（同时计入敌人数量）
+    fn spawn_enemy(app: &mut App, hp: f32, score: u64) -> Entity {
+        app.world_mut().resource_mut::<EnemyCount>().0 += 1;
+        app.world_mut()
+            .spawn((
+                Enemy,
+                Team::ENEMY,
+                EnemyKind::Basic,
+                Health::full(hp),
+                ScoreValue(score),
+                Transform::default(),
+            ))
+            .id()
+    }
+
+    /// 发送伤害事件并运行到结算完成
+    fn damage(app: &mut App, events: &[DamageEvent]) {
+        for event in events {
+            app.world_mut().send_event(*event);
+        }
+        run_frames(app, SETTLE_FRAMES);
+    }
+
+    fn health(app: &App, entity: Entity) -> f32 {
+        app.world().get::<Health>(entity).unwrap().current
+    }
+
+    #[test]
+    fn hit_reduces_health_without_kill() {
+        let mut app = headless_app(InputScript::default());
+        let enemy = spawn_enemy(&mut app, 3., 10);
+
+        damage(&mut app, &[DamageEvent::new(enemy, 1., None)]);
+        assert_eq!(health(&app, enemy), 2.);
+        let stats = app.world().resource::<GameStats>();
+        assert_eq!(stats.score, 0);
+        assert_eq!(stats.enemies_killed, 0);
+    }
+
+    #[test]
+    fn depleting_health_kills_and_scores() {
+        let mut app = headless_app(InputScript::default());
+        let enemy = spawn_enemy(&mut app, 2., 25);
+
+        damage(
+            &mut app,
+            &[
+                DamageEvent::new(enemy, 1., None),
+                DamageEvent::new(enemy, 1., None),
+            ],
+        );
+        assert!(app.world().get_entity(enemy).is_err());
+        let stats = app.world().resource::<GameStats>();
+        assert_eq!(stats.score, 25);
+        assert_eq!(stats.enemies_killed, 1);
+        assert_eq!(app.world().resource::<EnemyCount>().0, 0);
+    }
+
+    #[test]
+    fn overkill_in_one_step_scores_once() {
+        let mut app = headless_app(InputScript::default());
+        let enemy = spawn_enemy(&mut app, 1., 25);
+
+        damage(
+            &mut app,
+            &[
+                DamageEvent::lethal(enemy),
+                DamageEvent::new(enemy, 1., None),
+            ],
+        );
+        let stats = app.world().resource::<GameStats>();
+        assert_eq!(stats.score, 25);
+        assert_eq!(stats.enemies_killed, 1);
+    }
+
+    #[test]
+    fn elite_kill_adds_bonus_score() {
+        let mut app = headless_app(InputScript::default());
+        let enemy = spawn_enemy(&mut app, 1., 10);
+        app.world_mut()
+            .entity_mut(enemy)
+            .insert(Elite { bonus: 40 });
+
+        damage(&mut app, &[DamageEvent::new(enemy, 1., None)]);
+        assert_eq!(app.world().resource::<GameStats>().score, 50);
+    }
+
+    #[test]
+    fn core_shield_absorbs_lethal_damage() {
+        let mut app = headless_app(InputScript::default());
+        let enemy = spawn_enemy(&mut app, 5., 10);
+        app.world_mut().entity_mut(enemy).insert(CoreShield);
+
+        damage(&mut app, &[DamageEvent::lethal(enemy)]);
+        assert_eq!(health(&app, enemy), 5.);
+        assert_eq!(app.world().resource::<GameStats>().score, 0);
+    }
+
+    /// 生成一个带有抗性的敌人（动能减半、能量加倍、免疫爆炸）
+    fn spawn_resistant(app: &mut App, hp: f32) -> Entity {
+        let enemy = spawn_enemy(app, hp, 10);
+        app.world_mut().entity_mut(enemy).insert(Resistances {
+            kinetic: 0.5,
+            energy: 2.,
+            explosive: 0.,
+        });
+        enemy
+    }
+
+    fn armor(app: &App, entity: Entity) -> f32 {
+        app.world().get::<Armored>(entity).unwrap().hits
+    }
+
+    #[test]
+    fn resistances_scale_damage_per_type() {
+        let mut app = headless_app(InputScript::default());
+        let cases = [
+            (Some(DamageType::Kinetic), 9.5),
+            (Some(DamageType::Energy), 8.),
+            (Some(DamageType::Explosive), 10.),
+            (None, 9.),
+        ];
+        let enemies: Vec<Entity> = cases
+            .iter()
+            .map(|_| spawn_resistant(&mut app, 10.))
+            .collect();
+
+        let events: Vec<DamageEvent> = enemies
+            .iter()
+            .zip(cases)
+            .map(|(&enemy, (kind, _))| DamageEvent::new(enemy, 1., kind))
+            .collect();
+        damage(&mut app, &events);
+        for (&enemy, (kind, expected)) in enemies.iter().zip(cases) {
+            assert_eq!(health(&app, enemy), expected, "{kind:?}");
+        }
+    }
+
+    #[test]
+    fn armor_absorbs_damage_after_resistances() {
+        let mut app = headless_app(InputScript::default());
+        let enemy = spawn_resistant(&mut app, 10.);
+        app.world_mut()
+            .entity_mut(enemy)
+            .insert(Armored { hits: 1. });
+
+        let kinetic = DamageEvent::new(enemy, 1., Some(DamageType::Kinetic));
+        damage(&mut app, &[kinetic, kinetic]);
+        assert_eq!(armor(&app, enemy), 0.);
+        assert_eq!(health(&app, enemy), 10.);
+
+        damage(&mut app, &[kinetic]);
+        assert_eq!(health(&app, enemy), 9.5);
+    }
+
+    #[test]
+    fn hit_stronger_than_armor_reaches_health() {
+        let mut app = headless_app(InputScript::default());
+        let enemy = spawn_resistant(&mut app, 10.);
+        app.world_mut()
+            .entity_mut(enemy)
+            .insert(Armored { hits: 1. });
+
+        damage(
+            &mut app,
+            &[DamageEvent::new(enemy, 1., Some(DamageType::Energy))],
+        );
+        assert_eq!(armor(&app, enemy), 1.);
+        assert_eq!(health(&app, enemy), 8.);
+    }
+
+    #[test]
+    fn piercing_ignores_resistances_and_armor() {
+        let mut app = headless_app(InputScript::default());
+        let enemy = spawn_resistant(&mut app, 10.);
+        app.world_mut()
+            .entity_mut(enemy)
+            .insert(Armored { hits: 5. });
+
+        damage(
+            &mut app,
+            &[DamageEvent {
+                piercing: true,
+                ..DamageEvent::new(enemy, 3., Some(DamageType::Explosive))
+            }],
+        );
+        assert_eq!(armor(&app, enemy), 5.);
+        assert_eq!(health(&app, enemy), 7.);
+    }
+}
@@ -0,0 +1,273 @@
+use crate::GameSet;
+use crate::config::{CommsSpec, CommsTrigger, GameConfig};
+use crate::wave::WaveEvent;
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::collections::VecDeque;
+
+// 打字机效果每秒显示的字符数
+const COMMS_CHARS_PER_SEC: f32 = 40.;
+// 跳过通讯的按键（文字未打完时先显示全文，再按一次切到下一条）
+const COMMS_SKIP_KEY: KeyCode = KeyCode::Tab;
+// 头像的边长
+const COMMS_PORTRAIT_SIZE: f32 = 48.;
+
+/// 通讯角色
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+pub enum Speaker {
+    Command, // 指挥部
+    Boss,    // 首领
+}
+
+impl Speaker {
+    /// 通讯框中显示的角色名
+    fn name(self) -> &'static str {
+        match self {
+            Speaker::Command => "COMMAND",
+            Speaker::Boss => "DREADNOUGHT",
+        }
+    }
+
+    /// 头像底色
+    fn color(self) -> Color {
+        match self {
+            Speaker::Command => Color::srgb(0.2, 0.5, 0.9),
+            Speaker::Boss => Color::srgb(0.8, 0.2, 0.2),
+        }
+    }
+}
+
+/// 正在显示的通讯台词
+struct ActiveMessage {
+    spec: CommsSpec, // 台词内容
+    elapsed: f32,    // 已显示的时间（秒）
+}
+
+impl ActiveMessage {
+    /// 打完全部文字所需的时间（秒）
+    fn typing_secs(&self) -> f32 {
+        self.spec.text.chars().count() as f32 / COMMS_CHARS_PER_SEC
+    }
+
+    /// 打字机效果当前显示的文字
+    fn visible_text(&self) -> String {
+        let count = (self.elapsed * COMMS_CHARS_PER_SEC) as usize;
+        self.spec.text.chars().take(count).collect()
+    }
+}
+
+/// 通讯资源 - 等待显示的台词队列和当前台词
+#[derive(Resource, Default)]
+struct Comms {
+    queue: VecDeque<CommsSpec>,     // 等待显示的台词
+    current: Option<ActiveMessage>, // 当前台词
+}
+
+/// 通讯框根节点标记
+#[derive(Component)]
+struct CommsBox;
+
+/// 头像节点标记
+#[derive(Component)]
+struct CommsPortrait;
+
+/// 头像中的角色首字母标记
+#[derive(Component)]
+struct CommsInitial;
+
+/// 角色名文本标记
+#[derive(Component)]
+struct CommsName;
+
+/// 台词文本标记
+#[derive(Component)]
+struct CommsText;
+
+/// 通讯插件 - 关卡脚本按波次事件触发角色台词，在屏幕底部的通讯框中以打字机效果显示
+pub struct CommsPlugin;
+
+impl Plugin for CommsPlugin {
+    fn build(&self, app: &mut App) {
+        // 初始化通讯资源
+        app.init_resource::<Comms>()
+            // 创建通讯框（默认隐藏）
+            .add_systems(Startup, comms_setup_system)
+            // 波次事件触发台词，推进打字机效果并处理跳过
+            .add_systems(
+                Update,
+                (comms_trigger_system, comms_system, comms_ui_system)
+                    .chain()
+                    .in_set(GameSet::Presentation),
+            );
+    }
+}
+
+/// 通讯框初始化系统 - 底部居中的面板：左侧头像，右侧角色名和台词
+fn comms_setup_system(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                bottom: Val::Px(60.),
+                justify_content: JustifyContent::Center,
+                ..Default::default()
+            },
+            GlobalZIndex(5),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    CommsBox,
+                    Node {
+                        width: Val::Px(420.),
+                        padding: UiRect::all(Val::Px(8.)),
+                        column_gap: Val::Px(10.),
+                        align_items: AlignItems::Center,
+                        ..Default::default()
+                    },
+                    BackgroundColor(Color::srgba(0., 0., 0., 0.75)),
+                    Visibility::Hidden,
+                ))
+                .with_children(|panel| {
+                    // 头像（角色底色 + 首字母）
+                    panel
+                        .spawn((
+                            CommsPortrait,
+                            Node {
+                                width: Val::Px(COMMS_PORTRAIT_SIZE),
+                                height: Val::Px(COMMS_PORTRAIT_SIZE),
+                                flex_shrink: 0.,
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..Default::default()
+                            },
+                            BackgroundColor(Color::WHITE),
+                        ))
+                        .with_children(|portrait| {
+                            portrait.spawn((
+                                CommsInitial,
+                                Text::new(""),
+                                TextFont {
+                                    font_size: 28.,
+                                    ..Default::default()
+                                },
+                                TextColor(Color::WHITE),
+                            ));
+                        });
+                    // 角色名和台词
+                    panel
+                        .spawn(Node {
+                            flex_direction: FlexDirection::Column,
+                            row_gap: Val::Px(4.),
+                            ..Default::default()
+                        })
+                        .with_children(|column| {
+                            column.spawn((
+                                CommsName,
+                                Text::new(""),
+                                TextFont {
+                                    font_size: 14.,
+                                    ..Default::default()
+                                },
+                                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                            ));
+                            column.spawn((
+                                CommsText,
+                                Text::new(""),
+                                TextFont {
+                                    font_size: 16.,
+                                    ..Default::default()
+                                },
+                                TextColor(Color::WHITE),
+                            ));
+                        });
+                });
+        });
+}
+
+/// 通讯触发系统 - 波次开始和首领来袭时，把关卡脚本中对应的台词加入队列
+fn comms_trigger_system(
+    config: Res<GameConfig>,
+    mut comms: ResMut<Comms>,
+    mut events: EventReader<WaveEvent>,
+) {
+    for event in events.read() {
+        let trigger = match *event {
+            WaveEvent::Started(number) => CommsTrigger::WaveStart(number),
+            WaveEvent::BossIncoming(_) => CommsTrigger::BossIncoming,
+            WaveEvent::Cleared(_) => continue,
+        };
+        comms.queue.extend(config.level.comms_for(trigger).cloned());
+    }
+}
+
+/// 通讯系统 - 推进打字机效果，按跳过键补全文字或切到下一条，停留时间结束后自动切换
+fn comms_system(time: Res<Time>, kb: Res<ButtonInput<KeyCode>>, mut comms: ResMut<Comms>) {
+    if comms.current.is_none() {
+        let Some(spec) = comms.queue.pop_front() else {
+            return;
+        };
+        comms.current = Some(ActiveMessage { spec, elapsed: 0. });
+    }
+    let Some(message) = comms.current.as_mut() else {
+        return;
+    };
+
+    let typing_secs = message.typing_secs();
+    let finished = if kb.just_pressed(COMMS_SKIP_KEY) {
+        // 文字未打完时先显示全文，已打完时直接结束
+        let typed = message.elapsed >= typing_secs;
+        message.elapsed = message.elapsed.max(typing_secs);
+        typed
+    } else {
+        message.elapsed += time.delta_secs();
+        message.elapsed >= typing_secs + message.spec.secs
+    };
+
+    if finished {
+        comms.current = None;
+    }
+}
+
+/// 通讯界面系统 - 有台词时显示通讯框并刷新头像、角色名和已打出的文字
+#[allow(clippy::type_complexity)] // 允许复杂的查询类型
+fn comms_ui_system(
+    comms: Res<Comms>,
+    mut box_query: Query<&mut Visibility, With<CommsBox>>,
+    mut portrait_query: Query<&mut BackgroundColor, With<CommsPortrait>>,
+    mut text_query: Query<
+        (&mut Text, Has<CommsInitial>, Has<CommsName>, Has<CommsText>),
+        Or<(With<CommsInitial>, With<CommsName>, With<CommsText>)>,
+    >,
+) {
+    for mut visibility in &mut box_query {
+        visibility.set_if_neq(if comms.current.is_some() {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        });
+    }
+    let Some(message) = &comms.current else {
+        return;
+    };
+
+    let speaker = message.spec.speaker;
+    for mut color in &mut portrait_query {
+        color.set_if_neq(BackgroundColor(speaker.color()));
+    }
+    for (mut text, initial, name, line) in &mut text_query {
+        let content = if initial {
+            speaker.name().chars().take(1).collect()
+        } else if name {
+            speaker.name().to_string()
+        } else if line {
+            message.visible_text()
+        } else {
+            continue;
+        };
+        if text.0 != content {
+            text.0 = content;
+        }
+    }
+}
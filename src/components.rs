@@ -1,27 +1,32 @@
-use bevy::math::{Vec2, Vec3};
+use crate::faction_time::Faction;
+use crate::rng::GameRng;
+use bevy::math::Vec2;
 use bevy::prelude::Component;
 use bevy::time::{Timer, TimerMode};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 
 // region:    --- 通用组件
 /// 速度组件 - 控制实体的移动速度
-#[derive(Component)]
+#[derive(Component, Clone)]
 pub struct Velocity {
     pub x: f32, // X轴方向速度
     pub y: f32, // Y轴方向速度
 }
 
 /// 可移动组件 - 标记实体可以移动并控制自动销毁行为
-#[derive(Component)]
+#[derive(Component, Clone)]
 pub struct Movable {
     pub auto_despawn: bool, // 是否超出屏幕后自动销毁
 }
 
 /// 激光组件 - 标记实体为激光
-#[derive(Component)]
+#[derive(Component, Clone)]
+#[require(InRun)]
 pub struct Laser;
 
 /// 精灵尺寸组件 - 存储精灵的大小
-#[derive(Component)]
+#[derive(Component, Clone)]
 pub struct SpriteSize(pub Vec2);
 
 /// 从元组(f32, f32)转换为SpriteSize的实现
@@ -30,39 +35,145 @@ impl From<(f32, f32)> for SpriteSize {
         SpriteSize(Vec2::new(val.0, val.1))
     }
 }
+
+/// 开火冷却组件 - 控制两次射击之间的最短间隔
+#[derive(Component, Clone)]
+pub struct FireCooldown(pub Timer);
+
+impl FireCooldown {
+    /// 创建一个已就绪的冷却计时器（生成后可立即开火）
+    pub fn ready(secs: f32) -> Self {
+        let mut timer = Timer::from_seconds(secs, TimerMode::Once);
+        let duration = timer.duration();
+        timer.tick(duration);
+        Self(timer)
+    }
+}
+
+/// 伤害类型组件 - 标记玩家弹药的伤害类型，与目标的抗性共同决定实际伤害
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Component)]
+pub enum DamageType {
+    Kinetic, // 动能：散射弹
+    #[default]
+    Energy, // 能量：标准激光、光束（未标记的激光按能量计算）
+    Explosive, // 爆炸：导弹、重型鱼雷及其爆炸波及
+}
+
+/// 抗性组件 - 各伤害类型的伤害倍率（小于1为抵抗，大于1为弱点，0为免疫）
+#[derive(Clone, Copy, Debug, Component)]
+pub struct Resistances {
+    pub kinetic: f32,   // 动能伤害倍率
+    pub energy: f32,    // 能量伤害倍率
+    pub explosive: f32, // 爆炸伤害倍率
+}
+
+impl Resistances {
+    /// 指定伤害类型的倍率
+    pub fn multiplier(&self, damage_type: DamageType) -> f32 {
+        match damage_type {
+            DamageType::Kinetic => self.kinetic,
+            DamageType::Energy => self.energy,
+            DamageType::Explosive => self.explosive,
+        }
+    }
+
+    /// 计算一次命中的实际伤害（没有抗性组件的目标按1倍计算）
+    pub fn damage(resistances: Option<&Self>, damage_type: DamageType, base: f32) -> f32 {
+        base * resistances.map_or(1., |resistances| resistances.multiplier(damage_type))
+    }
+}
+
+/// 生成序号组件 - 实体生成（或从对象池复用）时的递增序号，数量超出上限时按序号清理最早的实体
+#[derive(Component, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SpawnTick(pub u64);
+
+/// 本局实体标记 - 只属于一局游戏的实体（玩家、敌人、激光、拾取物、爆炸和危险区域等组件自动带有），本局结束时统一销毁
+#[derive(Component, Clone, Default)]
+pub struct InRun;
+
+/// 队伍组件 - 战机（玩家、敌人）和投射物（激光、流星等障碍物）所属的阵营，投射物只命中其他队伍的战机
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+#[require(InRun)]
+pub struct Team(pub Faction);
+
+impl Team {
+    pub const PLAYER: Team = Team(Faction::Player); // 玩家队伍
+    pub const ENEMY: Team = Team(Faction::Enemy); // 敌方队伍
+
+    /// 是否与另一队伍敌对
+    pub fn hostile(self, other: Team) -> bool {
+        self != other
+    }
+}
 // endregion: --- 通用组件
 
 // region:    --- 玩家相关组件
 /// 玩家组件 - 标记玩家实体
-#[derive(Component)]
+#[derive(Component, Clone)]
+#[require(InRun)]
 pub struct Player;
 
-/// 玩家来源组件 - 标记实体来自玩家(如玩家发射的激光)
-#[derive(Component)]
-pub struct FromPlayer;
+/// 移动速度组件 - 玩家按方向键时的速度大小
+#[derive(Component, Clone)]
+pub struct MoveSpeed(pub f32);
+
+/// 生命值组件 - 承受伤害，降到0时被击毁（玩家、敌人部件）
+#[derive(Component, Clone)]
+pub struct Health {
+    pub current: f32, // 当前生命值
+    pub max: f32,     // 最大生命值
+}
+
+impl Health {
+    /// 创建满生命值的组件
+    pub fn full(max: f32) -> Self {
+        Self { current: max, max }
+    }
+
+    /// 剩余生命值比例（0~1）
+    pub fn fraction(&self) -> f32 {
+        self.current / self.max
+    }
+}
 // endregion: --- 玩家相关组件
 
 // region:    --- 敌人相关组件
 /// 敌人组件 - 标记敌人实体
-#[derive(Component)]
+#[derive(Component, Clone)]
+#[require(InRun)]
 pub struct Enemy;
 
-/// 敌人来源组件 - 标记实体来自敌人(如敌人发射的激光)
-#[derive(Component)]
-pub struct FromEnemy;
+/// 敌人种类组件 - 决定掉落表等与种类相关的数据
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Component, Serialize, Deserialize)]
+pub enum EnemyKind {
+    #[default]
+    Basic, // 普通编队敌人
+    Boss, // 首领
+}
+
+/// 敌人开火间隔组件 - 每次开火后在[min, max)范围内随机下一次开火间隔（秒）
+#[derive(Clone, Copy, Component)]
+pub struct FirePeriod {
+    pub min: f32, // 最短间隔
+    pub max: f32, // 最长间隔
+}
+
+impl FirePeriod {
+    /// 随机生成一个开火间隔
+    pub fn roll(&self, rng: &mut GameRng) -> f32 {
+        rng.gen_range(self.min..self.max)
+    }
+}
 // endregion: --- 敌人相关组件
 
 // region:    --- 爆炸效果相关组件
 /// 爆炸组件 - 标记爆炸实体
-#[derive(Component)]
+#[derive(Component, Clone)]
+#[require(InRun)]
 pub struct Explosion;
 
-/// 待生成爆炸组件 - 存储爆炸生成位置
-#[derive(Component)]
-pub struct ExplosionToSpawn(pub Vec3); // 爆炸位置
-
 /// 爆炸计时器组件 - 控制爆炸动画的播放速度
-#[derive(Component)]
+#[derive(Component, Clone)]
 pub struct ExplosionTimer(pub Timer);
 
 /// 爆炸计时器默认实现 - 设置为每0.05秒触发一次的重复计时器
@@ -0,0 +1,227 @@
+use crate::comms::Speaker;
+use crate::registry::read_asset_text;
+use crate::theme::SpriteTheme;
+use crate::{BASE_SPEED, ENEMY_MAX, ENEMY_SPAWN_INTERVAL, EXPLOSION_CAP, LASER_CAP};
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::str::FromStr;
+
+// 环境变量覆盖使用的前缀（如INVADERS_ENEMY_MAX）
+const ENV_PREFIX: &str = "INVADERS_";
+// 默认关卡脚本文件路径（无尽、演示等非战役模式使用）
+const DEFAULT_LEVEL_PATH: &str = "assets/default_level.ron";
+
+/// 基础玩法参数 - 默认取自代码中的常量，可在启动时用环境变量覆盖（便于CI压力测试和快速试验）
+#[derive(Clone, Copy, Debug)]
+pub struct Tuning {
+    pub enemy_max: u32,            // 同屏最大敌人数量
+    pub base_speed: f32,           // 基础移动速度
+    pub enemy_spawn_interval: f32, // 初始难度下的敌人生成间隔（秒）
+    pub laser_cap: usize,          // 同时存在的激光数量上限
+    pub explosion_cap: usize,      // 同时存在的爆炸数量上限
+}
+
+impl Default for Tuning {
+    fn default() -> Self {
+        Self {
+            enemy_max: ENEMY_MAX,
+            base_speed: BASE_SPEED,
+            enemy_spawn_interval: ENEMY_SPAWN_INTERVAL,
+            laser_cap: LASER_CAP,
+            explosion_cap: EXPLOSION_CAP,
+        }
+    }
+}
+
+/// 难度曲线配置 - 描述存活时间与难度之间的关系
+#[derive(Clone, Copy, Debug)]
+pub struct DifficultyRamp {
+    pub ramp_secs: f32,       // 从初始难度升到最高难度所需的存活时间（秒）
+    pub exponent: f32,        // 曲线指数（1为线性，大于1为先慢后快）
+    pub max_speed_scale: f32, // 最高难度下敌人速度上下限的倍率
+    pub max_spawn_scale: f32, // 最高难度下敌人生成频率的倍率
+}
+
+impl Default for DifficultyRamp {
+    fn default() -> Self {
+        Self {
+            ramp_secs: 180.,
+            exponent: 1.5,
+            max_speed_scale: 1.6,
+            max_spawn_scale: 2.5,
+        }
+    }
+}
+
+impl DifficultyRamp {
+    /// 计算存活时间对应的难度进度（0表示初始难度，1表示最高难度）
+    pub fn progress(&self, survived_secs: f32) -> f32 {
+        (survived_secs / self.ramp_secs)
+            .clamp(0., 1.)
+            .powf(self.exponent)
+    }
+
+    /// 计算存活时间对应的敌人速度倍率
+    pub fn speed_scale(&self, survived_secs: f32) -> f32 {
+        1. + (self.max_speed_scale - 1.) * self.progress(survived_secs)
+    }
+
+    /// 计算存活时间对应的敌人生成频率倍率
+    pub fn spawn_scale(&self, survived_secs: f32) -> f32 {
+        1. + (self.max_spawn_scale - 1.) * self.progress(survived_secs)
+    }
+}
+
+/// 力场区域配置 - 位置和尺寸按窗口比例表示（窗口中心为原点）
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct ForceZoneSpec {
+    pub wave: u32,    // 生成力场的波次
+    pub center: Vec2, // 区域中心（-0.5~0.5倍窗口尺寸）
+    pub size: Vec2,   // 区域尺寸（0~1倍窗口尺寸）
+    pub accel: Vec2,  // 区域内施加的加速度（速度单位/秒）
+}
+
+/// 通讯触发时机
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+pub enum CommsTrigger {
+    WaveStart(u32), // 指定波次开始时
+    BossIncoming,   // 首领来袭时
+}
+
+/// 通讯消息配置 - 触发时在通讯框中显示的一条角色台词
+#[derive(Clone, Debug, Deserialize)]
+pub struct CommsSpec {
+    pub trigger: CommsTrigger, // 触发时机
+    pub speaker: Speaker,      // 发言角色
+    pub text: String,          // 台词（英文，默认字体不含中文字形）
+    #[serde(default = "CommsSpec::default_secs")]
+    pub secs: f32, // 文字打完后的停留时间（秒）
+}
+
+impl CommsSpec {
+    /// 默认停留时间
+    fn default_secs() -> f32 {
+        2.5
+    }
+}
+
+/// 关卡主题配置 - 敌人精灵组、背景图、敌人配色和背景音乐，路径相对assets目录（省略时使用默认外观且不播放音乐）
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ThemeSpec {
+    pub sprites: SpriteTheme,         // 敌人精灵组（沙漠、冰原、虚空等变体）
+    pub background: Option<String>,   // 背景图路径
+    pub enemy_tint: Option<[f32; 3]>, // 敌人精灵着色（RGB，0~1）
+    pub music: Option<String>,        // 背景音乐路径（循环播放）
+}
+
+impl ThemeSpec {
+    /// 敌人精灵的着色（未指定时不着色）
+    pub fn enemy_color(&self) -> Color {
+        self.enemy_tint
+            .map_or(Color::WHITE, |[r, g, b]| Color::srgb(r, g, b))
+    }
+}
+
+/// 关卡脚本 - 按波次安排的环境效果和通讯台词（战役关卡从关卡文件读取，其他模式从默认关卡脚本文件读取，缺省字段为空）
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct LevelScript {
+    #[serde(default)]
+    pub force_zones: Vec<ForceZoneSpec>, // 各波次的力场区域
+    #[serde(default)]
+    pub comms: Vec<CommsSpec>, // 任务简报和首领挑衅等通讯台词
+    #[serde(default)]
+    pub theme: ThemeSpec, // 关卡主题（背景、敌人配色、音乐）
+}
+
+impl LevelScript {
+    /// 从默认关卡脚本文件读取（无尽、演示等非战役模式使用），文件不存在或格式错误时没有力场和通讯台词
+    pub fn load_default() -> Self {
+        match read_asset_text(DEFAULT_LEVEL_PATH) {
+            Ok(content) => ron::from_str(&content).unwrap_or_else(|e| {
+                warn!("默认关卡脚本文件格式错误: {e}");
+                Self::default()
+            }),
+            Err(e) => {
+                warn!("读取默认关卡脚本文件失败: {e}");
+                Self::default()
+            }
+        }
+    }
+
+    /// 指定波次的力场区域
+    pub fn force_zones_for(&self, wave: u32) -> impl Iterator<Item = &ForceZoneSpec> {
+        self.force_zones
+            .iter()
+            .filter(move |spec| spec.wave == wave)
+    }
+
+    /// 指定时机触发的通讯台词
+    pub fn comms_for(&self, trigger: CommsTrigger) -> impl Iterator<Item = &CommsSpec> {
+        self.comms
+            .iter()
+            .filter(move |spec| spec.trigger == trigger)
+    }
+}
+
+/// 游戏配置资源 - 集中存放可调整的玩法参数
+#[derive(Resource, Default)]
+pub struct GameConfig {
+    pub tuning: Tuning,             // 基础玩法参数
+    pub difficulty: DifficultyRamp, // 难度曲线
+    pub level: LevelScript,         // 关卡脚本
+}
+
+impl GameConfig {
+    /// 默认配置叠加环境变量覆盖
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        config.apply_overrides(|name| std::env::var(name).ok());
+        config
+    }
+
+    /// 按变量名（不含前缀）查询覆盖值并替换对应参数，取值无法解析时保留默认值
+    /// 支持：ENEMY_MAX、BASE_SPEED、ENEMY_SPAWN_INTERVAL、LASER_CAP、EXPLOSION_CAP、RAMP_SECS、RAMP_EXPONENT、MAX_SPEED_SCALE、MAX_SPAWN_SCALE
+    fn apply_overrides(&mut self, lookup: impl Fn(&str) -> Option<String>) {
+        let lookup = |name: &str| lookup(&format!("{ENV_PREFIX}{name}"));
+        override_value(&lookup, "ENEMY_MAX", &mut self.tuning.enemy_max);
+        override_value(&lookup, "BASE_SPEED", &mut self.tuning.base_speed);
+        override_value(
+            &lookup,
+            "ENEMY_SPAWN_INTERVAL",
+            &mut self.tuning.enemy_spawn_interval,
+        );
+        override_value(&lookup, "LASER_CAP", &mut self.tuning.laser_cap);
+        override_value(&lookup, "EXPLOSION_CAP", &mut self.tuning.explosion_cap);
+        override_value(&lookup, "RAMP_SECS", &mut self.difficulty.ramp_secs);
+        override_value(&lookup, "RAMP_EXPONENT", &mut self.difficulty.exponent);
+        override_value(
+            &lookup,
+            "MAX_SPEED_SCALE",
+            &mut self.difficulty.max_speed_scale,
+        );
+        override_value(
+            &lookup,
+            "MAX_SPAWN_SCALE",
+            &mut self.difficulty.max_spawn_scale,
+        );
+    }
+}
+
+/// 用覆盖值替换单个参数并打印生效的覆盖
+fn override_value<T: FromStr>(
+    lookup: &impl Fn(&str) -> Option<String>,
+    name: &str,
+    target: &mut T,
+) {
+    let Some(value) = lookup(name) else {
+        return;
+    };
+    match value.trim().parse() {
+        Ok(parsed) => {
+            *target = parsed;
+            info!("环境变量覆盖: {ENV_PREFIX}{name}={value}");
+        }
+        Err(_) => warn!("环境变量{ENV_PREFIX}{name}的取值无效，使用默认值: {value}"),
+    }
+}
@@ -0,0 +1,259 @@
+use crate::sim::RollbackAppExt;
+use crate::{AppState, GameSet};
+use bevy::ecs::entity::{EntityMapper, MapEntities};
+use bevy::prelude::*;
+use rand::Rng;
+use std::collections::VecDeque;
+
+// 字幕闪烁的周期（秒）
+const CAPTION_BLINK_SECS: f32 = 0.2;
+
+/// 过场步骤 - 过场动画由步骤列表依次执行，瞬时步骤执行后立即进入下一步
+#[derive(Clone, Debug)]
+pub enum CutsceneStep {
+    LockInput,                                       // 锁定玩家输入（瞬时）
+    UnlockInput,                                     // 恢复玩家输入（瞬时）
+    Shake { intensity: f32, secs: f32 },             // 开始屏幕震动（瞬时，震动与后续步骤同时进行）
+    SlideTo { entity: Entity, to: Vec3, secs: f32 }, // 将实体从当前位置平滑移动到目标位置
+    FlashText { text: String, secs: f32 },           // 在屏幕中央闪烁显示文字
+    Wait(f32),                                       // 等待一段时间
+}
+
+impl CutsceneStep {
+    /// 步骤的持续时间（瞬时步骤为0）
+    fn secs(&self) -> f32 {
+        match self {
+            CutsceneStep::SlideTo { secs, .. } | CutsceneStep::FlashText { secs, .. } => *secs,
+            CutsceneStep::Wait(secs) => *secs,
+            _ => 0.,
+        }
+    }
+}
+
+/// 正在执行的过场步骤
+#[derive(Clone)]
+struct ActiveStep {
+    step: CutsceneStep,  // 步骤内容
+    timer: Timer,        // 步骤计时器
+    start: Option<Vec3>, // 移动步骤的起始位置
+}
+
+/// 过场资源 - 待执行的步骤队列和当前步骤
+#[derive(Resource, Clone, Default)]
+pub struct Cutscene {
+    steps: VecDeque<CutsceneStep>, // 待执行的步骤
+    current: Option<ActiveStep>,   // 当前步骤
+}
+
+impl Cutscene {
+    /// 把一段过场的步骤加入队列
+    pub fn play(&mut self, steps: impl IntoIterator<Item = CutsceneStep>) {
+        self.steps.extend(steps);
+    }
+
+    /// 是否有过场正在执行
+    pub fn active(&self) -> bool {
+        self.current.is_some() || !self.steps.is_empty()
+    }
+}
+
+impl MapEntities for Cutscene {
+    /// 快照恢复时把移动步骤引用的实体换成重新生成的实体
+    fn map_entities<M: EntityMapper>(&mut self, mapper: &mut M) {
+        let current = self.current.iter_mut().map(|active| &mut active.step);
+        for step in self.steps.iter_mut().chain(current) {
+            if let CutsceneStep::SlideTo { entity, .. } = step {
+                *entity = mapper.get_mapped(*entity);
+            }
+        }
+    }
+}
+
+/// 输入锁定资源 - 为true时玩家的移动、射击等输入被忽略
+#[derive(Resource, Clone, Default)]
+pub struct InputLock(pub bool);
+
+/// 屏幕震动资源 - 计时结束前按逐渐减弱的幅度随机偏移摄像机
+#[derive(Resource, Default)]
+pub struct ScreenShake {
+    timer: Timer,   // 震动计时器
+    intensity: f32, // 初始震动幅度（像素）
+}
+
+impl ScreenShake {
+    /// 开始震动（覆盖正在进行的震动）
+    pub fn start(&mut self, intensity: f32, secs: f32) {
+        *self = ScreenShake {
+            timer: Timer::from_seconds(secs, TimerMode::Once),
+            intensity,
+        };
+    }
+}
+
+/// 过场字幕资源 - 当前闪烁显示的文字
+#[derive(Resource, Default)]
+struct CutsceneCaption(Option<String>);
+
+/// 过场字幕文本标记
+#[derive(Component)]
+struct CaptionText;
+
+/// 过场插件 - 按步骤列表执行过场动画（锁定输入、移动实体、闪烁字幕、屏幕震动）
+pub struct CutscenePlugin;
+
+impl Plugin for CutscenePlugin {
+    fn build(&self, app: &mut App) {
+        // 初始化过场相关资源
+        app.init_resource::<Cutscene>()
+            .init_resource::<InputLock>()
+            .rollback_resource_mapped::<Cutscene>()
+            .rollback_resource::<InputLock>()
+            .init_resource::<ScreenShake>()
+            .init_resource::<CutsceneCaption>()
+            // 创建过场字幕（默认隐藏）
+            .add_systems(Startup, caption_setup_system)
+            // 推进过场步骤（移动实体需与固定步长的模拟同步）
+            .add_systems(FixedUpdate, cutscene_system.in_set(GameSet::Movement))
+            // 每帧刷新字幕闪烁和屏幕震动（观战时摄像机由观战者控制，不复位）
+            .add_systems(
+                Update,
+                (
+                    caption_system,
+                    screen_shake_system.run_if(not(in_state(AppState::Spectating))),
+                )
+                    .in_set(GameSet::Presentation),
+            );
+    }
+}
+
+/// 过场系统 - 依次执行步骤：瞬时步骤立即生效，持续步骤计时结束后进入下一步
+fn cutscene_system(
+    time: Res<Time>,
+    mut cutscene: ResMut<Cutscene>,
+    mut input_lock: ResMut<InputLock>,
+    mut shake: ResMut<ScreenShake>,
+    mut caption: ResMut<CutsceneCaption>,
+    mut transform_query: Query<&mut Transform>,
+) {
+    // 取出下一个步骤，直到遇到需要持续一段时间的步骤
+    while cutscene.current.is_none() {
+        let Some(step) = cutscene.steps.pop_front() else {
+            return;
+        };
+        match step {
+            CutsceneStep::LockInput => input_lock.0 = true,
+            CutsceneStep::UnlockInput => input_lock.0 = false,
+            CutsceneStep::Shake { intensity, secs } => shake.start(intensity, secs),
+            step => {
+                if let CutsceneStep::FlashText { text, .. } = &step {
+                    caption.0 = Some(text.clone());
+                }
+                let start = match &step {
+                    CutsceneStep::SlideTo { entity, .. } => transform_query
+                        .get(*entity)
+                        .ok()
+                        .map(|transform| transform.translation),
+                    _ => None,
+                };
+                cutscene.current = Some(ActiveStep {
+                    timer: Timer::from_seconds(step.secs(), TimerMode::Once),
+                    step,
+                    start,
+                });
+            }
+        }
+    }
+
+    let Some(active) = cutscene.current.as_mut() else {
+        return;
+    };
+    active.timer.tick(time.delta());
+
+    // 移动步骤按缓出曲线插值位置
+    if let CutsceneStep::SlideTo { entity, to, .. } = active.step {
+        let t = 1. - (1. - active.timer.fraction()).powi(2);
+        if let (Some(start), Ok(mut transform)) = (active.start, transform_query.get_mut(entity)) {
+            transform.translation = start.lerp(to, t);
+        }
+    }
+
+    if active.timer.finished() {
+        if matches!(active.step, CutsceneStep::FlashText { .. }) {
+            caption.0 = None;
+        }
+        cutscene.current = None;
+    }
+}
+
+/// 过场字幕初始化系统
+fn caption_setup_system(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                top: Val::Percent(45.),
+                justify_content: JustifyContent::Center,
+                ..Default::default()
+            },
+            GlobalZIndex(6),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                CaptionText,
+                Text::new(""),
+                TextFont {
+                    font_size: 40.,
+                    ..Default::default()
+                },
+                TextColor(Color::srgb(1., 0.3, 0.3)),
+                Visibility::Hidden,
+            ));
+        });
+}
+
+/// 字幕系统 - 有字幕时按固定周期闪烁显示
+fn caption_system(
+    time: Res<Time>,
+    caption: Res<CutsceneCaption>,
+    mut query: Query<(&mut Text, &mut Visibility), With<CaptionText>>,
+) {
+    let blink_on = (time.elapsed_secs() / CAPTION_BLINK_SECS) as u32 % 2 == 0;
+    for (mut text, mut visibility) in &mut query {
+        let Some(caption) = &caption.0 else {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        };
+        if text.0 != *caption {
+            text.0 = caption.clone();
+        }
+        visibility.set_if_neq(if blink_on {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        });
+    }
+}
+
+/// 屏幕震动系统 - 震动期间随机偏移摄像机，幅度随时间线性减弱，结束后复位
+/// （震动只影响画面且按渲染帧运行，使用线程随机数，不消耗玩法随机数）
+fn screen_shake_system(
+    time: Res<Time>,
+    mut shake: ResMut<ScreenShake>,
+    mut camera_query: Query<&mut Transform, With<Camera2d>>,
+) {
+    let offset = if shake.timer.tick(time.delta()).finished() {
+        Vec2::ZERO
+    } else {
+        let amplitude = shake.intensity * (1. - shake.timer.fraction());
+        let mut rng = rand::rng();
+        Vec2::new(
+            rng.gen_range(-amplitude..=amplitude),
+            rng.gen_range(-amplitude..=amplitude),
+        )
+    };
+    for mut transform in &mut camera_query {
+        transform.translation.x = offset.x;
+        transform.translation.y = offset.y;
+    }
+}
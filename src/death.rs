@@ -0,0 +1,91 @@
+use crate::combat::{DeathEvent, death_system};
+use crate::cutscene::ScreenShake;
+use crate::faction_time::{Faction, FactionTime};
+use crate::{GameSet, GameStats, PlayerState};
+use bevy::prelude::*;
+
+// 玩家死亡时屏幕震动的幅度（像素）
+const DEATH_SHAKE_INTENSITY: f32 = 10.;
+// 玩家死亡时屏幕震动的时长（秒）
+const DEATH_SHAKE_SECS: f32 = 0.5;
+// 玩家死亡后慢动作的时长（秒）
+const DEATH_SLOW_MOTION_SECS: f32 = 0.8;
+
+/// 玩家死亡插件 - 玩家的生命值耗尽后由战斗插件的死亡处理系统销毁战机、生成爆炸并播放音效，
+/// 重生安排、扣除生命、统计、屏幕震动和慢动作分别响应玩家阵营的死亡事件
+pub struct PlayerDeathPlugin;
+
+impl Plugin for PlayerDeathPlugin {
+    fn build(&self, app: &mut App) {
+        // 固定步长处理死亡：先安排重生、扣除生命，再记录统计（日志中的剩余生命为扣除后的数量）
+        app.add_systems(
+            FixedUpdate,
+            (
+                (death_respawn_system, death_lives_system, death_stats_system).chain(),
+                death_shake_system,
+                death_slow_motion_system,
+            )
+                .after(death_system)
+                .in_set(GameSet::Spawn),
+        );
+    }
+}
+
+/// 本帧死亡的玩家数量
+fn player_deaths(events: &mut EventReader<DeathEvent>) -> usize {
+    events
+        .read()
+        .filter(|event| event.faction == Faction::Player)
+        .count()
+}
+
+/// 重生安排系统 - 标记玩家死亡并记录死亡时间，重生延迟从此时开始计算
+fn death_respawn_system(
+    time: Res<Time>,
+    mut player_state: ResMut<PlayerState>,
+    mut events: EventReader<DeathEvent>,
+) {
+    for _ in 0..player_deaths(&mut events) {
+        player_state.shot(time.elapsed_secs_f64());
+    }
+}
+
+/// 生命系统 - 每次死亡扣除一条命（生命无限的模式由模式规则补满）
+fn death_lives_system(mut player_state: ResMut<PlayerState>, mut events: EventReader<DeathEvent>) {
+    for _ in 0..player_deaths(&mut events) {
+        player_state.lose_life();
+    }
+}
+
+/// 死亡统计系统 - 记录死亡次数
+fn death_stats_system(
+    player_state: Res<PlayerState>,
+    mut stats: ResMut<GameStats>,
+    mut events: EventReader<DeathEvent>,
+) {
+    for _ in 0..player_deaths(&mut events) {
+        stats.player_deaths += 1;
+        info!(
+            lives = player_state.lives,
+            deaths = stats.player_deaths,
+            "玩家死亡"
+        );
+    }
+}
+
+/// 死亡震动系统 - 玩家死亡时震动屏幕
+fn death_shake_system(mut shake: ResMut<ScreenShake>, mut events: EventReader<DeathEvent>) {
+    if player_deaths(&mut events) > 0 {
+        shake.start(DEATH_SHAKE_INTENSITY, DEATH_SHAKE_SECS);
+    }
+}
+
+/// 死亡慢动作系统 - 玩家死亡后敌方阵营短暂进入慢动作
+fn death_slow_motion_system(
+    mut faction_time: ResMut<FactionTime>,
+    mut events: EventReader<DeathEvent>,
+) {
+    if player_deaths(&mut events) > 0 {
+        faction_time.start_slow_motion(DEATH_SLOW_MOTION_SECS);
+    }
+}
@@ -0,0 +1,91 @@
+use crate::AppState;
+use crate::atlas::pack_atlas;
+use crate::registry::AssetRegistry;
+use bevy::prelude::*;
+
+// 重新加载爆炸网格布局的按键
+const RELOAD_EXPLOSION_KEY: KeyCode = KeyCode::F5;
+
+/// 开发插件（dev特性）- 资源文件在磁盘上被替换后重新打包精灵图集，按F5按资源清单重新加载爆炸序列帧图的网格布局
+/// 资源文件的监视由dev特性开启的bevy/file_watcher完成
+pub struct DevPlugin;
+
+impl Plugin for DevPlugin {
+    fn build(&self, app: &mut App) {
+        // 源图变化时重新打包图集（加载阶段的图集尚未打包）
+        app.add_systems(
+            Update,
+            atlas_hot_reload_system.run_if(not(in_state(AppState::Loading))),
+        )
+        // 按键重新加载爆炸网格布局
+        .add_systems(Update, explosion_grid_reload_system);
+    }
+}
+
+/// 图集热重载系统 - 打包进图集的源图被修改后重新打包，原地替换图集纹理和布局，并把场上精灵的索引换成新索引
+fn atlas_hot_reload_system(
+    mut events: EventReader<AssetEvent<Image>>,
+    mut images: ResMut<Assets<Image>>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut registry: ResMut<AssetRegistry>,
+    mut sprite_query: Query<&mut Sprite>,
+) {
+    let mut modified = false;
+    for event in events.read() {
+        if let AssetEvent::Modified { id } = event {
+            modified |= registry
+                .packed_textures()
+                .any(|(_, handle)| handle.id() == *id);
+        }
+    }
+    if !modified {
+        return;
+    }
+    let Some((layout, atlas, atlas_index)) = pack_atlas(&registry, &images) else {
+        return;
+    };
+
+    // 精灵尺寸变化后打包顺序可能改变，按精灵键换算场上精灵的新索引
+    let (atlas_handle, layout_handle) = registry.atlas();
+    let (atlas_handle, layout_handle) = (atlas_handle.clone(), layout_handle.clone());
+    for mut sprite in &mut sprite_query {
+        let Some(texture_atlas) = sprite.texture_atlas.as_mut() else {
+            continue;
+        };
+        if texture_atlas.layout != layout_handle {
+            continue;
+        }
+        if let Some(index) = registry
+            .atlas_key(texture_atlas.index)
+            .and_then(|key| atlas_index.get(&key))
+        {
+            texture_atlas.index = *index;
+        }
+    }
+
+    if let Some(image) = images.get_mut(&atlas_handle) {
+        *image = atlas;
+    }
+    if let Some(old_layout) = layouts.get_mut(&layout_handle) {
+        *old_layout = layout;
+    }
+    registry.set_atlas(atlas_handle, layout_handle, atlas_index);
+    info!("精灵图集已重新打包");
+}
+
+/// 爆炸网格重载系统 - 按F5重新读取资源清单中的爆炸网格（修改帧尺寸或行列数后使用）
+fn explosion_grid_reload_system(
+    kb: Res<ButtonInput<KeyCode>>,
+    mut registry: ResMut<AssetRegistry>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+) {
+    if !kb.just_pressed(RELOAD_EXPLOSION_KEY) {
+        return;
+    }
+    registry.reload_explosion_grid(&mut layouts);
+    let grid = registry.explosion_grid;
+    info!(
+        "爆炸网格已重新加载: {}x{}像素，{}列{}行",
+        grid.tile.x, grid.tile.y, grid.columns, grid.rows
+    );
+}
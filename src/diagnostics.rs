@@ -0,0 +1,150 @@
+use crate::components::{Enemy, Explosion, Laser};
+use crate::pickup::Pickup;
+use crate::stress::StressBullet;
+use bevy::diagnostic::{
+    Diagnostic, DiagnosticPath, Diagnostics, DiagnosticsStore, RegisterDiagnostic,
+};
+use bevy::prelude::*;
+
+// 写入日志的间隔（秒）
+const LOG_INTERVAL_SECS: f32 = 30.;
+
+// 全部实体数量
+const ENTITIES: DiagnosticPath = DiagnosticPath::const_new("game/entities");
+// 敌人数量
+const ENEMIES: DiagnosticPath = DiagnosticPath::const_new("game/enemies");
+// 激光数量（不含对象池中隐藏的实体）
+const LASERS: DiagnosticPath = DiagnosticPath::const_new("game/lasers");
+// 爆炸数量
+const EXPLOSIONS: DiagnosticPath = DiagnosticPath::const_new("game/explosions");
+// 压力测试弹幕数量（游戏中没有粒子实体）
+const STRESS_BULLETS: DiagnosticPath = DiagnosticPath::const_new("game/stress_bullets");
+// 拾取物数量
+const PICKUPS: DiagnosticPath = DiagnosticPath::const_new("game/pickups");
+// 纹理占用的内存（MB）
+const IMAGE_MEMORY: DiagnosticPath = DiagnosticPath::const_new("game/image_memory_mb");
+// 音效占用的内存（MB）
+const AUDIO_MEMORY: DiagnosticPath = DiagnosticPath::const_new("game/audio_memory_mb");
+
+// 按实体统计的诊断项（第一项为全部实体，其余与统计查询中的标记依次对应）
+const COUNT_PATHS: [DiagnosticPath; 6] = [
+    ENTITIES,
+    ENEMIES,
+    LASERS,
+    EXPLOSIONS,
+    STRESS_BULLETS,
+    PICKUPS,
+];
+
+/// 诊断日志计时器资源 - 每30秒把诊断数据写入日志
+#[derive(Resource)]
+struct DiagnosticsLogTimer(Timer);
+
+impl Default for DiagnosticsLogTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(LOG_INTERVAL_SECS, TimerMode::Repeating))
+    }
+}
+
+/// 实体与内存诊断插件 - 每帧统计各类实体数量和资源内存（约数，只计算主世界中保留的纹理和音频数据），
+/// 显示在帧时间曲线面板中，并每30秒写入日志，用于排查实体泄漏和内存增长
+pub struct EntityDiagnosticsPlugin;
+
+impl Plugin for EntityDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        for path in COUNT_PATHS.into_iter().chain([IMAGE_MEMORY, AUDIO_MEMORY]) {
+            app.register_diagnostic(Diagnostic::new(path)); // 注册诊断项
+        }
+        // 初始化诊断日志计时器
+        app.init_resource::<DiagnosticsLogTimer>()
+            // 每帧统计，定期写入日志
+            .add_systems(
+                Update,
+                (entity_diagnostics_system, diagnostics_log_system).chain(),
+            );
+    }
+}
+
+/// 诊断统计系统 - 遍历一次全部实体统计各类数量，并统计纹理、音效的数据字节数
+#[allow(clippy::type_complexity)] // 允许复杂的查询类型
+fn entity_diagnostics_system(
+    mut diagnostics: Diagnostics,
+    images: Res<Assets<Image>>,
+    audio: Res<Assets<AudioSource>>,
+    query: Query<(
+        Has<Enemy>,
+        Has<Laser>,
+        Has<Explosion>,
+        Has<StressBullet>,
+        Has<Pickup>,
+    )>,
+) {
+    let mut counts = [0usize; 6];
+    for (enemy, laser, explosion, stress, pickup) in &query {
+        counts[0] += 1;
+        for (i, has) in [enemy, laser, explosion, stress, pickup]
+            .into_iter()
+            .enumerate()
+        {
+            counts[i + 1] += usize::from(has);
+        }
+    }
+    for (path, count) in COUNT_PATHS.iter().zip(counts) {
+        diagnostics.add_measurement(path, || count as f64);
+    }
+    diagnostics.add_measurement(&IMAGE_MEMORY, || {
+        let bytes: usize = images
+            .iter()
+            .filter_map(|(_, image)| image.data.as_ref())
+            .map(Vec::len)
+            .sum();
+        megabytes(bytes)
+    });
+    diagnostics.add_measurement(&AUDIO_MEMORY, || {
+        let bytes: usize = audio.iter().map(|(_, source)| source.bytes.len()).sum();
+        megabytes(bytes)
+    });
+}
+
+/// 字节数换算为MB
+fn megabytes(bytes: usize) -> f64 {
+    bytes as f64 / (1024. * 1024.)
+}
+
+/// 诊断日志系统 - 每30秒（真实时间）把各诊断项的最新值写入日志
+fn diagnostics_log_system(
+    time: Res<Time<Real>>,
+    store: Res<DiagnosticsStore>,
+    mut timer: ResMut<DiagnosticsLogTimer>,
+) {
+    if !timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+    info!(
+        summary = summary(&store).replace('\n', "  "),
+        "实体与内存诊断"
+    );
+}
+
+/// 各诊断项最新值的摘要（英文，三行，同时用于面板显示）
+pub fn summary(store: &DiagnosticsStore) -> String {
+    let value = |path: &DiagnosticPath| {
+        store
+            .get(path)
+            .and_then(|diagnostic| diagnostic.value())
+            .unwrap_or_default()
+    };
+    format!(
+        "entities {:.0}  enemies {:.0}  lasers {:.0}\n\
+         explosions {:.0}  stress bullets {:.0}  pickups {:.0}\n\
+         images {:.1} MB  audio {:.1} MB",
+        value(&ENTITIES),
+        value(&ENEMIES),
+        value(&LASERS),
+        value(&EXPLOSIONS),
+        value(&STRESS_BULLETS),
+        value(&PICKUPS),
+        value(&IMAGE_MEMORY),
+        value(&AUDIO_MEMORY),
+    )
+}
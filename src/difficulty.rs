@@ -0,0 +1,57 @@
+use crate::config::GameConfig;
+use crate::game_mode::{GameMode, GameModes};
+use crate::sim::RollbackAppExt;
+use crate::{GameSet, PlayerState};
+use bevy::prelude::*;
+
+/// 当前难度资源 - 根据玩家本条命的存活时间计算
+#[derive(Resource, Clone)]
+pub struct Difficulty {
+    pub survived_secs: f32, // 玩家本条命已存活的时间（秒）
+    pub speed_scale: f32,   // 敌人速度上下限倍率
+    pub spawn_scale: f32,   // 敌人生成频率倍率
+}
+
+impl Default for Difficulty {
+    fn default() -> Self {
+        Self {
+            survived_secs: 0.,
+            speed_scale: 1.,
+            spawn_scale: 1.,
+        }
+    }
+}
+
+/// 难度插件 - 随玩家存活时间逐步提高难度（与波次无关）
+pub struct DifficultyPlugin;
+
+impl Plugin for DifficultyPlugin {
+    fn build(&self, app: &mut App) {
+        // 初始化难度资源
+        app.init_resource::<Difficulty>()
+            .rollback_resource::<Difficulty>()
+            // 在敌人移动和生成之前更新难度
+            .add_systems(FixedUpdate, difficulty_ramp_system.in_set(GameSet::Input));
+    }
+}
+
+/// 难度递增系统 - 玩家存活时累计时间并按配置的曲线更新难度，玩家死亡后重新开始（得分挑战的生成频率额外提高）
+fn difficulty_ramp_system(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mode: Res<GameMode>,
+    modes: Res<GameModes>,
+    player_state: Res<PlayerState>,
+    mut difficulty: ResMut<Difficulty>,
+) {
+    if player_state.on {
+        difficulty.survived_secs += time.delta_secs();
+    } else {
+        difficulty.survived_secs = 0.;
+    }
+
+    let ramp = &config.difficulty;
+    difficulty.speed_scale = ramp.speed_scale(difficulty.survived_secs);
+    difficulty.spawn_scale =
+        ramp.spawn_scale(difficulty.survived_secs) * modes.get(*mode).spawn_scale();
+}
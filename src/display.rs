@@ -0,0 +1,100 @@
+use crate::WINDOW_SIZE;
+use crate::launch::LaunchOptions;
+use crate::settings::{Settings, UI_SCALE_MAX, UI_SCALE_MIN};
+use bevy::prelude::*;
+use bevy::window::{MonitorSelection, PresentMode, PrimaryWindow, WindowMode};
+use std::time::Instant;
+
+// 黑边的尺寸（足够覆盖任何窗口比例下游戏区域以外的部分）
+const LETTERBOX_SIZE: f32 = 10000.;
+// 黑边的Z轴（高于全部游戏实体）
+const LETTERBOX_Z: f32 = 100.;
+
+/// 显示插件 - 把设置中的显示选项应用到窗口和界面（界面缩放在启动时和设置变化时应用，窗口分辨率、全屏和垂直同步在设置变化时应用，
+/// 启动时的窗口由main按设置和启动参数创建）；游戏区域以外的部分显示黑边，设置了帧率上限时限制帧率
+pub struct DisplayPlugin;
+
+impl Plugin for DisplayPlugin {
+    fn build(&self, app: &mut App) {
+        // 在游戏区域四周生成黑边
+        app.add_systems(Startup, letterbox_setup_system)
+            .add_systems(Update, ui_scale_system.run_if(resource_changed::<Settings>))
+            .add_systems(
+                Update,
+                window_system
+                    .run_if(resource_changed::<Settings>.and(not(resource_added::<Settings>))),
+            )
+            // 每帧最后等待到帧率上限对应的帧时间
+            .add_systems(Last, frame_limit_system);
+    }
+}
+
+/// 黑边初始化系统 - 窗口比例与游戏区域不同时，摄像机多显示的游戏区域以外部分被黑边遮住，任何窗口比例下看到的场面都相同
+fn letterbox_setup_system(mut commands: Commands) {
+    let (w, h) = WINDOW_SIZE;
+    let offset = LETTERBOX_SIZE / 2.;
+    for (x, y) in [
+        (-(w / 2. + offset), 0.),
+        (w / 2. + offset, 0.),
+        (0., -(h / 2. + offset)),
+        (0., h / 2. + offset),
+    ] {
+        commands.spawn((
+            Sprite::from_color(Color::BLACK, Vec2::splat(LETTERBOX_SIZE)),
+            Transform::from_xyz(x, y, LETTERBOX_Z),
+        ));
+    }
+}
+
+/// 界面缩放系统 - 全部界面节点和文字按设置中的缩放倍数显示
+fn ui_scale_system(settings: Res<Settings>, mut ui_scale: ResMut<UiScale>) {
+    let scale = settings.ui_scale.clamp(UI_SCALE_MIN, UI_SCALE_MAX);
+    if ui_scale.0 != scale {
+        ui_scale.0 = scale;
+    }
+}
+
+/// 窗口系统 - 按设置调整窗口分辨率、全屏模式（带有--windowed参数时始终为窗口模式）和垂直同步
+fn window_system(
+    settings: Res<Settings>,
+    options: Res<LaunchOptions>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let Ok(mut window) = windows.get_single_mut() else {
+        return;
+    };
+    let (w, h) = settings.resolution;
+    if window.resolution.width() != w || window.resolution.height() != h {
+        info!(w, h, "调整窗口分辨率");
+        window.resolution.set(w, h);
+    }
+    let mode = if settings.fullscreen && !options.windowed {
+        WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+    } else {
+        WindowMode::Windowed
+    };
+    if window.mode != mode {
+        info!(?mode, "切换窗口模式");
+        window.mode = mode;
+    }
+    let present_mode = if settings.vsync {
+        PresentMode::AutoVsync
+    } else {
+        PresentMode::AutoNoVsync
+    };
+    if window.present_mode != present_mode {
+        info!(?present_mode, "切换垂直同步");
+        window.present_mode = present_mode;
+    }
+}
+
+/// 帧率限制系统 - 距上一帧结束不足帧率上限对应的帧时间时休眠补足，降低笔记本的耗电
+fn frame_limit_system(settings: Res<Settings>, mut last: Local<Option<Instant>>) {
+    if let (Some(frame_time), Some(last)) = (settings.frame_cap.frame_time(), *last) {
+        let elapsed = last.elapsed();
+        if elapsed < frame_time {
+            std::thread::sleep(frame_time - elapsed);
+        }
+    }
+    *last = Some(Instant::now());
+}
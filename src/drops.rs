@@ -0,0 +1,130 @@
+use crate::components::EnemyKind;
+use crate::pickup::{PickupKind, PowerUpKind};
+use crate::registry::read_asset_text;
+use crate::rng::GameRng;
+use bevy::prelude::*;
+use rand::Rng;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+// 掉落表文件路径
+const DROP_TABLES_PATH: &str = "assets/drops.ron";
+
+/// 掉落条目 - 掉落物及其权重（权重越低越稀有）
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct DropEntry {
+    pub item: PickupKind, // 掉落物
+    pub weight: u32,      // 权重
+}
+
+/// 掉落表 - 一种敌人被击毁时的掉落概率和候选掉落物
+#[derive(Clone, Debug, Deserialize)]
+pub struct DropTable {
+    pub chance: f32,             // 掉落概率（0~1）
+    pub entries: Vec<DropEntry>, // 候选掉落物
+}
+
+impl DropTable {
+    /// 按掉落概率和权重随机选出掉落物，不掉落时返回None
+    pub fn roll(&self, rng: &mut GameRng) -> Option<PickupKind> {
+        let total: u32 = self.entries.iter().map(|entry| entry.weight).sum();
+        if total == 0 || rng.gen_range(0.0..1.0) >= self.chance {
+            return None;
+        }
+
+        let mut pick = rng.gen_range(0..total);
+        for entry in &self.entries {
+            if pick < entry.weight {
+                return Some(entry.item);
+            }
+            pick -= entry.weight;
+        }
+        None
+    }
+}
+
+/// 掉落表资源 - 每种敌人的掉落表，从drops.ron读取，修改掉落平衡无需重新编译
+#[derive(Resource, Debug, Deserialize)]
+pub struct DropTables(pub HashMap<EnemyKind, DropTable>);
+
+impl Default for DropTables {
+    fn default() -> Self {
+        let basic = DropTable {
+            chance: 0.9,
+            entries: vec![
+                DropEntry {
+                    item: PickupKind::Scrap(10),
+                    weight: 70,
+                },
+                DropEntry {
+                    item: PickupKind::Scrap(25),
+                    weight: 10,
+                },
+                DropEntry {
+                    item: PickupKind::PowerUp(PowerUpKind::RapidFire),
+                    weight: 5,
+                },
+                DropEntry {
+                    item: PickupKind::PowerUp(PowerUpKind::Shield),
+                    weight: 5,
+                },
+                DropEntry {
+                    item: PickupKind::PowerUp(PowerUpKind::Magnet),
+                    weight: 5,
+                },
+                DropEntry {
+                    item: PickupKind::PowerUp(PowerUpKind::TimeSlow),
+                    weight: 3,
+                },
+                DropEntry {
+                    item: PickupKind::Ammo,
+                    weight: 5,
+                },
+                DropEntry {
+                    item: PickupKind::Repair,
+                    weight: 3,
+                },
+                DropEntry {
+                    item: PickupKind::Emp,
+                    weight: 2,
+                },
+            ],
+        };
+        // 首领必定掉落，以维修包和大块废料为主
+        let boss = DropTable {
+            chance: 1.,
+            entries: vec![
+                DropEntry {
+                    item: PickupKind::Repair,
+                    weight: 2,
+                },
+                DropEntry {
+                    item: PickupKind::Scrap(25),
+                    weight: 1,
+                },
+            ],
+        };
+        Self(HashMap::from([
+            (EnemyKind::Basic, basic),
+            (EnemyKind::Boss, boss),
+        ]))
+    }
+}
+
+impl DropTables {
+    /// 从掉落表文件读取，文件不存在或格式错误时使用内置掉落表
+    pub fn load() -> Self {
+        match read_asset_text(DROP_TABLES_PATH) {
+            Ok(content) => ron::from_str(&content).unwrap_or_else(|e| {
+                warn!("掉落表格式错误，使用内置掉落表: {e}");
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// 为指定种类的敌人随机选出掉落物（没有掉落表的敌人不掉落）
+    pub fn roll(&self, kind: EnemyKind, rng: &mut GameRng) -> Option<PickupKind> {
+        self.0.get(&kind).and_then(|table| table.roll(rng))
+    }
+}
@@ -0,0 +1,86 @@
+use bevy::asset::io::embedded::EmbeddedAssetRegistry;
+use bevy::prelude::*;
+use std::path::{Path, PathBuf};
+
+/// 把assets目录中的文件编译进可执行文件，生成（文件名，内容）列表
+macro_rules! embed_files {
+    ($($file:literal),* $(,)?) => {
+        &[$(($file, include_bytes!(concat!("../assets/", $file)) as &[u8])),*]
+    };
+}
+
+// 内嵌的纹理、音效、敌人定义、关卡和波次脚本（新增资源时需同时登记在此处）
+const ASSET_FILES: &[(&str, &[u8])] = embed_files![
+    "player_a_01.png",
+    "player_b_01.png",
+    "laser_a_01.png",
+    "laser_b_01.png",
+    "enemy_a_01.png",
+    "enemy_desert.png",
+    "enemy_ice.png",
+    "enemy_void.png",
+    "repair_pack.png",
+    "explo_a_sheet.png",
+    "bg_desert.png",
+    "bg_ice.png",
+    "bg_void.png",
+    "enemy_explosion.ogg",
+    "empty_click.wav",
+    "repair.wav",
+    "music_desert.wav",
+    "music_ice.wav",
+    "music_void.wav",
+    "enemies/scout.enemy.ron",
+    "enemies/gunner.enemy.ron",
+    "levels/01_first_contact.level",
+    "levels/02_crosswind.level",
+    "levels/03_split_current.level",
+    "levels/04_dreadnought.level",
+    "levels/05_storm_front.level",
+    "levels/06_attrition.level",
+    "levels/07_maelstrom.level",
+    "levels/08_last_stand.level",
+    "waves/wave_03.lua",
+];
+
+// 内嵌的数据文件（路径与读取文件时使用的路径一致）
+const TEXT_FILES: &[(&str, &str)] = &[
+    (
+        "assets/manifest.ron",
+        include_str!("../assets/manifest.ron"),
+    ),
+    (
+        "assets/campaign.ron",
+        include_str!("../assets/campaign.ron"),
+    ),
+    ("assets/drops.ron", include_str!("../assets/drops.ron")),
+    (
+        "assets/default_level.ron",
+        include_str!("../assets/default_level.ron"),
+    ),
+];
+
+/// 内嵌资源插件 - 把内嵌的纹理和音效登记到embedded://资源来源，发布时无需附带assets目录
+pub struct EmbeddedAssetsPlugin;
+
+impl Plugin for EmbeddedAssetsPlugin {
+    fn build(&self, app: &mut App) {
+        let registry = app.world_mut().resource_mut::<EmbeddedAssetRegistry>();
+        for (file, bytes) in ASSET_FILES {
+            registry.insert_asset(PathBuf::from(file), Path::new(file), *bytes);
+        }
+    }
+}
+
+/// 是否内嵌了该资源文件（相对assets目录的路径）
+pub fn contains(file: &str) -> bool {
+    ASSET_FILES.iter().any(|(name, _)| *name == file)
+}
+
+/// 内嵌的数据文件内容
+pub fn text(path: &str) -> Option<&'static str> {
+    TEXT_FILES
+        .iter()
+        .find(|(name, _)| *name == path)
+        .map(|(_, content)| *content)
+}
@@ -0,0 +1,182 @@
+use super::formation::Formation;
+use super::weak_point::spawn_weak_point;
+use super::{EnemyDef, EnemySpawn, SpawnEnemyEvent, SpawnKind, Upgrades, spawn_enemy};
+use crate::components::Resistances;
+use crate::config::GameConfig;
+use crate::cutscene::Cutscene;
+use crate::faction_time::{Faction, FactionTime};
+use crate::registry::AssetRegistry;
+use crate::rng::GameRng;
+use crate::theme::ThemeAssets;
+use crate::{ENEMY_SCORE, ENEMY_SIZE, EnemyCount, SPRITE_SCALE};
+use bevy::ecs::entity::{EntityMapper, MapEntities};
+use bevy::prelude::*;
+use rand::Rng;
+
+// 编队生成的敌人成为母舰的概率
+const CARRIER_CHANCE: f32 = 0.08;
+// 母舰相对普通敌人的放大倍率
+const CARRIER_SCALE: f32 = 1.8;
+// 母舰的着色
+const CARRIER_TINT: Color = Color::srgb(0.45, 0.7, 1.);
+// 母舰的移动速度倍率（缓慢巡航）
+const CARRIER_SPEED_SCALE: f32 = 0.4;
+// 母舰的生命值（普通命中为1）
+const CARRIER_HP: u32 = 7;
+// 母舰的抗性：厚重的船壳抵抗能量武器，机库容易被爆炸引燃
+const CARRIER_RESISTANCES: Resistances = Resistances {
+    kinetic: 1.,
+    energy: 0.5,
+    explosive: 1.5,
+};
+// 击毁母舰的额外得分
+const CARRIER_BONUS_SCORE: u64 = 150;
+// 母舰放出舰载机的间隔（秒）
+const CARRIER_BAY_SECS: f32 = 3.;
+// 每艘母舰同时存在的舰载机上限
+const CARRIER_MINIONS_MAX: usize = 4;
+// 母舰机库弱点的伤害倍率
+const CARRIER_WEAK_POINT_MULTIPLIER: f32 = 2.;
+// 舰载机相对普通敌人的缩放倍率
+const MINION_SCALE: f32 = 0.45;
+// 舰载机的移动速度倍率
+const MINION_SPEED_SCALE: f32 = 1.4;
+
+/// 母舰组件 - 按机库计时器放出舰载机
+#[derive(Component, Clone)]
+pub struct Carrier {
+    bay: Timer, // 机库计时器
+}
+
+/// 舰载机组件 - 记录所属的母舰（用于统计每艘母舰的舰载机数量）
+#[derive(Component, Clone)]
+pub struct Minion {
+    carrier: Entity, // 所属母舰
+}
+
+impl MapEntities for Minion {
+    /// 快照恢复时把所属母舰换成重新生成的实体
+    fn map_entities<M: EntityMapper>(&mut self, mapper: &mut M) {
+        self.carrier = mapper.get_mapped(self.carrier);
+    }
+}
+
+/// 按概率决定编队生成的敌人是否为母舰
+pub fn roll_carrier(rng: &mut GameRng) -> bool {
+    rng.gen_range(0.0..1.0) < CARRIER_CHANCE
+}
+
+/// 生成母舰 - 放大、着色、缓慢移动，需要多次命中才能击毁；下方的机库是弱点
+pub fn spawn_carrier(
+    commands: &mut Commands,
+    registry: &AssetRegistry,
+    theme_assets: &ThemeAssets,
+    enemy_count: &mut EnemyCount,
+    rng: &mut GameRng,
+    translation: Vec3,
+    formation: Formation,
+) {
+    spawn_enemy(
+        commands,
+        registry,
+        theme_assets,
+        enemy_count,
+        rng,
+        EnemySpawn {
+            translation,
+            scale: SPRITE_SCALE * CARRIER_SCALE,
+            tint: Some(CARRIER_TINT),
+            formation,
+            upgrades: Upgrades::None,
+            def: EnemyDef {
+                hp: CARRIER_HP,
+                speed: CARRIER_SPEED_SCALE,
+                score: ENEMY_SCORE + CARRIER_BONUS_SCORE,
+                ..Default::default()
+            },
+        },
+    )
+    .insert(Carrier {
+        bay: Timer::from_seconds(CARRIER_BAY_SECS, TimerMode::Repeating),
+    }) // 机库计时器
+    .insert(CARRIER_RESISTANCES) // 各伤害类型的倍率
+    .with_children(|parent| {
+        spawn_weak_point(
+            parent,
+            Vec2::new(0., -ENEMY_SIZE.1 * 0.35),
+            ENEMY_SIZE.1 * 0.3,
+            CARRIER_WEAK_POINT_MULTIPLIER,
+        );
+    });
+}
+
+/// 生成舰载机 - 缩小、着色、移动更快的普通敌人，记录所属的母舰；同样计入敌人数量，击毁前本波不会结束
+#[allow(clippy::too_many_arguments)] // 生成舰载机需要同时访问多个资源
+pub fn spawn_minion(
+    commands: &mut Commands,
+    registry: &AssetRegistry,
+    theme_assets: &ThemeAssets,
+    enemy_count: &mut EnemyCount,
+    rng: &mut GameRng,
+    translation: Vec3,
+    formation: Formation,
+    carrier: Entity,
+) {
+    spawn_enemy(
+        commands,
+        registry,
+        theme_assets,
+        enemy_count,
+        rng,
+        EnemySpawn {
+            translation,
+            scale: SPRITE_SCALE * MINION_SCALE,
+            tint: Some(CARRIER_TINT),
+            formation,
+            upgrades: Upgrades::None,
+            def: EnemyDef {
+                speed: MINION_SPEED_SCALE,
+                ..Default::default()
+            },
+        },
+    )
+    .insert(Minion { carrier }); // 所属母舰
+}
+
+/// 母舰机库系统 - 机库计时器到期、舰载机未达上限且同屏敌人（含本帧已请求生成的）未达最大值时，
+/// 从母舰下方的机库放出一架舰载机（过场期间暂停）
+#[allow(clippy::too_many_arguments)] // 放出舰载机需要同时访问多个资源
+pub fn carrier_bay_system(
+    time: Res<Time>,
+    faction_time: Res<FactionTime>,
+    cutscene: Res<Cutscene>,
+    config: Res<GameConfig>,
+    enemy_count: Res<EnemyCount>,
+    mut carrier_query: Query<(Entity, &Transform, &mut Carrier)>,
+    minion_query: Query<&Minion>,
+    mut spawn_events: EventWriter<SpawnEnemyEvent>,
+) {
+    if cutscene.active() {
+        return;
+    }
+    let mut pending = 0; // 本帧已请求生成的舰载机数量
+    for (carrier, carrier_tf, mut bay) in &mut carrier_query {
+        bay.bay.tick(faction_time.delta(&time, Faction::Enemy));
+        if !bay.bay.just_finished() {
+            continue;
+        }
+        let minions = minion_query
+            .iter()
+            .filter(|minion| minion.carrier == carrier)
+            .count();
+        if minions >= CARRIER_MINIONS_MAX || enemy_count.0 + pending >= config.tuning.enemy_max {
+            continue;
+        }
+        pending += 1;
+
+        // 机库位于母舰下方，舰载机从机库出发后并入编队
+        let bay_offset = ENEMY_SIZE.1 / 2. * carrier_tf.scale.y;
+        let translation = carrier_tf.translation - Vec3::new(0., bay_offset, 0.);
+        spawn_events.send(SpawnEnemyEvent::at(SpawnKind::Minion(carrier), translation));
+    }
+}
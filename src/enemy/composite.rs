@@ -0,0 +1,150 @@
+use crate::collision::{sprites_collide, world_transform};
+use crate::combat::ExplosionRequested;
+use crate::components::{DamageType, Health, Laser, Resistances, SpriteSize, Team};
+use crate::pool::recycle;
+use crate::rng::GameRng;
+use crate::{ENEMY_SIZE, GameStats};
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+use rand::Rng;
+use std::collections::HashSet;
+
+// 生成的敌人带有机翼和炮塔部件的概率
+const COMPOSITE_CHANCE: f32 = 0.2;
+// 击毁一个部件的得分
+const PART_SCORE: u64 = 25;
+// 玩家激光对部件造成的基础伤害（按部件的抗性调整）
+const PART_LASER_DAMAGE: f32 = 1.;
+
+/// 部件种类
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PartKind {
+    Wing,   // 机翼：位于船体两侧
+    Turret, // 炮塔：位于船体下方
+    Orb,    // 护盾球：环绕首领旋转
+}
+
+impl PartKind {
+    /// 部件的默认生命值（每次激光命中扣除1点）
+    pub fn health(self) -> f32 {
+        match self {
+            PartKind::Wing => 2.,
+            PartKind::Turret => 3.,
+            PartKind::Orb => 4.,
+        }
+    }
+}
+
+// 复合敌人的部件布局：种类、相对船体中心的位置（船体缩放前的像素）、相对船体的缩放、是否水平翻转
+const PART_LAYOUT: [(PartKind, Vec2, f32, bool); 3] = [
+    (
+        PartKind::Wing,
+        Vec2::new(-ENEMY_SIZE.0 * 0.7, 0.),
+        0.45,
+        false,
+    ),
+    (
+        PartKind::Wing,
+        Vec2::new(ENEMY_SIZE.0 * 0.7, 0.),
+        0.45,
+        true,
+    ),
+    (
+        PartKind::Turret,
+        Vec2::new(0., -ENEMY_SIZE.1 * 0.7),
+        0.35,
+        false,
+    ),
+];
+
+/// 敌人部件组件 - 复合敌人或首领（父实体）的子实体，带有自己的生命值，可被单独击毁；父实体被击毁时连同全部部件一起销毁
+#[derive(Component, Clone)]
+pub struct EnemyPart {
+    pub kind: PartKind, // 部件种类
+}
+
+/// 按概率为新生成的敌人挂上机翼和炮塔部件
+pub fn roll_composite(entity: &mut EntityCommands, sprite: &Sprite, rng: &mut GameRng) {
+    if rng.gen_range(0.0..1.0) < COMPOSITE_CHANCE {
+        attach_parts(entity, sprite);
+    }
+}
+
+/// 为新生成的敌人挂上机翼和炮塔部件（外观沿用船体精灵）
+pub fn attach_parts(entity: &mut EntityCommands, sprite: &Sprite) {
+    entity.with_children(|parent| {
+        for (kind, offset, scale, flip_x) in PART_LAYOUT {
+            parent.spawn((
+                Sprite {
+                    flip_x,
+                    ..sprite.clone()
+                },
+                Transform {
+                    translation: offset.extend(-1.), // 略低于船体显示
+                    scale: Vec3::new(scale, scale, 1.),
+                    ..Default::default()
+                },
+                SpriteSize::from(ENEMY_SIZE),
+                EnemyPart { kind },
+                Health::full(kind.health()),
+            ));
+        }
+    });
+}
+
+/// 部件命中系统 - 与敌方队伍敌对的激光（如玩家激光）先与各部件检测碰撞（部件位置沿父实体链合成，可跟随旋转的父实体移动），命中的激光被部件吸收，不再打到父实体
+#[allow(clippy::type_complexity)] // 允许复杂的查询类型
+pub fn part_hit_system(
+    mut commands: Commands,
+    mut stats: ResMut<GameStats>,
+    laser_query: Query<(Entity, &Transform, &SpriteSize, Option<&DamageType>, &Team), With<Laser>>,
+    transform_query: Query<(&Transform, Option<&ChildOf>)>,
+    mut part_query: Query<(
+        Entity,
+        &SpriteSize,
+        &EnemyPart,
+        Option<&Resistances>,
+        &mut Health,
+    )>,
+    mut explosions: EventWriter<ExplosionRequested>,
+) {
+    let mut destroyed = HashSet::new(); // 本帧已击毁的部件
+
+    for (laser_entity, laser_tf, laser_size, damage_type, team) in &laser_query {
+        if !team.hostile(Team::ENEMY) {
+            continue;
+        }
+        let hit = part_query
+            .iter_mut()
+            .filter(|(entity, ..)| !destroyed.contains(entity))
+            .find_map(|(entity, part_size, part, resistances, health)| {
+                let world_tf = world_transform(entity, &transform_query)?;
+                sprites_collide(laser_tf, laser_size, &world_tf, part_size).then_some((
+                    entity,
+                    world_tf.translation,
+                    part.kind,
+                    resistances.copied(),
+                    health,
+                ))
+            });
+        let Some((part_entity, position, kind, resistances, mut health)) = hit else {
+            continue;
+        };
+
+        // 销毁激光（池化的激光回收到对象池）
+        commands.entity(laser_entity).queue(recycle);
+
+        let damage_type = damage_type.copied().unwrap_or_default();
+        health.current -= Resistances::damage(resistances.as_ref(), damage_type, PART_LASER_DAMAGE);
+        if health.current > 0. {
+            continue;
+        }
+
+        // 部件被击毁：父实体保留，继续作战
+        debug!(?kind, "敌人部件击毁");
+        destroyed.insert(part_entity);
+        commands.entity(part_entity).despawn();
+        explosions.send(ExplosionRequested(position));
+        stats.score += PART_SCORE;
+    }
+}
@@ -0,0 +1,250 @@
+use super::elite::DOUBLE_SHOT_OFFSET;
+use crate::mods::ModList;
+use crate::registry::asset_path;
+use crate::rng::GameRng;
+use crate::{ENEMY_FIRE_PERIOD, ENEMY_SCORE, ENEMY_SIZE};
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext, LoadedFolder};
+use bevy::prelude::*;
+use rand::Rng;
+use serde::Deserialize;
+
+// 敌人定义文件所在目录（相对assets目录）
+const ENEMIES_DIR: &str = "enemies";
+// 敌人定义文件的扩展名
+const ENEMY_EXTENSION: &str = "enemy.ron";
+const ENEMY_EXTENSIONS: &[&str] = &[ENEMY_EXTENSION];
+// 扇形开火时最外侧激光的水平速度（竖直速度为1）
+const SPREAD_SPEED: f32 = 0.35;
+
+/// 开火方式组件 - 每次开火发射的激光束数和方向
+#[derive(Clone, Copy, Debug, Default, PartialEq, Component, Deserialize)]
+pub enum FirePattern {
+    #[default]
+    Single, // 单发：正下方一束
+    Double,     // 双发：左右并排两束
+    Spread(u8), // 扇形：向下方呈扇形散开的多束
+}
+
+impl FirePattern {
+    /// 每次开火的各束激光（水平偏移，水平速度）；双发词缀把单发升级为双发
+    pub fn shots(self, double_shot: bool) -> Vec<(f32, f32)> {
+        match self {
+            FirePattern::Single if !double_shot => vec![(0., 0.)],
+            FirePattern::Single | FirePattern::Double => {
+                vec![(-DOUBLE_SHOT_OFFSET, 0.), (DOUBLE_SHOT_OFFSET, 0.)]
+            }
+            FirePattern::Spread(count) => {
+                let count = count.max(1);
+                (0..count)
+                    .map(|i| {
+                        let t = if count > 1 {
+                            i as f32 / (count - 1) as f32 * 2. - 1.
+                        } else {
+                            0.
+                        };
+                        (0., t * SPREAD_SPEED)
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// 得分组件 - 击毁敌人的基础得分（精英额外加分另计）
+#[derive(Clone, Copy, Component)]
+pub struct ScoreValue(pub u64);
+
+impl ScoreValue {
+    /// 敌人的基础得分（没有得分组件时使用默认得分）
+    pub fn of(score: Option<&ScoreValue>) -> u64 {
+        score.map_or(ENEMY_SCORE, |score| score.0)
+    }
+}
+
+/// 速度组件 - 敌人定义中的移动速度倍率（与精英的迅捷词缀叠加）
+#[derive(Clone, Copy, Component)]
+pub struct SpeedScale(pub f32);
+
+/// 行为脚本组件 - 敌人定义中指定的脚本路径（相对assets目录，模组中的同名文件优先）
+#[derive(Clone, Debug, Component)]
+pub struct Behavior(pub String);
+
+/// 脚本控制标记 - 行为脚本实例化成功后添加，带有该标记的敌人不再按编队移动、按冷却开火
+#[derive(Component, Clone)]
+pub struct ScriptControlled;
+
+/// 敌人定义 - 一种普通敌人的外观和属性，从assets/enemies/*.enemy.ron读取，放入新文件即可增加敌人种类
+#[derive(Asset, TypePath, Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct EnemyDef {
+    pub name: String,             // 名称（日志中显示）
+    pub weight: u32,              // 生成权重（越高越常见）
+    pub image: Option<String>,    // 贴图路径（相对assets目录，省略时使用关卡主题的敌人贴图）
+    pub size: (f32, f32),         // 精灵尺寸（缩放前的像素，同时决定碰撞尺寸）
+    pub hp: u32,                  // 生命值（普通命中一次扣1点）
+    pub speed: f32,               // 移动速度倍率（相对编队速度）
+    pub fire: FirePattern,        // 开火方式
+    pub fire_period: (f32, f32),  // 开火间隔范围（秒）
+    pub score: u64,               // 击毁得分
+    pub behavior: Option<String>, // 行为脚本路径（.wasm，相对assets目录，需要scripting特性）
+    #[serde(skip)]
+    pub texture: Option<Handle<Image>>, // 加载的贴图（由加载器按image填入）
+}
+
+impl Default for EnemyDef {
+    /// 内置的普通敌人（没有读取到定义文件时使用，如无窗口模拟）
+    fn default() -> Self {
+        Self {
+            name: "Basic".to_string(),
+            weight: 1,
+            image: None,
+            size: ENEMY_SIZE,
+            hp: 1,
+            speed: 1.,
+            fire: FirePattern::Single,
+            fire_period: ENEMY_FIRE_PERIOD,
+            score: ENEMY_SCORE,
+            behavior: None,
+            texture: None,
+        }
+    }
+}
+
+/// 敌人定义加载器 - 读取.enemy.ron文件，并加载其中指定的贴图（模组中的同名贴图优先）
+pub struct EnemyDefLoader {
+    mods: ModList, // 启动时扫描到的模组
+}
+
+impl FromWorld for EnemyDefLoader {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            mods: world.get_resource::<ModList>().cloned().unwrap_or_default(),
+        }
+    }
+}
+
+impl AssetLoader for EnemyDefLoader {
+    type Asset = EnemyDef;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<EnemyDef, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let mut def: EnemyDef = ron::de::from_bytes(&bytes)?;
+        def.texture = def
+            .image
+            .as_ref()
+            .map(|path| load_context.load(self.mods.asset_path(path)));
+        Ok(def)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        ENEMY_EXTENSIONS
+    }
+}
+
+/// 敌人名册资源 - 当前可生成的敌人定义（为空时只生成内置的普通敌人）
+#[derive(Resource, Default)]
+pub struct EnemyRoster(pub Vec<EnemyDef>);
+
+impl EnemyRoster {
+    /// 按生成权重随机选出一种敌人
+    pub fn pick(&self, rng: &mut GameRng) -> EnemyDef {
+        let total: u32 = self.0.iter().map(|def| def.weight).sum();
+        if total == 0 {
+            return EnemyDef::default();
+        }
+        let mut pick = rng.gen_range(0..total);
+        for def in &self.0 {
+            if pick < def.weight {
+                return def.clone();
+            }
+            pick -= def.weight;
+        }
+        EnemyDef::default()
+    }
+}
+
+/// 敌人定义来源资源 - 持有assets/enemies目录和模组中定义文件的句柄，保持这些定义处于加载状态
+#[derive(Resource)]
+struct EnemyDefSources {
+    folder: Handle<LoadedFolder>, // assets/enemies目录
+    mods: Vec<Handle<EnemyDef>>,  // 模组包的enemies目录中的定义（按加载顺序）
+}
+
+/// 敌人定义插件 - 注册敌人定义资源类型和加载器，启动时加载assets/enemies目录和模组中的定义，
+/// 目录加载完成或定义文件被修改（dev特性下热重载）时重建敌人名册
+pub struct EnemyDefPlugin;
+
+impl Plugin for EnemyDefPlugin {
+    fn build(&self, app: &mut App) {
+        // 注册敌人定义资源类型和加载器
+        app.init_asset::<EnemyDef>()
+            .init_asset_loader::<EnemyDefLoader>()
+            // 加载定义目录
+            .add_systems(Startup, enemy_def_load_system)
+            // 定义变化时重建名册
+            .add_systems(
+                Update,
+                enemy_roster_system.run_if(
+                    on_event::<AssetEvent<EnemyDef>>.or(on_event::<AssetEvent<LoadedFolder>>),
+                ),
+            );
+    }
+}
+
+/// 定义加载系统
+fn enemy_def_load_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mods: Res<ModList>,
+) {
+    let folder = asset_server.load_folder(asset_path(ENEMIES_DIR));
+    let mods = mods
+        .files_in(ENEMIES_DIR, ENEMY_EXTENSION)
+        .into_iter()
+        .map(|(_, path)| asset_server.load(path))
+        .collect();
+    commands.insert_resource(EnemyDefSources { folder, mods });
+}
+
+/// 名册系统 - 按名称排序收集目录中全部已加载的定义（目录中的其他文件忽略），模组中的同名定义覆盖原定义
+fn enemy_roster_system(
+    sources: Res<EnemyDefSources>,
+    folders: Res<Assets<LoadedFolder>>,
+    defs: Res<Assets<EnemyDef>>,
+    mut roster: ResMut<EnemyRoster>,
+) {
+    let Some(folder) = folders.get(&sources.folder) else {
+        return;
+    };
+    let base = folder
+        .handles
+        .iter()
+        .filter_map(|handle| handle.id().try_typed::<EnemyDef>().ok());
+    let mods = sources.mods.iter().map(Handle::id);
+    let mut list: Vec<EnemyDef> = Vec::new();
+    for def in base.chain(mods).filter_map(|id| defs.get(id)) {
+        list.retain(|other| other.name != def.name);
+        list.push(def.clone());
+    }
+    list.sort_by(|a, b| a.name.cmp(&b.name));
+    if !cfg!(feature = "scripting") {
+        for def in list.iter().filter(|def| def.behavior.is_some()) {
+            warn!(enemy = %def.name, "行为脚本需要scripting特性，该敌人按编队移动");
+        }
+    }
+    info!(
+        count = list.len(),
+        names = ?list.iter().map(|def| def.name.as_str()).collect::<Vec<_>>(),
+        "更新敌人名册"
+    );
+    roster.0 = list;
+}
@@ -0,0 +1,169 @@
+use super::formation::Formation;
+use super::{EnemyDef, EnemySpawn, SpawnEnemyEvent, SpawnKind, Upgrades, spawn_enemy};
+use crate::components::Resistances;
+use crate::registry::AssetRegistry;
+use crate::rng::GameRng;
+use crate::theme::ThemeAssets;
+use crate::{ENEMY_SIZE, EnemyCount, SPRITE_SCALE};
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+use rand::Rng;
+
+// 生成的敌人成为精英的概率
+const ELITE_CHANCE: f32 = 0.15;
+// 每个精英词缀提供的额外得分
+const ELITE_BONUS_PER_MODIFIER: u64 = 50;
+// 精英敌人的着色
+const ELITE_TINT: Color = Color::srgb(1., 0.75, 0.2);
+// 精英敌人的放大倍率（配合着色作为外观区分）
+const ELITE_SCALE: f32 = 1.2;
+// 迅捷词缀的移动速度倍率
+const FAST_SCALE: f32 = 1.6;
+// 装甲词缀额外承受的伤害（普通命中为1）
+const ARMOR_HITS: f32 = 2.;
+// 装甲词缀的抗性：抵抗动能，惧怕爆炸
+const ARMOR_RESISTANCES: Resistances = Resistances {
+    kinetic: 0.5,
+    energy: 1.,
+    explosive: 2.,
+};
+// 分裂产生的碎片数量
+const SPLIT_COUNT: u32 = 2;
+// 分裂碎片的缩放倍率
+const FRAGMENT_SCALE: f32 = 0.6;
+// 双发词缀两束激光的水平间距
+pub const DOUBLE_SHOT_OFFSET: f32 = 12.;
+
+/// 精英词缀种类
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EliteModifier {
+    Fast,       // 迅捷：移动更快
+    Armored,    // 装甲：需要多次命中才能击毁
+    Splitting,  // 分裂：被击毁时分裂成两个小型敌人
+    DoubleShot, // 双发：每次开火发射两束激光
+}
+
+impl EliteModifier {
+    pub const ALL: [EliteModifier; 4] = [
+        EliteModifier::Fast,
+        EliteModifier::Armored,
+        EliteModifier::Splitting,
+        EliteModifier::DoubleShot,
+    ];
+
+    /// 为敌人实体添加词缀对应的组件
+    fn apply(self, entity: &mut EntityCommands) {
+        match self {
+            EliteModifier::Fast => entity.insert(Fast(FAST_SCALE)),
+            EliteModifier::Armored => {
+                entity.insert((Armored { hits: ARMOR_HITS }, ARMOR_RESISTANCES))
+            }
+            EliteModifier::Splitting => entity.insert(Splitting),
+            EliteModifier::DoubleShot => entity.insert(DoubleShot),
+        };
+    }
+}
+
+/// 精英组件 - 标记精英敌人，击毁时获得额外得分
+#[derive(Component, Clone)]
+pub struct Elite {
+    pub bonus: u64, // 额外得分
+}
+
+/// 迅捷组件 - 敌人移动速度倍率
+#[derive(Component, Clone)]
+pub struct Fast(pub f32);
+
+/// 装甲组件 - 剩余可抵挡的伤害，不足以抵挡下一次命中时敌人被击毁
+#[derive(Component, Clone)]
+pub struct Armored {
+    pub hits: f32, // 剩余可抵挡的伤害（普通命中为1）
+}
+
+/// 分裂组件 - 被击毁时分裂成小型敌人
+#[derive(Component, Clone)]
+pub struct Splitting;
+
+/// 双发组件 - 每次开火发射两束激光
+#[derive(Component, Clone)]
+pub struct DoubleShot;
+
+/// 敌人分裂事件 - 带分裂词缀的敌人被击毁时发送（分裂位置）
+#[derive(Event)]
+pub struct EnemySplitEvent(pub Vec3);
+
+/// 按概率把新生成的敌人升级为精英
+pub fn roll_elite(
+    entity: &mut EntityCommands,
+    sprite: &mut Sprite,
+    transform: &mut Transform,
+    rng: &mut GameRng,
+) {
+    if rng.gen_range(0.0..1.0) < ELITE_CHANCE {
+        make_elite(entity, sprite, transform, rng);
+    }
+}
+
+/// 把新生成的敌人升级为精英：随机添加1~2个不同的词缀，着色并放大
+pub fn make_elite(
+    entity: &mut EntityCommands,
+    sprite: &mut Sprite,
+    transform: &mut Transform,
+    rng: &mut GameRng,
+) {
+    // 随机选出1~2个不重复的词缀
+    let count = rng.gen_range(1..=2);
+    let mut pool = EliteModifier::ALL.to_vec();
+    for _ in 0..count {
+        let modifier = pool.swap_remove(rng.gen_range(0..pool.len()));
+        modifier.apply(entity);
+    }
+
+    entity.insert(Elite {
+        bonus: ELITE_BONUS_PER_MODIFIER * count,
+    });
+    sprite.color = ELITE_TINT;
+    transform.scale *= Vec3::new(ELITE_SCALE, ELITE_SCALE, 1.);
+}
+
+/// 生成分裂碎片 - 小型普通敌人（碎片不会再成为精英），同样计入敌人数量，击毁前本波不会结束
+pub fn spawn_fragment(
+    commands: &mut Commands,
+    registry: &AssetRegistry,
+    theme_assets: &ThemeAssets,
+    enemy_count: &mut EnemyCount,
+    rng: &mut GameRng,
+    translation: Vec3,
+    formation: Formation,
+) {
+    spawn_enemy(
+        commands,
+        registry,
+        theme_assets,
+        enemy_count,
+        rng,
+        EnemySpawn {
+            translation,
+            scale: SPRITE_SCALE * FRAGMENT_SCALE,
+            tint: None,
+            formation,
+            upgrades: Upgrades::None,
+            def: EnemyDef::default(),
+        },
+    );
+}
+
+/// 分裂系统 - 在分裂位置发送生成碎片的事件
+pub fn enemy_split_system(
+    mut events: EventReader<EnemySplitEvent>,
+    mut spawn_events: EventWriter<SpawnEnemyEvent>,
+) {
+    for EnemySplitEvent(position) in events.read() {
+        for i in 0..SPLIT_COUNT {
+            // 碎片从分裂位置出发，向左右两侧错开后并入编队轨迹
+            let offset = (i as f32 - 0.5) * ENEMY_SIZE.0 * SPRITE_SCALE;
+            let translation = Vec3::new(position.x + offset, position.y, 10.);
+            spawn_events.send(SpawnEnemyEvent::at(SpawnKind::Fragment, translation));
+        }
+    }
+}
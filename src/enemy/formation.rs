@@ -1,6 +1,7 @@
-use crate::{BASE_SPEED, FORMATION_MEMBERS_MAX, WinSize};
+use crate::rng::GameRng;
+use crate::{FORMATION_MEMBERS_MAX, WinSize};
 use bevy::prelude::{Component, Resource};
-use rand::{Rng, thread_rng};
+use rand::Rng;
 use std::f32::consts::PI;
 
 /// 组件 - 敌人编队（每个敌人都有）
@@ -20,7 +21,7 @@ pub struct Formation {
 
 /// 资源 - 编队生成器
 /// 负责创建和管理敌人编队模板
-#[derive(Default, Resource)]
+#[derive(Default, Resource, Clone)]
 pub struct FormationMaker {
     current_template: Option<Formation>, // 当前使用的编队模板
     current_members: u32,                // 当前编队中的敌人数量
@@ -28,14 +29,22 @@ pub struct FormationMaker {
 
 /// 编队工厂实现
 impl FormationMaker {
+    /// 结束当前编队，下一次创建时使用新的模板
+    pub fn reset(&mut self) {
+        self.current_template = None;
+        self.current_members = 0;
+    }
+
     /// 创建一个新的编队或使用现有模板
     ///
     /// 参数:
     /// - win_size: 窗口尺寸，用于计算编队参数
+    /// - base_speed: 基础移动速度（新编队的初始速度）
+    /// - rng: 随机数资源，用于随机生成新编队的起点和轨迹
     ///
     /// 返回:
     /// 一个新的Formation实例，用于控制敌人移动
-    pub fn make(&mut self, win_size: &WinSize) -> Formation {
+    pub fn make(&mut self, win_size: &WinSize, base_speed: f32, rng: &mut GameRng) -> Formation {
         match (
             &self.current_template,
             self.current_members >= FORMATION_MEMBERS_MAX,
@@ -47,8 +56,6 @@ impl FormationMaker {
             }
             // 如果是第一个编队或前一个编队已满，则创建新编队
             (None, _) | (_, true) => {
-                let mut rng = thread_rng();
-
                 // 计算起始x/y坐标
                 // 从屏幕左侧或右侧随机位置生成
                 let w_span = win_size.w / 2. + 100.;
@@ -68,8 +75,8 @@ impl FormationMaker {
                 // 计算起始角度（朝向中心点）
                 let angle = (y - pivot.1).atan2(x - pivot.0);
 
-                // 速度（目前固定）
-                let speed = BASE_SPEED;
+                // 速度（取基础移动速度）
+                let speed = base_speed;
 
                 // 随机生成参数变化速度
                 // 这些参数将用于后续动态调整编队
@@ -1,16 +1,65 @@
-use self::formation::{Formation, FormationMaker};
-use crate::components::{Enemy, FromEnemy, Laser, Movable, SpriteSize, Velocity};
-use crate::{
-    BASE_SPEED, ENEMY_LASER_SIZE, ENEMY_MAX, ENEMY_SIZE, EnemyCount, GameTextures, SPRITE_SCALE,
-    WinSize,
+use self::carrier::{
+    Carrier, Minion, carrier_bay_system, roll_carrier, spawn_carrier, spawn_minion,
 };
+use self::composite::{attach_parts, part_hit_system, roll_composite};
+use self::elite::{Fast, enemy_split_system, make_elite, roll_elite, spawn_fragment};
+use self::weak_point::{CritFlash, crit_flash_system};
+use crate::boss::spawn_boss;
+use crate::combat::death_system;
+use crate::components::{
+    Enemy, EnemyKind, FireCooldown, FirePeriod, Health, Laser, Movable, SpriteSize, Team, Velocity,
+};
+use crate::config::{GameConfig, Tuning};
+use crate::cutscene::Cutscene;
+use crate::difficulty::Difficulty;
+use crate::faction_time::{Faction, FactionTime};
+use crate::game_mode::{GameMode, GameModes};
+use crate::hazard::Slowed;
+use crate::math::{distance, ellipse_point, next_angle, step_towards};
+use crate::pool::BulletPool;
+use crate::registry::{AssetRegistry, Tex};
+use crate::rng::GameRng;
+use crate::sim::RollbackAppExt;
+use crate::status::StatusEffects;
+use crate::theme::ThemeAssets;
+use crate::wave::Wave;
+use crate::{ENEMY_LASER_SIZE, EnemyCount, GameSet, SPRITE_SCALE, WinSize};
 
+use bevy::ecs::system::EntityCommands;
 use bevy::prelude::*;
-use bevy::time::common_conditions::on_timer;
-use rand::{Rng, thread_rng};
+use rand::Rng;
 use std::{f32::consts::PI, time::Duration};
 
+mod carrier;
+mod composite;
+mod definition;
+mod elite;
 mod formation;
+mod weak_point;
+
+pub use self::composite::{EnemyPart, PartKind};
+pub use self::definition::{
+    Behavior, EnemyDef, EnemyDefPlugin, EnemyRoster, FirePattern, ScoreValue, ScriptControlled,
+    SpeedScale,
+};
+pub use self::elite::{Armored, DoubleShot, Elite, EnemySplitEvent, Splitting};
+pub use self::formation::{Formation, FormationMaker};
+pub use self::weak_point::{WeakPoint, spawn_crit_flash, spawn_weak_point};
+
+/// 敌人生成计时器资源 - 按难度倍率加速推进
+#[derive(Resource, Clone)]
+struct EnemySpawnTimer(Timer);
+
+impl FromWorld for EnemySpawnTimer {
+    /// 生成间隔取自游戏配置（可被环境变量覆盖）
+    fn from_world(world: &mut World) -> Self {
+        let interval = world
+            .get_resource::<GameConfig>()
+            .map_or_else(Tuning::default, |config| config.tuning)
+            .enemy_spawn_interval;
+        Self(Timer::from_seconds(interval, TimerMode::Repeating))
+    }
+}
 
 /// 敌人插件 - 管理游戏中所有敌人相关的系统和资源
 pub struct EnemyPlugin;
@@ -19,100 +68,433 @@ impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
         // 初始化编队生成器资源
         app.insert_resource(FormationMaker::default())
-            // 每秒运行一次敌人生成系统
+            // 初始化敌人生成计时器资源
+            .init_resource::<EnemySpawnTimer>()
+            // 登记参与快照回滚的生成状态和敌人组件
+            .rollback_resource::<FormationMaker>()
+            .rollback_resource::<EnemySpawnTimer>()
+            .rollback_component::<Formation>()
+            .rollback_component::<FirePattern>()
+            .rollback_component::<ScoreValue>()
+            .rollback_component::<SpeedScale>()
+            .rollback_component::<Behavior>()
+            .rollback_component::<ScriptControlled>()
+            .rollback_component::<Elite>()
+            .rollback_component::<Fast>()
+            .rollback_component::<Armored>()
+            .rollback_component::<Splitting>()
+            .rollback_component::<DoubleShot>()
+            .rollback_component::<EnemyPart>()
+            .rollback_component::<WeakPoint>()
+            .rollback_component::<CritFlash>()
+            .rollback_component::<Carrier>()
+            .rollback_component_mapped::<Minion>()
+            // 初始化敌人名册资源（由敌人定义插件从定义文件填充）
+            .init_resource::<EnemyRoster>()
+            // 注册敌人分裂事件和生成敌人事件
+            .add_event::<EnemySplitEvent>()
+            .add_event::<SpawnEnemyEvent>()
+            // 按生成计时器发送生成事件（难度越高生成越快），带分裂词缀的敌人被击毁后生成碎片，
+            // 母舰按舰载机上限从机库放出小型战机，最后由敌人生成器统一生成（在本波是否结束的检查之前）
+            .add_systems(
+                FixedUpdate,
+                (
+                    enemy_spawn_system,
+                    enemy_split_system.after(death_system),
+                    carrier_bay_system,
+                    enemy_spawner_system,
+                )
+                    .chain()
+                    .in_set(GameSet::Spawn),
+            )
+            // 推进每个敌人的开火冷却，冷却完毕时开火
+            .add_systems(FixedUpdate, enemy_fire_system.in_set(GameSet::Spawn))
+            // 玩家激光先检测复合敌人的部件，再检测船体
             .add_systems(
-                Update,
-                enemy_spawn_system.run_if(on_timer(Duration::from_secs(1))),
+                FixedUpdate,
+                part_hit_system
+                    .in_set(GameSet::Collision)
+                    .before(crate::laser_hit_enemy_system),
             )
-            // 满足开火条件时运行敌人开火系统
-            .add_systems(Update, enemy_fire_system.run_if(enemy_fire_criteria))
-            // 每帧运行敌人移动系统
-            .add_systems(Update, enemy_movement_system);
+            // 固定步长运行敌人移动系统
+            .add_systems(FixedUpdate, enemy_movement_system.in_set(GameSet::Movement))
+            // 每帧播放命中弱点时的暴击闪光
+            .add_systems(Update, crit_flash_system.in_set(GameSet::Presentation));
     }
 }
 
-/// 敌人生成系统 - 控制敌人的生成逻辑
+/// 敌人生成系统 - 按生成计时器和波次计划发送生成敌人事件
+#[allow(clippy::too_many_arguments)] // 生成敌人需要同时访问多个资源
 fn enemy_spawn_system(
+    time: Res<Time>,
+    faction_time: Res<FactionTime>,
+    config: Res<GameConfig>,
+    difficulty: Res<Difficulty>,
+    mut spawn_timer: ResMut<EnemySpawnTimer>,
+    enemy_count: Res<EnemyCount>,
+    mut wave: ResMut<Wave>,
+    roster: Res<EnemyRoster>,
+    cutscene: Res<Cutscene>,
+    mut rng: ResMut<GameRng>,
+    mode: Res<GameMode>,
+    modes: Res<GameModes>,
+    mut spawn_events: EventWriter<SpawnEnemyEvent>,
+) {
+    // 过场期间或不自动生成敌人的模式中暂停生成
+    if cutscene.active() || !modes.get(*mode).auto_spawn() {
+        return;
+    }
+
+    // 按难度倍率和敌方阵营的时间推进生成计时器
+    spawn_timer.0.tick(
+        faction_time
+            .delta(&time, Faction::Enemy)
+            .mul_f32(difficulty.spawn_scale),
+    );
+    if !spawn_timer.0.just_finished() {
+        return;
+    }
+
+    // 确保本波还有待生成的敌人，且同屏敌人数量不超过最大值
+    if wave.can_spawn() && enemy_count.0 < config.tuning.enemy_max {
+        wave.remaining -= 1;
+
+        // 按概率生成母舰，否则按名册权重生成普通敌人（可能升级为精英或带有部件），从编队的起始位置出发
+        let kind = if roll_carrier(&mut rng) {
+            SpawnKind::Carrier
+        } else {
+            SpawnKind::Enemy(roster.pick(&mut rng), Upgrades::Roll)
+        };
+        spawn_events.send(SpawnEnemyEvent::new(kind));
+    }
+}
+
+/// 生成敌人事件 - 首领、母舰和舰载机、分裂碎片、编队生成、波次脚本和练习模式都发送该事件，
+/// 由敌人生成器统一生成（新增的生成来源同样只需发送该事件）
+#[derive(Event, Clone)]
+pub struct SpawnEnemyEvent {
+    pub kind: SpawnKind,              // 生成的敌人
+    pub position: Option<Vec3>, // 生成位置（为None时从编队的起始位置出发，首领总是从屏幕上方登场）
+    pub formation: Option<Formation>, // 加入的编队（为None时从编队生成器获取）
+}
+
+impl SpawnEnemyEvent {
+    /// 从编队生成器获取编队，并从编队的起始位置出发
+    pub fn new(kind: SpawnKind) -> Self {
+        Self {
+            kind,
+            position: None,
+            formation: None,
+        }
+    }
+
+    /// 在指定位置生成，之后并入编队
+    pub fn at(kind: SpawnKind, position: Vec3) -> Self {
+        Self {
+            position: Some(position),
+            ..Self::new(kind)
+        }
+    }
+}
+
+/// 生成的敌人
+#[derive(Clone, Debug)]
+pub enum SpawnKind {
+    Enemy(EnemyDef, Upgrades), // 按敌人定义生成的普通敌人（升级方式）
+    Carrier,                   // 母舰
+    Minion(Entity),            // 母舰放出的舰载机（所属母舰）
+    Fragment,                  // 分裂词缀的敌人留下的碎片
+    Boss,                      // 首领（播放登场过场）
+}
+
+/// 敌人生成器系统 - 处理生成敌人事件，所有敌人都从这里生成并计入敌人数量
+#[allow(clippy::too_many_arguments)] // 生成敌人需要同时访问多个资源
+pub fn enemy_spawner_system(
     mut commands: Commands,
-    game_textures: Res<GameTextures>,
+    config: Res<GameConfig>,
+    win_size: Res<WinSize>,
+    registry: Res<AssetRegistry>,
+    theme_assets: Res<ThemeAssets>,
     mut enemy_count: ResMut<EnemyCount>,
     mut formation_maker: ResMut<FormationMaker>,
-    win_size: Res<WinSize>,
+    mut cutscene: ResMut<Cutscene>,
+    mut rng: ResMut<GameRng>,
+    mut events: EventReader<SpawnEnemyEvent>,
 ) {
-    // 确保敌人数量不超过最大值
-    if enemy_count.0 < ENEMY_MAX {
-        // 从编队生成器获取编队参数
-        let formation = formation_maker.make(&win_size);
-        let (x, y) = formation.start;
-
-        // 生成敌人实体
-        commands
-            .spawn((
-                // 设置敌人精灵
-                Sprite::from_image(game_textures.enemy.clone()),
-                Transform {
-                    translation: Vec3::new(x, y, 10.), // Z轴设为10，确保显示在背景上方
-                    scale: Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.),
-                    ..Default::default()
-                },
-            ))
-            .insert(Enemy) // 标记为敌人实体
-            .insert(formation) // 添加编队组件控制移动
-            .insert(SpriteSize::from(ENEMY_SIZE)); // 设置精灵大小
-
-        enemy_count.0 += 1; // 更新敌人计数器
+    for event in events.read() {
+        // 事件没有指定时，从编队生成器获取编队并从其起始位置出发
+        let mut placement = || {
+            let formation = event.formation.clone().unwrap_or_else(|| {
+                formation_maker.make(&win_size, config.tuning.base_speed, &mut rng)
+            });
+            let (x, y) = formation.start;
+            let translation = event.position.unwrap_or(Vec3::new(x, y, 10.)); // Z轴设为10，确保显示在背景上方
+            (translation, formation)
+        };
+        match &event.kind {
+            SpawnKind::Enemy(def, upgrades) => {
+                let (translation, formation) = placement();
+                spawn_enemy(
+                    &mut commands,
+                    &registry,
+                    &theme_assets,
+                    &mut enemy_count,
+                    &mut rng,
+                    EnemySpawn {
+                        translation,
+                        scale: SPRITE_SCALE,
+                        tint: None,
+                        formation,
+                        upgrades: *upgrades,
+                        def: def.clone(),
+                    },
+                );
+            }
+            SpawnKind::Carrier => {
+                let (translation, formation) = placement();
+                spawn_carrier(
+                    &mut commands,
+                    &registry,
+                    &theme_assets,
+                    &mut enemy_count,
+                    &mut rng,
+                    translation,
+                    formation,
+                );
+            }
+            SpawnKind::Minion(carrier) => {
+                let (translation, formation) = placement();
+                spawn_minion(
+                    &mut commands,
+                    &registry,
+                    &theme_assets,
+                    &mut enemy_count,
+                    &mut rng,
+                    translation,
+                    formation,
+                    *carrier,
+                );
+            }
+            SpawnKind::Fragment => {
+                let (translation, formation) = placement();
+                spawn_fragment(
+                    &mut commands,
+                    &registry,
+                    &theme_assets,
+                    &mut enemy_count,
+                    &mut rng,
+                    translation,
+                    formation,
+                );
+            }
+            SpawnKind::Boss => spawn_boss(
+                &mut commands,
+                &win_size,
+                &registry,
+                &theme_assets,
+                &mut enemy_count,
+                &mut cutscene,
+                &mut rng,
+            ),
+        }
     }
 }
 
-/// 敌人开火条件 - 随机决定是否开火
-fn enemy_fire_criteria() -> bool {
-    // 约每60帧有1次机会开火(约1秒1次)
-    thread_rng().gen_bool(1. / 60.)
+/// 敌人生成参数
+pub struct EnemySpawn {
+    pub translation: Vec3,    // 生成位置
+    pub scale: f32,           // 缩放
+    pub tint: Option<Color>,  // 着色（为None时使用关卡主题的敌人配色）
+    pub formation: Formation, // 编队（控制移动）
+    pub upgrades: Upgrades,   // 升级为精英、挂上部件的方式
+    pub def: EnemyDef,        // 敌人定义（外观、耐久、速度、开火方式和得分）
+}
+
+/// 敌人升级方式
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Upgrades {
+    None,      // 不升级
+    Roll,      // 按概率升级为精英、挂上部件
+    Elite,     // 必定升级为精英
+    Composite, // 必定挂上部件
+}
+
+/// 生成普通敌人并计入敌人数量 - 属性取自敌人定义，定义没有贴图时外观按关卡主题选择，随机首次开火时间，避免同批敌人同时开火
+/// 由敌人生成器调用（其他模块发送SpawnEnemyEvent），返回的实体命令可继续追加组件
+pub fn spawn_enemy<'a>(
+    commands: &'a mut Commands,
+    registry: &AssetRegistry,
+    theme_assets: &ThemeAssets,
+    enemy_count: &mut EnemyCount,
+    rng: &mut GameRng,
+    spawn: EnemySpawn,
+) -> EntityCommands<'a> {
+    let def = spawn.def;
+    let fire_period = FirePeriod {
+        min: def.fire_period.0,
+        max: def.fire_period.1,
+    };
+    let fire_cooldown = FireCooldown(Timer::from_seconds(fire_period.roll(rng), TimerMode::Once));
+
+    // 设置敌人精灵（使用定义中的贴图，没有时按关卡主题选择贴图变体并着色）
+    let color = spawn.tint.unwrap_or(theme_assets.enemy_tint);
+    let mut sprite = match def.texture {
+        Some(image) => Sprite {
+            image,
+            custom_size: Some(Vec2::new(def.size.0, def.size.1)),
+            color,
+            ..Default::default()
+        },
+        None => Sprite {
+            color,
+            ..registry.sprite(theme_assets.enemy(EnemyKind::Basic))
+        },
+    };
+    let mut transform = Transform {
+        translation: spawn.translation,
+        scale: Vec3::new(spawn.scale, spawn.scale, 1.),
+        ..Default::default()
+    };
+
+    // 生成敌人实体
+    let mut entity = commands.spawn_empty();
+    entity
+        .insert(Enemy) // 标记为敌人实体
+        .insert(Team::ENEMY) // 敌方队伍
+        .insert(EnemyKind::Basic) // 敌人种类
+        .insert(spawn.formation) // 添加编队组件控制移动
+        .insert(SpriteSize::from(def.size)) // 设置精灵大小（碰撞尺寸随缩放变化）
+        .insert(Health::full(def.hp as f32)) // 生命值（耗尽时被击毁）
+        .insert(SpeedScale(def.speed)) // 移动速度倍率
+        .insert(fire_period) // 开火间隔范围
+        .insert(fire_cooldown) // 开火冷却计时器
+        .insert(def.fire) // 开火方式
+        .insert(ScoreValue(def.score)); // 击毁得分
+    if let Some(path) = def.behavior {
+        entity.insert(Behavior(path)); // 行为脚本（启用scripting特性时由脚本控制移动和开火）
+    }
+    match spawn.upgrades {
+        Upgrades::None => {}
+        Upgrades::Roll => {
+            // 按概率升级为精英（添加词缀组件并改变外观）
+            roll_elite(&mut entity, &mut sprite, &mut transform, rng);
+            // 按概率挂上机翼和炮塔部件（船体被击毁时一并销毁）
+            roll_composite(&mut entity, &sprite, rng);
+        }
+        Upgrades::Elite => make_elite(&mut entity, &mut sprite, &mut transform, rng),
+        Upgrades::Composite => attach_parts(&mut entity, &sprite),
+    }
+    entity.insert((sprite, transform));
+
+    enemy_count.0 += 1; // 更新敌人计数器
+    entity
 }
 
-/// 敌人开火系统 - 控制敌人发射激光
+/// 敌人开火系统 - 每个敌人按自己的冷却计时器发射激光（眩晕期间冷却暂停且不开火）
+#[allow(clippy::type_complexity)] // 允许复杂的查询类型
 fn enemy_fire_system(
     mut commands: Commands,
-    game_textures: Res<GameTextures>,
-    enemy_query: Query<&Transform, With<Enemy>>,
+    time: Res<Time>,
+    faction_time: Res<FactionTime>,
+    registry: Res<AssetRegistry>,
+    mut pool: ResMut<BulletPool>,
+    mut rng: ResMut<GameRng>,
+    mut enemy_query: Query<
+        (
+            &Transform,
+            &mut FireCooldown,
+            &FirePeriod,
+            Option<&FirePattern>,
+            Has<DoubleShot>,
+            Option<&StatusEffects>,
+        ),
+        (With<Enemy>, Without<ScriptControlled>),
+    >,
 ) {
-    // 遍历所有敌人
-    for &tf in enemy_query.iter() {
-        let (x, y) = (tf.translation.x, tf.translation.y);
-
-        // 生成敌人激光
-        commands
-            .spawn((
-                Sprite::from_image(game_textures.enemy_laser.clone()),
+    // 遍历所有敌人（由行为脚本控制的敌人由脚本决定何时开火）
+    for (tf, mut cooldown, fire_period, pattern, double_shot, status) in &mut enemy_query {
+        if StatusEffects::suppressed(status) {
+            continue;
+        }
+        cooldown.0.tick(faction_time.delta(&time, Faction::Enemy));
+        if !cooldown.0.finished() {
+            continue;
+        }
+
+        // 重新随机下一次开火间隔
+        cooldown
+            .0
+            .set_duration(Duration::from_secs_f32(fire_period.roll(&mut rng)));
+        cooldown.0.reset();
+
+        // 按开火方式发射激光（双发词缀的敌人在两侧各发射一束）
+        let shots = pattern.copied().unwrap_or_default().shots(double_shot);
+        fire_lasers(&mut commands, &registry, &mut pool, tf.translation, shots);
+    }
+}
+
+/// 从敌人位置发射一组激光（各束的水平偏移和水平速度见FirePattern::shots）
+pub fn fire_lasers(
+    commands: &mut Commands,
+    registry: &AssetRegistry,
+    pool: &mut BulletPool,
+    position: Vec3,
+    shots: Vec<(f32, f32)>,
+) {
+    let (x, y) = (position.x, position.y);
+    for (offset, vx) in shots {
+        pool.spawn(
+            commands,
+            (
+                registry.sprite(Tex::EnemyLaser),
                 Transform {
-                    translation: Vec3::new(x, y - 15., 0.), // 激光初始位置
-                    rotation: Quat::from_rotation_x(PI),    // 旋转180度，使激光朝下
+                    translation: Vec3::new(x + offset, y - 15., 0.), // 激光初始位置
+                    // 旋转180度使激光朝下，扇形散开的激光再沿飞行方向倾斜
+                    rotation: Quat::from_rotation_z(vx.atan2(1.)) * Quat::from_rotation_x(PI),
                     scale: Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.),
                 },
-            ))
-            .insert(Laser) // 标记为激光实体
-            .insert(SpriteSize::from(ENEMY_LASER_SIZE)) // 设置激光大小
-            .insert(FromEnemy) // 标记为敌人发射的激光
-            .insert(Movable { auto_despawn: true }) // 可移动且超出屏幕自动销毁
-            .insert(Velocity { x: 0., y: -1. }); // 设置向下的速度
+            ),
+        )
+        .insert(Laser) // 标记为激光实体
+        .insert(SpriteSize::from(ENEMY_LASER_SIZE)) // 设置激光大小
+        .insert(Team::ENEMY) // 标记为敌方队伍的激光
+        .insert(Movable { auto_despawn: true }) // 可移动且超出屏幕自动回收到对象池
+        .insert(Velocity { x: vx, y: -1. }); // 设置向下（扇形时斜向下）的速度
     }
 }
 
-/// 敌人移动系统 - 控制敌人按照编队参数移动
+/// 敌人移动系统 - 控制敌人按照编队参数移动（由行为脚本控制的敌人除外）
+#[allow(clippy::type_complexity)] // 允许复杂的查询类型
 fn enemy_movement_system(
     time: Res<Time>,
+    faction_time: Res<FactionTime>,
+    config: Res<GameConfig>,
+    difficulty: Res<Difficulty>,
     win_size: Res<WinSize>,
-    mut query: Query<(&mut Transform, &mut Formation), With<Enemy>>,
+    mut rng: ResMut<GameRng>,
+    mut query: Query<
+        (
+            &mut Transform,
+            &mut Formation,
+            Option<&Slowed>,
+            Option<&SpeedScale>,
+            Option<&Fast>,
+            Option<&StatusEffects>,
+        ),
+        (With<Enemy>, Without<ScriptControlled>),
+    >,
 ) {
-    let delta = time.delta_secs(); // 获取每帧时间间隔
+    for (mut transform, mut formation, slowed, speed, fast, status) in &mut query {
+        // 每帧时间间隔（减速区域内、减速或眩晕状态下按比例缩短）
+        let delta = faction_time.delta_secs(&time, Faction::Enemy)
+            * Slowed::scale(slowed)
+            * StatusEffects::speed_scale(status);
 
-    for (mut transform, mut formation) in &mut query {
         // 1. 更新编队参数（每0.5秒随机调整一次）
         formation.change_timer += delta;
 
         // 每0.5秒随机改变移动参数，使编队动态变化
         if formation.change_timer > 0.5 {
-            let mut rng = thread_rng();
             formation.pivot_delta = (rng.gen_range(-20.0..20.0), rng.gen_range(-20.0..20.0));
             formation.radius_delta = (rng.gen_range(-10.0..10.0), rng.gen_range(-10.0..10.0));
             formation.speed_delta = rng.gen_range(-10.0..10.0);
@@ -133,40 +515,39 @@ fn enemy_movement_system(
         formation.pivot.1 = formation.pivot.1.clamp(0.0, h_span);
         formation.radius.0 = formation.radius.0.clamp(50.0, 200.0);
         formation.radius.1 = formation.radius.1.clamp(50.0, 150.0);
-        formation.speed = formation.speed.clamp(BASE_SPEED * 0.5, BASE_SPEED * 1.5);
+        let speed_scale = difficulty.speed_scale; // 难度越高速度上下限越大
+        let base_speed = config.tuning.base_speed;
+        formation.speed = formation.speed.clamp(
+            base_speed * 0.5 * speed_scale,
+            base_speed * 1.5 * speed_scale,
+        );
 
         // 2. 计算敌人位置（沿椭圆轨迹移动）
         let (x_org, y_org) = (transform.translation.x, transform.translation.y);
-        let max_distance = delta * formation.speed; // 每帧最大移动距离
+        let def_scale = speed.map_or(1., |speed| speed.0); // 敌人定义中的速度倍率
+        let fast_scale = fast.map_or(1., |fast| fast.0); // 迅捷词缀加快移动
+        let max_distance = delta * formation.speed * def_scale * fast_scale; // 每帧最大移动距离
 
         // 决定移动方向（根据起始位置确定顺时针/逆时针）
         let dir: f32 = if formation.start.0 < 0. { 1. } else { -1. };
-        let (x_pivot, y_pivot) = formation.pivot;
-        let (x_radius, y_radius) = formation.radius;
 
         // 计算下一个角度（基于时间和速度）
-        let angle =
-            formation.angle + dir * formation.speed * delta / (x_radius.min(y_radius) * PI / 2.);
+        let angle = next_angle(
+            formation.angle,
+            dir,
+            formation.speed,
+            delta,
+            formation.radius,
+        );
 
         // 计算目标位置（椭圆轨迹上的点）
-        let x_dst = x_radius * angle.cos() + x_pivot;
-        let y_dst = y_radius * angle.sin() + y_pivot;
+        let dst = ellipse_point(formation.pivot, formation.radius, angle);
 
         // 计算当前位置与目标位置的距离
-        let dx = x_org - x_dst;
-        let dy = y_org - y_dst;
-        let distance = (dx * dx + dy * dy).sqrt();
-        let distance_ratio = if distance == 0. {
-            0.
-        } else {
-            max_distance / distance
-        };
+        let distance = distance((x_org, y_org), dst);
 
         // 计算最终位置（平滑过渡到目标位置）
-        let x = x_org - dx * distance_ratio;
-        let x = if dx > 0. { x.max(x_dst) } else { x.min(x_dst) };
-        let y = y_org - dy * distance_ratio;
-        let y = if dy > 0. { y.max(y_dst) } else { y.min(y_dst) };
+        let (x, y) = step_towards((x_org, y_org), dst, max_distance);
 
         // 只有当敌人接近椭圆轨迹时才更新角度，确保平滑过渡
         if distance < max_distance * formation.speed / 20. {
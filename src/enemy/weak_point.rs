@@ -0,0 +1,65 @@
+use crate::components::SpriteSize;
+use bevy::prelude::*;
+
+// 弱点的颜色
+const WEAK_POINT_COLOR: Color = Color::srgb(1., 0.55, 0.1);
+// 暴击闪光的颜色
+const CRIT_FLASH_COLOR: Color = Color::srgb(1., 1., 0.4);
+// 暴击闪光的初始尺寸（像素）
+const CRIT_FLASH_SIZE: f32 = 18.;
+// 暴击闪光的持续时间（秒）
+const CRIT_FLASH_SECS: f32 = 0.25;
+// 暴击闪光结束时相对初始尺寸的放大倍率
+const CRIT_FLASH_GROWTH: f32 = 2.5;
+
+/// 弱点组件 - 大型敌人（父实体）的子碰撞体，命中时对父实体造成倍数伤害
+#[derive(Component, Clone)]
+pub struct WeakPoint {
+    pub multiplier: f32, // 伤害倍率（一次命中相当于多少次普通命中）
+}
+
+/// 暴击闪光组件 - 命中弱点时在命中位置显示，放大并淡出后销毁
+#[derive(Component, Clone)]
+pub struct CritFlash(Timer);
+
+/// 在父实体上生成一个弱点（偏移和尺寸为父实体缩放前的像素）
+pub fn spawn_weak_point(
+    parent: &mut ChildSpawnerCommands,
+    offset: Vec2,
+    size: f32,
+    multiplier: f32,
+) {
+    parent.spawn((
+        Sprite::from_color(WEAK_POINT_COLOR, Vec2::splat(size)),
+        Transform::from_translation(offset.extend(0.5)), // 显示在父实体上方
+        SpriteSize(Vec2::splat(size)),
+        WeakPoint { multiplier },
+    ));
+}
+
+/// 在命中位置生成暴击闪光
+pub fn spawn_crit_flash(commands: &mut Commands, position: Vec3) {
+    commands.spawn((
+        Sprite::from_color(CRIT_FLASH_COLOR, Vec2::splat(CRIT_FLASH_SIZE)),
+        Transform::from_translation(position.with_z(30.)),
+        CritFlash(Timer::from_seconds(CRIT_FLASH_SECS, TimerMode::Once)),
+    ));
+}
+
+/// 暴击闪光系统 - 推进闪光计时器，逐渐放大淡出，结束后销毁
+pub fn crit_flash_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut CritFlash, &mut Transform, &mut Sprite)>,
+) {
+    for (entity, mut flash, mut transform, mut sprite) in &mut query {
+        flash.0.tick(time.delta());
+        if flash.0.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let progress = flash.0.fraction();
+        transform.scale = Vec3::splat(1. + (CRIT_FLASH_GROWTH - 1.) * progress);
+        sprite.color = CRIT_FLASH_COLOR.with_alpha(1. - progress);
+    }
+}
@@ -0,0 +1,184 @@
+use crate::GameSet;
+use crate::combat::EntityKilledEvent;
+use crate::pickup::PickupCollectedEvent;
+use crate::wave::WaveEvent;
+use bevy::log::tracing::{Event as TracingEvent, Subscriber};
+use bevy::log::tracing_subscriber::layer::{Context, Layer};
+use bevy::log::{BoxedLayer, Level};
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, PoisonError};
+
+// 开关事件日志面板的按键
+const EVENT_LOG_TOGGLE_KEY: KeyCode = KeyCode::F2;
+// 面板保留的最近事件条数
+const EVENT_LOG_LINES: usize = 14;
+
+/// 事件日志资源 - 最近的玩法事件（英文，默认字体不含中文字形）
+#[derive(Resource, Default)]
+struct EventLog {
+    lines: VecDeque<String>, // 最近的事件（最新的在末尾）
+    visible: bool,           // 面板是否显示
+}
+
+impl EventLog {
+    /// 记录一条事件（带游戏时间），超出条数时丢弃最早的
+    fn push(&mut self, secs: f32, text: String) {
+        if self.lines.len() == EVENT_LOG_LINES {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(format!("[{secs:>6.1}] {text}"));
+    }
+}
+
+/// 日志捕获资源 - 日志输出层收集到的警告和错误，每帧转入事件日志
+#[derive(Resource, Clone, Default)]
+struct LogCapture(Arc<Mutex<Vec<String>>>);
+
+/// 日志捕获层 - 把警告和错误级别的日志转为面板条目（日志正文为中文，面板只显示级别和模块，详情见日志输出）
+struct CaptureLayer(LogCapture);
+
+impl<S: Subscriber> Layer<S> for CaptureLayer {
+    fn on_event(&self, event: &TracingEvent<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let level = *metadata.level();
+        if level > Level::WARN {
+            return;
+        }
+        let module = metadata.target().rsplit("::").next().unwrap_or_default();
+        let mut captured = self.0.0.lock().unwrap_or_else(PoisonError::into_inner);
+        captured.push(format!("{level} in {module} (see log)"));
+    }
+}
+
+/// 创建日志捕获层，并把共享的捕获缓冲插入App供面板读取
+pub fn capture_layer(app: &mut App) -> BoxedLayer {
+    let capture = LogCapture::default();
+    app.insert_resource(capture.clone());
+    Box::new(CaptureLayer(capture))
+}
+
+/// 面板文本标记
+#[derive(Component)]
+struct EventLogText;
+
+/// 事件日志插件 - 按F2开关右侧面板，列出最近的击毁、拾取、波次切换和错误事件（便于调试和直播）
+pub struct EventLogPlugin;
+
+impl Plugin for EventLogPlugin {
+    fn build(&self, app: &mut App) {
+        // 初始化事件日志资源
+        app.init_resource::<EventLog>()
+            // 创建面板（默认隐藏）
+            .add_systems(Startup, event_log_setup_system)
+            // 收集玩法事件和捕获的日志
+            .add_systems(
+                Update,
+                (event_log_collect_system, event_log_capture_system).before(GameSet::Presentation),
+            )
+            // 开关面板并刷新文本
+            .add_systems(
+                Update,
+                (event_log_toggle_system, event_log_text_system)
+                    .chain()
+                    .in_set(GameSet::Presentation),
+            );
+    }
+}
+
+/// 面板初始化系统 - 右上角半透明面板
+fn event_log_setup_system(mut commands: Commands) {
+    commands.spawn((
+        EventLogText,
+        Text::new(""),
+        TextFont {
+            font_size: 12.,
+            ..Default::default()
+        },
+        TextColor(Color::srgb(0.85, 0.85, 0.85)),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(8.),
+            top: Val::Px(56.),
+            width: Val::Px(230.),
+            padding: UiRect::all(Val::Px(6.)),
+            ..Default::default()
+        },
+        BackgroundColor(Color::srgba(0., 0., 0., 0.6)),
+        GlobalZIndex(5),
+        Visibility::Hidden,
+    ));
+}
+
+/// 事件收集系统 - 把击毁、拾取和波次事件写入事件日志
+fn event_log_collect_system(
+    time: Res<Time>,
+    mut log: ResMut<EventLog>,
+    mut kill_events: EventReader<EntityKilledEvent>,
+    mut pickup_events: EventReader<PickupCollectedEvent>,
+    mut wave_events: EventReader<WaveEvent>,
+) {
+    let secs = time.elapsed_secs();
+    for event in kill_events.read() {
+        log.push(secs, format!("Kill: {:?}", event.kind));
+    }
+    for PickupCollectedEvent(kind) in pickup_events.read() {
+        log.push(secs, format!("Pickup: {kind:?}"));
+    }
+    for event in wave_events.read() {
+        let text = match *event {
+            WaveEvent::Started(number) => format!("Wave {number} started"),
+            WaveEvent::BossIncoming(number) => format!("Wave {number}: boss incoming"),
+            WaveEvent::Cleared(number) => format!("Wave {number} cleared"),
+        };
+        log.push(secs, text);
+    }
+}
+
+/// 日志转入系统 - 把捕获的警告和错误写入事件日志
+fn event_log_capture_system(
+    time: Res<Time>,
+    capture: Option<Res<LogCapture>>,
+    mut log: ResMut<EventLog>,
+) {
+    let Some(capture) = capture else {
+        return;
+    };
+    let captured: Vec<String> = capture
+        .0
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .drain(..)
+        .collect();
+    for text in captured {
+        log.push(time.elapsed_secs(), text);
+    }
+}
+
+/// 面板开关系统 - 按F2切换显示
+fn event_log_toggle_system(kb: Res<ButtonInput<KeyCode>>, mut log: ResMut<EventLog>) {
+    if kb.just_pressed(EVENT_LOG_TOGGLE_KEY) {
+        log.visible = !log.visible;
+    }
+}
+
+/// 面板刷新系统 - 事件日志变化或开关时更新文本和可见性
+fn event_log_text_system(
+    log: Res<EventLog>,
+    mut query: Query<(&mut Text, &mut Visibility), With<EventLogText>>,
+) {
+    if !log.is_changed() {
+        return;
+    }
+    let Ok((mut text, mut visibility)) = query.get_single_mut() else {
+        return;
+    };
+    visibility.set_if_neq(if log.visible {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    });
+    let mut lines = vec!["EVENTS [F2]".to_string()];
+    lines.extend(log.lines.iter().cloned());
+    text.0 = lines.join("\n");
+}
@@ -0,0 +1,104 @@
+use crate::GameSet;
+use crate::components::Player;
+use crate::sim::RollbackAppExt;
+use bevy::prelude::*;
+use std::time::Duration;
+
+// 时间减缓期间敌方阵营的时间倍率
+pub const TIME_SLOW_SCALE: f32 = 0.4;
+// 时间减缓的持续时间（秒）
+pub const TIME_SLOW_SECS: f32 = 5.;
+// 慢动作期间敌方阵营的时间倍率
+const SLOW_MOTION_SCALE: f32 = 0.25;
+
+/// 阵营 - 按阵营分别缩放模拟时间（实体通过队伍组件Team标记所属阵营）
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Faction {
+    Player, // 玩家阵营：玩家、玩家激光和拾取物，始终按全局时间运行
+    Enemy,  // 敌方阵营：敌人、敌人激光和流星等障碍物
+}
+
+/// 时间减缓组件 - 玩家持有时敌方阵营按减缓后的时间运行，计时结束后移除（计时按全局时间推进）
+#[derive(Component, Clone)]
+pub struct TimeSlow {
+    pub timer: Timer,
+}
+
+/// 阵营时间资源 - 各阵营相对全局时间的倍率，敌方系统用它换算每帧的时间间隔
+#[derive(Resource, Clone)]
+pub struct FactionTime {
+    enemy: f32,         // 敌方阵营的时间倍率
+    slow_motion: Timer, // 慢动作计时器（按全局时间推进）
+}
+
+impl Default for FactionTime {
+    fn default() -> Self {
+        Self {
+            enemy: 1.,
+            slow_motion: Timer::default(),
+        }
+    }
+}
+
+impl FactionTime {
+    /// 开始一段慢动作（如玩家死亡时），期间敌方阵营减速到25%
+    pub fn start_slow_motion(&mut self, secs: f32) {
+        self.slow_motion = Timer::from_seconds(secs, TimerMode::Once);
+    }
+
+    /// 阵营的时间倍率
+    pub fn scale(&self, faction: Faction) -> f32 {
+        match faction {
+            Faction::Player => 1.,
+            Faction::Enemy => self.enemy,
+        }
+    }
+
+    /// 阵营本帧的时间间隔（秒）
+    pub fn delta_secs(&self, time: &Time, faction: Faction) -> f32 {
+        time.delta_secs() * self.scale(faction)
+    }
+
+    /// 阵营本帧的时间间隔（用于推进计时器）
+    pub fn delta(&self, time: &Time, faction: Faction) -> Duration {
+        time.delta().mul_f32(self.scale(faction))
+    }
+}
+
+/// 阵营时间插件 - 根据玩家身上的时间减缓组件和慢动作计时器刷新敌方阵营的时间倍率
+pub struct FactionTimePlugin;
+
+impl Plugin for FactionTimePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FactionTime>()
+            .rollback_resource::<FactionTime>()
+            .rollback_component::<TimeSlow>()
+            // 在输入阶段刷新倍率，本帧的移动、碰撞和生成系统都使用同一倍率
+            .add_systems(FixedUpdate, faction_time_system.in_set(GameSet::Input));
+    }
+}
+
+/// 阵营时间系统 - 推进时间减缓计时器，结束后移除组件；玩家持有时间减缓时敌方阵营减速到40%，慢动作期间减速到25%
+fn faction_time_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut faction_time: ResMut<FactionTime>,
+    mut query: Query<(Entity, &mut TimeSlow), With<Player>>,
+) {
+    let mut slowed = false;
+    for (entity, mut time_slow) in &mut query {
+        if time_slow.timer.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<TimeSlow>();
+        } else {
+            slowed = true;
+        }
+    }
+    let slow_motion = !faction_time.slow_motion.tick(time.delta()).finished();
+    faction_time.enemy = if slow_motion {
+        SLOW_MOTION_SCALE
+    } else if slowed {
+        TIME_SLOW_SCALE
+    } else {
+        1.
+    };
+}
@@ -0,0 +1,118 @@
+use crate::components::{InRun, Movable};
+use crate::config::GameConfig;
+use crate::sim::RollbackAppExt;
+use crate::wave::{Wave, WavePhase, WaveWatch};
+use crate::{GameSet, WinSize, movable_system};
+use bevy::prelude::*;
+
+// 离开力场后漂移速度的衰减速率（每秒）
+const DRIFT_DAMPING: f32 = 3.;
+// 漂移速度的上限（速度单位）
+const DRIFT_MAX: f32 = 1.5;
+// 漂移速度低于该值时移除漂移组件
+const DRIFT_EPSILON: f32 = 0.01;
+
+/// 力场区域组件 - 区域内的可移动实体持续受到恒定加速度
+#[derive(Component, Clone)]
+#[require(InRun)]
+pub struct ForceZone {
+    pub area: Rect,  // 区域范围（世界坐标）
+    pub accel: Vec2, // 加速度（速度单位/秒）
+}
+
+/// 漂移组件 - 力场累积的额外速度，与实体自身速度叠加
+/// 玩家速度每帧由输入重新计算，因此外力单独累积，离开力场后逐渐衰减
+#[derive(Component, Clone, Default)]
+pub struct Drift(pub Vec2);
+
+/// 力场插件 - 按关卡脚本生成力场，并对区域内的可移动实体施加加速度
+pub struct ForcePlugin;
+
+impl Plugin for ForcePlugin {
+    fn build(&self, app: &mut App) {
+        // 登记参与快照回滚的组件
+        app.rollback_component::<Drift>()
+            .rollback_component::<ForceZone>();
+
+        // 在通用移动之前累积漂移速度
+        app.add_systems(
+            FixedUpdate,
+            force_zone_system
+                .before(movable_system)
+                .in_set(GameSet::Movement),
+        )
+        // 波次开始时按关卡脚本生成力场，商店阶段清除
+        .add_systems(FixedUpdate, force_zone_spawn_system.in_set(GameSet::Spawn));
+    }
+}
+
+/// 力场生成系统 - 新一波开始时生成关卡脚本中该波次的力场，波次结束后清除
+fn force_zone_spawn_system(
+    mut commands: Commands,
+    wave: Res<Wave>,
+    config: Res<GameConfig>,
+    win_size: Res<WinSize>,
+    query: Query<Entity, With<ForceZone>>,
+    mut watch: ResMut<WaveWatch>,
+) {
+    // 只在波次或阶段切换时处理（生成敌人也会修改波次资源）
+    if !watch.changed("force_zone_spawn", &wave) {
+        return;
+    }
+
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+    if wave.phase != WavePhase::Fighting {
+        return;
+    }
+
+    let window = Vec2::new(win_size.w, win_size.h);
+    for spec in config.level.force_zones_for(wave.number) {
+        let area = Rect::from_center_size(spec.center * window, spec.size * window);
+        commands
+            .spawn((
+                Sprite::from_color(Color::srgba(0.8, 0.9, 1., 0.06), area.size()),
+                Transform::from_translation(area.center().extend(0.5)),
+            ))
+            .insert(ForceZone {
+                area,
+                accel: spec.accel,
+            }); // 标记为力场区域
+    }
+}
+
+/// 力场系统 - 区域内的可移动实体累积漂移速度，区域外的漂移速度逐渐衰减
+fn force_zone_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    zone_query: Query<&ForceZone>,
+    mut query: Query<(Entity, &Transform, Option<&mut Drift>), With<Movable>>,
+) {
+    let delta = time.delta_secs();
+
+    for (entity, transform, drift) in &mut query {
+        let position = transform.translation.truncate();
+        let accel: Vec2 = zone_query
+            .iter()
+            .filter(|zone| zone.area.contains(position))
+            .map(|zone| zone.accel)
+            .sum();
+
+        match drift {
+            Some(mut drift) if accel != Vec2::ZERO => {
+                drift.0 = (drift.0 + accel * delta).clamp_length_max(DRIFT_MAX);
+            }
+            Some(mut drift) => {
+                drift.0 *= (-DRIFT_DAMPING * delta).exp();
+                if drift.0.length() < DRIFT_EPSILON {
+                    commands.entity(entity).remove::<Drift>();
+                }
+            }
+            None if accel != Vec2::ZERO => {
+                commands.entity(entity).insert(Drift(accel * delta));
+            }
+            None => {}
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use crate::GameSet;
+use crate::diagnostics;
+use bevy::diagnostic::DiagnosticsStore;
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+// 开关帧时间曲线的按键
+const FRAME_GRAPH_TOGGLE_KEY: KeyCode = KeyCode::F3;
+// 曲线保留的最近帧数（每帧一根柱）
+const FRAME_GRAPH_FRAMES: usize = 240;
+// 每根柱的宽度（像素）
+const BAR_WIDTH: f32 = 1.;
+// 曲线区域的高度（像素）
+const GRAPH_HEIGHT: f32 = 80.;
+// 曲线顶端对应的帧时间（毫秒，超出的柱截断在顶端）
+const GRAPH_MAX_MS: f32 = 50.;
+// 参考线对应的帧时间（毫秒，60帧/秒）
+const TARGET_MS: f32 = 1000. / 60.;
+// 帧时间超过最近平均值的这一倍数时视为卡顿
+const SPIKE_RATIO: f32 = 2.;
+// 柱的颜色（正常 / 卡顿）
+const BAR_COLOR: Color = Color::srgb(0.4, 1., 0.4);
+const SPIKE_COLOR: Color = Color::srgb(1., 0.25, 0.25);
+
+/// 帧时间曲线资源 - 最近的帧时间（毫秒，最新的在末尾）
+#[derive(Resource, Default)]
+struct FrameGraph {
+    samples: VecDeque<f32>, // 最近的帧时间
+    visible: bool,          // 曲线是否显示
+}
+
+impl FrameGraph {
+    /// 记录一帧的帧时间，超出帧数时丢弃最早的
+    fn push(&mut self, ms: f32) {
+        if self.samples.len() == FRAME_GRAPH_FRAMES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(ms);
+    }
+
+    /// 最近帧时间的平均值
+    fn mean(&self) -> f32 {
+        if self.samples.is_empty() {
+            return 0.;
+        }
+        self.samples.iter().sum::<f32>() / self.samples.len() as f32
+    }
+}
+
+/// 帧时间是否为卡顿（与最近平均值比较）
+fn is_spike(ms: f32, mean: f32) -> bool {
+    ms > mean * SPIKE_RATIO
+}
+
+/// 曲线面板标记
+#[derive(Component)]
+struct FrameGraphPanel;
+
+/// 曲线柱组件 - 从左到右的序号（最右一根为最新的一帧）
+#[derive(Component)]
+struct FrameGraphBar(usize);
+
+/// 曲线统计文本标记
+#[derive(Component)]
+struct FrameGraphText;
+
+/// 帧时间曲线插件 - 按F3开关右下角的帧时间曲线（最近240帧，卡顿帧标红）和实体、内存诊断，用于排查大量爆炸时的卡顿
+pub struct FrameGraphPlugin;
+
+impl Plugin for FrameGraphPlugin {
+    fn build(&self, app: &mut App) {
+        // 初始化帧时间曲线资源
+        app.init_resource::<FrameGraph>()
+            // 创建曲线面板（默认隐藏）
+            .add_systems(Startup, frame_graph_setup_system)
+            // 每帧记录帧时间，开关面板并刷新曲线
+            .add_systems(
+                Update,
+                (
+                    frame_graph_record_system,
+                    frame_graph_toggle_system,
+                    frame_graph_display_system,
+                )
+                    .chain()
+                    .in_set(GameSet::Presentation),
+            );
+    }
+}
+
+/// 曲线面板初始化系统 - 右下角半透明面板，上方为统计文本，下方为柱状曲线和60帧/秒参考线
+fn frame_graph_setup_system(mut commands: Commands) {
+    let width = FRAME_GRAPH_FRAMES as f32 * BAR_WIDTH;
+    commands
+        .spawn((
+            FrameGraphPanel,
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(8.),
+                bottom: Val::Px(8.),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(4.)),
+                ..Default::default()
+            },
+            BackgroundColor(Color::srgba(0., 0., 0., 0.6)),
+            GlobalZIndex(5),
+            Visibility::Hidden,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                FrameGraphText,
+                Text::new(""),
+                TextFont {
+                    font_size: 12.,
+                    ..Default::default()
+                },
+                TextColor(Color::srgb(0.85, 0.85, 0.85)),
+            ));
+            parent
+                .spawn(Node {
+                    width: Val::Px(width),
+                    height: Val::Px(GRAPH_HEIGHT),
+                    align_items: AlignItems::FlexEnd, // 柱从底部向上生长
+                    ..Default::default()
+                })
+                .with_children(|graph| {
+                    for i in 0..FRAME_GRAPH_FRAMES {
+                        graph.spawn((
+                            FrameGraphBar(i),
+                            Node {
+                                width: Val::Px(BAR_WIDTH),
+                                height: Val::Px(0.),
+                                ..Default::default()
+                            },
+                            BackgroundColor(BAR_COLOR),
+                        ));
+                    }
+                    // 60帧/秒参考线
+                    graph.spawn((
+                        Node {
+                            position_type: PositionType::Absolute,
+                            left: Val::Px(0.),
+                            bottom: Val::Px(TARGET_MS / GRAPH_MAX_MS * GRAPH_HEIGHT),
+                            width: Val::Px(width),
+                            height: Val::Px(1.),
+                            ..Default::default()
+                        },
+                        BackgroundColor(Color::srgba(1., 1., 1., 0.4)),
+                    ));
+                });
+        });
+}
+
+/// 帧时间记录系统 - 使用真实时间（不受暂停和时间缩放影响），面板隐藏时也记录，打开时即可看到之前的卡顿
+fn frame_graph_record_system(time: Res<Time<Real>>, mut graph: ResMut<FrameGraph>) {
+    graph.push(time.delta_secs() * 1000.);
+}
+
+/// 曲线开关系统 - 按F3切换显示
+fn frame_graph_toggle_system(
+    kb: Res<ButtonInput<KeyCode>>,
+    mut graph: ResMut<FrameGraph>,
+    mut query: Query<&mut Visibility, With<FrameGraphPanel>>,
+) {
+    if kb.just_pressed(FRAME_GRAPH_TOGGLE_KEY) {
+        graph.visible = !graph.visible;
+    }
+    for mut visibility in &mut query {
+        visibility.set_if_neq(if graph.visible {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        });
+    }
+}
+
+/// 曲线刷新系统 - 面板显示时按帧时间设置每根柱的高度，卡顿帧标红，并显示平均、最大帧时间、卡顿次数和诊断摘要
+fn frame_graph_display_system(
+    graph: Res<FrameGraph>,
+    store: Res<DiagnosticsStore>,
+    mut bar_query: Query<(&FrameGraphBar, &mut Node, &mut BackgroundColor)>,
+    mut text_query: Query<&mut Text, With<FrameGraphText>>,
+) {
+    if !graph.visible {
+        return;
+    }
+    let mean = graph.mean();
+    // 样本不足240帧时右对齐，左侧留空
+    let offset = FRAME_GRAPH_FRAMES - graph.samples.len();
+    for (bar, mut node, mut color) in &mut bar_query {
+        let sample = bar.0.checked_sub(offset).and_then(|i| graph.samples.get(i));
+        let Some(&ms) = sample else {
+            node.height = Val::Px(0.);
+            continue;
+        };
+        node.height = Val::Px(ms.min(GRAPH_MAX_MS) / GRAPH_MAX_MS * GRAPH_HEIGHT);
+        color.0 = if is_spike(ms, mean) {
+            SPIKE_COLOR
+        } else {
+            BAR_COLOR
+        };
+    }
+
+    let Ok(mut text) = text_query.get_single_mut() else {
+        return;
+    };
+    let max = graph.samples.iter().copied().fold(0., f32::max);
+    let spikes = graph
+        .samples
+        .iter()
+        .filter(|&&ms| is_spike(ms, mean))
+        .count();
+    text.0 = format!(
+        "FRAME [F3]  avg {mean:.1} ms  max {max:.1} ms  spikes {spikes}\n{}",
+        diagnostics::summary(&store)
+    );
+}
@@ -0,0 +1,204 @@
+use crate::wave::{Wave, WavePhase};
+use crate::{AppState, GameSet, PLAYER_LIVES, PlayerState};
+use bevy::prelude::*;
+
+/// 游戏模式资源 - 无尽模式、正在进行的战役关卡、得分挑战、练习模式、主菜单演示或注册的自定义模式
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GameMode {
+    #[default]
+    Endless, // 无尽模式：波次不断推进
+    Campaign(usize),      // 战役模式：完成指定关卡的全部波次（关卡下标）
+    ScoreAttack,          // 得分挑战：限时120秒，只计得分
+    Practice,             // 练习模式：按需生成敌人，生命无限
+    Attract,              // 演示模式：主菜单无操作时由自动驾驶进行的演示
+    Custom(&'static str), // 通过add_game_mode注册的其他模式（模式名称）
+}
+
+impl GameMode {
+    /// 是否为同一种模式（战役的各个关卡视为同一种）
+    pub fn same_kind(self, other: GameMode) -> bool {
+        match (self, other) {
+            (GameMode::Campaign(_), GameMode::Campaign(_)) => true,
+            _ => self == other,
+        }
+    }
+}
+
+/// 模式结果 - 一局结束的原因
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ModeOutcome {
+    Victory, // 达成目标（如清除关卡的最后一波）
+    Defeat,  // 失败（如生命耗尽）
+    TimeUp,  // 限时结束
+}
+
+/// 游戏模式插件 - 一种游戏模式的插件及其规则：开局准备、敌人生成规则、胜负条件和计分方式；
+/// 内置模式和分支、模组新增的模式都实现该trait，通过App::add_game_mode注册（同时添加插件本身），
+/// 玩法系统按当前模式从GameModes资源查询规则，未重写的方法使用无尽模式的规则
+pub trait GameModePlugin: Plugin {
+    /// 模式标识（战役返回任一关卡即可）
+    fn mode(&self) -> GameMode;
+
+    /// 主菜单中开始该模式的按键和提示文字（为None时不在主菜单中列出）
+    fn menu_entry(&self) -> Option<(KeyCode, &'static str)> {
+        None
+    }
+
+    /// 开局准备 - 进入游戏时调用（此时已载入关卡脚本）
+    fn setup(&self, _world: &mut World) {}
+
+    /// 是否按波次自动生成敌人
+    fn auto_spawn(&self) -> bool {
+        true
+    }
+
+    /// 敌人生成频率的额外倍率
+    fn spawn_scale(&self) -> f32 {
+        1.
+    }
+
+    /// 清除一波后是否打开商店（否则直接开始下一波）
+    fn shop(&self) -> bool {
+        true
+    }
+
+    /// 生命是否无限（生命数始终保持为初始值，死亡后照常重生）
+    fn infinite_lives(&self) -> bool {
+        false
+    }
+
+    /// 胜负条件 - 游戏进行中每帧检查，返回结果时调用finish结束本局
+    fn outcome(&self, _world: &World) -> Option<ModeOutcome> {
+        None
+    }
+
+    /// 结束本局 - 结算、清理本局实体并切换到下一个界面
+    fn finish(&self, _outcome: ModeOutcome, _world: &mut World) {}
+
+    /// 击毁敌人是否计入档案的累计得分和核心
+    fn counts_score(&self) -> bool {
+        true
+    }
+}
+
+/// 游戏模式规则资源 - 已注册的全部游戏模式（按注册顺序）
+#[derive(Resource, Default)]
+pub struct GameModes(Vec<Box<dyn GameModePlugin>>);
+
+impl GameModes {
+    /// 模式的规则（没有注册的模式使用无尽模式的规则）
+    pub fn get(&self, mode: GameMode) -> &dyn GameModePlugin {
+        self.0
+            .iter()
+            .find(|plugin| plugin.mode().same_kind(mode))
+            .map_or(&EndlessPlugin as &dyn GameModePlugin, |plugin| {
+                plugin.as_ref()
+            })
+    }
+
+    /// 全部已注册的模式
+    pub fn iter(&self) -> impl Iterator<Item = &dyn GameModePlugin> {
+        self.0.iter().map(|plugin| plugin.as_ref())
+    }
+}
+
+/// 注册游戏模式的App扩展
+pub trait GameModeAppExt {
+    /// 添加模式插件并登记其规则
+    fn add_game_mode<M: GameModePlugin + Clone>(&mut self, plugin: M) -> &mut Self;
+}
+
+impl GameModeAppExt for App {
+    fn add_game_mode<M: GameModePlugin + Clone>(&mut self, plugin: M) -> &mut Self {
+        self.init_resource::<GameModes>();
+        self.world_mut()
+            .resource_mut::<GameModes>()
+            .0
+            .push(Box::new(plugin.clone()));
+        self.add_plugins(plugin)
+    }
+}
+
+/// 在模式的setup/finish中运行一次系统（便于用系统参数访问资源和实体），出错时记录警告
+pub fn run_mode_system<O: 'static, M, S: IntoSystem<(), O, M> + 'static>(
+    world: &mut World,
+    system: S,
+) {
+    if let Err(e) = world.run_system_cached(system) {
+        warn!("模式系统运行失败: {e}");
+    }
+}
+
+/// 无尽模式插件 - 波次不断推进、清除一波后打开商店，生命耗尽后不再重生
+#[derive(Clone)]
+pub struct EndlessPlugin;
+
+impl Plugin for EndlessPlugin {
+    fn build(&self, _app: &mut App) {}
+}
+
+impl GameModePlugin for EndlessPlugin {
+    fn mode(&self) -> GameMode {
+        GameMode::Endless
+    }
+}
+
+/// 游戏模式规则插件 - 初始化游戏模式资源，按当前模式的规则执行开局准备、跳过商店、补满生命和胜负判定
+pub struct GameModesPlugin;
+
+impl Plugin for GameModesPlugin {
+    fn build(&self, app: &mut App) {
+        // 初始化游戏模式和模式规则资源
+        app.init_resource::<GameMode>()
+            .init_resource::<GameModes>()
+            // 进入游戏时执行当前模式的开局准备
+            .add_systems(OnEnter(AppState::Playing), game_mode_setup_system)
+            // 固定步长应用商店和生命规则
+            .add_systems(FixedUpdate, game_mode_rules_system.in_set(GameSet::Input))
+            // 检查胜负条件
+            .add_systems(
+                Update,
+                game_mode_outcome_system.run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+/// 开局准备系统
+fn game_mode_setup_system(world: &mut World) {
+    let mode = *world.resource::<GameMode>();
+    world.resource_scope(|world, modes: Mut<GameModes>| modes.get(mode).setup(world));
+}
+
+/// 模式规则系统 - 没有商店的模式清除一波后直接开始下一波，生命无限的模式补满生命
+fn game_mode_rules_system(
+    mode: Res<GameMode>,
+    modes: Res<GameModes>,
+    mut wave: ResMut<Wave>,
+    mut player_state: ResMut<PlayerState>,
+) {
+    let rules = modes.get(*mode);
+    if !rules.shop() && wave.phase == WavePhase::Shop {
+        wave.start_next();
+    }
+    if rules.infinite_lives() && player_state.lives < PLAYER_LIVES {
+        player_state.lives = PLAYER_LIVES;
+    }
+}
+
+/// 胜负判定系统 - 当前模式给出结果时结束本局（已在切换界面时跳过，避免重复结算）
+fn game_mode_outcome_system(world: &mut World) {
+    if matches!(
+        world.resource::<NextState<AppState>>(),
+        NextState::Pending(_)
+    ) {
+        return;
+    }
+    let mode = *world.resource::<GameMode>();
+    world.resource_scope(|world, modes: Mut<GameModes>| {
+        let rules = modes.get(mode);
+        if let Some(outcome) = rules.outcome(world) {
+            info!(?mode, ?outcome, "本局结束");
+            rules.finish(outcome, world);
+        }
+    });
+}
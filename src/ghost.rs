@@ -0,0 +1,252 @@
+use crate::components::Player;
+use crate::game_mode::GameMode;
+use crate::profile::Profile;
+use crate::registry::{AssetRegistry, Tex};
+use crate::run::{GameOverEvent, run_teardown_system};
+use crate::save::{self, SaveFile};
+use crate::settings::Settings;
+use crate::unlocks::ShipSkin;
+use crate::{AppState, GameSet, GameStats, SPRITE_SCALE};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// 最佳成绩回放文件路径
+const GHOST_PATH: &str = "ghost.ron";
+// 记录玩家位置的间隔（秒）
+const GHOST_SAMPLE_SECS: f32 = 0.1;
+// 幽灵战机的不透明度
+const GHOST_ALPHA: f32 = 0.3;
+
+/// 一局的回放 - 得分和按固定间隔记录的玩家位置（玩家不在场时为None）
+#[derive(Clone, Default, Serialize, Deserialize)]
+struct GhostRun {
+    score: u64,                       // 本局得分
+    samples: Vec<Option<(f32, f32)>>, // 每隔GHOST_SAMPLE_SECS记录一次的玩家位置
+}
+
+impl GhostRun {
+    /// 回放到指定时间时的位置，在相邻两次记录之间线性插值（玩家不在场或回放结束时为None）
+    fn position_at(&self, secs: f32) -> Option<Vec2> {
+        let at = secs / GHOST_SAMPLE_SECS;
+        let index = at as usize;
+        let (x, y) = (*self.samples.get(index)?)?;
+        let from = Vec2::new(x, y);
+        match self.samples.get(index + 1).copied().flatten() {
+            Some((x, y)) => Some(from.lerp(Vec2::new(x, y), at.fract())),
+            None => Some(from),
+        }
+    }
+}
+
+/// 最佳成绩回放资源 - 每种可比较成绩的模式各保留一局最高分的回放
+#[derive(Resource, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct GhostRecords {
+    endless: Option<GhostRun>,      // 无尽模式
+    score_attack: Option<GhostRun>, // 得分挑战
+}
+
+impl SaveFile for GhostRecords {
+    const PATH: &'static str = GHOST_PATH;
+    const LABEL: &'static str = "最佳成绩回放";
+    const VERSION: u32 = 1;
+}
+
+impl GhostRecords {
+    /// 从回放文件读取（旧版本的文件先迁移），文件不存在或无法读取时没有回放
+    fn load() -> Self {
+        save::load()
+    }
+
+    /// 写入回放文件，失败时只记录警告
+    fn save(&self) {
+        save::save(self);
+    }
+
+    /// 模式对应的回放（战役、练习和演示的成绩不可比较，没有回放）
+    fn slot(&mut self, mode: GameMode) -> Option<&mut Option<GhostRun>> {
+        match mode {
+            GameMode::Endless => Some(&mut self.endless),
+            GameMode::ScoreAttack => Some(&mut self.score_attack),
+            _ => None,
+        }
+    }
+}
+
+/// 回放录制资源 - 本局的模式、已进行的时间和记录的位置
+#[derive(Resource, Default)]
+struct GhostRecorder {
+    mode: Option<GameMode>,           // 正在录制的模式（没有在录制时为None）
+    elapsed: f32,                     // 本局已进行的时间（秒）
+    score_at_start: u64,              // 开始时的累计得分（本局得分从这里算起）
+    samples: Vec<Option<(f32, f32)>>, // 本局记录的玩家位置
+    best: Option<GhostRun>,           // 本局回放的最佳成绩
+}
+
+/// 幽灵战机标记
+#[derive(Component)]
+struct Ghost;
+
+/// 幽灵回放插件 - 记录无尽模式和得分挑战中玩家的位置，打破最高分时保存为新的回放；之后的每局以半透明战机重现最佳一局的走位（G键开关显示）
+pub struct GhostPlugin;
+
+impl Plugin for GhostPlugin {
+    fn build(&self, app: &mut App) {
+        // 读取最佳成绩回放
+        app.insert_resource(GhostRecords::load())
+            .init_resource::<GhostRecorder>()
+            // 开始游戏时开始录制并生成幽灵战机
+            .add_systems(OnEnter(AppState::Playing), ghost_start_system)
+            // 离开游戏时结算本局并移除幽灵战机
+            .add_systems(OnExit(AppState::Playing), ghost_finish_system)
+            // 本局结束时在重置统计之前结算本局
+            .add_systems(
+                PostUpdate,
+                ghost_game_over_system.before(run_teardown_system),
+            )
+            // 固定步长在移动和碰撞之后记录玩家位置
+            .add_systems(FixedUpdate, ghost_record_system.in_set(GameSet::Spawn))
+            // 移动幽灵战机
+            .add_systems(Update, ghost_playback_system.in_set(GameSet::Presentation))
+            // 退出游戏时结算本局（无尽模式通常在这里结束）
+            .add_systems(Last, ghost_finish_on_exit_system);
+    }
+}
+
+/// 开局系统 - 可比较成绩的模式开始录制，取出该模式的最佳回放并生成幽灵战机
+fn ghost_start_system(
+    mut commands: Commands,
+    mode: Res<GameMode>,
+    stats: Res<GameStats>,
+    profile: Res<Profile>,
+    registry: Res<AssetRegistry>,
+    mut records: ResMut<GhostRecords>,
+    mut recorder: ResMut<GhostRecorder>,
+) {
+    let Some(slot) = records.slot(*mode) else {
+        *recorder = GhostRecorder::default();
+        return;
+    };
+    *recorder = GhostRecorder {
+        mode: Some(*mode),
+        score_at_start: stats.score,
+        best: slot.clone(),
+        ..Default::default()
+    };
+    if recorder.best.is_none() {
+        return;
+    }
+
+    let mut sprite = registry.sprite(match profile.ship {
+        ShipSkin::A => Tex::Player,
+        ShipSkin::B => Tex::PlayerB,
+    });
+    sprite.color = Color::srgba(1., 1., 1., GHOST_ALPHA);
+    commands.spawn((
+        Ghost,
+        sprite,
+        // Z轴略低于玩家，重叠时玩家显示在上方
+        Transform::from_xyz(0., 0., 9.).with_scale(Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.)),
+        Visibility::Hidden,
+    ));
+}
+
+/// 录制系统 - 每隔GHOST_SAMPLE_SECS记录一次玩家位置
+fn ghost_record_system(
+    time: Res<Time>,
+    mut recorder: ResMut<GhostRecorder>,
+    query: Query<&Transform, With<Player>>,
+) {
+    if recorder.mode.is_none() {
+        return;
+    }
+    recorder.elapsed += time.delta_secs();
+    let position = query
+        .get_single()
+        .ok()
+        .map(|tf| (tf.translation.x, tf.translation.y));
+    while (recorder.samples.len() as f32) * GHOST_SAMPLE_SECS <= recorder.elapsed {
+        recorder.samples.push(position);
+    }
+}
+
+/// 回放系统 - 把幽灵战机移到最佳一局同一时间的位置，设置关闭、最佳一局的玩家不在场或回放结束时隐藏
+fn ghost_playback_system(
+    settings: Res<Settings>,
+    recorder: Res<GhostRecorder>,
+    mut query: Query<(&mut Transform, &mut Visibility), With<Ghost>>,
+) {
+    let Ok((mut tf, mut visibility)) = query.get_single_mut() else {
+        return;
+    };
+    let position = recorder
+        .best
+        .as_ref()
+        .and_then(|best| best.position_at(recorder.elapsed))
+        .filter(|_| settings.ghost);
+    let Some(position) = position else {
+        visibility.set_if_neq(Visibility::Hidden);
+        return;
+    };
+    tf.translation.x = position.x;
+    tf.translation.y = position.y;
+    visibility.set_if_neq(Visibility::Inherited);
+}
+
+/// 结算本局 - 本局得分超过该模式的最佳成绩时保存为新的回放，并停止录制
+fn finish_run(stats: &GameStats, records: &mut GhostRecords, recorder: &mut GhostRecorder) {
+    let Some(mode) = recorder.mode.take() else {
+        return;
+    };
+    let run = GhostRun {
+        score: stats.score - recorder.score_at_start,
+        samples: std::mem::take(&mut recorder.samples),
+    };
+    let Some(slot) = records.slot(mode) else {
+        return;
+    };
+    if run.score == 0 || slot.as_ref().is_some_and(|best| best.score >= run.score) {
+        return;
+    }
+    info!(?mode, score = run.score, "打破最高分，保存最佳成绩回放");
+    *slot = Some(run);
+    records.save();
+}
+
+/// 结算系统 - 离开游戏时结算本局并移除幽灵战机
+fn ghost_finish_system(
+    mut commands: Commands,
+    stats: Res<GameStats>,
+    mut records: ResMut<GhostRecords>,
+    mut recorder: ResMut<GhostRecorder>,
+    query: Query<Entity, With<Ghost>>,
+) {
+    finish_run(&stats, &mut records, &mut recorder);
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// 本局结束结算系统 - 收到本局结束事件时结算本局（收尾系统随后会清零得分）
+fn ghost_game_over_system(
+    stats: Res<GameStats>,
+    mut records: ResMut<GhostRecords>,
+    mut recorder: ResMut<GhostRecorder>,
+    mut events: EventReader<GameOverEvent>,
+) {
+    if events.read().next().is_some() {
+        finish_run(&stats, &mut records, &mut recorder);
+    }
+}
+
+/// 退出结算系统 - 收到退出事件时结算本局
+fn ghost_finish_on_exit_system(
+    stats: Res<GameStats>,
+    mut records: ResMut<GhostRecords>,
+    mut recorder: ResMut<GhostRecorder>,
+    mut exit_events: EventReader<AppExit>,
+) {
+    if exit_events.read().next().is_some() {
+        finish_run(&stats, &mut records, &mut recorder);
+    }
+}
@@ -0,0 +1,95 @@
+use crate::components::{InRun, Laser, Velocity};
+use crate::sim::RollbackAppExt;
+use crate::{GameSet, movable_system};
+use bevy::prelude::*;
+
+// 引力井的影响半径
+pub const GRAVITY_WELL_RADIUS: f32 = 130.;
+// 引力井中心处的加速度（速度单位/秒）
+pub const GRAVITY_WELL_STRENGTH: f32 = 3.;
+// 引力井核心的尺寸
+const GRAVITY_WELL_CORE_SIZE: f32 = 20.;
+
+/// 引力井组件 - 吸引影响半径内的激光，使弹道弯曲
+#[derive(Component, Clone)]
+#[require(InRun)]
+pub struct GravityWell {
+    pub strength: f32, // 中心处的加速度，越靠近边缘越弱
+    pub radius: f32,   // 影响半径
+}
+
+/// 引力井插件 - 每帧按引力修改激光速度
+pub struct GravityPlugin;
+
+impl Plugin for GravityPlugin {
+    fn build(&self, app: &mut App) {
+        // 登记参与快照回滚的组件
+        app.rollback_component::<GravityWell>();
+
+        // 在通用移动之前修改激光速度
+        app.add_systems(
+            FixedUpdate,
+            gravity_well_system
+                .before(movable_system)
+                .in_set(GameSet::Movement),
+        );
+    }
+}
+
+/// 在指定位置生成一个引力井（半透明的影响范围和中心核心）
+pub fn spawn_gravity_well(commands: &mut Commands, position: Vec2) -> Entity {
+    commands
+        .spawn((
+            Sprite::from_color(
+                Color::srgba(0.6, 0.2, 1., 0.15),
+                Vec2::splat(GRAVITY_WELL_RADIUS * 2.),
+            ),
+            Transform::from_translation(position.extend(1.)),
+        ))
+        .insert(GravityWell {
+            strength: GRAVITY_WELL_STRENGTH,
+            radius: GRAVITY_WELL_RADIUS,
+        }) // 标记为引力井
+        .with_children(|parent| {
+            parent.spawn(Sprite::from_color(
+                Color::srgb(0.5, 0.1, 0.9),
+                Vec2::splat(GRAVITY_WELL_CORE_SIZE),
+            ));
+        })
+        .id()
+}
+
+/// 引力井系统 - 给影响半径内的激光施加指向井中心的加速度，并让激光朝向飞行方向
+fn gravity_well_system(
+    time: Res<Time>,
+    well_query: Query<(&Transform, &GravityWell), Without<Laser>>,
+    mut laser_query: Query<(&mut Velocity, &mut Transform), With<Laser>>,
+) {
+    let delta = time.delta_secs();
+
+    for (mut velocity, mut laser_tf) in &mut laser_query {
+        let laser_pos = laser_tf.translation.truncate();
+        let mut accel = Vec2::ZERO;
+
+        for (well_tf, well) in &well_query {
+            let offset = well_tf.translation.truncate() - laser_pos;
+            let distance = offset.length();
+            if distance < well.radius && distance > f32::EPSILON {
+                // 加速度随距离线性衰减，边缘处为0
+                accel += offset / distance * well.strength * (1. - distance / well.radius);
+            }
+        }
+        if accel == Vec2::ZERO {
+            continue;
+        }
+
+        velocity.x += accel.x * delta;
+        velocity.y += accel.y * delta;
+
+        // 激光贴图朝上，旋转到当前飞行方向
+        let direction = Vec2::new(velocity.x, velocity.y).normalize_or_zero();
+        if direction != Vec2::ZERO {
+            laser_tf.rotation = Quat::from_rotation_arc_2d(Vec2::Y, direction);
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use crate::collision::sprites_collide;
+use crate::components::{Enemy, Health, InRun, Player, SpriteSize};
+use crate::gravity::{GravityWell, spawn_gravity_well};
+use crate::player::Invincible;
+use crate::rng::GameRng;
+use crate::sim::RollbackAppExt;
+use crate::wave::{Wave, WavePhase, WaveWatch};
+use crate::{GameSet, WinSize};
+use bevy::prelude::*;
+use rand::Rng;
+
+// 从第几波开始出现危险区域
+const HAZARD_FIRST_WAVE: u32 = 2;
+// 危险区域的尺寸
+const HAZARD_SIZE: (f32, f32) = (180., 140.);
+// 辐射云每秒造成的伤害
+const RADIATION_DPS: f32 = 25.;
+// 减速区域内的速度倍率
+const SLOW_FIELD_SCALE: f32 = 0.5;
+
+/// 危险区域种类
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HazardKind {
+    Radiation { dps: f32 }, // 辐射云：区域内的玩家持续受到伤害
+    Slow { scale: f32 },    // 减速区域：区域内的玩家和敌人速度降低
+}
+
+impl HazardKind {
+    /// 区域的半透明着色
+    fn color(self) -> Color {
+        match self {
+            HazardKind::Radiation { .. } => Color::srgba(0.3, 1., 0.2, 0.25),
+            HazardKind::Slow { .. } => Color::srgba(0.3, 0.5, 1., 0.25),
+        }
+    }
+}
+
+/// 危险区域组件 - 区域范围由SpriteSize决定
+#[derive(Component, Clone)]
+#[require(InRun)]
+pub struct HazardZone(pub HazardKind);
+
+/// 减速组件 - 位于减速区域内的实体按倍率缩短移动使用的时间间隔
+#[derive(Component, Clone)]
+pub struct Slowed(pub f32);
+
+impl Slowed {
+    /// 实体当前的速度倍率（不在减速区域内时为1）
+    pub fn scale(slowed: Option<&Slowed>) -> f32 {
+        slowed.map_or(1., |slowed| slowed.0)
+    }
+}
+
+/// 危险区域插件 - 每波随机生成危险区域，并对区域内的实体施加效果
+pub struct HazardPlugin;
+
+impl Plugin for HazardPlugin {
+    fn build(&self, app: &mut App) {
+        // 登记参与快照回滚的组件
+        app.rollback_component::<Slowed>()
+            .rollback_component::<HazardZone>();
+
+        // 移动之前计算区域效果（减速与辐射伤害）
+        app.add_systems(
+            FixedUpdate,
+            (hazard_slow_system, hazard_radiation_system).in_set(GameSet::Input),
+        )
+        // 波次开始时生成危险区域，商店阶段清除
+        .add_systems(FixedUpdate, hazard_spawn_system.in_set(GameSet::Spawn));
+    }
+}
+
+/// 危险区域生成系统 - 新一波开始时在屏幕中部随机生成一个危险区域或引力井，波次结束后清除
+fn hazard_spawn_system(
+    mut commands: Commands,
+    wave: Res<Wave>,
+    win_size: Res<WinSize>,
+    mut rng: ResMut<GameRng>,
+    query: Query<Entity, Or<(With<HazardZone>, With<GravityWell>)>>,
+    mut watch: ResMut<WaveWatch>,
+) {
+    // 只在波次或阶段切换时处理（生成敌人也会修改波次资源）
+    if !watch.changed("hazard_spawn", &wave) {
+        return;
+    }
+
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+    if wave.phase != WavePhase::Fighting || wave.number < HAZARD_FIRST_WAVE {
+        return;
+    }
+
+    let w_span = win_size.w / 2. - HAZARD_SIZE.0 / 2.;
+    let x = rng.gen_range(-w_span..w_span);
+    let y = rng.gen_range((-win_size.h / 4.)..(win_size.h / 6.));
+
+    // 在辐射云、减速区域和引力井中随机选择一种
+    let kind = match rng.gen_range(0..3) {
+        0 => HazardKind::Radiation { dps: RADIATION_DPS },
+        1 => HazardKind::Slow {
+            scale: SLOW_FIELD_SCALE,
+        },
+        _ => {
+            spawn_gravity_well(&mut commands, Vec2::new(x, y));
+            return;
+        }
+    };
+
+    commands
+        .spawn((
+            Sprite::from_color(kind.color(), Vec2::new(HAZARD_SIZE.0, HAZARD_SIZE.1)),
+            Transform::from_xyz(x, y, 1.),
+        ))
+        .insert(HazardZone(kind)) // 标记为危险区域
+        .insert(SpriteSize::from(HAZARD_SIZE)); // 区域范围
+}
+
+/// 减速系统 - 给位于减速区域内的玩家和敌人添加减速组件，离开后移除
+#[allow(clippy::type_complexity)] // 允许复杂的查询类型
+fn hazard_slow_system(
+    mut commands: Commands,
+    zone_query: Query<(&Transform, &SpriteSize, &HazardZone)>,
+    query: Query<
+        (Entity, &Transform, &SpriteSize, Option<&Slowed>),
+        Or<(With<Player>, With<Enemy>)>,
+    >,
+) {
+    for (entity, tf, size, slowed) in &query {
+        // 同时位于多个减速区域时取最慢的倍率
+        let scale = zone_query
+            .iter()
+            .filter(|(zone_tf, zone_size, _)| sprites_collide(zone_tf, zone_size, tf, size))
+            .filter_map(|(_, _, zone)| match zone.0 {
+                HazardKind::Slow { scale } => Some(scale),
+                _ => None,
+            })
+            .reduce(f32::min);
+
+        match (scale, slowed) {
+            (Some(scale), Some(slowed)) if slowed.0 == scale => {}
+            (Some(scale), _) => {
+                commands.entity(entity).insert(Slowed(scale));
+            }
+            (None, Some(_)) => {
+                commands.entity(entity).remove::<Slowed>();
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+/// 辐射伤害系统 - 玩家位于辐射云内时持续扣除生命值（无敌时免疫），生命值耗尽后由死亡处理系统击毁玩家
+#[allow(clippy::type_complexity)] // 允许复杂的查询类型
+fn hazard_radiation_system(
+    time: Res<Time>,
+    zone_query: Query<(&Transform, &SpriteSize, &HazardZone)>,
+    mut player_query: Query<
+        (&Transform, &SpriteSize, &mut Health),
+        (With<Player>, Without<Invincible>),
+    >,
+) {
+    let Ok((player_tf, player_size, mut health)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    let dps: f32 = zone_query
+        .iter()
+        .filter(|(zone_tf, zone_size, _)| {
+            sprites_collide(zone_tf, zone_size, player_tf, player_size)
+        })
+        .filter_map(|(_, _, zone)| match zone.0 {
+            HazardKind::Radiation { dps } => Some(dps),
+            _ => None,
+        })
+        .sum();
+    if dps == 0. {
+        return;
+    }
+
+    health.current -= dps * time.delta_secs();
+}
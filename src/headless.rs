@@ -0,0 +1,207 @@
+use crate::bench::BenchPlugin;
+use crate::bot::Autopilot;
+use crate::launch::LaunchOptions;
+use crate::logging;
+use crate::registry::AssetRegistry;
+use crate::settings::{AutoFire, Settings};
+use crate::sim::{InputLog, Snapshot};
+use crate::theme::ThemeAssets;
+use crate::wave::Wave;
+use crate::{AppState, EnemyCount, GamePlugin, GameStats, WINDOW_SIZE, WinSize};
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+
+// 默认模拟帧数（60帧/秒下约1分钟）
+pub const DEFAULT_HEADLESS_FRAMES: u32 = 3600;
+// 模拟使用的固定帧时长（秒）
+const HEADLESS_FRAME_TIME: f32 = 1. / 60.;
+// 确定性检查未指定--seed时使用的随机种子
+const DETERMINISM_SEED: u64 = 1;
+
+/// 解析命令行参数 - 带有--headless时返回要模拟的帧数（可用--frames N指定）
+pub fn frames_from_args() -> Option<u32> {
+    let args: Vec<String> = std::env::args().collect();
+    if !args.iter().any(|arg| arg == "--headless") {
+        return None;
+    }
+
+    let frames = args
+        .iter()
+        .position(|arg| arg == "--frames")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_HEADLESS_FRAMES);
+    Some(frames)
+}
+
+/// 运行无窗口模拟 - 不创建窗口、音频和渲染，由自动驾驶代替玩家输入，结束后打印统计
+pub fn run(frames: u32, options: &LaunchOptions) {
+    if options.check_determinism {
+        check_determinism(frames, options);
+        return;
+    }
+
+    let mut app = build_app(options, true);
+    let simulated = simulate(&mut app, frames);
+
+    let stats = app.world().resource::<GameStats>();
+    println!(
+        "无窗口模拟完成：{} 帧（{:.1} 秒）",
+        simulated,
+        simulated as f32 * HEADLESS_FRAME_TIME
+    );
+    println!("得分: {}", stats.score);
+    println!("开火次数: {}", stats.shots_fired);
+    println!("击毁敌人: {}", stats.enemies_killed);
+    println!("玩家死亡: {}", stats.player_deaths);
+    println!("到达波次: {}", app.world().resource::<Wave>().number);
+}
+
+/// 确定性检查 - 第一次由自动驾驶操作并记录每一步的输入（中途保存一份快照），第二次以相同种子回放记录的输入，
+/// 最后把第一次的模拟恢复到快照后回放快照之后的输入，两项检查都逐步与第一次的状态校验和比较
+fn check_determinism(frames: u32, options: &LaunchOptions) {
+    // 两次模拟的随机数资源都按同一种子初始化
+    let options = &LaunchOptions {
+        seed: Some(options.seed.unwrap_or(DETERMINISM_SEED)),
+        ..options.clone()
+    };
+
+    let logs = determinism_logs(frames, options, true);
+    report("回放", &logs.recorded, &logs.replayed);
+    report("回滚", &logs.recorded, &logs.rolled_back);
+}
+
+/// 确定性检查的三份输入记录
+struct DeterminismLogs {
+    recorded: InputLog,    // 第一次模拟的记录
+    replayed: InputLog,    // 以相同种子回放得到的记录
+    rolled_back: InputLog, // 恢复快照后重新模拟得到的记录
+}
+
+/// 依次运行记录、回放和回滚三次模拟，返回各自的输入记录（with_log为true时第一次模拟初始化全局日志）
+fn determinism_logs(frames: u32, options: &LaunchOptions, with_log: bool) -> DeterminismLogs {
+    let mut recorded = build_app(options, with_log);
+    recorded.insert_resource(InputLog::record());
+    let before = frames / 2;
+    simulate(&mut recorded, before);
+    let snapshot = Snapshot::save(recorded.world_mut());
+    simulate(&mut recorded, frames - before);
+    let log = take_log(&mut recorded);
+
+    // 回放时关闭自动驾驶，输入完全来自记录（全局日志已由第一次模拟初始化）
+    let mut replayed = build_app(options, false);
+    replayed
+        .insert_resource(Autopilot::default())
+        .insert_resource(log.replay());
+    simulate(&mut replayed, frames);
+
+    // 回滚：恢复快照后关闭自动驾驶，快照之后的输入来自记录（快照之前各步的校验和沿用第一次的记录）
+    snapshot.restore(recorded.world_mut());
+    recorded
+        .insert_resource(Autopilot::default())
+        .insert_resource(log.replay_from(snapshot.tick));
+    simulate(&mut recorded, frames - before);
+
+    DeterminismLogs {
+        replayed: take_log(&mut replayed),
+        rolled_back: take_log(&mut recorded),
+        recorded: log,
+    }
+}
+
+/// 取出模拟结束时的输入记录
+fn take_log(app: &mut App) -> InputLog {
+    app.world_mut()
+        .remove_resource::<InputLog>()
+        .unwrap_or_default()
+}
+
+/// 输出一项确定性检查的结果（与记录的校验和逐步比较）
+fn report(name: &str, log: &InputLog, other: &InputLog) {
+    match log.first_divergence(other) {
+        None => println!("{name}检查通过：{} 步的状态全部一致", log.checksums.len()),
+        Some(tick) => println!(
+            "{name}检查失败：第 {} 步开始出现分歧（共记录 {} 步）",
+            tick,
+            log.checksums.len()
+        ),
+    }
+}
+
+/// 创建无窗口模拟的App（with_log为false时不添加日志插件，同一进程中只能初始化一次全局日志）
+fn build_app(options: &LaunchOptions, with_log: bool) -> App {
+    let mut app = App::new();
+    app.insert_resource(options.clone())
+        .add_plugins(MinimalPlugins);
+    if with_log {
+        app.add_plugins(logging::log_plugin(options)); // 日志级别与日志文件同窗口模式
+    }
+    app.add_plugins(StatesPlugin)
+        // 跳过主菜单，直接开始游戏
+        .insert_state(AppState::Playing)
+        // 使用固定帧时长，模拟速度不受真实时间限制
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+            HEADLESS_FRAME_TIME,
+        )))
+        .init_resource::<ButtonInput<KeyCode>>()
+        .insert_resource(WinSize {
+            w: WINDOW_SIZE.0,
+            h: WINDOW_SIZE.1,
+        })
+        .init_resource::<AssetRegistry>() // 无渲染时使用空的资源句柄
+        .init_resource::<ThemeAssets>() // 无渲染时敌人精灵为空句柄
+        .insert_resource(EnemyCount(0))
+        // 开火交给始终连发，自动驾驶负责躲避和瞄准
+        .insert_resource(Settings {
+            auto_fire: AutoFire::Always,
+            ..Default::default()
+        })
+        // 自动驾驶始终代替玩家操作
+        .insert_resource(Autopilot::always())
+        .add_plugins(GamePlugin);
+
+    // 带有--bench参数时运行基准测试场景（测试结束后提前退出）
+    if options.bench {
+        app.add_plugins(BenchPlugin);
+    }
+
+    app.finish();
+    app.cleanup();
+    app
+}
+
+/// 运行指定帧数（提前退出时停止），返回实际模拟的帧数
+fn simulate(app: &mut App, frames: u32) -> u32 {
+    let mut simulated = 0;
+    while simulated < frames {
+        app.update();
+        simulated += 1;
+        if app.should_exit().is_some() {
+            break;
+        }
+    }
+    simulated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 测试模拟的帧数（约4秒，快照保存在中途）
+    const TEST_FRAMES: u32 = 240;
+
+    #[test]
+    fn snapshot_resimulation_matches_recorded_checksums() {
+        let options = LaunchOptions {
+            seed: Some(DETERMINISM_SEED),
+            ..Default::default()
+        };
+        let logs = determinism_logs(TEST_FRAMES, &options, false);
+
+        assert!(!logs.recorded.checksums.is_empty());
+        assert_eq!(logs.recorded.first_divergence(&logs.replayed), None);
+        assert_eq!(logs.recorded.first_divergence(&logs.rolled_back), None);
+    }
+}
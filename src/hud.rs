@@ -0,0 +1,182 @@
+use crate::GameSet;
+use crate::components::Player;
+use crate::faction_time::TimeSlow;
+use crate::pickup::{Magnet, PowerUpKind, RapidFire, ShieldPowerUp};
+use crate::weapon::{Ammo, Loadout, SecondaryWeapon, TORPEDO_AMMO};
+use bevy::prelude::*;
+
+// 强化图标的边长
+const HUD_ICON_SIZE: f32 = 14.;
+// 倒计时条的宽度和高度
+const HUD_BAR_SIZE: (f32, f32) = (60., 6.);
+
+/// 强化指示行组件 - HUD中一种限时强化的图标和倒计时条
+#[derive(Component)]
+struct PowerUpIndicator(PowerUpKind);
+
+/// 倒计时条填充组件 - 宽度按剩余时间比例缩放
+#[derive(Component)]
+struct PowerUpBarFill(PowerUpKind);
+
+/// 当前武器文本标记
+#[derive(Component)]
+struct WeaponText;
+
+/// HUD插件 - 在屏幕左上角显示生效中的限时强化及其剩余时间，右上角显示当前武器和弹药
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        // 创建强化指示行（默认隐藏）
+        app.add_systems(Startup, hud_setup_system)
+            // 按玩家身上的强化组件刷新指示行
+            .add_systems(
+                Update,
+                (hud_power_up_system, hud_weapon_system).in_set(GameSet::Presentation),
+            );
+    }
+}
+
+/// HUD初始化系统 - 为每种限时强化生成一行（彩色图标 + 倒计时条），并生成当前武器文本
+fn hud_setup_system(mut commands: Commands) {
+    commands.spawn((
+        WeaponText,
+        Text::new(""),
+        TextFont {
+            font_size: 14.,
+            ..Default::default()
+        },
+        TextColor(Color::WHITE),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(8.),
+            top: Val::Px(8.),
+            ..Default::default()
+        },
+    ));
+
+    commands
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(8.),
+            top: Val::Px(8.),
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(4.),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            for kind in PowerUpKind::ALL {
+                parent
+                    .spawn((
+                        PowerUpIndicator(kind),
+                        Node {
+                            align_items: AlignItems::Center,
+                            column_gap: Val::Px(6.),
+                            ..Default::default()
+                        },
+                        Visibility::Hidden,
+                    ))
+                    .with_children(|row| {
+                        // 强化图标
+                        row.spawn((
+                            Node {
+                                width: Val::Px(HUD_ICON_SIZE),
+                                height: Val::Px(HUD_ICON_SIZE),
+                                ..Default::default()
+                            },
+                            BackgroundColor(kind.color()),
+                        ));
+                        // 倒计时条（底色 + 填充）
+                        row.spawn((
+                            Node {
+                                width: Val::Px(HUD_BAR_SIZE.0),
+                                height: Val::Px(HUD_BAR_SIZE.1),
+                                ..Default::default()
+                            },
+                            BackgroundColor(Color::srgba(1., 1., 1., 0.2)),
+                        ))
+                        .with_children(|bar| {
+                            bar.spawn((
+                                PowerUpBarFill(kind),
+                                Node {
+                                    width: Val::Percent(100.),
+                                    height: Val::Percent(100.),
+                                    ..Default::default()
+                                },
+                                BackgroundColor(kind.color()),
+                            ));
+                        });
+                    });
+            }
+        });
+}
+
+/// 强化指示系统 - 显示玩家身上生效中的强化，倒计时条按计时器剩余比例缩短
+#[allow(clippy::type_complexity)] // 允许复杂的查询类型
+fn hud_power_up_system(
+    player_query: Query<
+        (
+            Option<&RapidFire>,
+            Option<&ShieldPowerUp>,
+            Option<&Magnet>,
+            Option<&TimeSlow>,
+        ),
+        With<Player>,
+    >,
+    mut indicator_query: Query<(&PowerUpIndicator, &mut Visibility)>,
+    mut fill_query: Query<(&PowerUpBarFill, &mut Node)>,
+) {
+    let (rapid_fire, shield, magnet, time_slow) = player_query.get_single().unwrap_or_default();
+    // 剩余时间比例（1为刚拾取，0为即将结束），未生效时为None
+    let remaining = |kind: PowerUpKind| {
+        let timer = match kind {
+            PowerUpKind::RapidFire => rapid_fire.map(|rapid_fire| &rapid_fire.timer),
+            PowerUpKind::Shield => shield.map(|shield| &shield.timer),
+            PowerUpKind::Magnet => magnet.map(|magnet| &magnet.timer),
+            PowerUpKind::TimeSlow => time_slow.map(|time_slow| &time_slow.timer),
+        };
+        timer.map(|timer| 1. - timer.fraction())
+    };
+
+    for (indicator, mut visibility) in &mut indicator_query {
+        visibility.set_if_neq(if remaining(indicator.0).is_some() {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        });
+    }
+    for (fill, mut node) in &mut fill_query {
+        if let Some(fraction) = remaining(fill.0) {
+            node.width = Val::Percent(fraction * 100.);
+        }
+    }
+}
+
+/// 当前武器系统 - 刷新右上角的武器名称、剩余弹药和副武器弹药
+fn hud_weapon_system(
+    player_query: Query<(&Loadout, &Ammo, &SecondaryWeapon), With<Player>>,
+    mut text_query: Query<&mut Text, With<WeaponText>>,
+) {
+    let Ok((loadout, ammo, secondary)) = player_query.get_single() else {
+        return;
+    };
+
+    let weapon = loadout.current();
+    let rounds = match (ammo.get(weapon), weapon.max_ammo()) {
+        (Some(rounds), Some(max)) => format!("{rounds}/{max}"),
+        _ => "INF".to_string(),
+    };
+    let label = format!(
+        "Weapon: {} {} [1-4]\nTorpedo: {}/{} [Ctrl]",
+        weapon.name(),
+        rounds,
+        secondary.ammo,
+        TORPEDO_AMMO
+    );
+    // 弹药每次开火都会变化，只在文本不同时写入，避免每帧重新排版
+    for mut text in &mut text_query {
+        if text.0 != label {
+            text.0 = label.clone();
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use crate::GameSet;
+use crate::components::{Enemy, Movable};
+use crate::sim::RollbackAppExt;
+use bevy::prelude::*;
+
+/// 插值位置组件 - 记录最近两次固定步长结束时的模拟位置
+/// 渲染时在两者之间插值，避免高刷新率下画面按固定步长卡顿
+#[derive(Component, Clone)]
+pub struct InterpolatedPosition {
+    pub previous: Vec3, // 上一个固定步长结束时的位置
+    pub current: Vec3,  // 当前固定步长结束时的位置
+}
+
+/// 插值插件 - 管理固定步长模拟与逐帧渲染之间的位置插值
+pub struct InterpolationPlugin;
+
+impl Plugin for InterpolationPlugin {
+    fn build(&self, app: &mut App) {
+        // 登记参与快照回滚的组件
+        app.rollback_component::<InterpolatedPosition>();
+
+        // 每个固定步长开始前恢复模拟位置
+        app.add_systems(FixedFirst, restore_position_system)
+            // 每个固定步长结束后记录模拟位置
+            .add_systems(FixedLast, capture_position_system)
+            // 每帧按固定步长的剩余进度插值渲染位置
+            .add_systems(
+                Update,
+                interpolate_transform_system.in_set(GameSet::Presentation),
+            );
+    }
+}
+
+/// 恢复位置系统 - 撤销上一帧的插值，让模拟系统始终基于真实的模拟位置计算
+fn restore_position_system(mut query: Query<(&mut Transform, &mut InterpolatedPosition)>) {
+    for (mut transform, mut position) in &mut query {
+        transform.translation = position.current;
+        position.previous = position.current;
+    }
+}
+
+/// 记录位置系统 - 保存本次固定步长的模拟结果，并为新生成的移动实体添加插值组件
+#[allow(clippy::type_complexity)] // 允许复杂的查询类型
+fn capture_position_system(
+    mut commands: Commands,
+    mut query: Query<(&Transform, &mut InterpolatedPosition)>,
+    new_query: Query<
+        (Entity, &Transform),
+        (
+            Without<InterpolatedPosition>,
+            Or<(With<Movable>, With<Enemy>)>,
+        ),
+    >,
+) {
+    for (transform, mut position) in &mut query {
+        position.current = transform.translation;
+    }
+
+    // 新实体从生成位置开始插值
+    for (entity, transform) in new_query.iter() {
+        commands.entity(entity).insert(InterpolatedPosition {
+            previous: transform.translation,
+            current: transform.translation,
+        });
+    }
+}
+
+/// 插值系统 - 根据固定步长的剩余进度在上一位置和当前位置之间插值
+fn interpolate_transform_system(
+    fixed_time: Res<Time<Fixed>>,
+    mut query: Query<(&mut Transform, &InterpolatedPosition)>,
+) {
+    let alpha = fixed_time.overstep_fraction();
+
+    for (mut transform, position) in &mut query {
+        transform.translation = position.previous.lerp(position.current, alpha);
+    }
+}
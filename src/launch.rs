@@ -0,0 +1,110 @@
+use crate::bench::BENCH_SEED;
+use bevy::log::Level;
+use bevy::prelude::*;
+
+// 未指定--log-level时读取的日志级别环境变量
+const LOG_LEVEL_ENV: &str = "INVADERS_LOG";
+// 设置后（任意值）与--log-file相同，同时写入日志文件
+const LOG_FILE_ENV: &str = "INVADERS_LOG_FILE";
+
+/// 启动选项资源 - 启动时从命令行参数解析，窗口、音频、随机数和初始状态按此设置
+/// --windowed                强制窗口模式
+/// --resolution 1280x720     窗口分辨率
+/// --mute                    静音
+/// --seed 1234               固定随机种子
+/// --skip-menu               跳过主菜单直接开始无尽模式
+/// --level 3                 跳过主菜单直接开始第3个战役关卡
+/// --log-level debug         日志级别（trace/debug/info/warn/error，也可用INVADERS_LOG设置）
+/// --log-file                同时写入滚动的日志文件（也可用INVADERS_LOG_FILE开启）
+/// --bench                   运行基准测试场景并输出帧时间统计（可与--headless同时使用）
+/// --check-determinism       无窗口模拟运行两次，回放第一次记录的输入并逐步比较状态校验和
+#[derive(Resource, Clone, Debug, Default)]
+pub struct LaunchOptions {
+    pub windowed: bool,                 // 强制窗口模式（忽略设置中的全屏选项）
+    pub resolution: Option<(f32, f32)>, // 窗口分辨率（宽，高）
+    pub mute: bool,                     // 静音
+    pub seed: Option<u64>,              // 随机种子
+    pub skip_menu: bool,                // 跳过主菜单
+    pub level: Option<usize>,           // 直接开始的战役关卡下标（命令行中从1开始计数）
+    pub log_level: Option<Level>,       // 日志级别
+    pub log_file: bool,                 // 是否写入日志文件
+    pub bench: bool,                    // 运行基准测试
+    pub check_determinism: bool,        // 检查回放输入时模拟结果是否一致
+}
+
+impl LaunchOptions {
+    /// 解析本次启动的命令行参数
+    pub fn from_args() -> Self {
+        let mut options = Self::parse(std::env::args().skip(1));
+        if options.log_level.is_none() {
+            options.log_level = parse_env(LOG_LEVEL_ENV, |v| v.parse().ok());
+        }
+        options.log_file |= std::env::var_os(LOG_FILE_ENV).is_some();
+        options
+    }
+
+    /// 本次启动使用的随机种子（基准测试未指定--seed时使用固定种子），为None时使用随机种子
+    pub fn rng_seed(&self) -> Option<u64> {
+        self.seed.or(self.bench.then_some(BENCH_SEED))
+    }
+
+    /// 解析命令行参数，无法识别的参数忽略（交给无窗口模拟等其他入口处理），取值错误时警告并忽略该选项
+    fn parse(args: impl IntoIterator<Item = String>) -> Self {
+        let mut options = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--windowed" => options.windowed = true,
+                "--mute" => options.mute = true,
+                "--skip-menu" => options.skip_menu = true,
+                "--log-file" => options.log_file = true,
+                "--bench" => options.bench = true,
+                "--check-determinism" => options.check_determinism = true,
+                "--log-level" => {
+                    options.log_level = parse_value(&arg, args.next(), |v| v.parse().ok())
+                }
+                "--resolution" => {
+                    options.resolution = parse_value(&arg, args.next(), parse_resolution)
+                }
+                "--seed" => options.seed = parse_value(&arg, args.next(), |v| v.parse().ok()),
+                "--level" => {
+                    options.level = parse_value(&arg, args.next(), |v| {
+                        v.parse::<usize>().ok().and_then(|n| n.checked_sub(1))
+                    })
+                }
+                _ => {}
+            }
+        }
+        options
+    }
+}
+
+/// 解析选项的取值，缺少取值或格式错误时打印警告并返回None（此时日志插件尚未初始化）
+fn parse_value<T>(
+    flag: &str,
+    value: Option<String>,
+    parse: impl FnOnce(&str) -> Option<T>,
+) -> Option<T> {
+    let parsed = value.as_deref().and_then(parse);
+    if parsed.is_none() {
+        eprintln!("启动参数{flag}的取值无效: {}", value.unwrap_or_default());
+    }
+    parsed
+}
+
+/// 解析环境变量，未设置时返回None，取值无效时打印警告并返回None
+fn parse_env<T>(name: &str, parse: impl FnOnce(&str) -> Option<T>) -> Option<T> {
+    let value = std::env::var(name).ok()?;
+    let parsed = parse(&value);
+    if parsed.is_none() {
+        eprintln!("环境变量{name}的取值无效: {value}");
+    }
+    parsed
+}
+
+/// 解析“宽x高”格式的分辨率
+fn parse_resolution(value: &str) -> Option<(f32, f32)> {
+    let (w, h) = value.split_once(['x', 'X'])?;
+    let (w, h) = (w.trim().parse::<f32>().ok()?, h.trim().parse::<f32>().ok()?);
+    (w > 0. && h > 0.).then_some((w, h))
+}
@@ -0,0 +1,41 @@
+use crate::config::LevelScript;
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use bevy::prelude::*;
+use serde::Deserialize;
+
+// 关卡文件的扩展名
+const LEVEL_EXTENSIONS: &[&str] = &["level"];
+
+/// 战役关卡 - 名称、需要完成的波次数和关卡脚本，每个关卡是assets/levels目录下的一个.level文件
+#[derive(Asset, TypePath, Clone, Debug, Deserialize)]
+pub struct CampaignLevel {
+    pub name: String,        // 关卡名称
+    pub waves: u32,          // 完成关卡需要清除的波次数
+    pub script: LevelScript, // 关卡脚本（力场、通讯台词）
+}
+
+/// 关卡加载器 - 读取.level文件（RON格式，内容为一个关卡）
+#[derive(Default)]
+pub struct LevelLoader;
+
+impl AssetLoader for LevelLoader {
+    type Asset = CampaignLevel;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<CampaignLevel, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        LEVEL_EXTENSIONS
+    }
+}
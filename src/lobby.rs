@@ -0,0 +1,322 @@
+use crate::AppState;
+use crate::config::GameConfig;
+use crate::game_mode::GameMode;
+use crate::menu::start_mode;
+use crate::net::{CODE_LEN, Client, Host, NetEvent, NetMessage, NetSession};
+use crate::rng::GameRng;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::prelude::*;
+
+/// 大厅界面根节点标记
+#[derive(Component)]
+struct LobbyScreen;
+
+/// 大厅文本标记
+#[derive(Component)]
+struct LobbyText;
+
+/// 大厅界面状态资源 - 正在输入的加入码和最近一条提示
+#[derive(Resource, Default)]
+struct LobbyUi {
+    entering: Option<String>, // 正在输入的加入码（未在输入时为None）
+    spectate: bool,           // 输入的加入码用于观战
+    status: String,           // 最近一条提示（连接失败、主机断开等）
+}
+
+/// 联机大厅插件 - 主机获得加入码，朋友输入加入码加入并切换准备状态（或以观战身份加入），主机在全部成员准备后开始对局，全部成员以同一随机种子进入同一局
+pub struct LobbyPlugin;
+
+impl Plugin for LobbyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LobbyUi>()
+            // 进入大厅时创建界面
+            .add_systems(OnEnter(AppState::Lobby), lobby_setup_system)
+            // 离开大厅时销毁界面
+            .add_systems(OnExit(AppState::Lobby), lobby_cleanup_system)
+            // 处理按键、收发大厅消息并刷新界面
+            .add_systems(
+                Update,
+                (lobby_input_system, lobby_net_system, lobby_text_system)
+                    .chain()
+                    .run_if(in_state(AppState::Lobby)),
+            );
+    }
+}
+
+/// 大厅界面初始化系统
+fn lobby_setup_system(mut commands: Commands, mut ui: ResMut<LobbyUi>) {
+    *ui = LobbyUi::default();
+    commands
+        .spawn((
+            LobbyScreen,
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            BackgroundColor(Color::srgba(0., 0., 0., 0.9)),
+            GlobalZIndex(10),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                LobbyText,
+                Text::new(""),
+                TextFont {
+                    font_size: 20.,
+                    ..Default::default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// 大厅界面销毁系统
+fn lobby_cleanup_system(mut commands: Commands, query: Query<Entity, With<LobbyScreen>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// 大厅按键系统 - H创建大厅，J输入加入码（回车连接），V输入加入码观战，客户端按R切换准备，主机在全部成员准备后按回车开始，Esc离开大厅
+#[allow(clippy::too_many_arguments)] // 开始对局需要同时设置多个资源
+fn lobby_input_system(
+    kb: Res<ButtonInput<KeyCode>>,
+    mut typed: EventReader<KeyboardInput>,
+    mut ui: ResMut<LobbyUi>,
+    mut session: ResMut<NetSession>,
+    mut mode: ResMut<GameMode>,
+    mut config: ResMut<GameConfig>,
+    mut rng: ResMut<GameRng>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    // 输入加入码：只接受字母和数字
+    if let Some(code) = ui.entering.as_mut() {
+        for event in typed.read().filter(|event| event.state.is_pressed()) {
+            if let Key::Character(text) = &event.logical_key {
+                let chars = text.chars().filter(char::is_ascii_alphanumeric);
+                code.extend(chars.map(|c| c.to_ascii_uppercase()));
+                code.truncate(CODE_LEN);
+            }
+        }
+        if kb.just_pressed(KeyCode::Backspace) {
+            code.pop();
+        }
+        if kb.just_pressed(KeyCode::Escape) {
+            ui.entering = None;
+        } else if kb.just_pressed(KeyCode::Enter) {
+            // 连接在后台进行，结果由大厅网络系统处理
+            let code = ui.entering.take().unwrap_or_default();
+            match Client::join(&code) {
+                Some(joining) => {
+                    *session = NetSession::Joining(joining);
+                    ui.status.clear();
+                }
+                None => {
+                    warn!(%code, "加入码无效");
+                    ui.status = format!("Invalid code {code}");
+                }
+            }
+        }
+        return;
+    }
+    typed.clear();
+
+    if kb.just_pressed(KeyCode::Escape) {
+        // 丢弃会话即关闭全部连接
+        *session = NetSession::Offline;
+        next_state.set(AppState::MainMenu);
+        return;
+    }
+
+    match &mut *session {
+        NetSession::Offline => {
+            if kb.just_pressed(KeyCode::KeyH) {
+                match Host::open() {
+                    Ok(host) => {
+                        info!(code = %host.code, "创建大厅");
+                        *session = NetSession::Host(host);
+                        ui.status.clear();
+                    }
+                    Err(e) => {
+                        warn!("创建大厅失败: {e}");
+                        ui.status = "Could not open lobby".to_string();
+                    }
+                }
+            } else if kb.just_pressed(KeyCode::KeyJ) || kb.just_pressed(KeyCode::KeyV) {
+                ui.entering = Some(String::new());
+                ui.spectate = kb.just_pressed(KeyCode::KeyV);
+                ui.status.clear();
+            }
+        }
+        NetSession::Joining(_) => {}
+        NetSession::Host(host) => {
+            if kb.just_pressed(KeyCode::Enter) && host.all_ready() {
+                let seed = rand::random();
+                host.broadcast(&NetMessage::Start { seed });
+                info!(seed, peers = host.peers.len(), "主机开始联机对局");
+                start_match(seed, &mut mode, &mut config, &mut rng, &mut next_state);
+            }
+        }
+        NetSession::Client(client) => {
+            if kb.just_pressed(KeyCode::KeyR) {
+                let ready = !client.ready;
+                if client.connection.send(&NetMessage::Ready(ready)).is_ok() {
+                    client.ready = ready;
+                }
+            }
+        }
+    }
+}
+
+/// 大厅网络系统 - 主机处理成员加入、离开和准备状态并广播成员列表；客户端连接成功后加入大厅（观战时请求观战并进入观战界面），
+/// 接收成员列表和开始消息，连接失败或与主机断开时显示提示
+fn lobby_net_system(
+    mut events: EventReader<NetEvent>,
+    mut ui: ResMut<LobbyUi>,
+    mut session: ResMut<NetSession>,
+    mut mode: ResMut<GameMode>,
+    mut config: ResMut<GameConfig>,
+    mut rng: ResMut<GameRng>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let mut changed = false;
+    let mut leave = false;
+    for event in events.read() {
+        match (&mut *session, event) {
+            (NetSession::Client(client), NetEvent::Connected) if ui.spectate => {
+                if let Err(e) = client.connection.send(&NetMessage::Spectate) {
+                    warn!("请求观战失败: {e}");
+                    ui.status = "Could not spectate".to_string();
+                    leave = true;
+                } else {
+                    next_state.set(AppState::Spectating);
+                }
+            }
+            (NetSession::Offline, NetEvent::ConnectFailed(code)) => {
+                let action = if ui.spectate { "spectate" } else { "join" };
+                ui.status = format!("Could not {action} {code}");
+            }
+            (NetSession::Host(_), NetEvent::Joined(_) | NetEvent::Left(_)) => changed = true,
+            (NetSession::Host(host), NetEvent::Received { from, message }) => match message {
+                NetMessage::Ready(ready) => {
+                    if let Some(peer) = host.peer_mut(from) {
+                        peer.ready = *ready;
+                        changed = true;
+                    }
+                }
+                NetMessage::Spectate => changed = true,
+                _ => {}
+            },
+            (NetSession::Client(client), NetEvent::Received { message, .. }) => match message {
+                NetMessage::Lobby { peers } => client.peers = peers.clone(),
+                NetMessage::Start { seed } => {
+                    info!(seed, "主机开始联机对局");
+                    start_match(*seed, &mut mode, &mut config, &mut rng, &mut next_state);
+                }
+                _ => {}
+            },
+            (NetSession::Offline, NetEvent::Left(_)) => {
+                ui.status = "Host disconnected".to_string();
+            }
+            _ => {}
+        }
+    }
+
+    // 观战请求无法发送时断开连接
+    if leave {
+        *session = NetSession::Offline;
+    }
+    if !changed {
+        return;
+    }
+    if let NetSession::Host(host) = &mut *session {
+        let peers = host.lobby();
+        host.broadcast(&NetMessage::Lobby { peers });
+    }
+}
+
+/// 大厅文本系统 - 按会话状态显示加入码、成员列表和操作提示
+fn lobby_text_system(
+    ui: Res<LobbyUi>,
+    session: Res<NetSession>,
+    mut query: Query<&mut Text, With<LobbyText>>,
+) {
+    let Ok(mut text) = query.get_single_mut() else {
+        return;
+    };
+
+    let mut lines = vec!["MULTIPLAYER".to_string(), String::new()];
+    let peers = match &*session {
+        NetSession::Offline => Vec::new(),
+        NetSession::Joining(joining) => {
+            lines.push(format!("Connecting to {}...", joining.code));
+            lines.push(String::new());
+            Vec::new()
+        }
+        NetSession::Host(host) => {
+            lines.push(format!("CODE: {}", host.code));
+            lines.push(String::new());
+            host.lobby()
+        }
+        NetSession::Client(client) => client.peers.clone(),
+    };
+    for peer in &peers {
+        let ready = match (peer.spectator, peer.ready) {
+            (true, _) => "SPECTATING",
+            (false, true) => "READY",
+            (false, false) => "...",
+        };
+        lines.push(format!("{:<12} {ready}", peer.name));
+    }
+    if !peers.is_empty() {
+        lines.push(String::new());
+    }
+
+    if let Some(code) = &ui.entering {
+        lines.push(format!("Enter code: {code}_"));
+        let action = if ui.spectate { "spectate" } else { "join" };
+        lines.push(format!("ENTER: {action}   ESC: cancel"));
+    } else {
+        match &*session {
+            NetSession::Offline => {
+                lines.push("H: host a lobby".to_string());
+                lines.push("J: join by code".to_string());
+                lines.push("V: spectate by code".to_string());
+            }
+            NetSession::Joining(_) => {}
+            NetSession::Host(host) if host.all_ready() => {
+                lines.push("ENTER: start match".to_string());
+            }
+            NetSession::Host(_) => lines.push("Waiting for players to ready up".to_string()),
+            NetSession::Client(client) => {
+                let action = if client.ready { "unready" } else { "ready" };
+                lines.push(format!("R: {action}   Waiting for host"));
+            }
+        }
+        lines.push("ESC: leave".to_string());
+    }
+    if !ui.status.is_empty() {
+        lines.push(String::new());
+        lines.push(ui.status.clone());
+    }
+
+    let content = lines.join("\n");
+    if text.0 != content {
+        text.0 = content;
+    }
+}
+
+/// 以主机给出的随机种子开始无尽模式
+fn start_match(
+    seed: u64,
+    mode: &mut GameMode,
+    config: &mut GameConfig,
+    rng: &mut GameRng,
+    next_state: &mut NextState<AppState>,
+) {
+    *rng = GameRng::seeded(seed);
+    start_mode(GameMode::Endless, mode, config, next_state);
+}
@@ -0,0 +1,144 @@
+use crate::combat::EntityKilledEvent;
+use crate::components::{Enemy, EnemyKind, Player};
+use crate::event_log;
+use crate::launch::LaunchOptions;
+use crate::wave::WaveEvent;
+use bevy::log::tracing_subscriber::Layer;
+use bevy::log::tracing_subscriber::fmt;
+use bevy::log::{BoxedLayer, Level, LogPlugin};
+use bevy::prelude::*;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+// 日志文件所在目录
+const LOG_DIR: &str = "logs";
+// 日志文件名（不含扩展名）
+const LOG_NAME: &str = "invaders";
+// 保留的历史日志文件数（每次启动滚动一次）
+const LOG_KEEP: usize = 5;
+// 默认日志级别
+const DEFAULT_LOG_LEVEL: Level = Level::INFO;
+// 默认的模块过滤（降低渲染后端的日志噪声）
+const DEFAULT_LOG_FILTER: &str = "wgpu=error,naga=warn";
+
+/// 创建日志插件 - 级别取自启动参数（--log-level或INVADERS_LOG），RUST_LOG可进一步按模块过滤
+/// 带有--log-file时同时写入logs/invaders.log（旧日志依次滚动为invaders.1.log ~ invaders.5.log）
+pub fn log_plugin(options: &LaunchOptions) -> LogPlugin {
+    LogPlugin {
+        level: options.log_level.unwrap_or(DEFAULT_LOG_LEVEL),
+        filter: DEFAULT_LOG_FILTER.to_string(),
+        custom_layer: custom_layers,
+        ..Default::default()
+    }
+}
+
+/// 附加的日志输出层：事件日志面板的警告捕获，以及开启时的日志文件
+fn custom_layers(app: &mut App) -> Option<BoxedLayer> {
+    let mut layers = vec![event_log::capture_layer(app)];
+    layers.extend(file_layer(app));
+    Some(Box::new(layers))
+}
+
+/// 日志文件输出层（未开启日志文件或无法创建文件时不添加）
+fn file_layer(app: &mut App) -> Option<BoxedLayer> {
+    let enabled = app
+        .world()
+        .get_resource::<LaunchOptions>()
+        .is_some_and(|options| options.log_file);
+    if !enabled {
+        return None;
+    }
+    match open_log_file() {
+        Ok(file) => Some(
+            fmt::layer()
+                .with_ansi(false)
+                .with_writer(Mutex::new(file))
+                .boxed(),
+        ),
+        Err(e) => {
+            eprintln!("创建日志文件失败: {e}");
+            None
+        }
+    }
+}
+
+/// 滚动旧日志并创建本次运行的日志文件
+fn open_log_file() -> std::io::Result<File> {
+    let dir = Path::new(LOG_DIR);
+    fs::create_dir_all(dir)?;
+    let path = |index: usize| -> PathBuf {
+        match index {
+            0 => dir.join(format!("{LOG_NAME}.log")),
+            _ => dir.join(format!("{LOG_NAME}.{index}.log")),
+        }
+    };
+    // 从最旧的开始依次后移，超出保留数的日志被覆盖
+    for index in (0..LOG_KEEP).rev() {
+        let from = path(index);
+        if from.exists() {
+            fs::rename(&from, path(index + 1))?;
+        }
+    }
+    File::create(path(0))
+}
+
+/// 日志插件 - 把生成、死亡和波次切换等玩法事件记录为结构化日志
+pub struct LoggingPlugin;
+
+impl Plugin for LoggingPlugin {
+    fn build(&self, app: &mut App) {
+        // 每帧记录新生成的实体和收到的玩法事件
+        app.add_systems(
+            Update,
+            (
+                enemy_spawn_log_system,
+                player_spawn_log_system,
+                enemy_destroyed_log_system,
+                wave_log_system,
+            ),
+        );
+    }
+}
+
+/// 敌人生成日志
+fn enemy_spawn_log_system(query: Query<(Entity, &EnemyKind, &Transform), Added<Enemy>>) {
+    for (entity, kind, transform) in &query {
+        let (x, y) = (transform.translation.x, transform.translation.y);
+        match kind {
+            EnemyKind::Boss => info!(?entity, x, y, "首领生成"),
+            _ => debug!(?entity, ?kind, x, y, "敌人生成"),
+        }
+    }
+}
+
+/// 玩家生成日志
+fn player_spawn_log_system(query: Query<(Entity, &Transform), Added<Player>>) {
+    for (entity, transform) in &query {
+        debug!(?entity, x = transform.translation.x, "玩家生成");
+    }
+}
+
+/// 敌人击毁日志
+fn enemy_destroyed_log_system(mut events: EventReader<EntityKilledEvent>) {
+    for event in events.read() {
+        debug!(
+            kind = ?event.kind,
+            x = event.position.x,
+            y = event.position.y,
+            score = event.score,
+            "敌人击毁"
+        );
+    }
+}
+
+/// 波次切换日志
+fn wave_log_system(mut events: EventReader<WaveEvent>) {
+    for event in events.read() {
+        match *event {
+            WaveEvent::Started(wave) => info!(wave, "波次开始"),
+            WaveEvent::BossIncoming(wave) => info!(wave, "首领来袭"),
+            WaveEvent::Cleared(wave) => info!(wave, "波次清除"),
+        }
+    }
+}
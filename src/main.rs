@@ -1,94 +1,232 @@
 #![allow(unused)] // 探索阶段用来屏蔽未使用警告
 
-use bevy::audio::{AudioPlayer, PlaybackSettings}; // 用于音频播放
-use bevy::math::bounding::IntersectsVolume;
-use bevy::math::{Vec3Swizzles, bounding::Aabb2d};
+use action::ActionPlugin;
+use atlas::AtlasPlugin;
+use attract::AttractPlugin;
+use bench::BenchPlugin;
+use bevy::audio::Volume;
+use bevy::ecs::schedule::{ExecutorKind, ScheduleLabel};
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
+use bevy::render::camera::ScalingMode;
+use bevy::window::{MonitorSelection, PresentMode, WindowMode};
+use boss::BossPlugin;
+use bot::BotPlugin;
+use campaign::CampaignPlugin;
+use caps::CapsPlugin;
+use chat::ChatPlugin;
+use collision::{sprites_collide, world_transform};
+use combat::{CombatPlugin, DamageEvent, ExplosionRequested};
+use comms::CommsPlugin;
 use components::{
-    Enemy, Explosion, ExplosionTimer, ExplosionToSpawn, FromEnemy, FromPlayer, Laser, Movable,
-    Player, SpriteSize, Velocity,
+    DamageType, Enemy, EnemyKind, Explosion, ExplosionTimer, FireCooldown, FirePeriod, Health,
+    InRun, Laser, Movable, MoveSpeed, Player, Resistances, SpawnTick, SpriteSize, Team, Velocity,
 };
-use enemy::EnemyPlugin;
-use player::Invincible;
+use config::GameConfig;
+use cutscene::CutscenePlugin;
+use death::PlayerDeathPlugin;
+use diagnostics::EntityDiagnosticsPlugin;
+use difficulty::DifficultyPlugin;
+use display::DisplayPlugin;
+use enemy::{EnemyDefPlugin, EnemyPlugin, WeakPoint};
+use event_log::EventLogPlugin;
+use faction_time::{Faction, FactionTime, FactionTimePlugin};
+use force::{Drift, ForcePlugin};
+use frame_graph::FrameGraphPlugin;
+use game_mode::{EndlessPlugin, GameModeAppExt, GameModesPlugin};
+use ghost::GhostPlugin;
+use gravity::GravityPlugin;
+use hazard::{HazardPlugin, Slowed};
+use hud::HudPlugin;
+use interpolation::InterpolationPlugin;
+use launch::LaunchOptions;
+use lobby::LobbyPlugin;
+use logging::LoggingPlugin;
+use menu::MenuPlugin;
+use mods::{ModList, ModsPlugin};
+use net::NetPlugin;
+use options::OptionsPlugin;
+use pickup::PickupPlugin;
 use player::PlayerPlugin;
-use std::collections::HashSet;
-
+use player::{Invincible, Shield};
+use pool::{BulletPool, Pooled, recycle};
+use practice::PracticePlugin;
+use profile::{Profile, ProfilePlugin};
+use random_event::RandomEventPlugin;
+use registry::{AssetRegistry, AssetRegistryPlugin, Tex};
+use rng::GameRng;
+use run::RunPlugin;
+use score_attack::ScoreAttackPlugin;
+use settings::{Settings, SettingsFilePlugin, SettingsPlugin};
+use shop::ShopPlugin;
+use sim::{RollbackAppExt, SimPlugin};
+use skills::SkillTreePlugin;
+use sound::SoundPlugin;
+use spectator::SpectatorPlugin;
+use status::{Inflicts, StatusPlugin};
+use stress::StressPlugin;
+use survival::SurvivalPlugin;
+use theme::ThemePlugin;
+use unlocks::UnlocksPlugin;
+use wave::WavePlugin;
+use weapon::{Blast, WeaponPlugin};
+use world_dump::WorldDumpPlugin;
+
+mod action; // 输入动作模块
+mod atlas; // 精灵图集模块
+mod attract; // 主菜单演示模块
+mod bench; // 基准测试模块
+mod boss; // 首领模块
+mod bot; // 自动驾驶模块
+mod campaign; // 战役模块
+mod caps; // 实体数量上限模块
+mod chat; // 联机聊天模块
+mod collision; // 碰撞检测模块
+mod combat; // 战斗事件模块
+mod comms; // 通讯台词模块
 mod components; // 组件模块
+mod config; // 游戏配置模块
+mod cutscene; // 过场动画模块
+mod death; // 玩家死亡模块
+#[cfg(feature = "dev")]
+mod dev; // 开发调试模块
+mod diagnostics; // 实体与内存诊断模块
+mod difficulty; // 难度递增模块
+mod display; // 显示设置模块
+mod drops; // 掉落表模块
+#[cfg(feature = "embed-assets")]
+mod embedded; // 内嵌资源模块
 mod enemy; // 敌人相关模块
+mod event_log; // 事件日志面板模块
+mod faction_time; // 阵营时间缩放模块
+mod force; // 力场区域模块
+mod frame_graph; // 帧时间曲线模块
+mod game_mode; // 游戏模式模块
+mod ghost; // 幽灵回放模块
+mod gravity; // 引力井模块
+mod hazard; // 环境危险区域模块
+mod headless; // 无窗口模拟模块
+mod hud; // 抬头显示模块
+mod interpolation; // 渲染位置插值模块
+mod launch; // 启动选项模块
+mod level; // 关卡文件模块
+mod lobby; // 联机大厅模块
+mod logging; // 日志模块
+mod math; // 数学计算模块
+mod menu; // 主菜单模块
+mod mods; // 模组模块
+mod net; // 联机网络模块
+mod options; // 选项界面模块
+mod pickup; // 拾取物模块
 mod player; // 玩家相关模块
+mod pool; // 子弹对象池模块
+mod practice; // 练习模式模块
+mod profile; // 玩家档案模块
+mod random_event; // 随机事件模块
+mod registry; // 资源注册表模块
+mod rng; // 随机数模块
+mod run; // 本局收尾模块
+mod save; // 存档版本与迁移模块
+mod score_attack; // 得分挑战模块
+#[cfg(feature = "scripting")]
+mod scripting; // 行为脚本模块
+mod settings; // 游戏设置模块
+mod shop; // 波次间商店模块
+mod sim; // 确定性模拟核心模块
+mod skills; // 局外技能树模块
+mod sound; // 音效模块
+mod spectator; // 联机观战模块
+mod status; // 状态效果模块
+mod stress; // 渲染压力测试模块
+mod survival; // 生存模式模块
+#[cfg(test)]
+mod testing; // 测试工具模块
+mod theme; // 关卡主题模块
+#[cfg(feature = "touch")]
+mod touch; // 触屏控件模块
+mod unlocks; // 得分解锁模块
+mod wave; // 波次模块
+#[cfg(feature = "lua")]
+mod wave_script; // 波次脚本模块
+mod weapon; // 武器模块
+mod world_dump; // 世界快照模块
 
 // region:    --- 资源路径与常量
-const PLAYER_SPRITE: &str = "player_a_01.png"; // 玩家精灵图路径
 const PLAYER_SIZE: (f32, f32) = (144., 75.); // 玩家精灵尺寸
-const PLAYER_LASER_SPRITE: &str = "laser_a_01.png"; // 玩家激光精灵图路径
 const PLAYER_LASER_SIZE: (f32, f32) = (9., 54.); // 玩家激光尺寸
 
-const ENEMY_SPRITE: &str = "enemy_a_01.png"; // 敌人精灵图路径
 const ENEMY_SIZE: (f32, f32) = (144., 75.); // 敌人精灵尺寸
-const ENEMY_LASER_SPRITE: &str = "laser_b_01.png"; // 敌人激光精灵图路径
 const ENEMY_LASER_SIZE: (f32, f32) = (17., 55.); // 敌人激光尺寸
 
-const EXPLOSION_SHEET: &str = "explo_a_sheet.png"; // 爆炸精灵图集路径
-const EXPLOSION_LEN: usize = 16; // 爆炸动画帧数
-const ENEMY_EXPLOSION_SOUND: &str = "enemy_explosion.ogg"; // 敌人爆炸音效路径
-
 const SPRITE_SCALE: f32 = 0.5; // 精灵缩放比例
+const WINDOW_SIZE: (f32, f32) = (598., 676.); // 窗口分辨率
 // endregion: --- 资源路径与常量
 
 // region:    --- 游戏核心常量
 const BASE_SPEED: f32 = 500.; // 基础移动速度
+const FIXED_TIMESTEP_HZ: f64 = 60.; // 玩法模拟的固定步长频率
 
 const PLAYER_RESPAWN_DELAY: f64 = 2.; // 玩家重生延迟（秒）
+const PLAYER_FIRE_COOLDOWN: f32 = 0.2; // 玩家开火冷却（秒）
+const PLAYER_LIVES: u32 = 3; // 每局初始生命数
+const PLAYER_HEALTH: f32 = 100.; // 每条命的生命值（辐射等持续伤害扣除，维修包恢复1点）
+const PLAYER_BOMBS: u32 = 1; // 每局初始炸弹数
+const PLAYER_BOMBS_MAX: u32 = 3; // 炸弹携带上限
 const ENEMY_MAX: u32 = 2; // 最大敌人数量
+const ENEMY_HEALTH: f32 = 1.; // 敌人在装甲之外的生命值（普通命中一次即可击毁）
+const ENEMY_SPAWN_INTERVAL: f32 = 1.; // 初始难度下的敌人生成间隔（秒）
+const LASER_CAP: usize = 5000; // 同时存在的激光数量上限（超出时清理最早的）
+const EXPLOSION_CAP: usize = 300; // 同时存在的爆炸数量上限（超出时清理最早的）
+const LOW_GRAPHICS_EXPLOSION_MERGE: f32 = 48.; // 低画质下同一步内相距小于该距离的爆炸只生成一个
+const ENEMY_SCORE: u64 = 100; // 击毁一个敌人的得分
+const ENEMY_FIRE_PERIOD: (f32, f32) = (0.5, 1.5); // 敌人开火间隔范围（秒）
 const FORMATION_MEMBERS_MAX: u32 = 2; // 编队最大成员数
 // endregion: --- 游戏核心常量
 
 // region:    --- 资源结构体定义
 #[derive(Resource)]
 pub struct WinSize {
-    pub w: f32, // 窗口宽度
-    pub h: f32, // 窗口高度
-}
-
-#[derive(Resource)]
-struct GameTextures {
-    player: Handle<Image>,                        // 玩家精灵资源句柄
-    player_laser: Handle<Image>,                  // 玩家激光精灵资源句柄
-    enemy: Handle<Image>,                         // 敌人精灵资源句柄
-    enemy_laser: Handle<Image>,                   // 敌人激光精灵资源句柄
-    explosion_layout: Handle<TextureAtlasLayout>, // 爆炸精灵图集布局句柄
-    explosion_texture: Handle<Image>,             // 爆炸精灵图资源句柄
-    enemy_explosion_sound: Handle<AudioSource>,   // 敌人爆炸音效资源句柄
+    pub w: f32, // 游戏区域宽度
+    pub h: f32, // 游戏区域高度
 }
 
-#[derive(Resource)]
+#[derive(Resource, Clone)]
 struct EnemyCount(u32); // 当前敌人数量（资源形式存储）
 
-#[derive(Resource)]
+#[derive(Resource, Clone)]
 struct PlayerState {
     on: bool,       // 玩家是否存活
     last_shot: f64, // 最后一次死亡时间（-1表示未死亡过）
+    lives: u32,     // 剩余生命数（为0时不再重生）
+    bombs: u32,     // 剩余炸弹数
+    shield: bool,   // 下一次生成的战机是否带开局护盾
 }
 
 // PlayerState默认实现
 impl Default for PlayerState {
     fn default() -> Self {
         Self {
-            on: false,      // 初始状态：玩家未存活
-            last_shot: -1., // 初始无死亡记录
+            on: false,           // 初始状态：玩家未存活
+            last_shot: -1.,      // 初始无死亡记录
+            lives: PLAYER_LIVES, // 初始生命数
+            bombs: PLAYER_BOMBS, // 初始炸弹数
+            shield: false,       // 初始无开局护盾
         }
     }
 }
 
 // PlayerState方法扩展
 impl PlayerState {
-    // 标记玩家死亡，记录死亡时间
+    // 标记玩家死亡，记录死亡时间（重生延迟从此时开始计算）
     pub fn shot(&mut self, time: f64) {
         self.on = false;
         self.last_shot = time;
     }
 
+    // 扣除一条命
+    pub fn lose_life(&mut self) {
+        self.lives = self.lives.saturating_sub(1);
+    }
+
     // 标记玩家重生，重置死亡时间
     pub fn spawned(&mut self) {
         self.on = true;
@@ -96,33 +234,271 @@ impl PlayerState {
     }
 }
 
-// 自定义事件：敌人爆炸事件（用于触发音效等逻辑）
-#[derive(Event)]
-struct EnemyExplosionEvent;
+#[derive(Resource, Clone, Default)]
+struct GameStats {
+    score: u64,          // 本局得分
+    shots_fired: u32,    // 玩家开火次数
+    enemies_killed: u32, // 击毁敌人数量
+    player_deaths: u32,  // 玩家死亡次数
+}
 // endregion: --- 资源结构体定义
 
+// region:    --- 游戏状态
+/// 应用状态 - 加载、主菜单、技能树界面、解锁界面、战役关卡选择、联机大厅与游戏进行中
+#[derive(States, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+enum AppState {
+    #[default]
+    Loading, // 加载资源并打包精灵图集
+    MainMenu,    // 主菜单
+    SkillTree,   // 技能树界面
+    Unlocks,     // 解锁界面
+    LevelSelect, // 战役关卡选择
+    Lobby,       // 联机大厅
+    Spectating,  // 观战他人的联机对局
+    Options,     // 选项界面
+    Mods,        // 模组列表
+    Playing,     // 游戏进行中
+}
+// endregion: --- 游戏状态
+
+// region:    --- 系统集合
+/// 游戏系统集合 - 按 输入 → 移动 → 碰撞 → 生成/销毁 → 表现 的顺序执行
+/// 玩法模拟在FixedUpdate中以固定步长运行，表现（动画、音效）和按键记录在Update中运行
+/// 除表现外的集合只在游戏进行中（AppState::Playing）运行
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+enum GameSet {
+    Input,        // 读取输入、更新速度
+    Movement,     // 移动实体、推进状态计时器
+    Collision,    // 碰撞检测与命中处理
+    Spawn,        // 生成与销毁实体
+    Presentation, // 动画与音效
+}
+
+/// 配置固定步长频率和系统集合的执行顺序
+/// 固定步长单线程执行：没有显式先后关系的系统每次也按同一顺序运行，随机数的取数顺序与结果可复现
+fn configure_game_sets(app: &mut App) {
+    app.insert_resource(Time::<Fixed>::from_hz(FIXED_TIMESTEP_HZ))
+        .edit_schedule(FixedUpdate, |schedule| {
+            schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+        });
+    chain_game_sets(app, FixedUpdate);
+    chain_game_sets(app, Update);
+}
+
+/// 在指定调度中按顺序串联全部系统集合，并让玩法集合只在游戏进行中运行
+fn chain_game_sets(app: &mut App, schedule: impl ScheduleLabel) {
+    let schedule = schedule.intern();
+    app.configure_sets(
+        schedule,
+        (
+            GameSet::Input,
+            GameSet::Movement,
+            GameSet::Collision,
+            GameSet::Spawn,
+            GameSet::Presentation,
+        )
+            .chain(),
+    )
+    .configure_sets(
+        schedule,
+        (
+            GameSet::Input,
+            GameSet::Movement,
+            GameSet::Collision,
+            GameSet::Spawn,
+        )
+            .run_if(in_state(AppState::Playing)),
+    );
+}
+// endregion: --- 系统集合
+
+/// 游戏玩法插件 - 注册与渲染无关的全部玩法系统，窗口模式与无窗口模式共用
+struct GamePlugin;
+
+impl Plugin for GamePlugin {
+    fn build(&self, app: &mut App) {
+        configure_game_sets(app); // 配置系统集合顺序
+
+        app.init_state::<AppState>() // 初始化应用状态（无窗口模式下预先插入为游戏进行中）
+            .insert_resource(GameConfig::from_env()) // 初始化游戏配置资源（叠加环境变量覆盖）
+            .init_resource::<GameRng>() // 初始化随机数资源（按启动参数中的随机种子）
+            .init_resource::<GameStats>() // 初始化游戏统计资源
+            .init_resource::<Profile>() // 初始化玩家档案（窗口模式下由档案插件从文件读取）
+            .init_resource::<BulletPool>() // 初始化子弹对象池
+            // 登记参与快照回滚的通用资源和组件（各模块的状态由各自的插件登记）
+            .rollback_resource::<GameRng>()
+            .rollback_resource::<GameStats>()
+            .rollback_resource::<EnemyCount>()
+            .rollback_component::<Velocity>()
+            .rollback_component::<Movable>()
+            .rollback_component::<SpriteSize>()
+            .rollback_component::<Laser>()
+            .rollback_component::<FireCooldown>()
+            .rollback_component::<DamageType>()
+            .rollback_component::<Resistances>()
+            .rollback_component::<SpawnTick>()
+            .rollback_component::<InRun>()
+            .rollback_component::<Team>()
+            .rollback_component::<Player>()
+            .rollback_component::<MoveSpeed>()
+            .rollback_component::<Health>()
+            .rollback_component::<Enemy>()
+            .rollback_component::<EnemyKind>()
+            .rollback_component::<FirePeriod>()
+            .rollback_component::<Explosion>()
+            .rollback_component::<ExplosionTimer>()
+            .rollback_component::<Pooled>()
+            .add_plugins(LoggingPlugin) // 添加玩法事件日志插件
+            .add_plugins(SettingsPlugin) // 添加游戏设置插件
+            .add_plugins(ActionPlugin) // 添加输入动作插件
+            .add_plugins(SimPlugin) // 添加确定性模拟核心插件
+            .add_plugins(PlayerPlugin) // 添加玩家系统插件
+            .add_plugins(WeaponPlugin) // 添加武器插件
+            .add_plugins(EnemyPlugin) // 添加敌人系统插件
+            .add_plugins(DifficultyPlugin) // 添加难度递增插件
+            .add_plugins(WavePlugin) // 添加波次插件
+            .add_plugins(PickupPlugin) // 添加拾取物插件
+            .add_plugins(ShopPlugin) // 添加波次间商店插件
+            .add_plugins(RandomEventPlugin) // 添加随机事件插件
+            .add_plugins(HazardPlugin) // 添加环境危险区域插件
+            .add_plugins(GravityPlugin) // 添加引力井插件
+            .add_plugins(ForcePlugin) // 添加力场区域插件
+            .add_plugins(CutscenePlugin) // 添加过场动画插件
+            .add_plugins(BossPlugin) // 添加首领插件
+            .add_plugins(CapsPlugin) // 添加实体数量上限插件
+            .add_plugins(StatusPlugin) // 添加状态效果插件
+            .add_plugins(FactionTimePlugin) // 添加阵营时间缩放插件
+            .add_plugins(GameModesPlugin) // 添加游戏模式规则插件
+            .add_game_mode(EndlessPlugin) // 添加无尽模式
+            .add_game_mode(ScoreAttackPlugin) // 添加得分挑战模式
+            .add_game_mode(PracticePlugin) // 添加练习模式
+            .add_game_mode(SurvivalPlugin) // 添加生存模式
+            .add_plugins(BotPlugin) // 添加自动驾驶插件
+            .add_plugins(CombatPlugin) // 添加战斗事件插件
+            .add_plugins(PlayerDeathPlugin) // 添加玩家死亡插件
+            .add_plugins(RunPlugin) // 添加本局收尾插件
+            .add_plugins(SoundPlugin) // 添加音效插件
+            // 固定步长执行：可移动实体逻辑
+            .add_systems(FixedUpdate, movable_system.in_set(GameSet::Movement))
+            // 固定步长执行：激光命中敌人、投射物命中玩家逻辑
+            .add_systems(
+                FixedUpdate,
+                (laser_hit_enemy_system, projectile_hit_player_system).in_set(GameSet::Collision),
+            )
+            // 固定步长执行：爆炸生成逻辑（在伤害结算之后）
+            .add_systems(
+                FixedUpdate,
+                explosion_spawn_system
+                    .after(combat::death_system)
+                    .in_set(GameSet::Spawn),
+            )
+            // 每帧执行：爆炸动画逻辑
+            .add_systems(
+                Update,
+                explosion_animation_system.in_set(GameSet::Presentation),
+            );
+    }
+}
+
 fn main() {
-    App::new()
+    // 解析启动参数，指定了随机种子时对窗口模式和无窗口模拟都生效（基准测试默认使用固定种子，随机数资源按此初始化）
+    let options = LaunchOptions::from_args();
+    if let Some(seed) = options.rng_seed() {
+        println!("随机种子: {seed}");
+    }
+
+    // 带有--headless参数时运行无窗口模拟并输出统计
+    if let Some(frames) = headless::frames_from_args() {
+        headless::run(frames, &options);
+        return;
+    }
+
+    let mut app = App::new();
+    // 读取设置文件，窗口按设置中的分辨率和全屏选项创建（启动参数优先）
+    let settings = Settings::load();
+    let mode = if settings.fullscreen && !options.windowed {
+        WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+    } else {
+        WindowMode::Windowed
+    };
+    let present_mode = if settings.vsync {
+        PresentMode::AutoVsync
+    } else {
+        PresentMode::AutoNoVsync
+    };
+    // 启动选项需在日志插件之前插入（日志文件输出层据此开启）
+    app.insert_resource(options.clone())
+        .insert_resource(settings.clone())
         .insert_resource(ClearColor(Color::srgb(0.04, 0.04, 0.04))) // 设置背景颜色
-        .add_plugins(DefaultPlugins.set(WindowPlugin {
-            // 添加默认插件并配置窗口
-            primary_window: Some(Window {
-                title: "Rust Invaders!".into(),  // 窗口标题
-                resolution: (598., 676.).into(), // 窗口分辨率
-                ..Default::default()
-            }),
-            ..Default::default()
-        }))
-        .add_plugins(PlayerPlugin) // 添加玩家系统插件
-        .add_plugins(EnemyPlugin) // 添加敌人系统插件
-        .add_event::<EnemyExplosionEvent>() // 注册敌人爆炸事件
-        .add_systems(Startup, setup_system) // 启动阶段执行：初始化系统
-        .add_systems(Update, movable_system) // 每帧执行：可移动实体逻辑
-        .add_systems(Update, player_laser_hit_enemy_system) // 每帧执行：玩家激光命中敌人逻辑
-        .add_systems(Update, enemy_laser_hit_player_system) // 每帧执行：敌人激光命中玩家逻辑
-        .add_systems(Update, explosion_to_spawn_system) // 每帧执行：爆炸生成逻辑
-        .add_systems(Update, explosion_animation_system) // 每帧执行：爆炸动画逻辑
-        .add_systems(Update, enemy_explosion_audio_system) // 每帧执行：敌人爆炸音效逻辑
+        .add_plugins(ModsPlugin) // 添加模组插件（需在默认插件之前注册模组资源来源）
+        .add_plugins(
+            DefaultPlugins
+                .set(WindowPlugin {
+                    // 添加默认插件并配置窗口
+                    primary_window: Some(Window {
+                        title: "Rust Invaders!".into(), // 窗口标题
+                        resolution: options.resolution.unwrap_or(settings.resolution).into(), // 窗口分辨率
+                        mode,         // 窗口模式
+                        present_mode, // 垂直同步
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                })
+                .set(logging::log_plugin(&options)), // 日志级别与日志文件
+        );
+    // 带有--mute参数时静音
+    if options.mute {
+        app.insert_resource(GlobalVolume {
+            volume: Volume::Linear(0.),
+        });
+    }
+    // 启用embed-assets特性时从可执行文件内嵌的副本加载资源
+    #[cfg(feature = "embed-assets")]
+    app.add_plugins(embedded::EmbeddedAssetsPlugin);
+    // 启用dev特性时监视资源文件并支持热重载
+    #[cfg(feature = "dev")]
+    app.add_plugins(dev::DevPlugin);
+    // 启用touch特性时显示虚拟摇杆和开火按钮
+    #[cfg(feature = "touch")]
+    app.add_plugins(touch::TouchPlugin);
+    // 启用scripting特性时由WebAssembly行为脚本控制自定义敌人
+    #[cfg(feature = "scripting")]
+    app.add_plugins(scripting::ScriptingPlugin);
+    // 启用lua特性时由Lua波次脚本安排自定义波次
+    #[cfg(feature = "lua")]
+    app.add_plugins(wave_script::WaveScriptPlugin);
+    app.add_plugins(GamePlugin) // 添加游戏玩法插件
+        .add_plugins(InterpolationPlugin) // 添加渲染位置插值插件
+        .add_plugins(HudPlugin) // 添加抬头显示插件
+        .add_plugins(CommsPlugin) // 添加通讯台词插件
+        .add_plugins(SettingsFilePlugin) // 添加设置文件插件
+        .add_plugins(DisplayPlugin) // 添加显示设置插件
+        .add_plugins(ProfilePlugin) // 添加玩家档案插件
+        .add_plugins(GhostPlugin) // 添加幽灵回放插件
+        .add_plugins(MenuPlugin) // 添加主菜单插件
+        .add_game_mode(AttractPlugin) // 添加主菜单演示模式
+        .add_plugins(NetPlugin) // 添加联机网络插件
+        .add_plugins(LobbyPlugin) // 添加联机大厅插件
+        .add_plugins(ChatPlugin) // 添加联机聊天插件
+        .add_plugins(SpectatorPlugin) // 添加联机观战插件
+        .add_plugins(OptionsPlugin) // 添加选项界面插件
+        .add_plugins(SkillTreePlugin) // 添加技能树插件
+        .add_game_mode(CampaignPlugin) // 添加战役模式
+        .add_plugins(ThemePlugin) // 添加关卡主题插件
+        .add_plugins(AssetRegistryPlugin) // 添加资源占位替换插件
+        .add_plugins(AtlasPlugin) // 添加精灵图集插件
+        .add_plugins(EnemyDefPlugin) // 添加敌人定义插件
+        .add_plugins(StressPlugin) // 添加渲染压力测试插件
+        .add_plugins(UnlocksPlugin) // 添加得分解锁插件
+        .add_plugins(EventLogPlugin) // 添加事件日志面板插件
+        .add_plugins(FrameGraphPlugin) // 添加帧时间曲线插件
+        .add_plugins(EntityDiagnosticsPlugin) // 添加实体与内存诊断插件
+        .add_plugins(WorldDumpPlugin); // 添加世界快照插件
+    // 带有--bench参数时运行基准测试场景
+    if options.bench {
+        app.add_plugins(BenchPlugin);
+    }
+    app.add_systems(Startup, setup_system) // 启动阶段执行：初始化系统
         .run();
 }
 
@@ -131,59 +507,70 @@ fn setup_system(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
-    query: Query<&Window, With<PrimaryWindow>>,
+    mut images: ResMut<Assets<Image>>,
+    mut audio: ResMut<Assets<AudioSource>>,
+    mods: Res<ModList>,
 ) {
-    // 生成2D摄像机
-    commands.spawn(Camera2d);
-
-    // 获取窗口尺寸
-    let Ok(primary) = query.get_single() else {
-        return;
+    // 生成2D摄像机：无论窗口多大，都按比例缩放到完整显示游戏区域（多出的部分由显示插件加黑边）
+    commands.spawn((
+        Camera2d,
+        Projection::Orthographic(OrthographicProjection {
+            scaling_mode: ScalingMode::AutoMin {
+                min_width: WINDOW_SIZE.0,
+                min_height: WINDOW_SIZE.1,
+            },
+            ..OrthographicProjection::default_2d()
+        }),
+    ));
+
+    // 游戏区域固定为WINDOW_SIZE，生成边距、边界限制等玩法几何在任何窗口尺寸下都相同
+    let win_size = WinSize {
+        w: WINDOW_SIZE.0,
+        h: WINDOW_SIZE.1,
     };
-    let (win_w, win_h) = (primary.width(), primary.height());
-
-    // 存储窗口尺寸资源
-    let win_size = WinSize { w: win_w, h: win_h };
     commands.insert_resource(win_size);
 
-    // 创建爆炸精灵图集
-    let texture_handle = asset_server.load(EXPLOSION_SHEET);
-    let texture_atlas = TextureAtlasLayout::from_grid(UVec2::new(64, 64), 4, 4, None, None);
-    let explosion_layout = texture_atlases.add(texture_atlas);
-
-    // 加载敌人爆炸音效
-    let enemy_explosion_sound = asset_server.load(ENEMY_EXPLOSION_SOUND);
-
-    // 存储游戏纹理资源
-    let game_textures = GameTextures {
-        player: asset_server.load(PLAYER_SPRITE),
-        player_laser: asset_server.load(PLAYER_LASER_SPRITE),
-        enemy: asset_server.load(ENEMY_SPRITE),
-        enemy_laser: asset_server.load(ENEMY_LASER_SPRITE),
-        explosion_layout,
-        explosion_texture: texture_handle,
-        enemy_explosion_sound,
-    };
-    commands.insert_resource(game_textures);
+    // 按资源清单加载全部纹理和音效（模组中的同名文件优先）、创建爆炸序列帧图的网格布局（精灵图集在加载阶段打包后填入）
+    commands.insert_resource(AssetRegistry::load(
+        &asset_server,
+        &mods,
+        &mut images,
+        &mut audio,
+        &mut texture_atlases,
+    ));
     commands.insert_resource(EnemyCount(0)); // 初始化敌人数量为0
 }
 
-// 可移动实体逻辑：处理实体移动、超出屏幕自动销毁
+// 可移动实体逻辑：处理实体移动（叠加力场漂移）、超出屏幕自动销毁
+#[allow(clippy::type_complexity)] // 允许复杂的查询类型
 fn movable_system(
     mut commands: Commands,
     time: Res<Time>,
+    faction_time: Res<FactionTime>,
+    config: Res<GameConfig>,
     win_size: Res<WinSize>,
-    mut query: Query<(Entity, &Velocity, &mut Transform, &Movable)>,
+    mut query: Query<(
+        Entity,
+        &Velocity,
+        &mut Transform,
+        &Movable,
+        Option<&Slowed>,
+        Option<&Drift>,
+        Option<&Team>,
+    )>,
 ) {
-    let delta = time.delta_secs(); // 帧时间间隔
-
-    for (entity, velocity, mut transform, movable) in &mut query {
+    for (entity, velocity, mut transform, movable, slowed, drift, team) in &mut query {
+        // 帧时间间隔（敌方激光和障碍物按敌方阵营的时间运行，减速区域内按比例缩短）
+        let faction = team.map_or(Faction::Player, |team| team.0);
+        let delta = faction_time.delta_secs(&time, faction) * Slowed::scale(slowed);
         let translation = &mut transform.translation;
         // 根据速度和时间更新位置
-        translation.x += velocity.x * delta * BASE_SPEED;
-        translation.y += velocity.y * delta * BASE_SPEED;
+        let drift = drift.map_or(Vec2::ZERO, |drift| drift.0);
+        let speed = config.tuning.base_speed;
+        translation.x += (velocity.x + drift.x) * delta * speed;
+        translation.y += (velocity.y + drift.y) * delta * speed;
 
-        // 自动销毁逻辑：超出屏幕范围时销毁
+        // 自动销毁逻辑：超出屏幕范围时销毁（池化的子弹回收到对象池）
         if movable.auto_despawn {
             const MARGIN: f32 = 200.; // 超出屏幕的边距
             let out_of_bounds = translation.y > win_size.h / 2. + MARGIN
@@ -192,113 +579,145 @@ fn movable_system(
                 || translation.x < -win_size.w / 2. - MARGIN;
 
             if out_of_bounds {
-                commands.entity(entity).despawn();
+                commands.entity(entity).queue(recycle);
             }
         }
     }
 }
 
-// 玩家激光命中敌人逻辑：处理碰撞检测、敌人销毁、爆炸生成
+// 激光命中敌人逻辑：处理碰撞检测，向被命中的敌人发送伤害事件（带爆炸弹头的激光会波及周围敌人）
+// 激光只命中与其队伍敌对的敌人（如玩家激光命中敌方队伍的敌人）
+// 激光先与弱点检测碰撞，命中弱点时对其父实体造成倍数伤害并显示暴击闪光
 #[allow(clippy::type_complexity)] // 允许复杂的查询类型
-fn player_laser_hit_enemy_system(
+fn laser_hit_enemy_system(
     mut commands: Commands,
-    mut enemy_count: ResMut<EnemyCount>,
-    laser_query: Query<(Entity, &Transform, &SpriteSize), (With<Laser>, With<FromPlayer>)>,
-    enemy_query: Query<(Entity, &Transform, &SpriteSize), With<Enemy>>,
-    mut enemy_explosion_events: EventWriter<EnemyExplosionEvent>,
+    laser_query: Query<
+        (
+            Entity,
+            &Transform,
+            &SpriteSize,
+            Option<&Blast>,
+            Option<&DamageType>,
+            Option<&Inflicts>,
+            &Team,
+        ),
+        With<Laser>,
+    >,
+    enemy_query: Query<(Entity, &Transform, &SpriteSize, &Team), With<Enemy>>,
+    weak_point_query: Query<(Entity, &SpriteSize, &WeakPoint, &ChildOf)>,
+    transform_query: Query<(&Transform, Option<&ChildOf>)>,
+    mut damage_events: EventWriter<DamageEvent>,
 ) {
-    let mut despawned_entities = HashSet::new(); // 记录已销毁的实体
-
-    // 遍历所有玩家激光
-    for (laser_entity, laser_tf, laser_size) in laser_query.iter() {
-        if despawned_entities.contains(&laser_entity) {
-            continue; // 跳过已销毁的激光
-        }
-
-        let laser_scale = laser_tf.scale.xy(); // 获取激光缩放比例
-
-        // 遍历所有敌人
-        for (enemy_entity, enemy_tf, enemy_size) in enemy_query.iter() {
-            if despawned_entities.contains(&enemy_entity)
-                || despawned_entities.contains(&laser_entity)
-            {
-                continue; // 跳过已销毁的敌人或激光
-            }
-
-            let enemy_scale = enemy_tf.scale.xy(); // 获取敌人缩放比例
-
-            // 碰撞检测：用轴对齐包围盒（AABB）判断
-            let laser_aabb = Aabb2d::new(
-                laser_tf.translation.truncate(),
-                (laser_size.0 * laser_scale) / 2.,
-            );
-            let enemy_aabb = Aabb2d::new(
-                enemy_tf.translation.truncate(),
-                (enemy_size.0 * enemy_scale) / 2.,
-            );
-
-            if laser_aabb.intersects(&enemy_aabb) {
-                // 销毁敌人
-                commands.entity(enemy_entity).despawn();
-                despawned_entities.insert(enemy_entity);
-                enemy_count.0 -= 1; // 减少敌人数量
-
-                // 销毁激光
-                commands.entity(laser_entity).despawn();
-                despawned_entities.insert(laser_entity);
-
-                // 生成爆炸
-                commands.spawn(ExplosionToSpawn(enemy_tf.translation));
-
-                // 发送敌人爆炸事件（用于触发音效）
-                enemy_explosion_events.send(EnemyExplosionEvent);
-            }
+    // 遍历所有激光
+    for (laser_entity, laser_tf, laser_size, blast, damage_type, inflicts, team) in
+        laser_query.iter()
+    {
+        let hostile = |enemy: Entity| {
+            enemy_query
+                .get(enemy)
+                .is_ok_and(|(.., enemy_team)| team.hostile(*enemy_team))
+        };
+        let damage_type = damage_type.copied().unwrap_or_default();
+        // 先寻找被命中的弱点（命中的碰撞体、其所属敌人和伤害倍率）
+        let weak_hit = weak_point_query
+            .iter()
+            .filter(|(.., child_of)| hostile(child_of.parent()))
+            .find_map(|(entity, size, weak_point, child_of)| {
+                let world_tf = world_transform(entity, &transform_query)?;
+                sprites_collide(laser_tf, laser_size, &world_tf, size).then_some((
+                    child_of.parent(),
+                    world_tf.translation,
+                    weak_point.multiplier,
+                ))
+            });
+        // 未命中弱点时寻找第一个被该激光命中的敌人
+        let hit = weak_hit.or_else(|| {
+            enemy_query
+                .iter()
+                .find(|(_, enemy_tf, enemy_size, enemy_team)| {
+                    team.hostile(**enemy_team)
+                        && sprites_collide(laser_tf, laser_size, enemy_tf, enemy_size)
+                })
+                .map(|(entity, enemy_tf, ..)| (entity, enemy_tf.translation, 1.))
+        });
+        let Some((enemy_entity, impact, damage)) = hit else {
+            continue;
+        };
+        let critical = weak_hit.is_some();
+
+        // 销毁激光（池化的激光回收到对象池）
+        commands.entity(laser_entity).queue(recycle);
+
+        // 直接命中的敌人承受弹药类型的伤害（命中弱点时加倍）和附带的状态效果
+        damage_events.send(DamageEvent {
+            critical: critical.then_some(impact),
+            inflicts: inflicts.copied(),
+            ..DamageEvent::new(enemy_entity, damage, Some(damage_type))
+        });
+
+        // 爆炸半径内的其他敌对敌人承受普通的爆炸伤害
+        let Some(blast) = blast else {
+            continue;
+        };
+        for (victim, ..) in enemy_query.iter().filter(|(entity, tf, _, enemy_team)| {
+            *entity != enemy_entity
+                && team.hostile(**enemy_team)
+                && tf.translation.distance(impact) <= blast.radius
+        }) {
+            damage_events.send(DamageEvent::new(victim, 1., Some(DamageType::Explosive)));
         }
     }
 }
 
-// 敌人激光命中玩家逻辑：处理碰撞检测、玩家销毁、爆炸生成
+// 投射物命中玩家逻辑：处理碰撞检测，与玩家队伍敌对的激光和流星等障碍物命中时耗尽玩家的生命值
+// 生命值耗尽后由死亡处理系统销毁玩家、生成爆炸并播放音效
 #[allow(clippy::type_complexity)] // 允许复杂的查询类型
-fn enemy_laser_hit_player_system(
+fn projectile_hit_player_system(
     mut commands: Commands,
-    mut player_state: ResMut<PlayerState>,
-    time: Res<Time>,
-    laser_query: Query<(Entity, &Transform, &SpriteSize), (With<Laser>, With<FromEnemy>)>,
-    player_query: Query<(Entity, &Transform, &SpriteSize, Option<&Invincible>), With<Player>>,
+    projectile_query: Query<
+        (Entity, &Transform, &SpriteSize, &Team),
+        (Without<Player>, Without<Enemy>),
+    >,
+    mut player_query: Query<
+        (
+            Entity,
+            &Transform,
+            &SpriteSize,
+            &Team,
+            &mut Health,
+            Option<&Invincible>,
+            Option<&Shield>,
+        ),
+        With<Player>,
+    >,
 ) {
-    // 获取玩家实体（游戏中应该只有一个玩家）
-    if let Ok((player_entity, player_tf, player_size, invincible)) = player_query.get_single() {
+    for (player_entity, player_tf, player_size, player_team, mut health, invincible, shield) in
+        &mut player_query
+    {
         // 如果玩家处于无敌状态，跳过碰撞处理
         if invincible.is_some() {
-            return;
+            continue;
         }
 
-        let player_scale = player_tf.scale.xy(); // 获取玩家缩放比例
-
-        // 遍历所有敌人激光
-        for (laser_entity, laser_tf, laser_size) in laser_query.iter() {
-            let laser_scale = laser_tf.scale.xy(); // 获取激光缩放比例
-
+        // 遍历所有敌对的投射物
+        for (laser_entity, laser_tf, laser_size, _) in projectile_query
+            .iter()
+            .filter(|(.., team)| team.hostile(*player_team))
+        {
             // 碰撞检测：用轴对齐包围盒（AABB）判断
-            let laser_aabb = Aabb2d::new(
-                laser_tf.translation.truncate(),
-                (laser_size.0 * laser_scale) / 2.,
-            );
-            let player_aabb = Aabb2d::new(
-                player_tf.translation.truncate(),
-                (player_size.0 * player_scale) / 2.,
-            );
+            if sprites_collide(laser_tf, laser_size, player_tf, player_size) {
+                // 护盾抵挡这一次伤害后消失
+                if shield.is_some() {
+                    commands.entity(player_entity).remove::<Shield>();
+                    commands.entity(laser_entity).queue(recycle);
+                    break;
+                }
 
-            if laser_aabb.intersects(&player_aabb) {
-                // 销毁玩家
-                commands.entity(player_entity).despawn();
-                player_state.shot(time.elapsed_secs_f64()); // 记录死亡时间
+                // 一次命中即耗尽生命值
+                health.current = 0.;
 
                 // 销毁激光
-                commands.entity(laser_entity).despawn();
-
-                // 生成爆炸
-                commands.spawn(ExplosionToSpawn(player_tf.translation));
+                commands.entity(laser_entity).queue(recycle);
 
                 break; // 玩家死亡后跳出循环
             }
@@ -306,32 +725,41 @@ fn enemy_laser_hit_player_system(
     }
 }
 
-// 爆炸生成逻辑：将ExplosionToSpawn转换为实际爆炸精灵
-fn explosion_to_spawn_system(
+// 爆炸生成逻辑：响应ExplosionRequested生成实际爆炸精灵（低画质下合并相邻的爆炸）
+fn explosion_spawn_system(
     mut commands: Commands,
-    game_textures: Res<GameTextures>,
-    query: Query<(Entity, &ExplosionToSpawn)>,
+    registry: Res<AssetRegistry>,
+    settings: Res<Settings>,
+    mut events: EventReader<ExplosionRequested>,
 ) {
-    for (explosion_spawn_entity, explosion_to_spawn) in query.iter() {
+    let mut spawned: Vec<Vec2> = Vec::new(); // 本步已生成的爆炸位置
+    for ExplosionRequested(position) in events.read() {
+        // 低画质下附近已有本步生成的爆炸时跳过
+        if settings.graphics.is_low()
+            && spawned
+                .iter()
+                .any(|p| p.distance(position.truncate()) < LOW_GRAPHICS_EXPLOSION_MERGE)
+        {
+            continue;
+        }
+        spawned.push(position.truncate());
+
         // 生成爆炸精灵
         commands
             .spawn((
                 Sprite {
-                    image: game_textures.explosion_texture.clone(), // 爆炸精灵图
+                    image: registry.texture(Tex::Explosion), // 爆炸精灵图
                     texture_atlas: Some(TextureAtlas {
                         // 精灵图集配置
-                        layout: game_textures.explosion_layout.clone(),
+                        layout: registry.explosion_layout.clone(),
                         index: 0, // 从第一帧开始播放
                     }),
                     ..Default::default()
                 },
-                Transform::from_translation(explosion_to_spawn.0), // 爆炸位置
+                Transform::from_translation(*position), // 爆炸位置
             ))
             .insert(Explosion) // 标记为爆炸实体
             .insert(ExplosionTimer::default()); // 爆炸动画计时器
-
-        // 销毁ExplosionToSpawn标记实体
-        commands.entity(explosion_spawn_entity).despawn();
     }
 }
 
@@ -339,6 +767,7 @@ fn explosion_to_spawn_system(
 fn explosion_animation_system(
     mut commands: Commands,
     time: Res<Time>,
+    registry: Res<AssetRegistry>,
     mut query: Query<(Entity, &mut ExplosionTimer, &mut Sprite), With<Explosion>>,
 ) {
     for (entity, mut timer, mut sprite) in &mut query {
@@ -350,25 +779,10 @@ fn explosion_animation_system(
                 texture.index += 1; // 切换到下一帧
 
                 // 动画播放完毕：销毁爆炸实体
-                if texture.index >= EXPLOSION_LEN {
+                if texture.index >= registry.explosion_grid.frames() {
                     commands.entity(entity).despawn();
                 }
             }
         }
     }
 }
-
-// 敌人爆炸音效逻辑：响应EnemyExplosionEvent播放音效
-fn enemy_explosion_audio_system(
-    mut commands: Commands,
-    game_textures: Res<GameTextures>,
-    mut events: EventReader<EnemyExplosionEvent>,
-) {
-    for _ in events.read() {
-        // 播放敌人爆炸音效（单次播放）
-        commands.spawn((
-            AudioPlayer::new(game_textures.enemy_explosion_sound.clone()),
-            PlaybackSettings::ONCE,
-        ));
-    }
-}
@@ -0,0 +1,94 @@
+use std::f32::consts::PI;
+
+/// 计算椭圆轨迹上指定角度的点
+///
+/// 参数:
+/// - pivot: 椭圆中心点坐标
+/// - radius: 椭圆半径(x轴半径,y轴半径)
+/// - angle: 角度（弧度）
+pub fn ellipse_point(pivot: (f32, f32), radius: (f32, f32), angle: f32) -> (f32, f32) {
+    (
+        radius.0 * angle.cos() + pivot.0,
+        radius.1 * angle.sin() + pivot.1,
+    )
+}
+
+/// 计算沿椭圆轨迹移动后的下一个角度
+///
+/// 参数:
+/// - angle: 当前角度
+/// - dir: 移动方向（1为逆时针，-1为顺时针）
+/// - speed: 移动速度
+/// - delta: 帧时间间隔
+/// - radius: 椭圆半径，以较短的半径估算角速度
+pub fn next_angle(angle: f32, dir: f32, speed: f32, delta: f32, radius: (f32, f32)) -> f32 {
+    angle + dir * speed * delta / (radius.0.min(radius.1) * PI / 2.)
+}
+
+/// 计算两点之间的距离
+pub fn distance(a: (f32, f32), b: (f32, f32)) -> f32 {
+    let dx = a.0 - b.0;
+    let dy = a.1 - b.1;
+    (dx * dx + dy * dy).sqrt()
+}
+
+/// 从起点向目标点移动不超过max_distance的距离，且不会越过目标点
+pub fn step_towards(org: (f32, f32), dst: (f32, f32), max_distance: f32) -> (f32, f32) {
+    let dx = org.0 - dst.0;
+    let dy = org.1 - dst.1;
+    let distance = distance(org, dst);
+    let distance_ratio = if distance == 0. {
+        0.
+    } else {
+        max_distance / distance
+    };
+
+    let x = org.0 - dx * distance_ratio;
+    let x = if dx > 0. { x.max(dst.0) } else { x.min(dst.0) };
+    let y = org.1 - dy * distance_ratio;
+    let y = if dy > 0. { y.max(dst.1) } else { y.min(dst.1) };
+    (x, y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f32 = 1e-4;
+
+    fn assert_close(a: (f32, f32), b: (f32, f32)) {
+        assert!(distance(a, b) < EPSILON, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn ellipse_point_follows_radii() {
+        assert_close(ellipse_point((10., 20.), (100., 50.), 0.), (110., 20.));
+        assert_close(ellipse_point((10., 20.), (100., 50.), PI / 2.), (10., 70.));
+    }
+
+    #[test]
+    fn next_angle_respects_direction() {
+        let forward = next_angle(1., 1., 500., 0.016, (100., 80.));
+        let backward = next_angle(1., -1., 500., 0.016, (100., 80.));
+        assert!(forward > 1.);
+        assert!(backward < 1.);
+        assert!((forward - 1. - (1. - backward)).abs() < EPSILON);
+    }
+
+    #[test]
+    fn step_moves_max_distance_when_far() {
+        let step = step_towards((0., 0.), (30., 40.), 5.);
+        assert_close(step, (3., 4.));
+    }
+
+    #[test]
+    fn step_does_not_overshoot_target() {
+        assert_close(step_towards((0., 0.), (3., 4.), 100.), (3., 4.));
+        assert_close(step_towards((3., 4.), (0., 0.), 100.), (0., 0.));
+    }
+
+    #[test]
+    fn step_stays_put_at_target() {
+        assert_close(step_towards((7., 7.), (7., 7.), 5.), (7., 7.));
+    }
+}
@@ -0,0 +1,162 @@
+use crate::AppState;
+use crate::action::{Action, ActionState};
+use crate::config::{GameConfig, LevelScript};
+use crate::game_mode::{GameMode, GameModes};
+use crate::launch::LaunchOptions;
+use crate::profile::Profile;
+use bevy::prelude::*;
+
+/// 主菜单界面根节点标记
+#[derive(Component)]
+struct MainMenuScreen;
+
+/// 主菜单插件 - 启动后显示主菜单，可开始无尽模式或已注册的其他模式（得分挑战、练习、生存等），进入战役关卡选择、技能树、联机大厅、选项界面或模组列表
+pub struct MenuPlugin;
+
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        // 进入主菜单时创建界面
+        app.add_systems(OnEnter(AppState::MainMenu), main_menu_setup_system)
+            // 首次进入主菜单时按启动参数跳过主菜单
+            .add_systems(OnEnter(AppState::MainMenu), skip_menu_system)
+            // 离开主菜单时销毁界面
+            .add_systems(OnExit(AppState::MainMenu), main_menu_cleanup_system)
+            // 处理菜单按键
+            .add_systems(
+                Update,
+                main_menu_input_system.run_if(in_state(AppState::MainMenu)),
+            );
+    }
+}
+
+/// 主菜单初始化系统 - 显示标题、可用核心、操作提示（包括已注册模式的按键）和得分挑战排行榜
+fn main_menu_setup_system(mut commands: Commands, profile: Res<Profile>, modes: Res<GameModes>) {
+    let mut lines = vec![
+        "RUST INVADERS".to_string(),
+        String::new(),
+        format!("Cores: {}", profile.cores),
+        String::new(),
+        "ENTER: endless".to_string(),
+        "C: campaign".to_string(),
+    ];
+    lines.extend(
+        modes
+            .iter()
+            .filter_map(|plugin| plugin.menu_entry())
+            .map(|(_, label)| label.to_string()),
+    );
+    lines.extend([
+        "T: upgrade tree".to_string(),
+        "U: unlocks".to_string(),
+        "L: multiplayer lobby".to_string(),
+        "O: options".to_string(),
+        "M: mods".to_string(),
+    ]);
+    if !profile.score_attack.is_empty() {
+        lines.push(String::new());
+        lines.push("SCORE ATTACK BEST".to_string());
+        for (i, score) in profile.score_attack.iter().enumerate() {
+            lines.push(format!("{:>2}. {score}", i + 1));
+        }
+    }
+
+    commands
+        .spawn((
+            MainMenuScreen,
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            BackgroundColor(Color::srgba(0., 0., 0., 0.9)),
+            GlobalZIndex(10),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(lines.join("\n")),
+                TextFont {
+                    font_size: 22.,
+                    ..Default::default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// 主菜单销毁系统
+fn main_menu_cleanup_system(mut commands: Commands, query: Query<Entity, With<MainMenuScreen>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// 主菜单按键系统 - 确认（回车或手柄A）开始无尽模式（使用默认关卡脚本），已注册模式的按键（如S得分挑战、P练习模式）开始对应模式，C进入战役关卡选择，T进入技能树，U进入解锁界面，L进入联机大厅，O进入选项界面，M进入模组列表
+fn main_menu_input_system(
+    kb: Res<ButtonInput<KeyCode>>,
+    actions: Res<ActionState>,
+    modes: Res<GameModes>,
+    mut mode: ResMut<GameMode>,
+    mut config: ResMut<GameConfig>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let selected = modes.iter().find_map(|plugin| {
+        let (key, _) = plugin.menu_entry()?;
+        kb.just_pressed(key).then(|| plugin.mode())
+    });
+    if actions.just_pressed(Action::MenuConfirm) {
+        start_endless(&mut mode, &mut config, &mut next_state);
+    } else if let Some(selected) = selected {
+        start_mode(selected, &mut mode, &mut config, &mut next_state);
+    } else if kb.just_pressed(KeyCode::KeyC) {
+        next_state.set(AppState::LevelSelect);
+    } else if kb.just_pressed(KeyCode::KeyT) {
+        next_state.set(AppState::SkillTree);
+    } else if kb.just_pressed(KeyCode::KeyU) {
+        next_state.set(AppState::Unlocks);
+    } else if kb.just_pressed(KeyCode::KeyL) {
+        next_state.set(AppState::Lobby);
+    } else if kb.just_pressed(KeyCode::KeyO) {
+        next_state.set(AppState::Options);
+    } else if kb.just_pressed(KeyCode::KeyM) {
+        next_state.set(AppState::Mods);
+    }
+}
+
+/// 开始无尽模式
+fn start_endless(
+    mode: &mut GameMode,
+    config: &mut GameConfig,
+    next_state: &mut NextState<AppState>,
+) {
+    start_mode(GameMode::Endless, mode, config, next_state);
+}
+
+/// 以指定模式开始游戏（使用默认关卡脚本）
+pub fn start_mode(
+    selected: GameMode,
+    mode: &mut GameMode,
+    config: &mut GameConfig,
+    next_state: &mut NextState<AppState>,
+) {
+    *mode = selected;
+    config.level = LevelScript::load_default();
+    next_state.set(AppState::Playing);
+}
+
+/// 跳过主菜单系统 - 带有--skip-menu参数时首次进入主菜单后直接开始无尽模式（指定了--level时由战役模块处理）
+fn skip_menu_system(
+    options: Res<LaunchOptions>,
+    mut launched: Local<bool>,
+    mut mode: ResMut<GameMode>,
+    mut config: ResMut<GameConfig>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if *launched || !options.skip_menu || options.level.is_some() {
+        return;
+    }
+    *launched = true;
+    start_endless(&mut mode, &mut config, &mut next_state);
+}
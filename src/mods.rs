@@ -0,0 +1,270 @@
+use crate::AppState;
+use crate::registry::asset_path;
+use bevy::asset::io::AssetSourceBuilder;
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+// 模组目录（每个子目录是一个模组包，按目录名顺序加载）
+const MODS_DIR: &str = "mods";
+// 模组目录对应的资源来源（模组文件以mods://<模组包>/<路径>加载）
+const MODS_SOURCE: &str = "mods";
+// 模组包的信息文件
+const MOD_INFO_FILE: &str = "mod.ron";
+
+/// 模组信息 - 模组包中mod.ron记录的名称、版本和作者（均可省略）
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ModInfo {
+    pub name: String,    // 名称（省略时使用目录名）
+    pub version: String, // 版本
+    pub author: String,  // 作者
+}
+
+/// 模组包 - mods目录下的一个子目录，目录结构与assets目录相同
+#[derive(Clone, Debug)]
+pub struct ModPack {
+    pub dir: String,           // 目录名
+    pub info: ModInfo,         // 模组信息
+    pub files: Vec<String>,    // 包中的全部文件（相对模组包目录，以/分隔，已排序）
+    pub error: Option<String>, // mod.ron格式错误的原因（此时使用目录名作为名称）
+}
+
+impl ModPack {
+    /// 显示名称
+    pub fn name(&self) -> &str {
+        if self.info.name.is_empty() {
+            &self.dir
+        } else {
+            &self.info.name
+        }
+    }
+
+    /// 包中是否有该文件（相对assets目录的路径）
+    fn contains(&self, path: &str) -> bool {
+        self.files
+            .binary_search_by(|file| file.as_str().cmp(path))
+            .is_ok()
+    }
+
+    /// 包中文件的加载路径
+    fn load_path(&self, path: &str) -> String {
+        format!("{MODS_SOURCE}://{}/{path}", self.dir)
+    }
+}
+
+/// 模组列表资源 - 启动时扫描到的全部模组包，按目录名排序；模组包中与assets目录同路径的文件覆盖原文件，
+/// 多个模组包有同一文件时后加载的优先
+#[derive(Resource, Clone, Debug, Default)]
+pub struct ModList(pub Vec<ModPack>);
+
+impl ModList {
+    /// 扫描mods目录（目录不存在时没有模组）
+    pub fn scan() -> Self {
+        let Ok(entries) = fs::read_dir(MODS_DIR) else {
+            debug!("没有模组目录{MODS_DIR}");
+            return Self::default();
+        };
+        let mut dirs: Vec<_> = entries
+            .filter_map(Result::ok)
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .collect();
+        dirs.sort();
+
+        let packs = dirs
+            .into_iter()
+            .map(|dir| {
+                let root = Path::new(MODS_DIR).join(&dir);
+                let mut files = Vec::new();
+                list_files(&root, &root, &mut files);
+                files.sort();
+                let (info, error) = match fs::read_to_string(root.join(MOD_INFO_FILE)) {
+                    Ok(content) => match ron::from_str(&content) {
+                        Ok(info) => (info, None),
+                        Err(e) => (ModInfo::default(), Some(e.to_string())),
+                    },
+                    Err(_) => (ModInfo::default(), None),
+                };
+                ModPack {
+                    dir,
+                    info,
+                    files,
+                    error,
+                }
+            })
+            .collect();
+        Self(packs)
+    }
+
+    /// 是否有模组包提供该文件（相对assets目录的路径）
+    pub fn contains(&self, path: &str) -> bool {
+        self.0.iter().any(|pack| pack.contains(path))
+    }
+
+    /// 资源的加载路径：最后一个提供该文件的模组包中的副本，没有模组提供时为assets目录中的原文件
+    pub fn asset_path(&self, path: &str) -> String {
+        self.0
+            .iter()
+            .rev()
+            .find(|pack| pack.contains(path))
+            .map_or_else(|| asset_path(path), |pack| pack.load_path(path))
+    }
+
+    /// 模组包提供的某目录下带指定扩展名的文件（相对assets目录的路径及加载路径，同路径的文件只保留后加载的，按路径排序）
+    pub fn files_in(&self, dir: &str, extension: &str) -> Vec<(String, String)> {
+        let prefix = format!("{dir}/");
+        let suffix = format!(".{extension}");
+        let mut files: Vec<String> = self
+            .0
+            .iter()
+            .flat_map(|pack| pack.files.iter())
+            .filter(|file| file.starts_with(&prefix) && file.ends_with(&suffix))
+            .cloned()
+            .collect();
+        files.sort();
+        files.dedup();
+        files
+            .into_iter()
+            .map(|file| {
+                let path = self.asset_path(&file);
+                (file, path)
+            })
+            .collect()
+    }
+}
+
+/// 递归列出目录下的全部文件（相对模组包根目录，以/分隔，跳过模组信息文件）
+fn list_files(root: &Path, dir: &Path, files: &mut Vec<String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.filter_map(Result::ok) {
+        let path = entry.path();
+        if path.is_dir() {
+            list_files(root, &path, files);
+            continue;
+        }
+        let Ok(relative) = path.strip_prefix(root) else {
+            continue;
+        };
+        let relative: Vec<_> = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect();
+        let relative = relative.join("/");
+        if relative != MOD_INFO_FILE {
+            files.push(relative);
+        }
+    }
+}
+
+/// 模组列表界面根节点标记
+#[derive(Component)]
+struct ModsScreen;
+
+/// 模组插件 - 启动时扫描mods目录并注册mods://资源来源，主菜单按M列出已加载的模组
+/// （资源来源需在资源插件之前注册，因此该插件需在默认插件之前添加）
+pub struct ModsPlugin;
+
+impl Plugin for ModsPlugin {
+    fn build(&self, app: &mut App) {
+        let mods = ModList::scan();
+        // 有模组时注册模组目录资源来源
+        if !mods.0.is_empty() {
+            app.register_asset_source(
+                MODS_SOURCE,
+                AssetSourceBuilder::platform_default(MODS_DIR, None),
+            );
+        }
+        // 插入模组列表资源，启动时记录已加载的模组
+        app.insert_resource(mods)
+            .add_systems(Startup, mods_log_system)
+            // 进入模组列表时创建界面，离开时销毁
+            .add_systems(OnEnter(AppState::Mods), mods_setup_system)
+            .add_systems(OnExit(AppState::Mods), mods_cleanup_system)
+            // Esc返回主菜单
+            .add_systems(Update, mods_input_system.run_if(in_state(AppState::Mods)));
+    }
+}
+
+/// 模组日志系统 - 按加载顺序记录每个模组包（扫描时日志尚未初始化）
+fn mods_log_system(mods: Res<ModList>) {
+    for pack in &mods.0 {
+        if let Some(error) = &pack.error {
+            warn!(pack = %pack.dir, "模组信息格式错误: {error}");
+        }
+        info!(
+            pack = %pack.dir,
+            name = pack.name(),
+            version = %pack.info.version,
+            files = pack.files.len(),
+            "加载模组"
+        );
+    }
+}
+
+/// 模组列表初始化系统 - 按加载顺序列出模组包（后面的覆盖前面的）
+fn mods_setup_system(mut commands: Commands, mods: Res<ModList>) {
+    let mut lines = vec!["MODS".to_string(), String::new()];
+    if mods.0.is_empty() {
+        lines.push(format!("No mods found in {MODS_DIR}/"));
+    }
+    for (i, pack) in mods.0.iter().enumerate() {
+        let mut line = format!("{}. {}", i + 1, pack.name());
+        if !pack.info.version.is_empty() {
+            line.push_str(&format!(" v{}", pack.info.version));
+        }
+        if !pack.info.author.is_empty() {
+            line.push_str(&format!(" by {}", pack.info.author));
+        }
+        line.push_str(&format!("  ({}/, {} files)", pack.dir, pack.files.len()));
+        if pack.error.is_some() {
+            line.push_str("  [invalid mod.ron]");
+        }
+        lines.push(line);
+    }
+    lines.push(String::new());
+    lines.push("Later mods override earlier ones".to_string());
+    lines.push("Esc: back".to_string());
+
+    commands
+        .spawn((
+            ModsScreen,
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            BackgroundColor(Color::srgba(0., 0., 0., 0.9)),
+            GlobalZIndex(10),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(lines.join("\n")),
+                TextFont {
+                    font_size: 20.,
+                    ..Default::default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// 模组列表销毁系统
+fn mods_cleanup_system(mut commands: Commands, query: Query<Entity, With<ModsScreen>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// 模组列表按键系统 - Esc返回主菜单
+fn mods_input_system(kb: Res<ButtonInput<KeyCode>>, mut next_state: ResMut<NextState<AppState>>) {
+    if kb.just_pressed(KeyCode::Escape) {
+        next_state.set(AppState::MainMenu);
+    }
+}
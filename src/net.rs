@@ -0,0 +1,530 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::io::{ErrorKind, Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream, UdpSocket};
+use std::sync::mpsc::{Receiver, TryRecvError, channel};
+use std::time::Duration;
+
+// 联机使用的端口（主机监听，加入码只编码地址）
+pub const NET_PORT: u16 = 47474;
+// 加入时连接主机的超时时间
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+// 加入码使用的字符（去掉了容易混淆的I、O、0、1）
+const CODE_ALPHABET: &[u8; 32] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+// 加入码长度（32位IPv4地址，每个字符5位）
+pub const CODE_LEN: usize = 7;
+// 每个连接待发送数据的上限（字节，对方长时间不接收导致超出时断开连接）
+const OUTGOING_MAX: usize = 256 * 1024;
+// 每个连接尚未凑成完整一行的接收数据上限（字节，对方发送超长的行时断开连接）
+const RECEIVED_MAX: usize = 64 * 1024;
+
+/// 联机消息 - 每条消息序列化为一行RON文本
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum NetMessage {
+    Ready(bool),                         // 客户端切换准备状态
+    Lobby { peers: Vec<LobbyPeer> },     // 主机广播大厅成员（第一个是主机）
+    Start { seed: u64 },                 // 主机开始对局，全部成员以同一随机种子开始
+    Chat { from: String, text: String }, // 聊天消息（客户端发给主机时from为空，由主机填入发送者后转发）
+    Spectate,                            // 客户端以观战身份加入（不参与对局、不发送输入）
+    Snapshot(Snapshot),                  // 主机定期发给观战者的对局状态
+}
+
+/// 大厅成员
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LobbyPeer {
+    pub name: String,    // 显示名
+    pub ready: bool,     // 是否已准备
+    pub spectator: bool, // 是否为观战者
+}
+
+/// 同步给观战者的实体种类
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReplicaKind {
+    Player,      // 主机的战机
+    Enemy,       // 敌人
+    PlayerLaser, // 玩家激光
+    EnemyLaser,  // 敌人激光
+}
+
+/// 对局状态快照 - 主机的得分、剩余生命和各实体的位置
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub score: u64,                             // 主机本局得分
+    pub lives: u32,                             // 主机剩余生命数
+    pub entities: Vec<(ReplicaKind, f32, f32)>, // 实体种类与位置
+}
+
+/// 联机事件 - 每帧统一读取全部连接后发出，大厅、聊天和观战各自处理需要的事件
+#[derive(Event)]
+pub enum NetEvent {
+    Connected,             // 客户端：已连接主机
+    ConnectFailed(String), // 客户端：无法连接主机（加入码）
+    Joined(String),        // 主机：客户端加入（显示名）
+    Left(String),          // 主机：客户端断开（显示名）；客户端：与主机断开（名字为空）
+    Received {
+        from: String, // 主机：发送消息的客户端；客户端：为空（来自主机）
+        message: NetMessage,
+    },
+}
+
+/// 连接 - 非阻塞的TCP连接，按行收发消息（发送的消息先放入发送缓冲区，每帧统一写出，不会阻塞主线程）
+pub struct Connection {
+    stream: TcpStream,
+    received: Vec<u8>, // 尚未凑成完整一行的数据
+    outgoing: Vec<u8>, // 尚未写出的数据
+}
+
+impl Connection {
+    fn new(stream: TcpStream) -> std::io::Result<Self> {
+        stream.set_nonblocking(true)?;
+        stream.set_nodelay(true)?;
+        Ok(Self {
+            stream,
+            received: Vec::new(),
+            outgoing: Vec::new(),
+        })
+    }
+
+    /// 把一条消息放入发送缓冲区（在本帧末尾写出），无法序列化时返回错误
+    pub fn send(&mut self, message: &NetMessage) -> std::io::Result<()> {
+        let line = ron::to_string(message).map_err(std::io::Error::other)?;
+        self.outgoing.extend_from_slice(line.as_bytes());
+        self.outgoing.push(b'\n');
+        Ok(())
+    }
+
+    /// 发送缓冲区中是否还有未写出的数据
+    pub fn backlogged(&self) -> bool {
+        !self.outgoing.is_empty()
+    }
+
+    /// 写出发送缓冲区中对方当前能接收的数据（不等待），连接已断开或缓冲区超过上限时返回错误
+    fn flush(&mut self) -> std::io::Result<()> {
+        while !self.outgoing.is_empty() {
+            match self.stream.write(&self.outgoing) {
+                Ok(0) => return Err(ErrorKind::WriteZero.into()),
+                Ok(n) => {
+                    self.outgoing.drain(..n);
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) => return Err(e),
+            }
+        }
+        if self.outgoing.len() > OUTGOING_MAX {
+            return Err(std::io::Error::other("发送缓冲区超过上限"));
+        }
+        Ok(())
+    }
+
+    /// 读取已到达的全部消息，连接已断开或未凑成一行的数据超过上限时返回错误（格式错误的行只记录警告）
+    pub fn poll(&mut self) -> std::io::Result<Vec<NetMessage>> {
+        let mut buf = [0u8; 4096];
+        let mut messages = Vec::new();
+        loop {
+            match self.stream.read(&mut buf) {
+                Ok(0) => return Err(ErrorKind::ConnectionAborted.into()),
+                Ok(n) => self.received.extend_from_slice(&buf[..n]),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) => return Err(e),
+            }
+            // 每次读取后立即取出完整的行，缓冲区中只留下最后不完整的一行
+            self.take_lines(&mut messages);
+            if self.received.len() > RECEIVED_MAX {
+                return Err(std::io::Error::other("接收缓冲区超过上限"));
+            }
+        }
+        Ok(messages)
+    }
+
+    /// 取出接收缓冲区中全部完整的行并解析为消息
+    fn take_lines(&mut self, messages: &mut Vec<NetMessage>) {
+        while let Some(end) = self.received.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.received.drain(..=end).collect();
+            match std::str::from_utf8(&line)
+                .map_err(|e| e.to_string())
+                .and_then(|line| ron::from_str(line.trim()).map_err(|e| e.to_string()))
+            {
+                Ok(message) => messages.push(message),
+                Err(e) => warn!("无法解析联机消息: {e}"),
+            }
+        }
+    }
+}
+
+/// 主机 - 监听端口并接受客户端连接
+pub struct Host {
+    listener: TcpListener,
+    pub code: String,         // 告诉朋友的加入码
+    pub peers: Vec<HostPeer>, // 已连接的客户端
+    joined: usize,            // 累计加入过的客户端数（用于编号）
+}
+
+/// 主机一侧的客户端
+pub struct HostPeer {
+    pub connection: Connection,
+    pub name: String,    // 显示名
+    pub ready: bool,     // 是否已准备
+    pub spectator: bool, // 是否为观战者
+}
+
+impl Host {
+    /// 在联机端口上开始监听，加入码编码本机的局域网地址
+    pub fn open() -> std::io::Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::UNSPECIFIED, NET_PORT))?;
+        listener.set_nonblocking(true)?;
+        Ok(Self {
+            listener,
+            code: encode_code(local_ip()),
+            peers: Vec::new(),
+            joined: 0,
+        })
+    }
+
+    /// 接受新的连接，返回新客户端的显示名
+    fn accept(&mut self) -> Vec<String> {
+        let mut joined = Vec::new();
+        loop {
+            match self.listener.accept() {
+                Ok((stream, addr)) => match Connection::new(stream) {
+                    Ok(connection) => {
+                        info!(%addr, "客户端加入大厅");
+                        self.joined += 1;
+                        let name = format!("P{}", self.joined + 1);
+                        joined.push(name.clone());
+                        self.peers.push(HostPeer {
+                            connection,
+                            name,
+                            ready: false,
+                            spectator: false,
+                        });
+                    }
+                    Err(e) => warn!("无法接受客户端连接: {e}"),
+                },
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) => {
+                    warn!("接受客户端连接失败: {e}");
+                    break;
+                }
+            }
+        }
+        joined
+    }
+
+    /// 向全部客户端发送消息（连接断开的客户端在写出或读取时发现并移除）
+    pub fn broadcast(&mut self, message: &NetMessage) {
+        self.send_where(message, |_| true);
+    }
+
+    /// 只向观战者发送消息（上一条消息还没有写完的观战者跳过本条，积压时不会越积越多）
+    pub fn broadcast_spectators(&mut self, message: &NetMessage) {
+        self.send_where(message, |peer| {
+            peer.spectator && !peer.connection.backlogged()
+        });
+    }
+
+    fn send_where(&mut self, message: &NetMessage, filter: impl Fn(&HostPeer) -> bool) {
+        for peer in self.peers.iter_mut().filter(|peer| filter(peer)) {
+            if let Err(e) = peer.connection.send(message) {
+                debug!(name = %peer.name, "发送联机消息失败: {e}");
+            }
+        }
+    }
+
+    /// 按显示名查找客户端
+    pub fn peer_mut(&mut self, name: &str) -> Option<&mut HostPeer> {
+        self.peers.iter_mut().find(|peer| peer.name == name)
+    }
+
+    /// 参与对局的客户端是否都已准备（观战者不需要准备）
+    pub fn all_ready(&self) -> bool {
+        self.peers
+            .iter()
+            .filter(|peer| !peer.spectator)
+            .all(|peer| peer.ready)
+    }
+
+    /// 是否有观战者
+    pub fn has_spectators(&self) -> bool {
+        self.peers.iter().any(|peer| peer.spectator)
+    }
+
+    /// 大厅成员列表（主机在最前，始终视为已准备）
+    pub fn lobby(&self) -> Vec<LobbyPeer> {
+        let host = LobbyPeer {
+            name: "P1 (host)".to_string(),
+            ready: true,
+            spectator: false,
+        };
+        std::iter::once(host)
+            .chain(self.peers.iter().map(|peer| LobbyPeer {
+                name: peer.name.clone(),
+                ready: peer.ready,
+                spectator: peer.spectator,
+            }))
+            .collect()
+    }
+}
+
+/// 客户端 - 与主机的连接和主机广播的大厅状态
+pub struct Client {
+    pub connection: Connection,
+    pub peers: Vec<LobbyPeer>, // 主机广播的大厅成员
+    pub ready: bool,           // 本机是否已准备
+}
+
+impl Client {
+    /// 按加入码开始连接主机（加入码无效时返回None）；连接在后台线程中进行，不阻塞主线程，
+    /// 联机读取系统取回结果后发送连接成功或失败事件
+    pub fn join(code: &str) -> Option<Joining> {
+        let ip = decode_code(code)?;
+        let addr = SocketAddr::from((ip, NET_PORT));
+        let (sender, result) = channel();
+        std::thread::spawn(move || {
+            // 放弃加入（会话已丢弃）时接收端已关闭，连接随之关闭
+            let _ = sender.send(TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT));
+        });
+        info!(%addr, "正在连接大厅");
+        Some(Joining {
+            code: code.to_string(),
+            result,
+        })
+    }
+
+    /// 用连接成功的TCP连接创建客户端
+    fn connected(stream: std::io::Result<TcpStream>) -> std::io::Result<Self> {
+        Ok(Self {
+            connection: Connection::new(stream?)?,
+            peers: Vec::new(),
+            ready: false,
+        })
+    }
+}
+
+/// 正在加入 - 后台线程正在连接主机
+pub struct Joining {
+    pub code: String,                             // 输入的加入码
+    result: Receiver<std::io::Result<TcpStream>>, // 连接结果
+}
+
+/// 联机会话资源 - 离线、作为主机或作为客户端
+#[derive(Resource, Default)]
+pub enum NetSession {
+    #[default]
+    Offline,
+    Joining(Joining),
+    Host(Host),
+    Client(Client),
+}
+
+/// 联机网络插件 - 管理联机会话，每帧接受新连接并读取全部连接的消息，转换为联机事件，
+/// 并在各模块发送完本帧的消息后写出全部连接的发送缓冲区
+pub struct NetPlugin;
+
+impl Plugin for NetPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NetSession>()
+            .add_event::<NetEvent>()
+            // 在各模块处理联机事件之前读取全部连接
+            .add_systems(PreUpdate, net_receive_system)
+            // 在各模块发送消息之后写出发送缓冲区
+            .add_systems(PostUpdate, net_flush_system);
+    }
+}
+
+/// 联机读取系统 - 正在加入时取回连接结果；主机接受新连接、读取各客户端的消息并移除断开的客户端；
+/// 客户端读取主机的消息，与主机断开时回到离线状态
+fn net_receive_system(mut session: ResMut<NetSession>, mut events: EventWriter<NetEvent>) {
+    match &mut *session {
+        NetSession::Offline => {}
+        NetSession::Joining(joining) => {
+            let stream = match joining.result.try_recv() {
+                Ok(stream) => stream,
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => Err(ErrorKind::ConnectionAborted.into()),
+            };
+            let code = joining.code.clone();
+            match Client::connected(stream) {
+                Ok(client) => {
+                    info!(%code, "加入大厅");
+                    *session = NetSession::Client(client);
+                    events.send(NetEvent::Connected);
+                }
+                Err(e) => {
+                    warn!(%code, "加入大厅失败: {e}");
+                    *session = NetSession::Offline;
+                    events.send(NetEvent::ConnectFailed(code));
+                }
+            }
+        }
+        NetSession::Host(host) => {
+            for name in host.accept() {
+                events.send(NetEvent::Joined(name));
+            }
+            host.peers.retain_mut(|peer| match peer.connection.poll() {
+                Ok(messages) => {
+                    for message in messages {
+                        // 观战请求属于会话状态，在这里直接记录（对局中途也可以加入观战）
+                        if matches!(message, NetMessage::Spectate) {
+                            info!(name = %peer.name, "客户端开始观战");
+                            peer.spectator = true;
+                        }
+                        events.send(NetEvent::Received {
+                            from: peer.name.clone(),
+                            message,
+                        });
+                    }
+                    true
+                }
+                Err(e) => {
+                    info!(name = %peer.name, "客户端断开: {e}");
+                    events.send(NetEvent::Left(peer.name.clone()));
+                    false
+                }
+            });
+        }
+        NetSession::Client(client) => match client.connection.poll() {
+            Ok(messages) => {
+                for message in messages {
+                    events.send(NetEvent::Received {
+                        from: String::new(),
+                        message,
+                    });
+                }
+            }
+            Err(e) => {
+                info!("与主机断开: {e}");
+                *session = NetSession::Offline;
+                events.send(NetEvent::Left(String::new()));
+            }
+        },
+    }
+}
+
+/// 联机写出系统 - 非阻塞地写出各连接的发送缓冲区，写出失败或对方长时间不接收（缓冲区超过上限）时断开连接
+fn net_flush_system(mut session: ResMut<NetSession>, mut events: EventWriter<NetEvent>) {
+    match &mut *session {
+        NetSession::Offline | NetSession::Joining(_) => {}
+        NetSession::Host(host) => {
+            host.peers.retain_mut(|peer| match peer.connection.flush() {
+                Ok(()) => true,
+                Err(e) => {
+                    info!(name = %peer.name, "断开客户端: {e}");
+                    events.send(NetEvent::Left(peer.name.clone()));
+                    false
+                }
+            });
+        }
+        NetSession::Client(client) => {
+            if let Err(e) = client.connection.flush() {
+                info!("与主机断开: {e}");
+                *session = NetSession::Offline;
+                events.send(NetEvent::Left(String::new()));
+            }
+        }
+    }
+}
+
+/// 本机的局域网地址（通过UDP套接字选择出口地址，不会真正发送数据；失败时使用本地回环地址）
+fn local_ip() -> Ipv4Addr {
+    let ip = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+        .and_then(|socket| {
+            socket.connect((Ipv4Addr::new(8, 8, 8, 8), 80))?;
+            socket.local_addr()
+        })
+        .map(|addr| addr.ip());
+    match ip {
+        Ok(std::net::IpAddr::V4(ip)) => ip,
+        _ => Ipv4Addr::LOCALHOST,
+    }
+}
+
+/// 把IPv4地址编码为加入码
+pub fn encode_code(ip: Ipv4Addr) -> String {
+    let bits = u32::from(ip) as u64;
+    (0..CODE_LEN)
+        .rev()
+        .map(|i| CODE_ALPHABET[((bits >> (i * 5)) & 0x1f) as usize] as char)
+        .collect()
+}
+
+/// 把加入码解码为IPv4地址（不区分大小写，长度或字符无效时返回None）
+pub fn decode_code(code: &str) -> Option<Ipv4Addr> {
+    if code.len() != CODE_LEN {
+        return None;
+    }
+    let mut bits = 0u64;
+    for c in code.bytes() {
+        let value = CODE_ALPHABET
+            .iter()
+            .position(|&a| a == c.to_ascii_uppercase())?;
+        bits = (bits << 5) | value as u64;
+    }
+    u32::try_from(bits).ok().map(Ipv4Addr::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn code_round_trip() {
+        for ip in [
+            Ipv4Addr::LOCALHOST,
+            Ipv4Addr::new(192, 168, 1, 23),
+            Ipv4Addr::new(255, 255, 255, 255),
+        ] {
+            let code = encode_code(ip);
+            assert_eq!(code.len(), CODE_LEN);
+            assert_eq!(decode_code(&code), Some(ip));
+            assert_eq!(decode_code(&code.to_lowercase()), Some(ip));
+        }
+    }
+
+    /// 建立一对本地回环连接（发送端为阻塞的TCP连接）
+    fn local_pair() -> (TcpStream, Connection) {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let sender = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let receiver = Connection::new(listener.accept().unwrap().0).unwrap();
+        (sender, receiver)
+    }
+
+    /// 反复读取直到收到指定条数的消息或读取失败（数据可能分多次到达）
+    fn poll_until(connection: &mut Connection, count: usize) -> std::io::Result<Vec<NetMessage>> {
+        let mut messages = Vec::new();
+        for _ in 0..100 {
+            messages.extend(connection.poll()?);
+            if messages.len() >= count {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        Ok(messages)
+    }
+
+    #[test]
+    fn messages_arrive_line_by_line() {
+        let (mut sender, mut receiver) = local_pair();
+        sender.write_all(b"Ready(true)\nSpectate\n").unwrap();
+
+        let messages = poll_until(&mut receiver, 2).unwrap();
+        assert!(matches!(
+            messages[..],
+            [NetMessage::Ready(true), NetMessage::Spectate]
+        ));
+    }
+
+    #[test]
+    fn overlong_line_closes_connection() {
+        let (mut sender, mut receiver) = local_pair();
+        sender.write_all(&vec![b'a'; RECEIVED_MAX + 1]).unwrap();
+
+        assert!(poll_until(&mut receiver, 1).is_err());
+    }
+
+    #[test]
+    fn invalid_codes_are_rejected() {
+        assert_eq!(decode_code("ABC"), None);
+        assert_eq!(decode_code("ABCDEF0"), None); // 0不在字符表中
+        assert_eq!(decode_code("ZZZZZZZ"), None); // 超出32位
+    }
+}
@@ -0,0 +1,237 @@
+use crate::AppState;
+use crate::settings::{
+    FrameCap, GraphicsQuality, RESOLUTIONS, Settings, UI_SCALE_MAX, UI_SCALE_MIN,
+};
+use bevy::prelude::*;
+
+// 界面缩放每次调整的步长
+const UI_SCALE_STEP: f32 = 0.25;
+// 音效音量每次调整的步长
+const SFX_VOLUME_STEP: f32 = 0.1;
+
+/// 选项界面中的一行
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum OptionRow {
+    UiScale,    // 界面缩放
+    Resolution, // 窗口分辨率
+    Fullscreen, // 全屏
+    VSync,      // 垂直同步
+    FrameCap,   // 帧率上限
+    Graphics,   // 画质
+    SfxVolume,  // 音效音量
+}
+
+impl OptionRow {
+    const ALL: [OptionRow; 7] = [
+        OptionRow::UiScale,
+        OptionRow::Resolution,
+        OptionRow::Fullscreen,
+        OptionRow::VSync,
+        OptionRow::FrameCap,
+        OptionRow::Graphics,
+        OptionRow::SfxVolume,
+    ];
+
+    /// 显示名称
+    fn label(self) -> &'static str {
+        match self {
+            OptionRow::UiScale => "UI scale",
+            OptionRow::Resolution => "Resolution",
+            OptionRow::Fullscreen => "Fullscreen",
+            OptionRow::VSync => "VSync",
+            OptionRow::FrameCap => "FPS cap",
+            OptionRow::Graphics => "Graphics",
+            OptionRow::SfxVolume => "SFX volume",
+        }
+    }
+
+    /// 当前取值的显示文本
+    fn value(self, settings: &Settings) -> String {
+        match self {
+            OptionRow::UiScale => format!("{:.0}%", settings.ui_scale * 100.),
+            OptionRow::Resolution => {
+                let (w, h) = settings.resolution;
+                format!("{w}x{h}")
+            }
+            OptionRow::Fullscreen => on_off(settings.fullscreen),
+            OptionRow::VSync => on_off(settings.vsync),
+            OptionRow::FrameCap => match settings.frame_cap {
+                FrameCap::Unlimited => "Unlimited".to_string(),
+                FrameCap::Fps60 => "60".to_string(),
+                FrameCap::Fps120 => "120".to_string(),
+            },
+            OptionRow::Graphics => match settings.graphics {
+                GraphicsQuality::High => "High".to_string(),
+                GraphicsQuality::Low => "Low".to_string(),
+            },
+            OptionRow::SfxVolume => format!("{:.0}%", settings.sfx_volume * 100.),
+        }
+    }
+
+    /// 按左右方向调整取值（direction为-1或1）
+    fn step(self, settings: &mut Settings, direction: f32) {
+        match self {
+            OptionRow::UiScale => {
+                settings.ui_scale = (settings.ui_scale + direction * UI_SCALE_STEP)
+                    .clamp(UI_SCALE_MIN, UI_SCALE_MAX);
+            }
+            OptionRow::Resolution => {
+                // 不在列表中的分辨率（如手动编辑的设置文件）从列表开头切换
+                let current = RESOLUTIONS
+                    .iter()
+                    .position(|&resolution| resolution == settings.resolution);
+                let count = RESOLUTIONS.len();
+                let next = match current {
+                    Some(i) if direction > 0. => (i + 1) % count,
+                    Some(i) => (i + count - 1) % count,
+                    None => 0,
+                };
+                settings.resolution = RESOLUTIONS[next];
+            }
+            OptionRow::Fullscreen => settings.fullscreen = !settings.fullscreen,
+            OptionRow::VSync => settings.vsync = !settings.vsync,
+            // 只有三档，左右都按同一方向循环
+            OptionRow::FrameCap => settings.frame_cap = settings.frame_cap.next(),
+            OptionRow::Graphics => {
+                settings.graphics = match settings.graphics {
+                    GraphicsQuality::High => GraphicsQuality::Low,
+                    GraphicsQuality::Low => GraphicsQuality::High,
+                };
+            }
+            OptionRow::SfxVolume => {
+                settings.sfx_volume =
+                    (settings.sfx_volume + direction * SFX_VOLUME_STEP).clamp(0., 1.);
+            }
+        }
+    }
+}
+
+/// 开关选项的显示文本
+fn on_off(on: bool) -> String {
+    if on { "On" } else { "Off" }.to_string()
+}
+
+/// 选项界面根节点标记
+#[derive(Component)]
+struct OptionsScreen;
+
+/// 选项界面文本标记
+#[derive(Component)]
+struct OptionsText;
+
+/// 选项光标资源 - 当前选中的行
+#[derive(Resource, Default)]
+struct OptionsCursor(usize);
+
+/// 选项插件 - 从主菜单进入的选项界面，上下选择、左右调整显示和音效相关的设置，修改立即生效并写入设置文件
+pub struct OptionsPlugin;
+
+impl Plugin for OptionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<OptionsCursor>()
+            // 进入选项界面时创建界面
+            .add_systems(OnEnter(AppState::Options), options_setup_system)
+            // 离开选项界面时销毁界面
+            .add_systems(OnExit(AppState::Options), options_cleanup_system)
+            // 处理按键并刷新界面文本
+            .add_systems(
+                Update,
+                (options_input_system, options_text_system)
+                    .chain()
+                    .run_if(in_state(AppState::Options)),
+            );
+    }
+}
+
+/// 选项界面初始化系统
+fn options_setup_system(mut commands: Commands, mut cursor: ResMut<OptionsCursor>) {
+    cursor.0 = 0;
+    commands
+        .spawn((
+            OptionsScreen,
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            BackgroundColor(Color::srgba(0., 0., 0., 0.9)),
+            GlobalZIndex(10),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                OptionsText,
+                Text::new(""),
+                TextFont {
+                    font_size: 20.,
+                    ..Default::default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// 选项界面销毁系统
+fn options_cleanup_system(mut commands: Commands, query: Query<Entity, With<OptionsScreen>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// 选项按键系统 - 上下键选择，左右键调整，Esc返回主菜单
+fn options_input_system(
+    kb: Res<ButtonInput<KeyCode>>,
+    mut cursor: ResMut<OptionsCursor>,
+    mut settings: ResMut<Settings>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let rows = OptionRow::ALL.len();
+    if kb.just_pressed(KeyCode::ArrowUp) {
+        cursor.0 = (cursor.0 + rows - 1) % rows;
+    }
+    if kb.just_pressed(KeyCode::ArrowDown) {
+        cursor.0 = (cursor.0 + 1) % rows;
+    }
+
+    let row = OptionRow::ALL[cursor.0];
+    if kb.just_pressed(KeyCode::ArrowLeft) {
+        row.step(&mut settings, -1.);
+    }
+    if kb.just_pressed(KeyCode::ArrowRight) {
+        row.step(&mut settings, 1.);
+    }
+
+    if kb.just_pressed(KeyCode::Escape) {
+        next_state.set(AppState::MainMenu);
+    }
+}
+
+/// 选项文本系统 - 界面刚创建、光标移动或设置变化时列出全部选项，选中的行前加箭头
+fn options_text_system(
+    cursor: Res<OptionsCursor>,
+    settings: Res<Settings>,
+    mut query: Query<(&mut Text, Ref<OptionsText>)>,
+) {
+    for (mut text, marker) in &mut query {
+        if !cursor.is_changed() && !settings.is_changed() && !marker.is_added() {
+            continue;
+        }
+
+        let mut lines = vec!["OPTIONS".to_string(), String::new()];
+        for (i, row) in OptionRow::ALL.iter().enumerate() {
+            let arrow = if i == cursor.0 { ">" } else { " " };
+            lines.push(format!(
+                "{arrow} {:<14} < {} >",
+                row.label(),
+                row.value(&settings)
+            ));
+        }
+        lines.push(String::new());
+        lines.push("UP/DOWN: select   LEFT/RIGHT: change".to_string());
+        lines.push("ESC: back".to_string());
+
+        text.0 = lines.join("\n");
+    }
+}
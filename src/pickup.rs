@@ -0,0 +1,343 @@
+use crate::collision::sprites_collide;
+use crate::combat::{EntityKilledEvent, death_system};
+use crate::components::{Enemy, Health, InRun, Movable, Player, SpriteSize, Velocity};
+use crate::drops::DropTables;
+use crate::faction_time::{TIME_SLOW_SECS, TimeSlow};
+use crate::player::Invincible;
+use crate::registry::{AssetRegistry, Sfx, Tex};
+use crate::rng::GameRng;
+use crate::shop::Scrap;
+use crate::sim::RollbackAppExt;
+use crate::sound::PlaySound;
+use crate::status::{StatusKind, inflict};
+use crate::weapon::{Ammo, SecondaryWeapon, TORPEDO_AMMO};
+use crate::{GameSet, WinSize};
+use bevy::prelude::*;
+use serde::Deserialize;
+
+// 废料拾取物的尺寸
+const SCRAP_SIZE: (f32, f32) = (12., 12.);
+// 废料拾取物的颜色
+const SCRAP_COLOR: Color = Color::srgb(1., 0.8, 0.2);
+// 弹药补给拾取物的尺寸
+const AMMO_SIZE: (f32, f32) = (14., 14.);
+// 弹药补给拾取物的颜色
+const AMMO_COLOR: Color = Color::srgb(0.4, 1., 0.4);
+// 维修包拾取物的尺寸
+const REPAIR_SIZE: (f32, f32) = (16., 16.);
+// 维修包恢复的生命值
+const REPAIR_HP: f32 = 1.;
+// 强化道具拾取物的尺寸
+const POWER_UP_SIZE: (f32, f32) = (16., 16.);
+// 强化道具的持续时间（秒）
+pub const POWER_UP_SECS: f32 = 8.;
+// 连射强化下开火冷却的推进倍率
+pub const RAPID_FIRE_RATE: u32 = 2;
+// 电磁脉冲拾取物的尺寸
+const EMP_SIZE: (f32, f32) = (16., 16.);
+// 电磁脉冲拾取物的颜色
+const EMP_COLOR: Color = Color::srgb(0.3, 0.5, 1.);
+// 电磁脉冲使敌人眩晕的时长（秒）
+const EMP_STUN_SECS: f32 = 3.;
+// 电磁脉冲全屏闪光的颜色
+const EMP_FLASH_COLOR: Color = Color::srgb(0.4, 0.6, 1.);
+// 电磁脉冲全屏闪光的初始不透明度
+const EMP_FLASH_ALPHA: f32 = 0.6;
+// 电磁脉冲全屏闪光的持续时间（秒）
+const EMP_FLASH_SECS: f32 = 0.5;
+// 磁铁的吸引半径
+const MAGNET_RADIUS: f32 = 200.;
+// 磁铁吸引的速度（速度单位）
+const MAGNET_SPEED: f32 = 1.2;
+
+/// 强化道具种类
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+pub enum PowerUpKind {
+    RapidFire, // 连射：开火冷却减半
+    Shield,    // 护盾：一段时间内无敌
+    Magnet,    // 磁铁：吸引附近的拾取物
+    TimeSlow,  // 时间减缓：敌人和敌人激光减速到40%
+}
+
+impl PowerUpKind {
+    pub const ALL: [PowerUpKind; 4] = [
+        PowerUpKind::RapidFire,
+        PowerUpKind::Shield,
+        PowerUpKind::Magnet,
+        PowerUpKind::TimeSlow,
+    ];
+
+    /// 拾取物和HUD图标的颜色
+    pub fn color(self) -> Color {
+        match self {
+            PowerUpKind::RapidFire => Color::srgb(1., 0.4, 0.2),
+            PowerUpKind::Shield => Color::srgb(0.3, 0.9, 1.),
+            PowerUpKind::Magnet => Color::srgb(0.9, 0.3, 0.9),
+            PowerUpKind::TimeSlow => Color::srgb(0.9, 0.9, 0.9),
+        }
+    }
+}
+
+/// 拾取物种类
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub enum PickupKind {
+    Scrap(u32),           // 废料货币（数量）
+    PowerUp(PowerUpKind), // 限时强化道具
+    Ammo,                 // 弹药补给（特殊武器补充一半弹药，副武器补充一发）
+    Repair,               // 维修包（恢复1点生命值，不超过上限）
+    Emp,                  // 电磁脉冲（拾取时眩晕全部敌人3秒）
+}
+
+/// 拾取物组件 - 标记可被玩家收集的掉落物
+#[derive(Component, Clone)]
+#[require(InRun)]
+pub struct Pickup(pub PickupKind);
+
+/// 拾取事件 - 玩家收集拾取物时发送（用于事件日志等）
+#[derive(Event, Clone, Copy, Debug)]
+pub struct PickupCollectedEvent(pub PickupKind);
+
+/// 连射组件 - 计时结束前开火冷却以倍速推进
+#[derive(Component, Clone)]
+pub struct RapidFire {
+    pub timer: Timer,
+}
+
+/// 护盾强化组件 - 记录护盾强化的剩余时间（无敌由同时插入的无敌组件提供，与出生无敌区分开，供HUD显示）
+#[derive(Component, Clone)]
+pub struct ShieldPowerUp {
+    pub timer: Timer,
+}
+
+/// 磁铁组件 - 计时结束前吸引附近的拾取物
+#[derive(Component, Clone)]
+pub struct Magnet {
+    pub timer: Timer,
+}
+
+/// 电磁脉冲闪光组件 - 覆盖全屏的蓝色闪光，计时结束前逐渐淡出
+#[derive(Component, Clone)]
+struct EmpFlash(Timer);
+
+/// 拾取物插件 - 管理敌人掉落、玩家收集和限时强化
+pub struct PickupPlugin;
+
+impl Plugin for PickupPlugin {
+    fn build(&self, app: &mut App) {
+        // 登记参与快照回滚的组件
+        app.rollback_component::<Pickup>()
+            .rollback_component::<RapidFire>()
+            .rollback_component::<ShieldPowerUp>()
+            .rollback_component::<Magnet>()
+            .rollback_component::<EmpFlash>();
+
+        // 读取掉落表
+        app.insert_resource(DropTables::load())
+            .add_event::<PickupCollectedEvent>() // 注册拾取事件
+            // 磁铁吸引拾取物，推进限时强化的计时器
+            .add_systems(
+                FixedUpdate,
+                (pickup_magnet_system, power_up_timer_system).in_set(GameSet::Movement),
+            )
+            // 玩家接触拾取物时收集
+            .add_systems(
+                FixedUpdate,
+                pickup_collect_system.in_set(GameSet::Collision),
+            )
+            // 敌人击毁处按掉落表生成掉落物（在伤害结算之后）
+            .add_systems(
+                FixedUpdate,
+                pickup_drop_system
+                    .after(death_system)
+                    .in_set(GameSet::Spawn),
+            )
+            // 电磁脉冲闪光淡出
+            .add_systems(Update, emp_flash_system.in_set(GameSet::Presentation));
+    }
+}
+
+/// 掉落系统 - 敌人被击毁时按其种类的掉落表在爆炸位置生成缓慢下落的掉落物
+fn pickup_drop_system(
+    mut commands: Commands,
+    drop_tables: Res<DropTables>,
+    registry: Res<AssetRegistry>,
+    mut rng: ResMut<GameRng>,
+    mut events: EventReader<EntityKilledEvent>,
+) {
+    for EntityKilledEvent { position, kind, .. } in events.read() {
+        let Some(item) = drop_tables.roll(*kind, &mut rng) else {
+            continue;
+        };
+
+        let (sprite, size) = match item {
+            PickupKind::Scrap(_) => (
+                Sprite::from_color(SCRAP_COLOR, Vec2::from(SCRAP_SIZE)),
+                SCRAP_SIZE,
+            ),
+            PickupKind::PowerUp(power_up) => (
+                Sprite::from_color(power_up.color(), Vec2::from(POWER_UP_SIZE)),
+                POWER_UP_SIZE,
+            ),
+            PickupKind::Ammo => (
+                Sprite::from_color(AMMO_COLOR, Vec2::from(AMMO_SIZE)),
+                AMMO_SIZE,
+            ),
+            PickupKind::Emp => (
+                Sprite::from_color(EMP_COLOR, Vec2::from(EMP_SIZE)),
+                EMP_SIZE,
+            ),
+            // 维修包使用独立的十字精灵
+            PickupKind::Repair => (registry.sprite(Tex::Repair), REPAIR_SIZE),
+        };
+        commands
+            .spawn((sprite, Transform::from_translation(position.with_z(5.))))
+            .insert(Pickup(item)) // 标记为拾取物
+            .insert(SpriteSize::from(size)) // 设置碰撞尺寸
+            .insert(Movable { auto_despawn: true }) // 超出屏幕自动销毁
+            .insert(Velocity { x: 0., y: -0.3 }); // 缓慢下落
+    }
+}
+
+/// 收集系统 - 玩家碰到拾取物时收集（废料计入货币，强化道具开始计时，弹药补给补充弹药，维修包恢复生命值，电磁脉冲眩晕全部敌人）并销毁拾取物
+#[allow(clippy::type_complexity)] // 允许复杂的查询类型
+fn pickup_collect_system(
+    mut commands: Commands,
+    mut scrap: ResMut<Scrap>,
+    win_size: Res<WinSize>,
+    mut events: EventWriter<PickupCollectedEvent>,
+    mut sounds: EventWriter<PlaySound>,
+    pickup_query: Query<(Entity, &Transform, &SpriteSize, &Pickup)>,
+    mut player_query: Query<
+        (
+            Entity,
+            &Transform,
+            &SpriteSize,
+            &mut Ammo,
+            &mut SecondaryWeapon,
+            &mut Health,
+        ),
+        With<Player>,
+    >,
+    enemy_query: Query<Entity, With<Enemy>>,
+) {
+    let Ok((player_entity, player_tf, player_size, mut ammo, mut secondary, mut health)) =
+        player_query.get_single_mut()
+    else {
+        return;
+    };
+
+    for (entity, pickup_tf, pickup_size, pickup) in pickup_query.iter() {
+        if sprites_collide(pickup_tf, pickup_size, player_tf, player_size) {
+            let timer = Timer::from_seconds(POWER_UP_SECS, TimerMode::Once);
+            // 重复拾取同一种强化时重新开始计时
+            match pickup.0 {
+                PickupKind::Scrap(amount) => scrap.0 += amount,
+                PickupKind::PowerUp(PowerUpKind::RapidFire) => {
+                    commands.entity(player_entity).insert(RapidFire { timer });
+                }
+                PickupKind::PowerUp(PowerUpKind::Shield) => {
+                    commands.entity(player_entity).insert((
+                        Invincible {
+                            timer: timer.clone(),
+                        },
+                        ShieldPowerUp { timer },
+                    ));
+                }
+                PickupKind::PowerUp(PowerUpKind::Magnet) => {
+                    commands.entity(player_entity).insert(Magnet { timer });
+                }
+                PickupKind::PowerUp(PowerUpKind::TimeSlow) => {
+                    commands.entity(player_entity).insert(TimeSlow {
+                        timer: Timer::from_seconds(TIME_SLOW_SECS, TimerMode::Once),
+                    });
+                }
+                PickupKind::Ammo => {
+                    ammo.refill();
+                    secondary.ammo = (secondary.ammo + 1).min(TORPEDO_AMMO);
+                }
+                PickupKind::Repair => {
+                    health.current = (health.current + REPAIR_HP).min(health.max);
+                    sounds.send(PlaySound::at(Sfx::Repair, player_tf.translation));
+                }
+                PickupKind::Emp => {
+                    for enemy in &enemy_query {
+                        commands
+                            .entity(enemy)
+                            .queue(inflict(StatusKind::Stun, EMP_STUN_SECS));
+                    }
+                    commands.spawn((
+                        Sprite::from_color(
+                            EMP_FLASH_COLOR.with_alpha(EMP_FLASH_ALPHA),
+                            Vec2::new(win_size.w, win_size.h),
+                        ),
+                        Transform::from_xyz(0., 0., 40.), // 覆盖在全部游戏实体上方
+                        EmpFlash(Timer::from_seconds(EMP_FLASH_SECS, TimerMode::Once)),
+                    ));
+                }
+            }
+            commands.entity(entity).despawn();
+            events.send(PickupCollectedEvent(pickup.0));
+        }
+    }
+}
+
+/// 磁铁系统 - 玩家持有磁铁时，吸引半径内的拾取物飞向玩家
+fn pickup_magnet_system(
+    player_query: Query<&Transform, (With<Player>, With<Magnet>)>,
+    mut pickup_query: Query<(&Transform, &mut Velocity), With<Pickup>>,
+) {
+    let Ok(player_tf) = player_query.get_single() else {
+        return;
+    };
+    let player_pos = player_tf.translation.truncate();
+
+    for (pickup_tf, mut velocity) in &mut pickup_query {
+        let offset = player_pos - pickup_tf.translation.truncate();
+        if offset.length() < MAGNET_RADIUS {
+            let direction = offset.normalize_or_zero() * MAGNET_SPEED;
+            velocity.x = direction.x;
+            velocity.y = direction.y;
+        }
+    }
+}
+
+/// 限时强化计时系统 - 推进连射、护盾和磁铁的计时器，结束后移除组件（护盾的无敌计时由玩家模块处理，时间减缓由阵营时间模块处理）
+fn power_up_timer_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut rapid_fire_query: Query<(Entity, &mut RapidFire)>,
+    mut shield_query: Query<(Entity, &mut ShieldPowerUp)>,
+    mut magnet_query: Query<(Entity, &mut Magnet)>,
+) {
+    for (entity, mut rapid_fire) in &mut rapid_fire_query {
+        if rapid_fire.timer.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<RapidFire>();
+        }
+    }
+    for (entity, mut shield) in &mut shield_query {
+        if shield.timer.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<ShieldPowerUp>();
+        }
+    }
+    for (entity, mut magnet) in &mut magnet_query {
+        if magnet.timer.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<Magnet>();
+        }
+    }
+}
+
+/// 电磁脉冲闪光系统 - 推进闪光计时器并逐渐淡出，结束后销毁
+fn emp_flash_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut EmpFlash, &mut Sprite)>,
+) {
+    for (entity, mut flash, mut sprite) in &mut query {
+        flash.0.tick(time.delta());
+        if flash.0.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        sprite.color = EMP_FLASH_COLOR.with_alpha(EMP_FLASH_ALPHA * (1. - flash.0.fraction()));
+    }
+}
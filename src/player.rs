@@ -1,21 +1,122 @@
-use crate::components::{FromPlayer, Laser, Movable, Player, SpriteSize, Velocity};
+use crate::action::Action;
+use crate::combat::DamageEvent;
+use crate::components::{
+    DamageType, Enemy, FireCooldown, Health, Laser, Movable, MoveSpeed, Player, SpriteSize, Team,
+    Velocity,
+};
+use crate::cutscene::InputLock;
+use crate::pickup::{RAPID_FIRE_RATE, RapidFire};
+use crate::pool::{BulletPool, recycle};
+use crate::profile::Profile;
+use crate::registry::{AssetRegistry, Sfx, Tex};
+use crate::settings::{AutoFire, Settings};
+use crate::shop::RunUpgrades;
+use crate::sim::RollbackAppExt;
+use crate::sim::TickInput;
+use crate::skills::SkillNode;
+use crate::sound::PlaySound;
+use crate::status::{Inflicts, StatusKind};
+use crate::unlocks::{ShipSkin, StartPowerUp, UnlockItem};
+use crate::weapon::{
+    Ammo, BURN_SECS, Blast, HeavyWeapon, Homing, Loadout, SecondaryWeapon, TORPEDO_BLAST_RADIUS,
+    TORPEDO_SPEED,
+};
 use crate::{
-    GameTextures, PLAYER_LASER_SIZE, PLAYER_RESPAWN_DELAY, PLAYER_SIZE, PlayerState, SPRITE_SCALE,
-    WinSize,
+    AppState, GameSet, GameStats, PLAYER_HEALTH, PLAYER_LASER_SIZE, PLAYER_RESPAWN_DELAY,
+    PLAYER_SIZE, PlayerState, SPRITE_SCALE, WinSize, laser_hit_enemy_system, movable_system,
 };
 
 // 玩家移动速度常量
 pub const PLAYER_SPEED: f32 = 1.0;
+// 冲刺相关常量
+pub const PLAYER_DASH_SPEED: f32 = 3.0; // 冲刺时的速度倍率
+pub const PLAYER_DASH_DURATION: f32 = 0.15; // 冲刺持续时间（秒）
+pub const PLAYER_DASH_COOLDOWN: f32 = 1.0; // 冲刺冷却时间（秒）
+// 出生无敌时间（秒）
+pub const PLAYER_INVINCIBLE_SECS: f32 = 2.0;
+// 解锁出生护盾后的出生无敌时间（秒）
+pub const PLAYER_SHIELD_INVINCIBLE_SECS: f32 = 4.0;
+// 导弹的颜色
+const MISSILE_COLOR: Color = Color::srgb(1., 0.6, 0.2);
+// 重型鱼雷的颜色
+const TORPEDO_COLOR: Color = Color::srgb(1., 0.25, 0.2);
+// 输入缓冲时间窗口（秒）
+pub const INPUT_BUFFER_WINDOW: f64 = 0.1;
 use bevy::prelude::*;
 use bevy::time::common_conditions::on_timer;
 use std::time::Duration;
 
 /// 无敌状态组件
-#[derive(Component)]
+#[derive(Component, Clone)]
 pub struct Invincible {
     pub timer: Timer,
 }
 
+/// 护盾组件 - 抵挡一次敌人激光伤害后移除
+#[derive(Component, Clone)]
+pub struct Shield;
+
+/// 冲刺组件 - 记录冲刺的持续时间与冷却
+#[derive(Component, Clone)]
+pub struct Dash {
+    pub active: Timer,   // 冲刺持续计时器（未结束表示正在冲刺）
+    pub cooldown: Timer, // 冲刺冷却计时器
+}
+
+impl Default for Dash {
+    fn default() -> Self {
+        // 两个计时器都从结束状态开始：未在冲刺，且可以立即冲刺
+        let mut active = Timer::from_seconds(PLAYER_DASH_DURATION, TimerMode::Once);
+        let mut cooldown = Timer::from_seconds(PLAYER_DASH_COOLDOWN, TimerMode::Once);
+        active.tick(active.duration());
+        cooldown.tick(cooldown.duration());
+        Self { active, cooldown }
+    }
+}
+
+impl Dash {
+    /// 是否正在冲刺
+    pub fn is_dashing(&self) -> bool {
+        !self.active.finished()
+    }
+}
+
+/// 输入缓冲资源 - 记录最近一次开火/冲刺/炸弹按键的时间
+/// 冷却中或重生当帧的按键会被保留一小段时间，一旦可以执行就立即生效
+#[derive(Resource, Clone, Default)]
+pub struct InputBuffer {
+    fire: Option<f64>,      // 最近一次开火按键时间
+    dash: Option<f64>,      // 最近一次冲刺按键时间
+    bomb: Option<f64>,      // 最近一次炸弹按键时间
+    secondary: Option<f64>, // 最近一次副武器按键时间
+}
+
+impl InputBuffer {
+    /// 取出缓冲的开火输入（超出时间窗口的输入视为无效）
+    pub fn take_fire(&mut self, now: f64) -> bool {
+        Self::take(&mut self.fire, now)
+    }
+
+    /// 取出缓冲的冲刺输入（超出时间窗口的输入视为无效）
+    pub fn take_dash(&mut self, now: f64) -> bool {
+        Self::take(&mut self.dash, now)
+    }
+
+    /// 取出缓冲的炸弹输入（超出时间窗口的输入视为无效）
+    pub fn take_bomb(&mut self, now: f64) -> bool {
+        Self::take(&mut self.bomb, now)
+    }
+
+    /// 取出缓冲的副武器输入（超出时间窗口的输入视为无效）
+    pub fn take_secondary(&mut self, now: f64) -> bool {
+        Self::take(&mut self.secondary, now)
+    }
+
+    fn take(slot: &mut Option<f64>, now: f64) -> bool {
+        matches!(slot.take(), Some(pressed) if now - pressed <= INPUT_BUFFER_WINDOW)
+    }
+}
+
 /// 玩家系统插件 - 管理玩家的生成、移动和射击逻辑
 pub struct PlayerPlugin;
 
@@ -23,22 +124,154 @@ impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         // 初始化玩家状态资源
         app.insert_resource(PlayerState::default())
+            // 初始化输入缓冲资源
+            .init_resource::<InputBuffer>()
+            // 登记参与快照回滚的玩家资源和组件
+            .rollback_resource::<PlayerState>()
+            .rollback_resource::<InputBuffer>()
+            .rollback_component::<Invincible>()
+            .rollback_component::<Shield>()
+            .rollback_component::<Dash>()
+            // 每局开始时重置玩家状态并应用技能树
+            .add_systems(OnEnter(AppState::Playing), player_run_start_system)
             // 每0.5秒检查一次玩家重生条件
             .add_systems(
-                Update,
-                player_spawn_system.run_if(on_timer(Duration::from_secs_f32(0.5))),
+                FixedUpdate,
+                player_spawn_system
+                    .run_if(on_timer(Duration::from_secs_f32(0.5)))
+                    .in_set(GameSet::Spawn),
             )
+            // 每一步把开火/冲刺按键记录到输入缓冲（冷却中的按键稍后生效）
+            .add_systems(
+                FixedUpdate,
+                player_input_buffer_system
+                    .before(player_dash_system)
+                    .in_set(GameSet::Input),
+            )
+            // 处理玩家冲刺逻辑
+            .add_systems(FixedUpdate, player_dash_system.in_set(GameSet::Input))
             // 处理玩家键盘输入事件
-            .add_systems(Update, player_keyboard_event_system)
-            // 处理玩家移动和边界检查
+            .add_systems(
+                FixedUpdate,
+                player_keyboard_event_system
+                    .after(player_dash_system)
+                    .in_set(GameSet::Input),
+            )
+            // 处理玩家移动和边界检查（在通用移动之后执行，确保最终位置在边界内）
+            .add_systems(
+                FixedUpdate,
+                player_movement_system
+                    .after(movable_system)
+                    .in_set(GameSet::Movement),
+            )
+            // 处理玩家主武器和副武器射击逻辑（两者互不依赖）
+            .add_systems(
+                FixedUpdate,
+                (player_fire_system, player_secondary_fire_system).in_set(GameSet::Spawn),
+            )
+            // 处理玩家炸弹逻辑（在激光命中处理之后，避免重复击毁同一敌人）
+            .add_systems(
+                FixedUpdate,
+                player_bomb_system
+                    .after(laser_hit_enemy_system)
+                    .in_set(GameSet::Collision),
+            )
+            // 受伤或维修后刷新战机颜色
             .add_systems(
                 Update,
-                player_movement_system.after(player_keyboard_event_system),
+                player_health_tint_system.in_set(GameSet::Presentation),
             )
-            // 处理玩家射击逻辑
-            .add_systems(Update, player_fire_system)
             // 新增无敌状态计时器系统
-            .add_systems(Update, invincible_timer_system);
+            .add_systems(
+                FixedUpdate,
+                invincible_timer_system.in_set(GameSet::Movement),
+            );
+    }
+}
+
+/// 输入缓冲系统 - 记录开火、冲刺、炸弹和副武器动作的按下时间
+fn player_input_buffer_system(
+    time: Res<Time>,
+    input: Res<TickInput>,
+    input_lock: Res<InputLock>,
+    mut buffer: ResMut<InputBuffer>,
+) {
+    // 过场锁定输入期间不记录按键
+    if input_lock.0 {
+        return;
+    }
+    let now = time.elapsed_secs_f64();
+    if input.just_pressed(Action::Fire) {
+        buffer.fire = Some(now);
+    }
+    if input.just_pressed(Action::Dash) {
+        buffer.dash = Some(now);
+    }
+    if input.just_pressed(Action::Bomb) {
+        buffer.bomb = Some(now);
+    }
+    if input.just_pressed(Action::Secondary) {
+        buffer.secondary = Some(now);
+    }
+}
+
+/// 炸弹系统 - 消耗一枚炸弹，清除所有与玩家队伍敌对的激光并击毁屏幕上全部敌对的敌人
+fn player_bomb_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut buffer: ResMut<InputBuffer>,
+    mut player_state: ResMut<PlayerState>,
+    laser_query: Query<(Entity, &Team), (Without<Player>, Without<Enemy>)>,
+    enemy_query: Query<(Entity, &Team), With<Enemy>>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    // 玩家存活且有炸弹时才消费缓冲的炸弹输入
+    if !player_state.on || player_state.bombs == 0 || !buffer.take_bomb(time.elapsed_secs_f64()) {
+        return;
+    }
+    player_state.bombs -= 1;
+
+    // 清除所有敌对的激光（含流星等敌方障碍物）
+    for (laser_entity, _) in laser_query
+        .iter()
+        .filter(|(_, team)| team.hostile(Team::PLAYER))
+    {
+        commands.entity(laser_entity).queue(recycle);
+    }
+
+    // 击毁所有敌对的敌人（炸弹无视装甲但无法穿透首领的核心护盾，分裂词缀的敌人仍会留下碎片）
+    for (enemy_entity, _) in enemy_query
+        .iter()
+        .filter(|(_, team)| team.hostile(Team::PLAYER))
+    {
+        damage_events.send(DamageEvent::lethal(enemy_entity));
+    }
+}
+
+/// 玩家冲刺系统 - 推进冲刺计时器，冷却完毕时执行缓冲的冲刺输入
+fn player_dash_system(
+    time: Res<Time>,
+    mut buffer: ResMut<InputBuffer>,
+    mut query: Query<&mut Dash, With<Player>>,
+) {
+    if let Ok(mut dash) = query.get_single_mut() {
+        dash.active.tick(time.delta());
+        dash.cooldown.tick(time.delta());
+
+        if dash.cooldown.finished() && buffer.take_dash(time.elapsed_secs_f64()) {
+            dash.active.reset(); // 开始冲刺
+            dash.cooldown.reset(); // 开始冷却
+        }
+    }
+}
+
+/// 生命值着色系统 - 生命值变化时按剩余比例为战机着色（生命值越低越偏红）
+fn player_health_tint_system(
+    mut query: Query<(&Health, &mut Sprite), (With<Player>, Changed<Health>)>,
+) {
+    for (health, mut sprite) in &mut query {
+        let fraction = health.fraction().max(0.);
+        sprite.color = Color::srgb(1., fraction, fraction);
     }
 }
 
@@ -93,116 +326,273 @@ fn player_spawn_system(
     mut commands: Commands,
     mut player_state: ResMut<PlayerState>,
     time: Res<Time>,
-    game_textures: Res<GameTextures>,
+    registry: Res<AssetRegistry>,
+    profile: Res<Profile>,
+    upgrades: Res<RunUpgrades>,
     win_size: Res<WinSize>,
 ) {
     let now = time.elapsed_secs_f64(); // 当前游戏时间
     let last_shot = player_state.last_shot; // 玩家最后一次死亡时间
 
-    // 条件：玩家未存活，且重生延迟已过（或首次生成）
-    if !player_state.on && (last_shot == -1. || now > last_shot + PLAYER_RESPAWN_DELAY) {
+    // 条件：玩家未存活且还有剩余生命，且重生延迟已过（或首次生成）
+    if !player_state.on
+        && player_state.lives > 0
+        && (last_shot == -1. || now > last_shot + PLAYER_RESPAWN_DELAY)
+    {
         // 计算玩家生成位置（屏幕底部中央）
         let bottom = -win_size.h / 2.;
-        commands
-            .spawn((
-                // 玩家精灵
-                Sprite::from_image(game_textures.player.clone()),
-                Transform {
-                    // 位置：底部中央偏上，Z轴设为10确保显示在背景上方
-                    translation: Vec3::new(
-                        0.,
-                        bottom + PLAYER_SIZE.1 / 2. * SPRITE_SCALE + 5.,
-                        10.,
-                    ),
-                    scale: Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.), // 精灵缩放
-                    ..Default::default()
-                },
-            ))
+
+        // 根据档案选择战机外观，解锁出生护盾后延长无敌时间
+        let sprite = match profile.ship {
+            ShipSkin::A => Tex::Player,
+            ShipSkin::B => Tex::PlayerB,
+        };
+        let invincible_secs = if profile.is_unlocked(UnlockItem::PowerUp(StartPowerUp::Shield)) {
+            PLAYER_SHIELD_INVINCIBLE_SECS
+        } else {
+            PLAYER_INVINCIBLE_SECS
+        };
+
+        let loadout = Loadout::default(); // 每次重生携带全部武器，使用标准武器
+        let mut entity = commands.spawn((
+            // 玩家精灵
+            registry.sprite(sprite),
+            Transform {
+                // 位置：底部中央偏上，Z轴设为10确保显示在背景上方
+                translation: Vec3::new(0., bottom + PLAYER_SIZE.1 / 2. * SPRITE_SCALE + 5., 10.),
+                scale: Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.), // 精灵缩放
+                ..Default::default()
+            },
+        ));
+        entity
             .insert(Player) // 标记为玩家实体
             .insert(SpriteSize::from(PLAYER_SIZE)) // 设置精灵尺寸
             .insert(Movable {
                 auto_despawn: false,
             }) // 玩家不会自动销毁
             .insert(Velocity { x: 0., y: 0. }) // 初始速度为0
+            .insert(FireCooldown::ready(loadout.current().cooldown(&upgrades))) // 开火冷却（含武器与商店强化），生成后可立即射击
+            .insert(MoveSpeed(upgrades.move_speed(&profile))) // 移动速度（含商店强化与技能树加成）
+            .insert(Dash::default()) // 冲刺状态
+            .insert(Ammo::full(&loadout)) // 非标准武器的弹药（出生时装满）
+            .insert(loadout) // 武器配置
+            .insert(SecondaryWeapon::default()) // 副武器（冷却与弹药独立）
+            .insert(Health::full(PLAYER_HEALTH)) // 生命值
+            .insert(Team::PLAYER) // 玩家队伍
             .insert(Invincible {
-                timer: Timer::from_seconds(2.0, TimerMode::Once), // 2秒无敌状态
+                timer: Timer::from_seconds(invincible_secs, TimerMode::Once), // 出生无敌状态
             }); // 添加无敌组件
 
+        // 技能树的开局护盾只作用于本局第一次生成的战机
+        if player_state.shield {
+            entity.insert(Shield);
+            player_state.shield = false;
+        }
+
         player_state.spawned(); // 标记玩家已重生
     }
 }
 
-/// 玩家射击系统 - 处理空格键发射激光的逻辑
+/// 开局系统 - 每局开始时重置玩家状态，并应用技能树的额外炸弹和开局护盾
+fn player_run_start_system(mut player_state: ResMut<PlayerState>, profile: Res<Profile>) {
+    *player_state = PlayerState::default();
+    if profile.has_skill(SkillNode::ExtraBomb) {
+        player_state.bombs += 1;
+    }
+    player_state.shield = profile.has_skill(SkillNode::StartShield);
+}
+
+/// 玩家射击系统 - 按当前武器发射激光（受开火冷却和连发设置控制）
+#[allow(clippy::type_complexity)] // 允许复杂的查询类型
 fn player_fire_system(
     mut commands: Commands,
-    kb: Res<ButtonInput<KeyCode>>,          // 键盘输入资源
-    game_textures: Res<GameTextures>,       // 游戏纹理资源
-    query: Query<&Transform, With<Player>>, // 玩家位置查询
+    time: Res<Time>,
+    input: Res<TickInput>,              // 本步的输入
+    settings: Res<Settings>,            // 游戏设置资源
+    input_lock: Res<InputLock>,         // 过场输入锁定资源
+    mut buffer: ResMut<InputBuffer>,    // 输入缓冲资源
+    mut stats: ResMut<GameStats>,       // 游戏统计资源
+    profile: Res<Profile>,              // 玩家档案资源（激光颜色）
+    registry: Res<AssetRegistry>,       // 资源注册表
+    mut pool: ResMut<BulletPool>,       // 子弹对象池资源
+    mut sounds: EventWriter<PlaySound>, // 音效事件写入器（空膛声）
+    mut query: Query<
+        (
+            &Transform,
+            &mut FireCooldown,
+            &Loadout,
+            &mut Ammo,
+            Has<RapidFire>,
+        ),
+        With<Player>,
+    >, // 玩家位置、冷却、武器、弹药与连射强化查询
 ) {
     // 获取玩家位置（假设游戏中只有一个玩家）
-    if let Ok(player_tf) = query.get_single() {
-        // 检测空格键是否刚按下
-        if kb.just_pressed(KeyCode::Space) {
-            let (x, y) = (player_tf.translation.x, player_tf.translation.y);
-            // 计算激光发射的水平偏移量（从玩家两侧发射）
-            let x_offset = PLAYER_SIZE.0 / 2. * SPRITE_SCALE - 5.;
-
-            // 封装激光生成逻辑为闭包
-            let mut spawn_laser = |x_offset: f32| {
-                commands
-                    .spawn((
-                        // 玩家激光精灵
-                        Sprite::from_image(game_textures.player_laser.clone()),
+    if let Ok((player_tf, mut cooldown, loadout, mut ammo, rapid_fire)) = query.get_single_mut() {
+        // 推进开火冷却计时器（连射强化期间倍速推进）
+        let rate = if rapid_fire { RAPID_FIRE_RATE } else { 1 };
+        cooldown.0.tick(time.delta() * rate);
+
+        // 冷却未结束时不消费缓冲输入，等待冷却完毕再执行
+        if !cooldown.0.finished() {
+            return;
+        }
+
+        // 根据连发模式判断本帧是否扣动扳机
+        let now = time.elapsed_secs_f64();
+        let trigger = match settings.auto_fire {
+            _ if input_lock.0 => false, // 过场锁定输入期间不开火
+            AutoFire::Off => buffer.take_fire(now),
+            AutoFire::Hold => buffer.take_fire(now) || input.pressed(Action::Fire),
+            AutoFire::Always => true,
+        };
+
+        if trigger {
+            cooldown.0.reset(); // 重新开始冷却
+            // 弹药耗尽时只发出空膛声（空膛声同样受开火冷却限制）
+            if !ammo.try_use(loadout.current()) {
+                sounds.send(PlaySound::at(Sfx::EmptyClick, player_tf.translation));
+                return;
+            }
+            stats.shots_fired += 1; // 记录开火次数
+            let position = player_tf.translation.truncate();
+            // 计算玩家两侧炮口的水平偏移量
+            let side_offset = PLAYER_SIZE.0 / 2. * SPRITE_SCALE - 5.;
+
+            // 按当前武器的弹道逐一生成激光
+            let damage_type = loadout.current().damage_type();
+            let inflicts = loadout.current().inflicts();
+            for shot in loadout.current().shots(side_offset) {
+                let translation = (position + shot.offset).extend(0.);
+                let mut laser = pool.spawn(
+                    &mut commands,
+                    (
+                        // 玩家激光精灵（按档案中的激光颜色着色，导弹固定为橙色）
+                        Sprite {
+                            color: if shot.homing {
+                                MISSILE_COLOR
+                            } else {
+                                profile.laser_color.tint()
+                            },
+                            ..registry.sprite(Tex::PlayerLaser)
+                        },
                         Transform {
-                            // 位置：玩家上方两侧
-                            translation: Vec3::new(x + x_offset, y + 15., 0.),
+                            translation,
+                            // 朝向飞行方向
+                            rotation: Quat::from_rotation_arc_2d(
+                                Vec2::Y,
+                                shot.velocity.normalize(),
+                            ),
                             scale: Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.),
-                            ..Default::default()
                         },
-                    ))
+                    ),
+                );
+                laser
                     .insert(Laser) // 标记为激光实体
-                    .insert(FromPlayer) // 标记为玩家发射的激光
+                    .insert(Team::PLAYER) // 标记为玩家队伍的激光
                     .insert(SpriteSize::from(PLAYER_LASER_SIZE)) // 设置激光尺寸
-                    .insert(Movable { auto_despawn: true }) // 激光超出屏幕自动销毁
-                    .insert(Velocity { x: 0., y: 1. }); // 激光向上移动的速度
-            };
+                    .insert(Movable { auto_despawn: true }) // 激光超出屏幕自动回收
+                    .insert(Velocity {
+                        x: shot.velocity.x,
+                        y: shot.velocity.y,
+                    }) // 激光的飞行速度
+                    .insert(damage_type); // 伤害类型
+                if shot.homing {
+                    laser.insert(Homing); // 导弹追踪最近的敌人
+                }
+                if let Some(inflicts) = inflicts {
+                    laser.insert(inflicts); // 命中时施加的状态效果
+                }
+            }
+        }
+    }
+}
 
-            // 从玩家左右两侧各发射一束激光
-            spawn_laser(x_offset);
-            spawn_laser(-x_offset);
+/// 副武器射击系统 - 按下副武器键时发射重型武器（独立的冷却和弹药，与主武器互不影响）
+fn player_secondary_fire_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut buffer: ResMut<InputBuffer>,
+    mut stats: ResMut<GameStats>,
+    registry: Res<AssetRegistry>,
+    mut sounds: EventWriter<PlaySound>,
+    mut query: Query<(&Transform, &mut SecondaryWeapon), With<Player>>,
+) {
+    let Ok((player_tf, mut secondary)) = query.get_single_mut() else {
+        return;
+    };
+    secondary.cooldown.tick(time.delta());
+
+    // 冷却未结束时不消费缓冲输入
+    if !secondary.cooldown.finished() || !buffer.take_secondary(time.elapsed_secs_f64()) {
+        return;
+    }
+    secondary.cooldown.reset();
+    if secondary.ammo == 0 {
+        sounds.send(PlaySound::at(Sfx::EmptyClick, player_tf.translation));
+        return;
+    }
+    secondary.ammo -= 1;
+    stats.shots_fired += 1;
+
+    match secondary.kind {
+        HeavyWeapon::Torpedo => {
+            let translation = player_tf.translation.truncate() + Vec2::new(0., 20.);
+            commands
+                .spawn((
+                    // 重型鱼雷精灵（放大的红色激光）
+                    Sprite {
+                        color: TORPEDO_COLOR,
+                        ..registry.sprite(Tex::PlayerLaser)
+                    },
+                    Transform {
+                        translation: translation.extend(0.),
+                        scale: Vec3::new(SPRITE_SCALE * 2., SPRITE_SCALE * 1.5, 1.),
+                        ..Default::default()
+                    },
+                ))
+                .insert(Laser) // 标记为激光实体
+                .insert(Team::PLAYER) // 标记为玩家队伍
+                .insert(SpriteSize::from(PLAYER_LASER_SIZE)) // 设置碰撞尺寸
+                .insert(Movable { auto_despawn: true }) // 超出屏幕自动销毁
+                .insert(Velocity {
+                    x: 0.,
+                    y: TORPEDO_SPEED,
+                }) // 向上发射
+                .insert(Homing) // 追踪最近的敌人
+                .insert(DamageType::Explosive) // 爆炸伤害
+                .insert(Inflicts {
+                    kind: StatusKind::Burn,
+                    secs: BURN_SECS,
+                }) // 命中时点燃目标
+                .insert(Blast {
+                    radius: TORPEDO_BLAST_RADIUS,
+                }); // 命中后波及周围敌人
         }
     }
 }
 
-/// 玩家键盘事件系统 - 处理方向键控制玩家移动
+/// 玩家移动系统 - 按移动方向控制玩家移动（摇杆推到一半时以一半速度移动）
 fn player_keyboard_event_system(
-    kb: Res<ButtonInput<KeyCode>>,                 // 键盘输入资源
-    mut query: Query<&mut Velocity, With<Player>>, // 玩家速度组件查询
+    input: Res<TickInput>,      // 本步的输入
+    input_lock: Res<InputLock>, // 过场输入锁定资源
+    mut query: Query<(&mut Velocity, &Dash, &MoveSpeed), With<Player>>, // 玩家速度、冲刺与移动速度查询
 ) {
     // 获取玩家速度组件（假设游戏中只有一个玩家）
-    if let Ok(mut velocity) = query.get_single_mut() {
-        // 初始化速度向量
-        let mut input_velocity = Vec2::new(0., 0.);
-
-        // 处理水平输入
-        if kb.pressed(KeyCode::ArrowLeft) {
-            input_velocity.x -= 1.0;
-        }
-        if kb.pressed(KeyCode::ArrowRight) {
-            input_velocity.x += 1.0;
+    if let Ok((mut velocity, dash, move_speed)) = query.get_single_mut() {
+        // 过场锁定输入期间保持静止
+        if input_lock.0 {
+            velocity.x = 0.;
+            velocity.y = 0.;
+            return;
         }
 
-        // 处理垂直输入
-        if kb.pressed(KeyCode::ArrowUp) {
-            input_velocity.y += 1.0;
-        }
-        if kb.pressed(KeyCode::ArrowDown) {
-            input_velocity.y -= 1.0;
-        }
+        // 移动方向的长度不超过1，对角线移动速度与直线一致
+        let mut input_velocity = input.move_axis * move_speed.0;
 
-        // 归一化速度向量以确保对角线移动速度一致
-        if input_velocity.length_squared() > 0.0 {
-            input_velocity = input_velocity.normalize() * PLAYER_SPEED;
+        // 冲刺期间提高移动速度
+        if dash.is_dashing() {
+            input_velocity *= PLAYER_DASH_SPEED;
         }
 
         // 更新速度组件
@@ -210,3 +600,79 @@ fn player_keyboard_event_system(
         velocity.y = input_velocity.y;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::components::{Laser, Player};
+    use crate::testing::{InputScript, PLAYER_SPAWN_FRAMES, headless_app, positions, run_frames};
+    use bevy::prelude::*;
+
+    #[test]
+    fn player_spawns_at_bottom_center() {
+        let mut app = headless_app(InputScript::default());
+        run_frames(&mut app, PLAYER_SPAWN_FRAMES);
+
+        let players = positions::<Player>(&mut app);
+        assert_eq!(players.len(), 1);
+        assert_eq!(players[0].x, 0.);
+        assert!(players[0].y < 0.);
+    }
+
+    #[test]
+    fn holding_arrow_moves_player() {
+        let script = InputScript::default()
+            .press(PLAYER_SPAWN_FRAMES, KeyCode::ArrowRight)
+            .release(PLAYER_SPAWN_FRAMES + 10, KeyCode::ArrowRight);
+        let mut app = headless_app(script);
+        run_frames(&mut app, PLAYER_SPAWN_FRAMES);
+        let start = positions::<Player>(&mut app)[0];
+
+        run_frames(&mut app, 10);
+        let moved = positions::<Player>(&mut app)[0];
+        assert!(moved.x > start.x);
+        assert_eq!(moved.y, start.y);
+
+        // 松开按键后停止移动（留出一帧让速度归零）
+        run_frames(&mut app, 2);
+        let released = positions::<Player>(&mut app)[0];
+        run_frames(&mut app, 10);
+        let stopped = positions::<Player>(&mut app)[0];
+        assert_eq!(stopped.x, released.x);
+    }
+
+    #[test]
+    fn player_stays_inside_window() {
+        let script = InputScript::default().press(PLAYER_SPAWN_FRAMES, KeyCode::ArrowLeft);
+        let mut app = headless_app(script);
+        run_frames(&mut app, PLAYER_SPAWN_FRAMES + 120);
+
+        let player = positions::<Player>(&mut app)[0];
+        assert!(player.x > -598. / 2.);
+    }
+
+    #[test]
+    fn space_fires_two_lasers_upward() {
+        let script = InputScript::default().tap(PLAYER_SPAWN_FRAMES, KeyCode::Space);
+        let mut app = headless_app(script);
+        run_frames(&mut app, PLAYER_SPAWN_FRAMES + 5);
+
+        let player = positions::<Player>(&mut app)[0];
+        let lasers = positions::<Laser>(&mut app);
+        assert_eq!(lasers.len(), 2);
+        assert!(lasers.iter().all(|laser| laser.y > player.y));
+    }
+
+    #[test]
+    fn fire_press_during_cooldown_is_buffered() {
+        // 第二次按键落在冷却期内（0.15秒后），应在冷却结束后自动发射
+        let script = InputScript::default()
+            .tap(PLAYER_SPAWN_FRAMES, KeyCode::Space)
+            .tap(PLAYER_SPAWN_FRAMES + 9, KeyCode::Space);
+        let mut app = headless_app(script);
+        run_frames(&mut app, PLAYER_SPAWN_FRAMES + 10);
+        assert_eq!(positions::<Laser>(&mut app).len(), 2);
+
+        run_frames(&mut app, 10);
+        assert_eq!(positions::<Laser>(&mut app).len(), 4);
+    }
+}
@@ -0,0 +1,80 @@
+use crate::components::{DamageType, InRun, Laser, Movable, SpriteSize, Team, Velocity};
+use crate::force::Drift;
+use crate::hazard::Slowed;
+use crate::interpolation::InterpolatedPosition;
+use crate::status::Inflicts;
+use crate::stress::StressBullet;
+use crate::weapon::{Blast, Homing};
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+
+/// 对象池标记 - 带此标记的子弹离场时回收到对象池而不是销毁
+#[derive(Component, Clone)]
+pub struct Pooled;
+
+/// 子弹对象池资源 - 已回收（隐藏）等待复用的子弹实体
+/// 大量子弹同时存在时，复用实体避免每发子弹都重新分配实体和同步渲染数据
+#[derive(Resource, Default)]
+pub struct BulletPool {
+    free: Vec<Entity>, // 已回收的实体
+}
+
+impl BulletPool {
+    /// 已回收等待复用的实体数量
+    pub fn free_count(&self) -> usize {
+        self.free.len()
+    }
+
+    /// 生成子弹：优先复用回收的实体（替换精灵、位置等组件并重新显示），对象池为空时生成新实体
+    pub fn spawn<'a>(
+        &mut self,
+        commands: &'a mut Commands,
+        bundle: impl Bundle,
+    ) -> EntityCommands<'a> {
+        match self.free.pop() {
+            Some(entity) => {
+                let mut entity = commands.entity(entity);
+                entity.insert((bundle, Visibility::Inherited));
+                entity
+            }
+            None => commands.spawn((bundle, Pooled)),
+        }
+    }
+}
+
+/// 回收命令 - 池化的子弹移除玩法组件、隐藏后放回对象池，其他实体直接销毁
+/// 用法：commands.entity(entity).queue(recycle)
+pub fn recycle(mut entity: EntityWorldMut) {
+    if !entity.contains::<Pooled>() {
+        entity.despawn();
+        return;
+    }
+    // 同一帧内可能被多个系统回收（如命中的同时飞出屏幕），只回收一次
+    if !entity.contains::<Movable>() {
+        return;
+    }
+
+    entity.remove::<(
+        Laser,
+        Team,
+        Movable,
+        Velocity,
+        SpriteSize,
+        DamageType,
+        Inflicts,
+        Homing,
+        Blast,
+        Drift,
+        Slowed,
+        InterpolatedPosition,
+        StressBullet,
+        InRun,
+    )>();
+    entity.insert(Visibility::Hidden);
+    let id = entity.id();
+    entity.world_scope(|world| {
+        if let Some(mut pool) = world.get_resource_mut::<BulletPool>() {
+            pool.free.push(id);
+        }
+    });
+}
@@ -0,0 +1,183 @@
+use crate::action::{Action, ActionState};
+use crate::boss::Boss;
+use crate::components::Enemy;
+use crate::enemy::{EnemyDef, FormationMaker, SpawnEnemyEvent, SpawnKind, Upgrades};
+use crate::game_mode::{GameMode, GameModePlugin};
+use crate::run::GameOverEvent;
+use crate::{AppState, EnemyCount, FORMATION_MEMBERS_MAX, GameSet};
+use bevy::prelude::*;
+
+// 练习模式面板的操作提示
+const PRACTICE_HELP: &str = "PRACTICE\n5: basic\n6: elite\n7: composite\n8: carrier\n9: formation\n0: boss\nBksp: clear\nEsc: menu";
+
+/// 练习模式可按需生成的敌人
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PracticeSpawn {
+    Basic,     // 普通敌人
+    Elite,     // 精英敌人（随机词缀）
+    Composite, // 带机翼和炮塔部件的敌人
+    Carrier,   // 母舰
+    Formation, // 一整个编队的普通敌人
+    Boss,      // 首领（场上已有首领时忽略）
+}
+
+impl PracticeSpawn {
+    const ALL: [PracticeSpawn; 6] = [
+        PracticeSpawn::Basic,
+        PracticeSpawn::Elite,
+        PracticeSpawn::Composite,
+        PracticeSpawn::Carrier,
+        PracticeSpawn::Formation,
+        PracticeSpawn::Boss,
+    ];
+
+    /// 生成动作（键盘默认为数字键5~0）
+    fn action(self) -> Action {
+        Action::PracticeSpawn(self as u8)
+    }
+}
+
+/// 练习面板标记
+#[derive(Component)]
+struct PracticePanel;
+
+/// 练习模式插件 - 不自动生成敌人、生命无限，玩家用按键按需生成指定的敌人、编队和首领来熟悉它们的行为
+#[derive(Clone)]
+pub struct PracticePlugin;
+
+impl Plugin for PracticePlugin {
+    fn build(&self, app: &mut App) {
+        // 创建练习面板（默认隐藏）
+        app.add_systems(Startup, practice_panel_setup_system)
+            // 按键生成敌人、清场和返回主菜单
+            .add_systems(
+                Update,
+                (practice_spawn_system, practice_exit_system).in_set(GameSet::Input),
+            )
+            // 只在练习模式中显示面板
+            .add_systems(Update, practice_panel_system.in_set(GameSet::Presentation));
+    }
+}
+
+impl GameModePlugin for PracticePlugin {
+    fn mode(&self) -> GameMode {
+        GameMode::Practice
+    }
+
+    fn menu_entry(&self) -> Option<(KeyCode, &'static str)> {
+        Some((KeyCode::KeyP, "P: practice"))
+    }
+
+    fn auto_spawn(&self) -> bool {
+        false
+    }
+
+    fn infinite_lives(&self) -> bool {
+        true
+    }
+}
+
+/// 练习面板初始化系统 - 屏幕左侧的操作提示
+fn practice_panel_setup_system(mut commands: Commands) {
+    commands.spawn((
+        PracticePanel,
+        Text::new(PRACTICE_HELP),
+        TextFont {
+            font_size: 16.,
+            ..Default::default()
+        },
+        TextColor(Color::srgba(1., 1., 1., 0.8)),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(8.),
+            top: Val::Percent(35.),
+            ..Default::default()
+        },
+        Visibility::Hidden,
+    ));
+}
+
+/// 练习生成系统 - 练习生成动作（数字键5~0）发送对应的生成敌人事件，练习清场动作（退格键）清除全部敌人
+#[allow(clippy::too_many_arguments)] // 生成和清除敌人需要同时访问多个资源
+fn practice_spawn_system(
+    mut commands: Commands,
+    actions: Res<ActionState>,
+    mode: Res<GameMode>,
+    mut enemy_count: ResMut<EnemyCount>,
+    mut formation_maker: ResMut<FormationMaker>,
+    boss_query: Query<(), With<Boss>>,
+    enemy_query: Query<Entity, With<Enemy>>,
+    mut spawn_events: EventWriter<SpawnEnemyEvent>,
+) {
+    if *mode != GameMode::Practice {
+        return;
+    }
+
+    if actions.just_pressed(Action::PracticeClear) {
+        for entity in &enemy_query {
+            commands.entity(entity).despawn();
+        }
+        enemy_count.0 = 0;
+        return;
+    }
+
+    let Some(spawn) = PracticeSpawn::ALL
+        .into_iter()
+        .find(|spawn| actions.just_pressed(spawn.action()))
+    else {
+        return;
+    };
+    debug!(?spawn, "练习模式生成");
+
+    let (kind, members) = match spawn {
+        PracticeSpawn::Basic => (SpawnKind::Enemy(EnemyDef::default(), Upgrades::None), 1),
+        PracticeSpawn::Elite => (SpawnKind::Enemy(EnemyDef::default(), Upgrades::Elite), 1),
+        PracticeSpawn::Composite => (
+            SpawnKind::Enemy(EnemyDef::default(), Upgrades::Composite),
+            1,
+        ),
+        PracticeSpawn::Carrier => (SpawnKind::Carrier, 1),
+        // 新开一个编队并生成全部成员
+        PracticeSpawn::Formation => {
+            formation_maker.reset();
+            (
+                SpawnKind::Enemy(EnemyDef::default(), Upgrades::None),
+                FORMATION_MEMBERS_MAX,
+            )
+        }
+        PracticeSpawn::Boss if boss_query.is_empty() => (SpawnKind::Boss, 1),
+        PracticeSpawn::Boss => return,
+    };
+    for _ in 0..members {
+        spawn_events.send(SpawnEnemyEvent::new(kind.clone()));
+    }
+}
+
+/// 练习退出系统 - 暂停（Esc或手柄Start）时结束本局后返回主菜单
+fn practice_exit_system(
+    actions: Res<ActionState>,
+    mode: Res<GameMode>,
+    mut game_over: EventWriter<GameOverEvent>,
+) {
+    if *mode != GameMode::Practice || !actions.just_pressed(Action::Pause) {
+        return;
+    }
+    game_over.send(GameOverEvent::to(AppState::MainMenu));
+}
+
+/// 练习面板显示系统 - 练习模式进行中显示操作提示
+fn practice_panel_system(
+    mode: Res<GameMode>,
+    state: Res<State<AppState>>,
+    mut query: Query<&mut Visibility, With<PracticePanel>>,
+) {
+    let Ok(mut visibility) = query.get_single_mut() else {
+        return;
+    };
+    let active = *mode == GameMode::Practice && *state.get() == AppState::Playing;
+    visibility.set_if_neq(if active {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    });
+}
@@ -0,0 +1,152 @@
+use crate::AppState;
+use crate::combat::EntityKilledEvent;
+use crate::game_mode::{GameMode, GameModes};
+use crate::save::{self, SaveFile};
+use crate::skills::{SKILL_SPEED_SCALE, Skill, SkillNode};
+use crate::unlocks::{LaserColor, ShipSkin, UNLOCKS, UnlockItem};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// 玩家档案文件路径
+const PROFILE_PATH: &str = "profile.ron";
+// 每击毁一个敌人获得的核心（局外货币）
+const CORES_PER_KILL: u64 = 1;
+// 得分挑战排行榜保留的成绩数
+pub const SCORE_ATTACK_TABLE_SIZE: usize = 10;
+
+/// 玩家档案资源 - 跨局保存的累计得分、装备选择与技能树进度
+#[derive(Resource, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    pub lifetime_score: u64,     // 历史累计得分
+    pub ship: ShipSkin,          // 当前装备的战机
+    pub laser_color: LaserColor, // 当前装备的激光颜色
+    pub cores: u64,              // 可用于技能树的核心（局外货币）
+    pub skills: Vec<SkillNode>,  // 已购买的技能节点
+    pub campaign_stars: Vec<u8>, // 各战役关卡获得的最高星级（按关卡顺序）
+    pub score_attack: Vec<u64>,  // 得分挑战排行榜（从高到低）
+}
+
+impl SaveFile for Profile {
+    const PATH: &'static str = PROFILE_PATH;
+    const LABEL: &'static str = "玩家档案";
+    const VERSION: u32 = 1;
+}
+
+impl Profile {
+    /// 从档案文件读取（旧版本的文件先迁移），文件不存在或无法读取时使用默认档案
+    pub fn load() -> Self {
+        let _span = info_span!("profile_load", path = PROFILE_PATH).entered();
+        let profile: Self = save::load();
+        info!(
+            lifetime_score = profile.lifetime_score,
+            cores = profile.cores,
+            "读取玩家档案"
+        );
+        profile
+    }
+
+    /// 写入档案文件，失败时只记录警告
+    pub fn save(&self) {
+        let _span = info_span!("profile_save", path = PROFILE_PATH).entered();
+        save::save(self);
+    }
+
+    /// 判断物品是否已解锁（不在解锁表中的物品默认已解锁）
+    pub fn is_unlocked(&self, item: UnlockItem) -> bool {
+        UNLOCKS
+            .iter()
+            .find(|unlock| unlock.item == item)
+            .is_none_or(|unlock| self.lifetime_score >= unlock.score)
+    }
+
+    /// 判断技能节点是否已购买
+    pub fn has_skill(&self, node: SkillNode) -> bool {
+        self.skills.contains(&node)
+    }
+
+    /// 判断技能节点当前能否购买（未购买、前置已购买且核心足够）
+    pub fn can_buy_skill(&self, skill: &Skill) -> bool {
+        !self.has_skill(skill.node)
+            && skill.requires.is_none_or(|node| self.has_skill(node))
+            && self.cores >= skill.cost
+    }
+
+    /// 战役关卡获得的最高星级（未完成为0）
+    pub fn level_stars(&self, index: usize) -> u8 {
+        self.campaign_stars.get(index).copied().unwrap_or(0)
+    }
+
+    /// 记录战役关卡的星级，只保留最高成绩
+    pub fn record_level_stars(&mut self, index: usize, stars: u8) {
+        if self.campaign_stars.len() <= index {
+            self.campaign_stars.resize(index + 1, 0);
+        }
+        self.campaign_stars[index] = self.campaign_stars[index].max(stars);
+    }
+
+    /// 记录一局得分挑战的成绩，返回在排行榜中的名次（从0开始，未进入排行榜时为None）
+    pub fn record_score_attack(&mut self, score: u64) -> Option<usize> {
+        let rank = self.score_attack.partition_point(|&best| best >= score);
+        if rank >= SCORE_ATTACK_TABLE_SIZE {
+            return None;
+        }
+        self.score_attack.insert(rank, score);
+        self.score_attack.truncate(SCORE_ATTACK_TABLE_SIZE);
+        Some(rank)
+    }
+
+    /// 技能树提供的移动速度倍率
+    pub fn speed_scale(&self) -> f32 {
+        if self.has_skill(SkillNode::Speed) {
+            SKILL_SPEED_SCALE
+        } else {
+            1.
+        }
+    }
+}
+
+/// 档案插件 - 启动时读取档案，累计得分，离开游戏和退出时保存（游戏进行中不写文件）
+pub struct ProfilePlugin;
+
+impl Plugin for ProfilePlugin {
+    fn build(&self, app: &mut App) {
+        // 读取玩家档案
+        app.insert_resource(Profile::load())
+            // 每次击毁敌人累计得分
+            .add_systems(Update, profile_score_system)
+            // 离开游戏时保存本局累计的得分和核心
+            .add_systems(OnExit(AppState::Playing), profile_save_system)
+            // 退出游戏时保存
+            .add_systems(Last, profile_save_on_exit_system);
+    }
+}
+
+/// 累计得分系统 - 响应击毁事件增加历史累计得分和核心（不计分的模式如主菜单演示中的击毁不计入）
+fn profile_score_system(
+    mode: Res<GameMode>,
+    modes: Res<GameModes>,
+    mut profile: ResMut<Profile>,
+    mut events: EventReader<EntityKilledEvent>,
+) {
+    if !modes.get(*mode).counts_score() {
+        events.clear();
+        return;
+    }
+    for event in events.read() {
+        profile.lifetime_score += event.score;
+        profile.cores += CORES_PER_KILL;
+    }
+}
+
+/// 保存系统
+fn profile_save_system(profile: Res<Profile>) {
+    profile.save();
+}
+
+/// 退出保存系统 - 收到退出事件时写入档案
+fn profile_save_on_exit_system(profile: Res<Profile>, mut exit_events: EventReader<AppExit>) {
+    if exit_events.read().next().is_some() {
+        profile.save();
+    }
+}
@@ -0,0 +1,239 @@
+use crate::components::{Movable, SpriteSize, Team, Velocity};
+use crate::rng::GameRng;
+use crate::sim::RollbackAppExt;
+use crate::{GameSet, WinSize};
+use bevy::prelude::*;
+use rand::Rng;
+
+// 两次随机事件之间的间隔范围（秒）
+const RANDOM_EVENT_INTERVAL: (f32, f32) = (60., 120.);
+// 事件横幅的显示时间（秒）
+const BANNER_SECS: f32 = 2.5;
+// 流星雨中流星的生成间隔（秒）
+const METEOR_SPAWN_INTERVAL: f32 = 0.25;
+// 流星的尺寸
+const METEOR_SIZE: (f32, f32) = (26., 26.);
+// 流星的颜色
+const METEOR_COLOR: Color = Color::srgb(0.55, 0.45, 0.4);
+
+/// 随机事件种类
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RandomEventKind {
+    MeteorShower, // 流星雨：持续生成斜向高速飞行的流星
+}
+
+impl RandomEventKind {
+    pub const ALL: [RandomEventKind; 1] = [RandomEventKind::MeteorShower];
+
+    /// 事件持续时间（秒）
+    pub fn duration(self) -> f32 {
+        match self {
+            RandomEventKind::MeteorShower => 10.,
+        }
+    }
+
+    /// 事件开始时的横幅文字
+    pub fn banner(self) -> &'static str {
+        match self {
+            RandomEventKind::MeteorShower => "METEOR SHOWER!",
+        }
+    }
+}
+
+/// 随机事件开始事件（用于显示横幅等）
+#[derive(Event)]
+pub struct RandomEventStarted(pub RandomEventKind);
+
+/// 随机事件调度资源 - 每隔一段随机时间触发一个随机事件，游戏模式可以开启或关闭
+#[derive(Resource, Clone)]
+pub struct EventScheduler {
+    enabled: bool,                            // 是否允许触发随机事件
+    next: Timer,                              // 距下一次事件的计时器
+    active: Option<(RandomEventKind, Timer)>, // 正在进行的事件及其剩余时间
+}
+
+impl FromWorld for EventScheduler {
+    fn from_world(world: &mut World) -> Self {
+        let mut rng = world.get_resource_or_init::<GameRng>();
+        Self {
+            enabled: true,
+            next: Self::roll_next(&mut rng),
+            active: None,
+        }
+    }
+}
+
+impl EventScheduler {
+    /// 开启或关闭随机事件，关闭时立即结束当前事件
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.active = None;
+        }
+    }
+
+    /// 当前正在进行的事件
+    pub fn active(&self) -> Option<RandomEventKind> {
+        self.active.as_ref().map(|(kind, _)| *kind)
+    }
+
+    /// 随机生成距下一次事件的计时器
+    fn roll_next(rng: &mut GameRng) -> Timer {
+        let secs = rng.gen_range(RANDOM_EVENT_INTERVAL.0..RANDOM_EVENT_INTERVAL.1);
+        Timer::from_seconds(secs, TimerMode::Once)
+    }
+}
+
+/// 流星组件 - 流星雨中生成的障碍物，撞到玩家与敌人激光效果相同
+#[derive(Component, Clone)]
+pub struct Meteor;
+
+/// 流星生成计时器资源
+#[derive(Resource, Clone)]
+struct MeteorSpawnTimer(Timer);
+
+/// 事件横幅组件 - 计时结束后隐藏
+#[derive(Component)]
+struct EventBanner {
+    timer: Timer, // 横幅剩余显示时间
+}
+
+/// 随机事件插件 - 调度随机事件，处理流星雨并显示事件横幅
+pub struct RandomEventPlugin;
+
+impl Plugin for RandomEventPlugin {
+    fn build(&self, app: &mut App) {
+        // 初始化调度资源和流星生成计时器
+        app.init_resource::<EventScheduler>()
+            .rollback_resource::<EventScheduler>()
+            .rollback_resource::<MeteorSpawnTimer>()
+            .rollback_component::<Meteor>()
+            .insert_resource(MeteorSpawnTimer(Timer::from_seconds(
+                METEOR_SPAWN_INTERVAL,
+                TimerMode::Repeating,
+            )))
+            .add_event::<RandomEventStarted>() // 注册随机事件开始事件
+            // 创建事件横幅（默认隐藏）
+            .add_systems(Startup, event_banner_setup_system)
+            // 推进事件调度，并在流星雨期间生成流星
+            .add_systems(
+                FixedUpdate,
+                (event_scheduler_system, meteor_spawn_system)
+                    .chain()
+                    .in_set(GameSet::Spawn),
+            )
+            // 事件开始时显示横幅，到时隐藏
+            .add_systems(Update, event_banner_system.in_set(GameSet::Presentation));
+    }
+}
+
+/// 事件调度系统 - 推进当前事件或下一次事件的计时器
+fn event_scheduler_system(
+    time: Res<Time>,
+    mut scheduler: ResMut<EventScheduler>,
+    mut rng: ResMut<GameRng>,
+    mut started_events: EventWriter<RandomEventStarted>,
+) {
+    if !scheduler.enabled {
+        return;
+    }
+
+    // 当前事件结束后才开始计算下一次事件
+    if let Some((_, remaining)) = &mut scheduler.active {
+        if remaining.tick(time.delta()).finished() {
+            scheduler.active = None;
+        }
+        return;
+    }
+
+    if scheduler.next.tick(time.delta()).finished() {
+        let kinds = RandomEventKind::ALL;
+        let kind = kinds[rng.gen_range(0..kinds.len())];
+        scheduler.active = Some((kind, Timer::from_seconds(kind.duration(), TimerMode::Once)));
+        scheduler.next = EventScheduler::roll_next(&mut rng);
+        started_events.send(RandomEventStarted(kind));
+    }
+}
+
+/// 流星生成系统 - 流星雨期间从屏幕上方斜向发射流星
+fn meteor_spawn_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    win_size: Res<WinSize>,
+    scheduler: Res<EventScheduler>,
+    mut spawn_timer: ResMut<MeteorSpawnTimer>,
+    mut rng: ResMut<GameRng>,
+) {
+    if scheduler.active() != Some(RandomEventKind::MeteorShower) {
+        return;
+    }
+    if !spawn_timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let w_span = win_size.w / 2. - METEOR_SIZE.0;
+    let x = rng.gen_range(-w_span..w_span);
+    let y = win_size.h / 2. + METEOR_SIZE.1;
+    // 朝屏幕中央一侧斜向飞行，避免刚生成就飞出屏幕
+    let vx = -x.signum() * rng.gen_range(0.4..0.8);
+    let vy = -rng.gen_range(1.2..1.6);
+
+    commands
+        .spawn((
+            Sprite::from_color(METEOR_COLOR, Vec2::new(METEOR_SIZE.0, METEOR_SIZE.1)),
+            Transform::from_xyz(x, y, 8.),
+        ))
+        .insert(Meteor) // 标记为流星
+        .insert(Team::ENEMY) // 与敌人激光一样属于敌方队伍，会击毁玩家
+        .insert(SpriteSize::from(METEOR_SIZE)) // 设置碰撞尺寸
+        .insert(Movable { auto_despawn: true }) // 超出屏幕自动销毁
+        .insert(Velocity { x: vx, y: vy }); // 斜向高速飞行
+}
+
+/// 事件横幅初始化系统
+fn event_banner_setup_system(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                top: Val::Percent(30.),
+                justify_content: JustifyContent::Center,
+                ..Default::default()
+            },
+            GlobalZIndex(5),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                EventBanner {
+                    timer: Timer::from_seconds(BANNER_SECS, TimerMode::Once),
+                },
+                Text::new(""),
+                TextFont {
+                    font_size: 32.,
+                    ..Default::default()
+                },
+                TextColor(Color::srgb(1., 0.6, 0.2)),
+                Visibility::Hidden,
+            ));
+        });
+}
+
+/// 事件横幅系统 - 随机事件开始时显示横幅文字，显示时间结束后隐藏
+fn event_banner_system(
+    time: Res<Time>,
+    mut started_events: EventReader<RandomEventStarted>,
+    mut query: Query<(&mut EventBanner, &mut Text, &mut Visibility)>,
+) {
+    let started = started_events.read().last().map(|event| event.0);
+
+    for (mut banner, mut text, mut visibility) in &mut query {
+        if let Some(kind) = started {
+            text.0 = kind.banner().to_string();
+            banner.timer.reset();
+            *visibility = Visibility::Visible;
+        } else if banner.timer.tick(time.delta()).just_finished() {
+            *visibility = Visibility::Hidden;
+        }
+    }
+}
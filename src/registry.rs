@@ -0,0 +1,403 @@
+use crate::mods::ModList;
+use bevy::asset::{AssetLoadFailedEvent, RenderAssetUsages};
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+// 资源目录
+const ASSETS_DIR: &str = "assets";
+// 资源清单文件路径
+const MANIFEST_PATH: &str = "assets/manifest.ron";
+// 占位纹理的边长（像素）
+const PLACEHOLDER_SIZE: u32 = 64;
+// 占位纹理棋盘格的格子边长（像素）
+const PLACEHOLDER_CELL: u32 = 8;
+// 静音占位音效的采样率
+const SILENT_SAMPLE_RATE: u32 = 8000;
+
+/// 纹理键 - 资源清单中的纹理条目
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize)]
+pub enum Tex {
+    Player,      // 玩家战机（A型）
+    PlayerB,     // B型战机
+    PlayerLaser, // 玩家激光
+    Enemy,       // 敌人（标准主题）
+    EnemyDesert, // 敌人（沙漠主题）
+    EnemyIce,    // 敌人（冰原主题）
+    EnemyVoid,   // 敌人（虚空主题）
+    EnemyLaser,  // 敌人激光
+    Repair,      // 维修包
+    Explosion,   // 爆炸序列帧图
+}
+
+impl Tex {
+    pub const ALL: [Tex; 10] = [
+        Tex::Player,
+        Tex::PlayerB,
+        Tex::PlayerLaser,
+        Tex::Enemy,
+        Tex::EnemyDesert,
+        Tex::EnemyIce,
+        Tex::EnemyVoid,
+        Tex::EnemyLaser,
+        Tex::Repair,
+        Tex::Explosion,
+    ];
+
+    /// 是否打包进精灵图集（爆炸序列帧图使用独立的网格布局）
+    pub fn packed(self) -> bool {
+        self != Tex::Explosion
+    }
+}
+
+/// 音效键 - 资源清单中的音效条目
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize)]
+pub enum Sfx {
+    EnemyExplosion, // 敌人爆炸
+    EmptyClick,     // 弹药耗尽空膛声
+    Repair,         // 维修包拾取
+}
+
+impl Sfx {
+    pub const ALL: [Sfx; 3] = [Sfx::EnemyExplosion, Sfx::EmptyClick, Sfx::Repair];
+}
+
+/// 爆炸序列帧图的网格 - 每帧的像素尺寸和行列数
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct ExplosionGrid {
+    pub tile: UVec2,  // 每帧的像素尺寸
+    pub columns: u32, // 列数
+    pub rows: u32,    // 行数
+}
+
+impl Default for ExplosionGrid {
+    fn default() -> Self {
+        Self {
+            tile: UVec2::new(64, 64),
+            columns: 4,
+            rows: 4,
+        }
+    }
+}
+
+impl ExplosionGrid {
+    /// 按网格切分的图集布局
+    pub fn layout(self) -> TextureAtlasLayout {
+        TextureAtlasLayout::from_grid(self.tile, self.columns, self.rows, None, None)
+    }
+
+    /// 动画帧数
+    pub fn frames(self) -> usize {
+        (self.columns * self.rows) as usize
+    }
+}
+
+/// 资源清单 - 各纹理、音效键对应的资源路径（相对assets目录）以及爆炸序列帧图的网格
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct AssetManifest {
+    textures: HashMap<Tex, String>, // 纹理路径
+    sounds: HashMap<Sfx, String>,   // 音效路径
+    explosion_grid: ExplosionGrid,  // 爆炸序列帧图的网格
+}
+
+impl AssetManifest {
+    /// 从清单文件读取，文件不存在或格式错误时清单为空
+    fn load() -> Self {
+        match read_asset_text(MANIFEST_PATH) {
+            Ok(content) => ron::from_str(&content).unwrap_or_else(|e| {
+                warn!("资源清单格式错误: {e}");
+                Self::default()
+            }),
+            Err(e) => {
+                warn!("读取资源清单失败: {e}");
+                Self::default()
+            }
+        }
+    }
+}
+
+/// 资源注册表 - 按键查找资源清单中加载的纹理和音效；图集精灵额外记录在共享图集中的索引（加载阶段打包后填入）
+#[derive(Resource, Default)]
+pub struct AssetRegistry {
+    textures: HashMap<Tex, Handle<Image>>,            // 全部纹理
+    sounds: HashMap<Sfx, Handle<AudioSource>>,        // 全部音效
+    atlas: Handle<Image>,                             // 共享精灵图集
+    atlas_layout: Handle<TextureAtlasLayout>,         // 共享精灵图集布局
+    atlas_index: HashMap<Tex, usize>,                 // 图集精灵在图集中的索引
+    pub explosion_layout: Handle<TextureAtlasLayout>, // 爆炸序列帧图的网格布局
+    pub explosion_grid: ExplosionGrid,                // 爆炸序列帧图的网格
+    placeholder: Handle<Image>,                       // 缺失纹理使用的洋红色占位纹理
+    silent: Handle<AudioSource>,                      // 缺失音效使用的静音占位音效
+}
+
+impl AssetRegistry {
+    /// 按资源清单加载全部纹理和音效，并创建爆炸序列帧图的网格布局；模组包中与清单路径相同的文件代替原文件
+    /// 启动时检查清单中的每个文件，未登记或文件不存在的资源直接使用占位资源，并列出全部缺失的文件
+    pub fn load(
+        asset_server: &AssetServer,
+        mods: &ModList,
+        images: &mut Assets<Image>,
+        audio: &mut Assets<AudioSource>,
+        layouts: &mut Assets<TextureAtlasLayout>,
+    ) -> Self {
+        let manifest = AssetManifest::load();
+        let explosion_layout = layouts.add(manifest.explosion_grid.layout());
+        let placeholder = images.add(placeholder_image());
+        let silent = audio.add(silent_audio());
+        let mut missing = Vec::new();
+
+        let mut textures = HashMap::new();
+        for key in Tex::ALL {
+            let handle = match manifest.textures.get(&key) {
+                Some(path) if asset_exists(path) || mods.contains(path) => {
+                    asset_server.load(mods.asset_path(path))
+                }
+                Some(path) => {
+                    missing.push(path.clone());
+                    placeholder.clone()
+                }
+                None => {
+                    missing.push(format!("{key:?}（清单中未登记）"));
+                    placeholder.clone()
+                }
+            };
+            textures.insert(key, handle);
+        }
+        let mut sounds = HashMap::new();
+        for key in Sfx::ALL {
+            let handle = match manifest.sounds.get(&key) {
+                Some(path) if asset_exists(path) || mods.contains(path) => {
+                    asset_server.load(mods.asset_path(path))
+                }
+                Some(path) => {
+                    missing.push(path.clone());
+                    silent.clone()
+                }
+                None => {
+                    missing.push(format!("{key:?}（清单中未登记）"));
+                    silent.clone()
+                }
+            };
+            sounds.insert(key, handle);
+        }
+
+        if !missing.is_empty() {
+            warn!(
+                "缺少{}个资源文件，已使用占位资源代替: {}",
+                missing.len(),
+                missing.join(", ")
+            );
+        }
+
+        Self {
+            textures,
+            sounds,
+            explosion_layout,
+            explosion_grid: manifest.explosion_grid,
+            placeholder,
+            silent,
+            ..Default::default()
+        }
+    }
+
+    /// 把加载失败的纹理替换为占位纹理，返回是否找到对应的条目
+    fn replace_failed_texture(&mut self, id: AssetId<Image>) -> bool {
+        let placeholder = self.placeholder.clone();
+        let mut replaced = false;
+        for handle in self.textures.values_mut() {
+            if handle.id() == id {
+                *handle = placeholder.clone();
+                replaced = true;
+            }
+        }
+        replaced
+    }
+
+    /// 把加载失败的音效替换为静音占位音效，返回是否找到对应的条目
+    fn replace_failed_sound(&mut self, id: AssetId<AudioSource>) -> bool {
+        let silent = self.silent.clone();
+        let mut replaced = false;
+        for handle in self.sounds.values_mut() {
+            if handle.id() == id {
+                *handle = silent.clone();
+                replaced = true;
+            }
+        }
+        replaced
+    }
+
+    /// 重新读取资源清单中的爆炸网格并原地替换网格布局（正在播放的爆炸立即使用新布局）
+    pub fn reload_explosion_grid(&mut self, layouts: &mut Assets<TextureAtlasLayout>) {
+        self.explosion_grid = AssetManifest::load().explosion_grid;
+        if let Some(layout) = layouts.get_mut(&self.explosion_layout) {
+            *layout = self.explosion_grid.layout();
+        }
+    }
+
+    /// 纹理句柄（资源缺失时为占位纹理）
+    pub fn texture(&self, key: Tex) -> Handle<Image> {
+        self.textures.get(&key).cloned().unwrap_or_default()
+    }
+
+    /// 音效句柄（资源缺失时为静音占位音效）
+    pub fn sound(&self, key: Sfx) -> Handle<AudioSource> {
+        self.sounds.get(&key).cloned().unwrap_or_default()
+    }
+
+    /// 需要打包进图集的纹理
+    pub fn packed_textures(&self) -> impl Iterator<Item = (Tex, &Handle<Image>)> {
+        self.textures
+            .iter()
+            .filter(|(key, _)| key.packed())
+            .map(|(key, handle)| (*key, handle))
+    }
+
+    /// 记录打包好的图集及各精灵的索引
+    pub fn set_atlas(
+        &mut self,
+        atlas: Handle<Image>,
+        atlas_layout: Handle<TextureAtlasLayout>,
+        atlas_index: HashMap<Tex, usize>,
+    ) {
+        self.atlas = atlas;
+        self.atlas_layout = atlas_layout;
+        self.atlas_index = atlas_index;
+    }
+
+    /// 共享精灵图集及其布局
+    pub fn atlas(&self) -> (&Handle<Image>, &Handle<TextureAtlasLayout>) {
+        (&self.atlas, &self.atlas_layout)
+    }
+
+    /// 共享图集中该索引对应的精灵键
+    pub fn atlas_key(&self, index: usize) -> Option<Tex> {
+        self.atlas_index
+            .iter()
+            .find(|(_, i)| **i == index)
+            .map(|(key, _)| *key)
+    }
+
+    /// 使用图集中的精灵（未打包的精灵使用第0格）
+    pub fn sprite(&self, key: Tex) -> Sprite {
+        Sprite::from_atlas_image(
+            self.atlas.clone(),
+            TextureAtlas {
+                layout: self.atlas_layout.clone(),
+                index: self.atlas_index.get(&key).copied().unwrap_or_default(),
+            },
+        )
+    }
+}
+
+/// 资源文件是否存在于资源目录中（启用embed-assets特性时检查内嵌资源）
+fn asset_exists(path: &str) -> bool {
+    #[cfg(feature = "embed-assets")]
+    return crate::embedded::contains(path);
+    #[cfg(not(feature = "embed-assets"))]
+    Path::new(ASSETS_DIR).join(path).is_file()
+}
+
+/// 资源加载路径（启用embed-assets特性时从内嵌资源来源加载）
+pub fn asset_path(path: &str) -> String {
+    if cfg!(feature = "embed-assets") {
+        format!("embedded://{path}")
+    } else {
+        path.to_owned()
+    }
+}
+
+/// 读取数据文件（如assets/campaign.ron），启用embed-assets特性时读取编译进可执行文件的副本
+pub fn read_asset_text(path: &str) -> std::io::Result<String> {
+    #[cfg(feature = "embed-assets")]
+    return crate::embedded::text(path)
+        .map(str::to_owned)
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, path.to_owned()));
+    #[cfg(not(feature = "embed-assets"))]
+    std::fs::read_to_string(path)
+}
+
+/// 生成洋红与黑色相间的棋盘格占位纹理
+fn placeholder_image() -> Image {
+    let mut data = Vec::with_capacity((PLACEHOLDER_SIZE * PLACEHOLDER_SIZE * 4) as usize);
+    for y in 0..PLACEHOLDER_SIZE {
+        for x in 0..PLACEHOLDER_SIZE {
+            let magenta = (x / PLACEHOLDER_CELL + y / PLACEHOLDER_CELL) % 2 == 0;
+            data.extend_from_slice(if magenta {
+                &[255, 0, 255, 255]
+            } else {
+                &[0, 0, 0, 255]
+            });
+        }
+    }
+    Image::new(
+        Extent3d {
+            width: PLACEHOLDER_SIZE,
+            height: PLACEHOLDER_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    )
+}
+
+/// 生成0.1秒的静音WAV音效（8位单声道）
+fn silent_audio() -> AudioSource {
+    let samples = SILENT_SAMPLE_RATE / 10;
+    let mut bytes = Vec::with_capacity(44 + samples as usize);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + samples).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes()); // fmt块长度
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // PCM格式
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // 单声道
+    bytes.extend_from_slice(&SILENT_SAMPLE_RATE.to_le_bytes()); // 采样率
+    bytes.extend_from_slice(&SILENT_SAMPLE_RATE.to_le_bytes()); // 每秒字节数
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // 每帧字节数
+    bytes.extend_from_slice(&8u16.to_le_bytes()); // 采样位数
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&samples.to_le_bytes());
+    bytes.resize(44 + samples as usize, 128); // 8位PCM以128为静音
+    AudioSource {
+        bytes: Arc::from(bytes),
+    }
+}
+
+/// 资源注册表插件 - 运行中加载失败的纹理和音效替换为占位资源
+pub struct AssetRegistryPlugin;
+
+impl Plugin for AssetRegistryPlugin {
+    fn build(&self, app: &mut App) {
+        // 每帧检查资源加载失败事件
+        app.add_systems(Update, asset_fallback_system);
+    }
+}
+
+/// 占位替换系统 - 文件存在但加载失败（如格式损坏）的资源记录警告并改用占位资源
+pub fn asset_fallback_system(
+    mut registry: ResMut<AssetRegistry>,
+    mut image_events: EventReader<AssetLoadFailedEvent<Image>>,
+    mut audio_events: EventReader<AssetLoadFailedEvent<AudioSource>>,
+) {
+    for event in image_events.read() {
+        if registry.replace_failed_texture(event.id) {
+            warn!(
+                "纹理加载失败，已使用占位纹理代替: {}（{}）",
+                event.path, event.error
+            );
+        }
+    }
+    for event in audio_events.read() {
+        if registry.replace_failed_sound(event.id) {
+            warn!(
+                "音效加载失败，已使用静音代替: {}（{}）",
+                event.path, event.error
+            );
+        }
+    }
+}
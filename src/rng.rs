@@ -0,0 +1,43 @@
+use crate::launch::LaunchOptions;
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+
+/// 随机数资源 - 玩法使用的随机数生成器，由世界持有（固定步长的系统按固定顺序单线程执行，取数顺序每次相同），
+/// 固定种子后整局的随机结果可复现；只影响画面的随机效果不从这里取数
+#[derive(Resource, Clone)]
+pub struct GameRng(StdRng);
+
+impl FromWorld for GameRng {
+    /// 启动参数指定了随机种子时使用该种子，否则使用随机种子
+    fn from_world(world: &mut World) -> Self {
+        match world
+            .get_resource::<LaunchOptions>()
+            .and_then(LaunchOptions::rng_seed)
+        {
+            Some(seed) => Self::seeded(seed),
+            None => Self(StdRng::seed_from_u64(rand::random())),
+        }
+    }
+}
+
+impl GameRng {
+    /// 以指定种子创建生成器
+    pub fn seeded(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        self.0.fill_bytes(dst)
+    }
+}
@@ -0,0 +1,64 @@
+use crate::components::InRun;
+use crate::pool::recycle;
+use crate::wave::Wave;
+use crate::{AppState, EnemyCount, GameStats, PlayerState};
+use bevy::prelude::*;
+
+/// 本局结束事件 - 各模式结算完成后发送，由收尾系统统一清理本局并切换到指定界面
+#[derive(Event, Clone, Copy, Debug)]
+pub struct GameOverEvent {
+    pub next: AppState, // 结束后进入的界面
+}
+
+impl GameOverEvent {
+    /// 结束本局并进入指定界面
+    pub fn to(next: AppState) -> Self {
+        Self { next }
+    }
+}
+
+/// 本局收尾插件 - 收到本局结束事件时销毁全部本局实体、重置本局资源并切换界面，
+/// 避免上一局的敌人、激光或得分残留到下一局
+pub struct RunPlugin;
+
+impl Plugin for RunPlugin {
+    fn build(&self, app: &mut App) {
+        // 注册本局结束事件
+        app.add_event::<GameOverEvent>()
+            // 在本帧全部玩法和结算系统之后收尾
+            .add_systems(PostUpdate, run_teardown_system);
+    }
+}
+
+/// 收尾系统 - 回收全部本局实体（子实体随父实体一起销毁），重置敌人数量、波次、统计和玩家状态，再切换界面
+#[allow(clippy::too_many_arguments)] // 收尾需要同时重置多个资源
+pub fn run_teardown_system(
+    mut commands: Commands,
+    mut events: EventReader<GameOverEvent>,
+    mut enemy_count: ResMut<EnemyCount>,
+    mut wave: ResMut<Wave>,
+    mut stats: ResMut<GameStats>,
+    mut player_state: ResMut<PlayerState>,
+    query: Query<(Entity, Option<&ChildOf>), With<InRun>>,
+    in_run: Query<(), With<InRun>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    // 同一帧收到多个结束事件时以最后一个为准
+    let Some(event) = events.read().last() else {
+        return;
+    };
+
+    for (entity, parent) in &query {
+        if parent.is_some_and(|parent| in_run.contains(parent.parent())) {
+            continue;
+        }
+        // 池化的子弹放回对象池，其他实体直接销毁
+        commands.entity(entity).queue(recycle);
+    }
+    enemy_count.0 = 0;
+    *wave = Wave::default();
+    *stats = GameStats::default();
+    *player_state = PlayerState::default();
+    info!(next = ?event.next, "本局结束，清理本局实体");
+    next_state.set(event.next);
+}
@@ -0,0 +1,366 @@
+use bevy::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// 存档文件 - 持久化到磁盘的数据（设置、玩家档案、最佳成绩回放）
+///
+/// 文件外层为`(version: N, data: (...))`，版本号出现之前写入的文件没有外层，视为版本0。
+/// 修改格式时：把旧结构保留为带版本后缀的类型（如ProfileV1），VERSION加一，
+/// 并在migrate中把旧版本的内容解析为旧结构后逐版转换为当前结构
+pub trait SaveFile: Serialize + DeserializeOwned + Default {
+    const PATH: &'static str; // 文件路径
+    const LABEL: &'static str; // 日志中的名称
+    const VERSION: u32; // 当前格式版本
+
+    /// 把旧版本的文件内容迁移为当前结构（默认只支持版本0：没有外层，内容即为当前结构）
+    fn migrate(version: u32, content: &str) -> Result<Self, String> {
+        match version {
+            0 => ron::from_str(content).or_else(|e| recover_fields(content).ok_or(e.to_string())),
+            _ => Err(format!("不支持的版本{version}")),
+        }
+    }
+
+    /// 整体解析失败时逐字段恢复：传入各字段的名称和原文，返回None表示不支持恢复（默认），整个文件视为无法读取
+    fn recover(_fields: &[(&str, &str)]) -> Option<Self> {
+        None
+    }
+
+    /// 读取后校验取值范围，把超出范围的字段恢复为默认值（默认不校验）
+    fn validate(&mut self) {}
+}
+
+/// 版本探测 - 只读取外层的版本号（其他字段忽略，没有外层时为None）
+#[derive(Deserialize)]
+struct VersionProbe {
+    #[serde(default)]
+    version: Option<u32>,
+}
+
+/// 存档外层（读取）
+#[derive(Deserialize)]
+struct Envelope<T> {
+    data: T,
+}
+
+/// 存档外层（写入）
+#[derive(Serialize)]
+struct EnvelopeRef<'a, T> {
+    version: u32,
+    data: &'a T,
+}
+
+/// 解析文件内容：按版本号直接读取或迁移，比当前版本新的文件（较新版本的游戏写入）视为错误
+pub fn decode<T: SaveFile>(content: &str) -> Result<T, String> {
+    let version = ron::from_str::<VersionProbe>(content)
+        .map_err(|e| e.to_string())?
+        .version
+        .unwrap_or(0);
+    let mut value = if version == T::VERSION {
+        ron::from_str::<Envelope<T>>(content)
+            .map(|envelope| envelope.data)
+            .or_else(|e| {
+                struct_fields(content)
+                    .and_then(|fields| {
+                        let (_, data) = fields.into_iter().find(|(name, _)| *name == "data")?;
+                        recover_fields(data)
+                    })
+                    .ok_or(e.to_string())
+            })?
+    } else if version > T::VERSION {
+        return Err(format!(
+            "文件版本{version}比当前版本{}新，可能由较新版本的游戏写入",
+            T::VERSION
+        ));
+    } else {
+        info!(
+            file = T::LABEL,
+            from = version,
+            to = T::VERSION,
+            "迁移存档格式"
+        );
+        T::migrate(version, content)?
+    };
+    value.validate();
+    Ok(value)
+}
+
+/// 按字段恢复结构体文本（结构体本身无法拆分为字段时为None）
+fn recover_fields<T: SaveFile>(text: &str) -> Option<T> {
+    let fields = struct_fields(text)?;
+    warn!(file = T::LABEL, "存档中有无法读取的字段，逐字段恢复");
+    T::recover(&fields)
+}
+
+/// 读取单个字段的原文，失败时保留字段原值（通常为默认值）并返回错误
+pub fn parse_field<T: DeserializeOwned>(text: &str, field: &mut T) -> Result<(), String> {
+    *field = ron::from_str(text).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// 校验单个字段，无效时记录警告并恢复为默认值
+pub fn reset_invalid<T>(name: &str, field: &mut T, default: T, valid: impl Fn(&T) -> bool) {
+    if !valid(field) {
+        warn!(field = name, "存档字段超出范围，使用默认值");
+        *field = default;
+    }
+}
+
+/// 把`Name(a: 1, b: (2, 3))`形式的结构体文本拆分为各字段的名称和原文（结构体名可省略）；
+/// 只在最外层的逗号处拆分，忽略字符串和//注释中的括号、逗号，格式不对时为None
+pub fn struct_fields(text: &str) -> Option<Vec<(&str, &str)>> {
+    let text = text.trim();
+    let open = text.find('(')?;
+    let name = &text[..open];
+    if !name.trim().chars().all(|c| c.is_alphanumeric() || c == '_') || !text.ends_with(')') {
+        return None;
+    }
+    let body = &text[open + 1..text.len() - 1];
+
+    let mut pieces = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    let mut in_string = false;
+    let mut chars = body.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if in_string {
+            match c {
+                '\\' => {
+                    chars.next(); // 跳过被转义的字符
+                }
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '/' if chars.peek().is_some_and(|&(_, next)| next == '/') => {
+                // 跳过行注释
+                for (_, c) in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                pieces.push(&body[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    pieces.push(&body[start..]);
+
+    pieces
+        .into_iter()
+        .map(strip_comments)
+        .filter(|piece| !piece.is_empty())
+        .map(|piece| {
+            let (name, value) = piece.split_once(':')?;
+            Some((name.trim(), value.trim()))
+        })
+        .collect()
+}
+
+/// 去掉字段原文开头的空白和整行注释
+fn strip_comments(mut piece: &str) -> &str {
+    loop {
+        piece = piece.trim();
+        match piece.strip_prefix("//") {
+            Some(rest) => piece = rest.split_once('\n').map_or("", |(_, rest)| rest),
+            None => return piece,
+        }
+    }
+}
+
+/// 生成带版本号外层的文件内容
+pub fn encode<T: SaveFile>(value: &T) -> Result<String, String> {
+    let envelope = EnvelopeRef {
+        version: T::VERSION,
+        data: value,
+    };
+    ron::ser::to_string_pretty(&envelope, ron::ser::PrettyConfig::default())
+        .map_err(|e| e.to_string())
+}
+
+/// 读取存档，文件不存在时使用默认值；无法解析时先把原文件备份为<路径>.bak再使用默认值，
+/// 之后的保存不会覆盖掉玩家的数据
+pub fn load<T: SaveFile>() -> T {
+    let content = match std::fs::read_to_string(T::PATH) {
+        Ok(content) => content,
+        Err(e) => {
+            info!("没有可读取的{}，使用默认值: {e}", T::LABEL);
+            return T::default();
+        }
+    };
+    match decode(&content) {
+        Ok(value) => value,
+        Err(e) => {
+            let backup = format!("{}.bak", T::PATH);
+            match std::fs::write(&backup, &content) {
+                Ok(()) => warn!(
+                    "{}无法读取（{e}），原文件已备份到{backup}，使用默认值",
+                    T::LABEL
+                ),
+                Err(backup_error) => warn!(
+                    "{}无法读取（{e}），备份原文件失败（{backup_error}），使用默认值",
+                    T::LABEL
+                ),
+            }
+            T::default()
+        }
+    }
+}
+
+/// 写入存档，失败时只记录警告
+pub fn save<T: SaveFile>(value: &T) {
+    let result = encode(value)
+        .and_then(|content| std::fs::write(T::PATH, content).map_err(|e| e.to_string()));
+    match result {
+        Ok(()) => debug!("保存{}", T::LABEL),
+        Err(e) => warn!("保存{}失败: {e}", T::LABEL),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 当前格式：版本2，得分改名为best并新增名称
+    #[derive(Default, Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(default)]
+    struct Record {
+        best: u64,
+        name: String,
+    }
+
+    /// 版本1的格式
+    #[derive(Deserialize)]
+    struct RecordV1 {
+        score: u64,
+    }
+
+    impl From<RecordV1> for Record {
+        fn from(old: RecordV1) -> Self {
+            Self {
+                best: old.score,
+                name: "PLAYER".to_string(),
+            }
+        }
+    }
+
+    impl SaveFile for Record {
+        const PATH: &'static str = "record.ron";
+        const LABEL: &'static str = "测试记录";
+        const VERSION: u32 = 2;
+
+        fn migrate(version: u32, content: &str) -> Result<Self, String> {
+            match version {
+                // 版本0与版本1的结构相同，只是没有外层
+                0 => ron::from_str::<RecordV1>(content)
+                    .map(Record::from)
+                    .map_err(|e| e.to_string()),
+                1 => ron::from_str::<Envelope<RecordV1>>(content)
+                    .map(|envelope| envelope.data.into())
+                    .map_err(|e| e.to_string()),
+                _ => Err(format!("不支持的版本{version}")),
+            }
+        }
+    }
+
+    #[test]
+    fn current_version_round_trips() {
+        let record = Record {
+            best: 1200,
+            name: "ACE".to_string(),
+        };
+        let content = encode(&record).unwrap();
+        assert!(content.contains("version: 2"));
+        assert_eq!(decode::<Record>(&content).unwrap(), record);
+    }
+
+    #[test]
+    fn migrates_unversioned_file() {
+        let record = decode::<Record>("(score: 300)").unwrap();
+        assert_eq!(record.best, 300);
+        assert_eq!(record.name, "PLAYER");
+    }
+
+    #[test]
+    fn migrates_older_version() {
+        let record = decode::<Record>("(version: 1, data: (score: 450))").unwrap();
+        assert_eq!(record.best, 450);
+    }
+
+    #[test]
+    fn rejects_newer_version() {
+        assert!(decode::<Record>("(version: 3, data: (best: 1))").is_err());
+    }
+
+    #[test]
+    fn migrates_unversioned_settings_file() {
+        use crate::settings::Settings;
+        let settings = decode::<Settings>("(ui_scale: 1.25, fullscreen: true)").unwrap();
+        assert_eq!(settings.ui_scale, 1.25);
+        assert!(settings.fullscreen);
+        assert!(settings.vsync); // 旧文件中没有的字段取默认值
+        assert!(encode(&settings).unwrap().contains("version: 1"));
+    }
+
+    #[test]
+    fn splits_struct_fields() {
+        let fields = struct_fields(
+            "Settings(\n    // 注释, 带逗号\n    name: \"a, (b\",\n    size: (1., 2.),\n    list: [1, 2],\n)",
+        )
+        .unwrap();
+        assert_eq!(
+            fields,
+            vec![
+                ("name", "\"a, (b\""),
+                ("size", "(1., 2.)"),
+                ("list", "[1, 2]")
+            ]
+        );
+        assert!(struct_fields("[1, 2]").is_none());
+    }
+
+    #[test]
+    fn invalid_settings_fields_fall_back_individually() {
+        use crate::settings::{GraphicsQuality, Settings};
+        let content = "(version: 1, data: (ui_scale: 1.5, graphics: Ultra, vsync: \"yes\", resolution: (1920., 1080.)))";
+        let settings = decode::<Settings>(content).unwrap();
+        assert_eq!(settings.ui_scale, 1.5);
+        assert_eq!(settings.resolution, (1920., 1080.));
+        assert_eq!(settings.graphics, GraphicsQuality::default());
+        assert!(settings.vsync);
+    }
+
+    #[test]
+    fn out_of_range_settings_are_reset() {
+        use crate::settings::Settings;
+        let content =
+            "(version: 1, data: (ui_scale: 40., joystick_dead_zone: -1., resolution: (0., 1080.)))";
+        let settings = decode::<Settings>(content).unwrap();
+        let default = Settings::default();
+        assert_eq!(settings.ui_scale, default.ui_scale);
+        assert_eq!(settings.joystick_dead_zone, default.joystick_dead_zone);
+        assert_eq!(settings.resolution, default.resolution);
+    }
+
+    #[test]
+    fn default_migration_reads_unversioned_file() {
+        /// 没有重写migrate的存档
+        #[derive(Default, Debug, PartialEq, Serialize, Deserialize)]
+        struct Plain {
+            value: u32,
+        }
+        impl SaveFile for Plain {
+            const PATH: &'static str = "plain.ron";
+            const LABEL: &'static str = "测试";
+            const VERSION: u32 = 1;
+        }
+        assert_eq!(decode::<Plain>("(value: 7)").unwrap(), Plain { value: 7 });
+    }
+}
@@ -0,0 +1,176 @@
+use crate::game_mode::{GameMode, GameModePlugin, ModeOutcome, run_mode_system};
+use crate::profile::Profile;
+use crate::run::GameOverEvent;
+use crate::sim::RollbackAppExt;
+use crate::{AppState, GameSet, GameStats};
+use bevy::prelude::*;
+
+// 得分挑战的时长（秒）
+const SCORE_ATTACK_SECS: f32 = 120.;
+// 得分挑战中敌人生成频率的额外倍率
+const SCORE_ATTACK_SPAWN_SCALE: f32 = 2.;
+// 剩余时间少于该值时倒计时变为红色（秒）
+const SCORE_ATTACK_WARNING_SECS: f32 = 10.;
+
+/// 得分挑战记录资源 - 本局的倒计时和开始时的得分
+#[derive(Resource, Clone)]
+struct ScoreAttackRun {
+    timer: Timer,        // 本局倒计时
+    score_at_start: u64, // 开始时的累计得分（本局得分从这里算起）
+}
+
+impl Default for ScoreAttackRun {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(SCORE_ATTACK_SECS, TimerMode::Once),
+            score_at_start: 0,
+        }
+    }
+}
+
+/// 倒计时文字标记
+#[derive(Component)]
+struct ScoreAttackCountdown;
+
+/// 得分挑战插件 - 限时120秒、只计得分的模式：屏幕上方显示倒计时，敌人生成更密集且没有商店，时间到后记入得分挑战排行榜
+#[derive(Clone)]
+pub struct ScoreAttackPlugin;
+
+impl Plugin for ScoreAttackPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ScoreAttackRun>()
+            .rollback_resource::<ScoreAttackRun>()
+            // 创建倒计时文字（默认隐藏）
+            .add_systems(Startup, score_attack_countdown_setup_system)
+            // 固定步长推进倒计时
+            .add_systems(FixedUpdate, score_attack_tick_system.in_set(GameSet::Input))
+            // 刷新倒计时文字
+            .add_systems(
+                Update,
+                score_attack_countdown_system.in_set(GameSet::Presentation),
+            );
+    }
+}
+
+impl GameModePlugin for ScoreAttackPlugin {
+    fn mode(&self) -> GameMode {
+        GameMode::ScoreAttack
+    }
+
+    fn menu_entry(&self) -> Option<(KeyCode, &'static str)> {
+        Some((KeyCode::KeyS, "S: score attack (2 min)"))
+    }
+
+    /// 重置倒计时并记录开始时的得分
+    fn setup(&self, world: &mut World) {
+        run_mode_system(world, score_attack_start_system);
+    }
+
+    fn spawn_scale(&self) -> f32 {
+        SCORE_ATTACK_SPAWN_SCALE
+    }
+
+    fn shop(&self) -> bool {
+        false
+    }
+
+    /// 倒计时结束
+    fn outcome(&self, world: &World) -> Option<ModeOutcome> {
+        world
+            .resource::<ScoreAttackRun>()
+            .timer
+            .finished()
+            .then_some(ModeOutcome::TimeUp)
+    }
+
+    /// 记入排行榜并返回主菜单
+    fn finish(&self, _outcome: ModeOutcome, world: &mut World) {
+        run_mode_system(world, score_attack_finish_system);
+    }
+}
+
+/// 倒计时初始化系统 - 屏幕上方居中的大号文字
+fn score_attack_countdown_setup_system(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                top: Val::Px(8.),
+                justify_content: JustifyContent::Center,
+                ..Default::default()
+            },
+            GlobalZIndex(5),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                ScoreAttackCountdown,
+                Text::new(""),
+                TextFont {
+                    font_size: 48.,
+                    ..Default::default()
+                },
+                TextColor(Color::WHITE),
+                Visibility::Hidden,
+            ));
+        });
+}
+
+/// 开局系统 - 得分挑战开始时重置倒计时并记录开始时的得分
+fn score_attack_start_system(stats: Res<GameStats>, mut run: ResMut<ScoreAttackRun>) {
+    *run = ScoreAttackRun {
+        score_at_start: stats.score,
+        ..Default::default()
+    };
+}
+
+/// 倒计时系统 - 得分挑战进行中推进倒计时
+fn score_attack_tick_system(time: Res<Time>, mode: Res<GameMode>, mut run: ResMut<ScoreAttackRun>) {
+    if *mode == GameMode::ScoreAttack {
+        run.timer.tick(time.delta());
+    }
+}
+
+/// 结算系统 - 把本局得分记入排行榜，结束本局后返回主菜单
+fn score_attack_finish_system(
+    stats: Res<GameStats>,
+    run: Res<ScoreAttackRun>,
+    mut profile: ResMut<Profile>,
+    mut game_over: EventWriter<GameOverEvent>,
+) {
+    let score = stats.score - run.score_at_start;
+    let rank = profile.record_score_attack(score);
+    profile.save();
+    info!(score, rank = ?rank.map(|rank| rank + 1), "得分挑战结束");
+    game_over.send(GameOverEvent::to(AppState::MainMenu));
+}
+
+/// 倒计时显示系统 - 得分挑战进行中显示剩余时间（分:秒），最后10秒变为红色
+fn score_attack_countdown_system(
+    mode: Res<GameMode>,
+    state: Res<State<AppState>>,
+    run: Res<ScoreAttackRun>,
+    mut query: Query<(&mut Text, &mut TextColor, &mut Visibility), With<ScoreAttackCountdown>>,
+) {
+    let Ok((mut text, mut color, mut visibility)) = query.get_single_mut() else {
+        return;
+    };
+    let active = *mode == GameMode::ScoreAttack && *state.get() == AppState::Playing;
+    visibility.set_if_neq(if active {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    });
+    if !active {
+        return;
+    }
+
+    let remaining = run.timer.remaining_secs();
+    let secs = remaining.ceil() as u32;
+    text.0 = format!("{}:{:02}", secs / 60, secs % 60);
+    color.0 = if remaining < SCORE_ATTACK_WARNING_SECS {
+        Color::srgb(1., 0.3, 0.3)
+    } else {
+        Color::WHITE
+    };
+}
@@ -0,0 +1,358 @@
+use crate::components::{Enemy, FirePeriod, Player};
+use crate::enemy::{Behavior, DoubleShot, FirePattern, ScriptControlled, fire_lasers};
+use crate::faction_time::{Faction, FactionTime};
+use crate::mods::ModList;
+use crate::pool::BulletPool;
+use crate::registry::AssetRegistry;
+use crate::status::StatusEffects;
+use crate::{GameSet, WinSize};
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use bevy::prelude::*;
+use std::collections::HashMap;
+use wasmtime::{
+    Caller, Config, Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder, TypedFunc,
+};
+
+// 行为脚本文件的扩展名（.wat为文本格式，便于编写和调试）
+const SCRIPT_EXTENSIONS: &[&str] = &["wasm", "wat"];
+// 每次调用脚本可消耗的燃料（约等于执行的指令数，耗尽时中止脚本）
+const SCRIPT_FUEL: u64 = 100_000;
+// 每个脚本实例可使用的线性内存上限（字节）
+const SCRIPT_MEMORY: usize = 1 << 20;
+// 脚本可设置的最大移动速度（像素/秒）
+const SCRIPT_MAX_SPEED: f32 = 400.;
+// 扇形开火的最大激光束数
+const SCRIPT_MAX_SPREAD: i32 = 9;
+
+/// 行为脚本 - 编译好的WebAssembly模块（或WAT文本）的原始内容
+#[derive(Asset, TypePath)]
+pub struct BehaviorScript(Vec<u8>);
+
+/// 行为脚本加载器 - 读取.wasm/.wat文件，编译在首次实例化时进行
+#[derive(Default)]
+pub struct BehaviorScriptLoader;
+
+impl AssetLoader for BehaviorScriptLoader {
+    type Asset = BehaviorScript;
+    type Settings = ();
+    type Error = std::io::Error;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<BehaviorScript, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(BehaviorScript(bytes))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        SCRIPT_EXTENSIONS
+    }
+}
+
+/// 脚本状态 - 每个脚本实例可读写的数据，脚本只能通过导入函数访问
+struct ScriptState {
+    player: Vec2,              // 最近的玩家位置（没有玩家时为敌人自身位置）
+    position: Vec2,            // 敌人自身位置
+    velocity: Vec2,            // 脚本设置的速度（像素/秒）
+    fire: Option<FirePattern>, // 本次调用中请求的开火方式
+    limits: StoreLimits,       // 内存和实例数量上限
+}
+
+/// 脚本宿主资源 - WebAssembly引擎、向脚本开放的函数和已编译的模块
+#[derive(Resource)]
+struct ScriptHost {
+    engine: Engine,                                    // 引擎（开启燃料计量）
+    linker: Linker<ScriptState>,                       // 导入函数
+    modules: HashMap<AssetId<BehaviorScript>, Module>, // 已编译的模块
+}
+
+impl ScriptHost {
+    /// 创建引擎并登记env模块中的导入函数：
+    /// player_x/player_y/self_x/self_y() -> f32 读取位置，set_velocity(x, y) 设置速度，
+    /// fire(pattern, count) 开火（0单发、1双发、2扇形count束）
+    fn new() -> wasmtime::Result<Self> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config)?;
+
+        let mut linker = Linker::new(&engine);
+        linker.func_wrap("env", "player_x", |caller: Caller<'_, ScriptState>| {
+            caller.data().player.x
+        })?;
+        linker.func_wrap("env", "player_y", |caller: Caller<'_, ScriptState>| {
+            caller.data().player.y
+        })?;
+        linker.func_wrap("env", "self_x", |caller: Caller<'_, ScriptState>| {
+            caller.data().position.x
+        })?;
+        linker.func_wrap("env", "self_y", |caller: Caller<'_, ScriptState>| {
+            caller.data().position.y
+        })?;
+        linker.func_wrap(
+            "env",
+            "set_velocity",
+            |mut caller: Caller<'_, ScriptState>, x: f32, y: f32| {
+                caller.data_mut().velocity = Vec2::new(x, y);
+            },
+        )?;
+        linker.func_wrap(
+            "env",
+            "fire",
+            |mut caller: Caller<'_, ScriptState>, pattern: i32, count: i32| {
+                caller.data_mut().fire = match pattern {
+                    0 => Some(FirePattern::Single),
+                    1 => Some(FirePattern::Double),
+                    2 => Some(FirePattern::Spread(count.clamp(1, SCRIPT_MAX_SPREAD) as u8)),
+                    _ => None, // 未知的开火方式忽略
+                };
+            },
+        )?;
+
+        Ok(Self {
+            engine,
+            linker,
+            modules: HashMap::new(),
+        })
+    }
+
+    /// 为一个敌人创建脚本实例（模块按脚本资源缓存，只编译一次），脚本需导出update(dt: f32)
+    fn instantiate(
+        &mut self,
+        id: AssetId<BehaviorScript>,
+        script: &BehaviorScript,
+        position: Vec2,
+    ) -> wasmtime::Result<ScriptInstance> {
+        let module = match self.modules.get(&id) {
+            Some(module) => module.clone(),
+            None => {
+                let module = Module::new(&self.engine, &script.0)?;
+                self.modules.insert(id, module.clone());
+                module
+            }
+        };
+        let state = ScriptState {
+            player: position,
+            position,
+            velocity: Vec2::ZERO,
+            fire: None,
+            limits: StoreLimitsBuilder::new()
+                .memory_size(SCRIPT_MEMORY)
+                .instances(1)
+                .build(),
+        };
+        let mut store = Store::new(&self.engine, state);
+        store.limiter(|state| &mut state.limits);
+        store.set_fuel(SCRIPT_FUEL)?;
+        let instance = self.linker.instantiate(&mut store, &module)?;
+        let update = instance.get_typed_func::<f32, ()>(&mut store, "update")?;
+        Ok(ScriptInstance {
+            store,
+            update,
+            cooldown: 0.,
+        })
+    }
+}
+
+/// 行为脚本句柄组件 - 敌人正在使用（或等待加载）的脚本
+#[derive(Component)]
+struct BehaviorScriptHandle(Handle<BehaviorScript>);
+
+/// 脚本实例组件 - 每个敌人独立的脚本状态
+#[derive(Component)]
+struct ScriptInstance {
+    store: Store<ScriptState>,  // 脚本的内存和状态
+    update: TypedFunc<f32, ()>, // 每个固定步长调用一次的update函数
+    cooldown: f32,              // 距下次允许开火的时间（秒）
+}
+
+/// 脚本插件 - 启用scripting特性时，敌人定义中带有behavior的敌人由WebAssembly行为脚本控制移动和开火；
+/// 脚本运行在沙箱中，只能通过导入函数读取位置、设置速度和开火，每次调用限制燃料和内存，出错时该敌人改回按编队移动
+pub struct ScriptingPlugin;
+
+impl Plugin for ScriptingPlugin {
+    fn build(&self, app: &mut App) {
+        let host = match ScriptHost::new() {
+            Ok(host) => host,
+            Err(e) => {
+                error!("创建脚本引擎失败，行为脚本不可用: {e}");
+                return;
+            }
+        };
+        // 注册行为脚本资源类型和加载器，插入脚本宿主资源
+        app.init_asset::<BehaviorScript>()
+            .init_asset_loader::<BehaviorScriptLoader>()
+            .insert_resource(host)
+            // 加载新敌人的脚本，加载完成后创建脚本实例
+            .add_systems(
+                Update,
+                (script_attach_system, script_instantiate_system).chain(),
+            )
+            // 脚本文件被修改（dev特性下热重载）时重新编译并重建实例
+            .add_systems(
+                Update,
+                script_reload_system.run_if(on_event::<AssetEvent<BehaviorScript>>),
+            )
+            // 固定步长调用脚本，按脚本设置的速度移动并开火
+            .add_systems(FixedUpdate, script_update_system.in_set(GameSet::Movement));
+    }
+}
+
+/// 脚本加载系统 - 为新生成的敌人加载定义中指定的脚本（同一脚本只加载一次）
+fn script_attach_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mods: Res<ModList>,
+    query: Query<(Entity, &Behavior), Added<Behavior>>,
+) {
+    for (entity, behavior) in &query {
+        let handle = asset_server.load(mods.asset_path(&behavior.0));
+        commands.entity(entity).insert(BehaviorScriptHandle(handle));
+    }
+}
+
+/// 脚本实例化系统 - 脚本加载完成后为敌人创建实例并交由脚本控制，加载或实例化失败时记录警告并保持编队移动
+fn script_instantiate_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    scripts: Res<Assets<BehaviorScript>>,
+    mut host: ResMut<ScriptHost>,
+    query: Query<(Entity, &Transform, &Behavior, &BehaviorScriptHandle), Without<ScriptInstance>>,
+) {
+    for (entity, transform, behavior, handle) in &query {
+        let Some(script) = scripts.get(&handle.0) else {
+            if asset_server.load_state(&handle.0).is_failed() {
+                warn!(script = %behavior.0, "行为脚本加载失败");
+                commands.entity(entity).remove::<BehaviorScriptHandle>();
+            }
+            continue;
+        };
+        match host.instantiate(handle.0.id(), script, transform.translation.truncate()) {
+            Ok(instance) => {
+                commands.entity(entity).insert((instance, ScriptControlled));
+            }
+            Err(e) => {
+                warn!(script = %behavior.0, "行为脚本实例化失败: {e}");
+                commands.entity(entity).remove::<BehaviorScriptHandle>();
+            }
+        }
+    }
+}
+
+/// 脚本重载系统 - 丢弃被修改的脚本的已编译模块，使用该脚本的敌人在下一帧以新脚本重建实例
+fn script_reload_system(
+    mut commands: Commands,
+    mut host: ResMut<ScriptHost>,
+    mut events: EventReader<AssetEvent<BehaviorScript>>,
+    query: Query<(Entity, &BehaviorScriptHandle), With<ScriptInstance>>,
+) {
+    for event in events.read() {
+        let AssetEvent::Modified { id } = *event else {
+            continue;
+        };
+        host.modules.remove(&id);
+        for (entity, _) in query.iter().filter(|(_, handle)| handle.0.id() == id) {
+            commands
+                .entity(entity)
+                .remove::<(ScriptInstance, ScriptControlled)>();
+        }
+        info!(?id, "重新载入行为脚本");
+    }
+}
+
+/// 脚本更新系统 - 每个固定步长以敌方阵营的时间调用一次update，应用脚本设置的速度（限制在游戏区域内），
+/// 开火请求按开火间隔的下限限流；脚本出错（如燃料耗尽）时移除实例，该敌人改回按编队移动
+#[allow(clippy::too_many_arguments)] // 调用脚本需要同时访问多个资源
+#[allow(clippy::type_complexity)] // 允许复杂的查询类型
+fn script_update_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    faction_time: Res<FactionTime>,
+    win_size: Res<WinSize>,
+    registry: Res<AssetRegistry>,
+    mut pool: ResMut<BulletPool>,
+    player_query: Query<&Transform, (With<Player>, Without<ScriptControlled>)>,
+    mut query: Query<
+        (
+            Entity,
+            &mut ScriptInstance,
+            &mut Transform,
+            &FirePeriod,
+            &Behavior,
+            Has<DoubleShot>,
+            Option<&StatusEffects>,
+        ),
+        (With<Enemy>, With<ScriptControlled>),
+    >,
+) {
+    for (entity, mut instance, mut transform, fire_period, behavior, double_shot, status) in
+        &mut query
+    {
+        if StatusEffects::suppressed(status) {
+            continue;
+        }
+        let delta =
+            faction_time.delta_secs(&time, Faction::Enemy) * StatusEffects::speed_scale(status);
+        let position = transform.translation.truncate();
+        // 最近的玩家（多人对局中可能有多名玩家）
+        let player = player_query
+            .iter()
+            .map(|player| player.translation.truncate())
+            .min_by(|a, b| {
+                a.distance_squared(position)
+                    .total_cmp(&b.distance_squared(position))
+            })
+            .unwrap_or(position);
+
+        let ScriptInstance {
+            store,
+            update,
+            cooldown,
+        } = &mut *instance;
+        let state = store.data_mut();
+        state.player = player;
+        state.position = position;
+        state.fire = None;
+        let result = store
+            .set_fuel(SCRIPT_FUEL)
+            .and_then(|()| update.call(&mut *store, delta));
+        if let Err(e) = result {
+            warn!(script = %behavior.0, "行为脚本出错，改回按编队移动: {e}");
+            commands
+                .entity(entity)
+                .remove::<(ScriptInstance, ScriptControlled, BehaviorScriptHandle)>();
+            continue;
+        }
+
+        // 按脚本设置的速度移动（非有限值视为静止）
+        let state = store.data_mut();
+        let velocity = if state.velocity.is_finite() {
+            state.velocity.clamp_length_max(SCRIPT_MAX_SPEED)
+        } else {
+            Vec2::ZERO
+        };
+        let (half_w, half_h) = (win_size.w / 2., win_size.h / 2.);
+        transform.translation.x =
+            (transform.translation.x + velocity.x * delta).clamp(-half_w, half_w);
+        transform.translation.y =
+            (transform.translation.y + velocity.y * delta).clamp(-half_h, half_h);
+
+        // 开火请求按开火间隔的下限限流
+        *cooldown -= delta;
+        let fire = state.fire.take();
+        if let Some(pattern) = fire.filter(|_| *cooldown <= 0.) {
+            *cooldown = fire_period.min;
+            fire_lasers(
+                &mut commands,
+                &registry,
+                &mut pool,
+                transform.translation,
+                pattern.shots(double_shot),
+            );
+        }
+    }
+}
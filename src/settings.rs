@@ -0,0 +1,273 @@
+use crate::action::{Action, ActionState};
+use crate::save::{self, SaveFile};
+use crate::{GameSet, WINDOW_SIZE};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+// 设置文件路径
+const SETTINGS_PATH: &str = "settings.ron";
+// 界面缩放的范围
+pub const UI_SCALE_MIN: f32 = 0.75;
+pub const UI_SCALE_MAX: f32 = 1.5;
+// 触屏虚拟摇杆直径的范围（像素）
+const JOYSTICK_SIZE_MIN: f32 = 60.;
+const JOYSTICK_SIZE_MAX: f32 = 400.;
+// 触屏虚拟摇杆死区的上限（占半径的比例）
+const JOYSTICK_DEAD_ZONE_MAX: f32 = 0.9;
+// 窗口分辨率的范围（宽，高）
+const RESOLUTION_MIN: (f32, f32) = (320., 240.);
+const RESOLUTION_MAX: (f32, f32) = (7680., 4320.);
+// 选项界面中可选的窗口分辨率（前三项为游戏区域的1倍、1.5倍和2倍）
+pub const RESOLUTIONS: [(f32, f32); 7] = [
+    WINDOW_SIZE,
+    (897., 1014.),
+    (1196., 1352.),
+    (1280., 720.),
+    (1920., 1080.),
+    (2560., 1440.),
+    (3840., 2160.),
+];
+
+/// 连发模式 - 控制玩家射击的触发方式
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AutoFire {
+    #[default]
+    Off, // 关闭：每次按下空格发射一次
+    Hold,   // 按住空格时按冷却时间持续发射
+    Always, // 始终按冷却时间自动发射
+}
+
+impl AutoFire {
+    /// 切换到下一个连发模式（循环）
+    pub fn next(self) -> Self {
+        match self {
+            AutoFire::Off => AutoFire::Hold,
+            AutoFire::Hold => AutoFire::Always,
+            AutoFire::Always => AutoFire::Off,
+        }
+    }
+}
+
+/// 帧率上限 - 不限制时由垂直同步或显示器决定帧率
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FrameCap {
+    #[default]
+    Unlimited, // 不限制
+    Fps60,  // 60帧/秒
+    Fps120, // 120帧/秒
+}
+
+impl FrameCap {
+    /// 切换到下一个帧率上限（循环）
+    pub fn next(self) -> Self {
+        match self {
+            FrameCap::Unlimited => FrameCap::Fps60,
+            FrameCap::Fps60 => FrameCap::Fps120,
+            FrameCap::Fps120 => FrameCap::Unlimited,
+        }
+    }
+
+    /// 每帧的最短时间（不限制时为None）
+    pub fn frame_time(self) -> Option<Duration> {
+        match self {
+            FrameCap::Unlimited => None,
+            FrameCap::Fps60 => Some(Duration::from_secs_f64(1. / 60.)),
+            FrameCap::Fps120 => Some(Duration::from_secs_f64(1. / 120.)),
+        }
+    }
+}
+
+/// 画质 - 低画质关闭关卡背景图、合并相邻的爆炸并减少同时存在的爆炸，适合集成显卡和网页版本
+/// （游戏中没有泛光、粒子拖尾和视差滚动层，关卡背景图是唯一的背景层）
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GraphicsQuality {
+    #[default]
+    High, // 高画质
+    Low, // 低画质
+}
+
+impl GraphicsQuality {
+    /// 是否为低画质
+    pub fn is_low(self) -> bool {
+        self == GraphicsQuality::Low
+    }
+}
+
+/// 游戏设置资源 - 存储玩家可调整的选项
+#[derive(Resource, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub auto_fire: AutoFire,       // 连发模式
+    pub assist: bool,              // 辅助模式：玩家没有按方向键时由自动驾驶躲避和瞄准
+    pub ghost: bool,               // 显示最佳成绩回放的幽灵战机
+    pub joystick_size: f32,        // 触屏虚拟摇杆的直径（像素，开火按钮为其一半）
+    pub joystick_dead_zone: f32,   // 触屏虚拟摇杆的死区（占半径的比例）
+    pub ui_scale: f32,             // 界面缩放（UI_SCALE_MIN~UI_SCALE_MAX）
+    pub resolution: (f32, f32),    // 窗口分辨率（宽，高；游戏区域按比例缩放，多出的部分显示黑边）
+    pub fullscreen: bool,          // 无边框全屏（带有--windowed参数时忽略）
+    pub vsync: bool,               // 垂直同步
+    pub frame_cap: FrameCap,       // 帧率上限
+    pub graphics: GraphicsQuality, // 画质
+    pub sfx_volume: f32,           // 音效音量（0~1，不影响背景音乐）
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            auto_fire: AutoFire::default(),
+            assist: false,
+            ghost: true,
+            joystick_size: 160.,
+            joystick_dead_zone: 0.15,
+            ui_scale: 1.,
+            resolution: WINDOW_SIZE,
+            fullscreen: false,
+            vsync: true,
+            frame_cap: FrameCap::default(),
+            graphics: GraphicsQuality::default(),
+            sfx_volume: 1.,
+        }
+    }
+}
+
+impl SaveFile for Settings {
+    const PATH: &'static str = SETTINGS_PATH;
+    const LABEL: &'static str = "设置文件";
+    const VERSION: u32 = 1;
+
+    /// 逐字段读取，无效的字段（类型不对、未知的选项）使用默认值，未知的字段忽略
+    fn recover(fields: &[(&str, &str)]) -> Option<Self> {
+        let mut settings = Self::default();
+        for &(name, text) in fields {
+            let result = match name {
+                "auto_fire" => save::parse_field(text, &mut settings.auto_fire),
+                "assist" => save::parse_field(text, &mut settings.assist),
+                "ghost" => save::parse_field(text, &mut settings.ghost),
+                "joystick_size" => save::parse_field(text, &mut settings.joystick_size),
+                "joystick_dead_zone" => save::parse_field(text, &mut settings.joystick_dead_zone),
+                "ui_scale" => save::parse_field(text, &mut settings.ui_scale),
+                "resolution" => save::parse_field(text, &mut settings.resolution),
+                "fullscreen" => save::parse_field(text, &mut settings.fullscreen),
+                "vsync" => save::parse_field(text, &mut settings.vsync),
+                "frame_cap" => save::parse_field(text, &mut settings.frame_cap),
+                "graphics" => save::parse_field(text, &mut settings.graphics),
+                "sfx_volume" => save::parse_field(text, &mut settings.sfx_volume),
+                _ => Ok(()),
+            };
+            if let Err(e) = result {
+                warn!(field = name, "设置项无效，使用默认值: {e}");
+            }
+        }
+        Some(settings)
+    }
+
+    /// 超出范围的数值（包括NaN）恢复为默认值
+    fn validate(&mut self) {
+        let default = Self::default();
+        save::reset_invalid("ui_scale", &mut self.ui_scale, default.ui_scale, |scale| {
+            (UI_SCALE_MIN..=UI_SCALE_MAX).contains(scale)
+        });
+        save::reset_invalid(
+            "joystick_size",
+            &mut self.joystick_size,
+            default.joystick_size,
+            |size| (JOYSTICK_SIZE_MIN..=JOYSTICK_SIZE_MAX).contains(size),
+        );
+        save::reset_invalid(
+            "joystick_dead_zone",
+            &mut self.joystick_dead_zone,
+            default.joystick_dead_zone,
+            |dead_zone| (0. ..=JOYSTICK_DEAD_ZONE_MAX).contains(dead_zone),
+        );
+        save::reset_invalid(
+            "sfx_volume",
+            &mut self.sfx_volume,
+            default.sfx_volume,
+            |volume| (0. ..=1.).contains(volume),
+        );
+        save::reset_invalid(
+            "resolution",
+            &mut self.resolution,
+            default.resolution,
+            |&(w, h)| {
+                (RESOLUTION_MIN.0..=RESOLUTION_MAX.0).contains(&w)
+                    && (RESOLUTION_MIN.1..=RESOLUTION_MAX.1).contains(&h)
+            },
+        );
+    }
+}
+
+impl Settings {
+    /// 从设置文件读取（旧版本的文件先迁移），文件不存在或无法读取时使用默认设置
+    pub fn load() -> Self {
+        save::load()
+    }
+
+    /// 写入设置文件，失败时只记录警告
+    pub fn save(&self) {
+        save::save(self);
+    }
+}
+
+/// 设置插件 - 管理设置资源和游戏内快捷键
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        // 初始化设置资源
+        app.init_resource::<Settings>()
+            // 处理连发模式、辅助模式和幽灵回放切换按键
+            .add_systems(
+                Update,
+                (
+                    auto_fire_toggle_system,
+                    assist_toggle_system,
+                    ghost_toggle_system,
+                )
+                    .in_set(GameSet::Input),
+            );
+    }
+}
+
+/// 设置文件插件 - 设置变化时写回设置文件（窗口模式启动时先读取设置文件以创建窗口；无窗口模拟和测试使用默认设置）
+pub struct SettingsFilePlugin;
+
+impl Plugin for SettingsFilePlugin {
+    fn build(&self, app: &mut App) {
+        // 设置变化时保存
+        app.add_systems(
+            Last,
+            settings_save_system
+                .run_if(resource_changed::<Settings>.and(not(resource_added::<Settings>))),
+        );
+    }
+}
+
+/// 设置保存系统
+fn settings_save_system(settings: Res<Settings>) {
+    settings.save();
+}
+
+/// 连发切换系统 - 切换连发动作（F键或手柄Select）循环切换连发模式
+fn auto_fire_toggle_system(actions: Res<ActionState>, mut settings: ResMut<Settings>) {
+    if actions.just_pressed(Action::ToggleAutoFire) {
+        settings.auto_fire = settings.auto_fire.next();
+    }
+}
+
+/// 辅助模式切换系统 - 切换辅助动作（A键）开关辅助模式
+fn assist_toggle_system(actions: Res<ActionState>, mut settings: ResMut<Settings>) {
+    if actions.just_pressed(Action::ToggleAssist) {
+        settings.assist = !settings.assist;
+        info!(assist = settings.assist, "切换辅助模式");
+    }
+}
+
+/// 幽灵回放切换系统 - 切换幽灵动作（G键）开关最佳成绩回放的显示
+fn ghost_toggle_system(actions: Res<ActionState>, mut settings: ResMut<Settings>) {
+    if actions.just_pressed(Action::ToggleGhost) {
+        settings.ghost = !settings.ghost;
+        info!(ghost = settings.ghost, "切换幽灵回放");
+    }
+}
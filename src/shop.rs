@@ -0,0 +1,252 @@
+use crate::action::Action;
+use crate::components::{FireCooldown, MoveSpeed, Player};
+use crate::player::PLAYER_SPEED;
+use crate::profile::Profile;
+use crate::sim::RollbackAppExt;
+use crate::sim::TickInput;
+use crate::wave::{Wave, WavePhase};
+use crate::weapon::Loadout;
+use crate::{GameSet, PLAYER_BOMBS_MAX, PLAYER_FIRE_COOLDOWN, PlayerState};
+use bevy::prelude::*;
+use std::time::Duration;
+
+// 每级射速强化后的开火冷却倍率
+const FIRE_RATE_STEP: f32 = 0.85;
+// 每级速度强化增加的移动速度比例
+const SPEED_STEP: f32 = 0.15;
+// 射速与速度强化的最高等级
+const UPGRADE_LEVEL_MAX: u32 = 3;
+
+/// 废料资源 - 本局持有的废料货币
+#[derive(Resource, Clone, Default)]
+pub struct Scrap(pub u32);
+
+/// 本局强化资源 - 商店购买的强化等级，本局内持续生效
+#[derive(Resource, Clone, Default)]
+pub struct RunUpgrades {
+    pub fire_rate: u32, // 射速强化等级
+    pub speed: u32,     // 速度强化等级
+}
+
+impl RunUpgrades {
+    /// 当前强化等级下的开火冷却（秒）
+    pub fn fire_cooldown(&self) -> f32 {
+        PLAYER_FIRE_COOLDOWN * FIRE_RATE_STEP.powi(self.fire_rate as i32)
+    }
+
+    /// 当前强化等级下的移动速度（叠加技能树的永久加成）
+    pub fn move_speed(&self, profile: &Profile) -> f32 {
+        PLAYER_SPEED * profile.speed_scale() * (1. + SPEED_STEP * self.speed as f32)
+    }
+}
+
+/// 商店商品
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ShopItem {
+    FireRate,   // 射速强化
+    Speed,      // 速度强化
+    ExtraLife,  // 额外生命
+    BombRefill, // 补满炸弹
+}
+
+impl ShopItem {
+    const ALL: [ShopItem; 4] = [
+        ShopItem::FireRate,
+        ShopItem::Speed,
+        ShopItem::ExtraLife,
+        ShopItem::BombRefill,
+    ];
+
+    /// 购买槽位（1~4键）
+    fn slot(self) -> u8 {
+        match self {
+            ShopItem::FireRate => 1,
+            ShopItem::Speed => 2,
+            ShopItem::ExtraLife => 3,
+            ShopItem::BombRefill => 4,
+        }
+    }
+
+    /// 价格（废料）
+    fn cost(self) -> u32 {
+        match self {
+            ShopItem::FireRate => 30,
+            ShopItem::Speed => 30,
+            ShopItem::ExtraLife => 50,
+            ShopItem::BombRefill => 20,
+        }
+    }
+
+    /// 界面显示名称
+    fn name(self) -> &'static str {
+        match self {
+            ShopItem::FireRate => "Fire rate +",
+            ShopItem::Speed => "Speed +",
+            ShopItem::ExtraLife => "Extra life",
+            ShopItem::BombRefill => "Refill bombs",
+        }
+    }
+
+    /// 当前是否还能购买（强化未满级、炸弹未满）
+    fn available(self, upgrades: &RunUpgrades, player_state: &PlayerState) -> bool {
+        match self {
+            ShopItem::FireRate => upgrades.fire_rate < UPGRADE_LEVEL_MAX,
+            ShopItem::Speed => upgrades.speed < UPGRADE_LEVEL_MAX,
+            ShopItem::ExtraLife => true,
+            ShopItem::BombRefill => player_state.bombs < PLAYER_BOMBS_MAX,
+        }
+    }
+}
+
+/// 商店界面根节点标记
+#[derive(Component)]
+struct ShopScreen;
+
+/// 商店界面文本标记
+#[derive(Component)]
+struct ShopText;
+
+/// 商店插件 - 波次间隙开放商店，用废料购买本局强化
+pub struct ShopPlugin;
+
+impl Plugin for ShopPlugin {
+    fn build(&self, app: &mut App) {
+        // 初始化废料与强化资源
+        app.init_resource::<Scrap>()
+            .init_resource::<RunUpgrades>()
+            .rollback_resource::<Scrap>()
+            .rollback_resource::<RunUpgrades>()
+            // 创建商店界面（默认隐藏）
+            .add_systems(Startup, shop_screen_setup_system)
+            // 处理购买与离开商店按键
+            .add_systems(FixedUpdate, shop_purchase_system.in_set(GameSet::Input))
+            // 刷新商店界面
+            .add_systems(Update, shop_screen_system.in_set(GameSet::Presentation));
+    }
+}
+
+/// 商店界面初始化系统
+fn shop_screen_setup_system(mut commands: Commands) {
+    commands
+        .spawn((
+            ShopScreen,
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            BackgroundColor(Color::srgba(0., 0., 0.1, 0.85)),
+            GlobalZIndex(10),
+            Visibility::Hidden,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                ShopText,
+                Text::new(""),
+                TextFont {
+                    font_size: 18.,
+                    ..Default::default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// 购买系统 - 商店开放时按1~4购买商品，按回车开始下一波
+#[allow(clippy::type_complexity)] // 允许复杂的查询类型
+fn shop_purchase_system(
+    input: Res<TickInput>,
+    mut wave: ResMut<Wave>,
+    mut scrap: ResMut<Scrap>,
+    mut upgrades: ResMut<RunUpgrades>,
+    mut player_state: ResMut<PlayerState>,
+    profile: Res<Profile>,
+    mut player_query: Query<(&mut FireCooldown, &mut MoveSpeed, &Loadout), With<Player>>,
+) {
+    if wave.phase != WavePhase::Shop {
+        return;
+    }
+
+    for item in ShopItem::ALL {
+        if !input.just_pressed(Action::Slot(item.slot()))
+            || scrap.0 < item.cost()
+            || !item.available(&upgrades, &player_state)
+        {
+            continue;
+        }
+
+        scrap.0 -= item.cost();
+        match item {
+            ShopItem::FireRate => upgrades.fire_rate += 1,
+            ShopItem::Speed => upgrades.speed += 1,
+            ShopItem::ExtraLife => player_state.lives += 1,
+            ShopItem::BombRefill => player_state.bombs = PLAYER_BOMBS_MAX,
+        }
+
+        // 强化立即作用于当前的玩家实体，重生时由玩家生成系统重新应用
+        if let Ok((mut cooldown, mut move_speed, loadout)) = player_query.get_single_mut() {
+            cooldown.0.set_duration(Duration::from_secs_f32(
+                loadout.current().cooldown(&upgrades),
+            ));
+            move_speed.0 = upgrades.move_speed(&profile);
+        }
+    }
+
+    if input.just_pressed(Action::MenuConfirm) {
+        wave.start_next();
+    }
+}
+
+/// 商店界面系统 - 商店开放时显示界面并列出商品
+fn shop_screen_system(
+    wave: Res<Wave>,
+    scrap: Res<Scrap>,
+    upgrades: Res<RunUpgrades>,
+    player_state: Res<PlayerState>,
+    mut screen_query: Query<&mut Visibility, With<ShopScreen>>,
+    mut text_query: Query<&mut Text, With<ShopText>>,
+) {
+    let open = wave.phase == WavePhase::Shop;
+    for mut visibility in &mut screen_query {
+        visibility.set_if_neq(if open {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        });
+    }
+
+    let changed = wave.is_changed()
+        || scrap.is_changed()
+        || upgrades.is_changed()
+        || player_state.is_changed();
+    if !open || !changed {
+        return;
+    }
+
+    let mut lines = vec![
+        format!("WAVE {} CLEAR", wave.number),
+        format!("Scrap: {}", scrap.0),
+        String::new(),
+    ];
+    for (i, item) in ShopItem::ALL.into_iter().enumerate() {
+        let status = if item.available(&upgrades, &player_state) {
+            format!("{} scrap", item.cost())
+        } else {
+            "MAX".to_string()
+        };
+        lines.push(format!("{}: {}  ({status})", i + 1, item.name()));
+    }
+    lines.push(String::new());
+    lines.push(format!(
+        "Lives: {}   Bombs: {}",
+        player_state.lives, player_state.bombs
+    ));
+    lines.push("ENTER: next wave".to_string());
+
+    for mut text in &mut text_query {
+        text.0 = lines.join("\n");
+    }
+}
@@ -0,0 +1,432 @@
+use crate::action::ActionState;
+use crate::components::{Enemy, InRun, Movable, Player};
+use crate::{AppState, EnemyCount, GameStats, PlayerState};
+use bevy::app::RunFixedMainLoopSystem;
+use bevy::ecs::entity::{EntityHashMap, EntityHashSet, MapEntities};
+use bevy::prelude::*;
+use std::any::Any;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+/// 模拟步数资源 - 进入游戏后已执行的固定步长数
+#[derive(Resource, Clone, Default)]
+pub struct SimTick(pub u64);
+
+/// 步长输入资源 - 本次固定步长的动作状态（自上一步长以来的按下/松开都会被记录，不会因一帧内没有步长而丢失）
+/// 固定步长中的玩法系统只读取这里的输入，输入相同时模拟结果相同
+#[derive(Resource, Clone, Default, Deref)]
+pub struct TickInput(pub ActionState);
+
+/// 输入记录方式
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LogMode {
+    #[default]
+    Off, // 不记录
+    Record, // 记录每一步的输入和校验和
+    Replay, // 用记录的输入代替设备输入，并记录校验和
+}
+
+/// 输入记录资源 - 按步数记录的步长输入和每一步结束时的状态校验和
+/// 相同种子下回放同一份输入应得到相同的校验和，这是快照回滚重算的前提
+#[derive(Resource, Default)]
+pub struct InputLog {
+    pub mode: LogMode,          // 记录方式
+    pub inputs: Vec<TickInput>, // 每一步的输入
+    pub checksums: Vec<u64>,    // 每一步结束时的状态校验和
+}
+
+impl InputLog {
+    /// 记录每一步输入的空记录
+    pub fn record() -> Self {
+        Self {
+            mode: LogMode::Record,
+            ..Default::default()
+        }
+    }
+
+    /// 回放这份记录输入的新记录（重新计算校验和）
+    pub fn replay(&self) -> Self {
+        self.replay_from(0)
+    }
+
+    /// 从指定步数开始回放的新记录（之前各步的校验和沿用这份记录，用于从快照恢复后重新模拟）
+    pub fn replay_from(&self, tick: u64) -> Self {
+        let kept = (tick as usize).min(self.checksums.len());
+        Self {
+            mode: LogMode::Replay,
+            inputs: self.inputs.clone(),
+            checksums: self.checksums[..kept].to_vec(),
+        }
+    }
+
+    /// 与另一份记录比较，返回第一个校验和不同的步数（全部一致时为None）
+    pub fn first_divergence(&self, other: &InputLog) -> Option<usize> {
+        let common = self.checksums.len().min(other.checksums.len());
+        (0..common)
+            .find(|&tick| self.checksums[tick] != other.checksums[tick])
+            .or((self.checksums.len() != other.checksums.len()).then_some(common))
+    }
+}
+
+/// 快照中保存的一份组件或资源
+type Saved = Box<dyn Any + Send + Sync>;
+
+/// 组件的复制与写回函数（写回时把实体引用换成重新生成的实体）
+struct ComponentFns {
+    save: fn(&EntityRef) -> Option<Saved>,
+    load: fn(&mut EntityWorldMut, &(dyn Any + Send + Sync), &mut EntityHashMap<Entity>),
+}
+
+/// 资源的复制与写回函数
+struct ResourceFns {
+    save: fn(&World) -> Option<Saved>,
+    load: fn(&mut World, &(dyn Any + Send + Sync), &mut EntityHashMap<Entity>),
+}
+
+/// 回滚登记资源 - 参与快照的组件和资源类型，各模块在插件中登记自己的模拟状态
+#[derive(Resource, Default)]
+pub struct RollbackRegistry {
+    components: Vec<ComponentFns>, // 登记的组件
+    resources: Vec<ResourceFns>,   // 登记的资源
+}
+
+/// 登记回滚类型的App扩展
+pub trait RollbackAppExt {
+    /// 登记参与快照的组件（本局实体及其子实体上的该组件随快照复制）
+    fn rollback_component<C: Component + Clone>(&mut self) -> &mut Self;
+    /// 登记引用其他实体的组件（恢复时把引用换成重新生成的实体）
+    fn rollback_component_mapped<C: Component + Clone + MapEntities>(&mut self) -> &mut Self;
+    /// 登记参与快照的资源
+    fn rollback_resource<R: Resource + Clone>(&mut self) -> &mut Self;
+    /// 登记引用实体的资源（恢复时把引用换成重新生成的实体）
+    fn rollback_resource_mapped<R: Resource + Clone + MapEntities>(&mut self) -> &mut Self;
+}
+
+impl RollbackAppExt for App {
+    fn rollback_component<C: Component + Clone>(&mut self) -> &mut Self {
+        register_component(
+            self,
+            ComponentFns {
+                save: save_component::<C>,
+                load: load_component::<C>,
+            },
+        )
+    }
+
+    fn rollback_component_mapped<C: Component + Clone + MapEntities>(&mut self) -> &mut Self {
+        register_component(
+            self,
+            ComponentFns {
+                save: save_component::<C>,
+                load: load_component_mapped::<C>,
+            },
+        )
+    }
+
+    fn rollback_resource<R: Resource + Clone>(&mut self) -> &mut Self {
+        register_resource(
+            self,
+            ResourceFns {
+                save: save_resource::<R>,
+                load: load_resource::<R>,
+            },
+        )
+    }
+
+    fn rollback_resource_mapped<R: Resource + Clone + MapEntities>(&mut self) -> &mut Self {
+        register_resource(
+            self,
+            ResourceFns {
+                save: save_resource::<R>,
+                load: load_resource_mapped::<R>,
+            },
+        )
+    }
+}
+
+fn register_component(app: &mut App, fns: ComponentFns) -> &mut App {
+    app.init_resource::<RollbackRegistry>();
+    app.world_mut()
+        .resource_mut::<RollbackRegistry>()
+        .components
+        .push(fns);
+    app
+}
+
+fn register_resource(app: &mut App, fns: ResourceFns) -> &mut App {
+    app.init_resource::<RollbackRegistry>();
+    app.world_mut()
+        .resource_mut::<RollbackRegistry>()
+        .resources
+        .push(fns);
+    app
+}
+
+fn save_component<C: Component + Clone>(entity: &EntityRef) -> Option<Saved> {
+    entity
+        .get::<C>()
+        .map(|component| Box::new(component.clone()) as Saved)
+}
+
+fn load_component<C: Component + Clone>(
+    entity: &mut EntityWorldMut,
+    saved: &(dyn Any + Send + Sync),
+    _: &mut EntityHashMap<Entity>,
+) {
+    if let Some(component) = saved.downcast_ref::<C>() {
+        entity.insert(component.clone());
+    }
+}
+
+fn load_component_mapped<C: Component + Clone + MapEntities>(
+    entity: &mut EntityWorldMut,
+    saved: &(dyn Any + Send + Sync),
+    map: &mut EntityHashMap<Entity>,
+) {
+    if let Some(component) = saved.downcast_ref::<C>() {
+        let mut component = component.clone();
+        component.map_entities(map);
+        entity.insert(component);
+    }
+}
+
+fn save_resource<R: Resource + Clone>(world: &World) -> Option<Saved> {
+    world
+        .get_resource::<R>()
+        .map(|resource| Box::new(resource.clone()) as Saved)
+}
+
+fn load_resource<R: Resource + Clone>(
+    world: &mut World,
+    saved: &(dyn Any + Send + Sync),
+    _: &mut EntityHashMap<Entity>,
+) {
+    if let Some(resource) = saved.downcast_ref::<R>() {
+        world.insert_resource(resource.clone());
+    }
+}
+
+fn load_resource_mapped<R: Resource + Clone + MapEntities>(
+    world: &mut World,
+    saved: &(dyn Any + Send + Sync),
+    map: &mut EntityHashMap<Entity>,
+) {
+    if let Some(resource) = saved.downcast_ref::<R>() {
+        let mut resource = resource.clone();
+        resource.map_entities(map);
+        world.insert_resource(resource);
+    }
+}
+
+/// 一个实体的快照
+struct EntitySnapshot {
+    entity: Entity,                  // 快照时的实体
+    parent: Option<Entity>,          // 快照时的父实体
+    components: Vec<(usize, Saved)>, // 登记的组件（按登记序号）
+}
+
+/// 模拟快照 - 某一帧结束时全部本局实体（及其子实体）上登记的组件、登记的资源和应用状态，
+/// 恢复后按记录的输入重新模拟，得到的每一步状态与快照之后原本的模拟相同
+/// 只在帧与帧之间保存和恢复（固定步长内发送的事件在同一步内处理完毕，不需要保存）；脚本实例不参与快照
+pub struct Snapshot {
+    pub tick: u64,                  // 快照时已执行的步数
+    state: AppState,                // 快照时的应用状态
+    entities: Vec<EntitySnapshot>,  // 按世界中的存储顺序保存的实体（恢复后查询的遍历顺序不变）
+    resources: Vec<(usize, Saved)>, // 登记的资源（按登记序号）
+}
+
+impl Snapshot {
+    /// 保存当前世界的快照
+    pub fn save(world: &mut World) -> Self {
+        // 本局实体和它们的子实体（敌人部件、弱点、首领炮塔等）
+        let mut in_run = world.query_filtered::<Entity, With<InRun>>();
+        let mut kept: EntityHashSet = in_run.iter(world).collect();
+        let mut pending: Vec<Entity> = kept.iter().copied().collect();
+        while let Some(entity) = pending.pop() {
+            if let Some(children) = world.get::<Children>(entity) {
+                for child in children.to_vec() {
+                    if kept.insert(child) {
+                        pending.push(child);
+                    }
+                }
+            }
+        }
+
+        let registry = world.resource::<RollbackRegistry>();
+        let entities = world
+            .iter_entities()
+            .filter(|entity| kept.contains(&entity.id()))
+            .map(|entity| EntitySnapshot {
+                entity: entity.id(),
+                parent: entity.get::<ChildOf>().map(ChildOf::parent),
+                components: registry
+                    .components
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, fns)| (fns.save)(&entity).map(|saved| (index, saved)))
+                    .collect(),
+            })
+            .collect();
+        let resources = registry
+            .resources
+            .iter()
+            .enumerate()
+            .filter_map(|(index, fns)| (fns.save)(world).map(|saved| (index, saved)))
+            .collect();
+
+        Self {
+            tick: world.resource::<SimTick>().0,
+            state: *world.resource::<State<AppState>>().get(),
+            entities,
+            resources,
+        }
+    }
+
+    /// 恢复快照：销毁当前的本局实体，按快照重新生成实体并写回登记的组件和资源（同一份快照可多次恢复）
+    pub fn restore(&self, world: &mut World) {
+        // 子实体随父实体一起销毁
+        let mut in_run = world.query_filtered::<Entity, With<InRun>>();
+        let live: Vec<Entity> = in_run.iter(world).collect();
+        for entity in live {
+            if let Ok(entity) = world.get_entity_mut(entity) {
+                entity.despawn();
+            }
+        }
+
+        // 先为每个快照实体生成新实体，写回组件时才能换掉相互之间的引用
+        let mut map: EntityHashMap<Entity> = self
+            .entities
+            .iter()
+            .map(|snapshot| (snapshot.entity, world.spawn_empty().id()))
+            .collect();
+        world.resource_scope(|world, registry: Mut<RollbackRegistry>| {
+            for snapshot in &self.entities {
+                let id = map[&snapshot.entity];
+                let parent = snapshot
+                    .parent
+                    .map(|parent| map.get(&parent).copied().unwrap_or(parent));
+                let mut entity = world.entity_mut(id);
+                for (index, saved) in &snapshot.components {
+                    (registry.components[*index].load)(&mut entity, saved.as_ref(), &mut map);
+                }
+                if let Some(parent) = parent {
+                    entity.insert(ChildOf(parent));
+                }
+            }
+            for (index, saved) in &self.resources {
+                (registry.resources[*index].load)(world, saved.as_ref(), &mut map);
+            }
+        });
+        // 直接改回快照时的状态（不触发进入或退出状态的系统）
+        world.insert_resource(State::new(self.state));
+        info!(
+            tick = self.tick,
+            entities = self.entities.len(),
+            "已恢复模拟快照"
+        );
+    }
+}
+
+/// 确定性模拟插件 - 把玩法更新整理为“输入 → 固定步长 → 状态”：设备输入先汇总为每一步的步长输入，
+/// 固定步长中的玩法系统只读取步长输入和固定步长时间；开启输入记录时记录每一步的输入和状态校验和，可回放比对
+pub struct SimPlugin;
+
+impl Plugin for SimPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SimTick>()
+            .init_resource::<TickInput>()
+            .init_resource::<InputLog>()
+            .init_resource::<PendingInput>()
+            .init_resource::<RollbackRegistry>()
+            // 登记步数、步长输入、固定步长时钟和通用的画面组件（恢复后无需重新生成外观）
+            .rollback_resource::<SimTick>()
+            .rollback_resource::<TickInput>()
+            .rollback_resource::<PendingInput>()
+            .rollback_resource::<Time<Fixed>>()
+            .rollback_resource::<Time<Virtual>>()
+            .rollback_component::<Transform>()
+            .rollback_component::<GlobalTransform>()
+            .rollback_component::<Sprite>()
+            .rollback_component::<Visibility>()
+            // 每帧在固定步长循环之前汇总动作状态（自动驾驶在PreUpdate中改写的按键也会被汇总）
+            .add_systems(
+                RunFixedMainLoop,
+                input_collect_system.in_set(RunFixedMainLoopSystem::BeforeFixedMainLoop),
+            )
+            // 每一步开始时取出步长输入（回放时换成记录的输入）
+            .add_systems(FixedFirst, tick_input_system)
+            // 每一步结束时推进步数并记录校验和
+            .add_systems(
+                FixedLast,
+                tick_end_system.run_if(in_state(AppState::Playing)),
+            )
+            // 开始游戏时从第0步开始计数
+            .add_systems(OnEnter(AppState::Playing), tick_reset_system);
+    }
+}
+
+/// 待处理输入资源 - 上一步长之后各帧汇总的输入
+#[derive(Resource, Clone, Default)]
+struct PendingInput(TickInput);
+
+/// 输入汇总系统 - 把本帧的动作状态并入待处理输入
+fn input_collect_system(actions: Res<ActionState>, mut pending: ResMut<PendingInput>) {
+    pending.0.0.merge(&actions);
+}
+
+/// 步长输入系统 - 待处理输入成为本步的输入，之后只保留按住状态；记录或回放本步的输入
+fn tick_input_system(
+    tick: Res<SimTick>,
+    state: Res<State<AppState>>,
+    mut pending: ResMut<PendingInput>,
+    mut input: ResMut<TickInput>,
+    mut log: ResMut<InputLog>,
+) {
+    *input = pending.0.clone();
+    pending.0.0.clear_just();
+
+    if *state.get() != AppState::Playing {
+        return;
+    }
+    let index = tick.0 as usize;
+    match log.mode {
+        LogMode::Off => {}
+        LogMode::Record => log.inputs.push(input.clone()),
+        LogMode::Replay => *input = log.inputs.get(index).cloned().unwrap_or_default(),
+    }
+}
+
+/// 步长结束系统 - 推进步数，开启输入记录时记录本步结束时的状态校验和
+#[allow(clippy::type_complexity)] // 允许复杂的查询类型
+fn tick_end_system(
+    mut tick: ResMut<SimTick>,
+    mut log: ResMut<InputLog>,
+    stats: Res<GameStats>,
+    player_state: Res<PlayerState>,
+    enemy_count: Res<EnemyCount>,
+    query: Query<&Transform, Or<(With<Player>, With<Enemy>, With<Movable>)>>,
+) {
+    if log.mode != LogMode::Off {
+        let mut hasher = DefaultHasher::new();
+        tick.0.hash(&mut hasher);
+        stats.score.hash(&mut hasher);
+        stats.shots_fired.hash(&mut hasher);
+        stats.enemies_killed.hash(&mut hasher);
+        player_state.lives.hash(&mut hasher);
+        enemy_count.0.hash(&mut hasher);
+        // 实体的遍历顺序与模拟无关，各实体的位置哈希相加后计入校验和
+        let positions = query.iter().fold(0u64, |sum, tf| {
+            let mut hasher = DefaultHasher::new();
+            tf.translation.x.to_bits().hash(&mut hasher);
+            tf.translation.y.to_bits().hash(&mut hasher);
+            sum.wrapping_add(hasher.finish())
+        });
+        positions.hash(&mut hasher);
+        log.checksums.push(hasher.finish());
+    }
+    tick.0 += 1;
+}
+
+/// 步数重置系统
+fn tick_reset_system(mut tick: ResMut<SimTick>) {
+    tick.0 = 0;
+}
@@ -0,0 +1,164 @@
+use crate::AppState;
+use crate::profile::Profile;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// 速度技能提供的移动速度倍率
+pub const SKILL_SPEED_SCALE: f32 = 1.1;
+
+/// 技能树节点 - 用核心永久购买，每局开始时生效
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SkillNode {
+    Speed,       // 移动速度+10%
+    ExtraBomb,   // 开局多带一颗炸弹
+    StartShield, // 开局护盾，抵挡第一次伤害
+}
+
+/// 技能条目 - 节点的名称、价格、前置节点和购买按键
+pub struct Skill {
+    pub name: &'static str,          // 界面显示名称
+    pub cost: u64,                   // 购买所需的核心
+    pub requires: Option<SkillNode>, // 前置节点（需先购买）
+    pub node: SkillNode,             // 对应的技能节点
+    pub key: KeyCode,                // 购买按键
+}
+
+/// 技能树（按界面显示顺序排列）
+pub const SKILL_TREE: &[Skill] = &[
+    Skill {
+        name: "Speed +10%",
+        cost: 20,
+        requires: None,
+        node: SkillNode::Speed,
+        key: KeyCode::Digit1,
+    },
+    Skill {
+        name: "Extra bomb",
+        cost: 25,
+        requires: None,
+        node: SkillNode::ExtraBomb,
+        key: KeyCode::Digit2,
+    },
+    Skill {
+        name: "Start shield (blocks first hit)",
+        cost: 40,
+        requires: Some(SkillNode::ExtraBomb),
+        node: SkillNode::StartShield,
+        key: KeyCode::Digit3,
+    },
+];
+
+/// 技能树界面根节点标记
+#[derive(Component)]
+struct SkillTreeScreen;
+
+/// 技能树界面文本标记
+#[derive(Component)]
+struct SkillTreeText;
+
+/// 技能树插件 - 从主菜单进入的技能树界面，用核心购买永久节点
+pub struct SkillTreePlugin;
+
+impl Plugin for SkillTreePlugin {
+    fn build(&self, app: &mut App) {
+        // 进入技能树状态时创建界面
+        app.add_systems(OnEnter(AppState::SkillTree), skill_tree_setup_system)
+            // 离开技能树状态时销毁界面
+            .add_systems(OnExit(AppState::SkillTree), skill_tree_cleanup_system)
+            // 处理购买与返回按键，并刷新界面文本
+            .add_systems(
+                Update,
+                (skill_tree_purchase_system, skill_tree_text_system)
+                    .chain()
+                    .run_if(in_state(AppState::SkillTree)),
+            );
+    }
+}
+
+/// 技能树界面初始化系统
+fn skill_tree_setup_system(mut commands: Commands) {
+    commands
+        .spawn((
+            SkillTreeScreen,
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            BackgroundColor(Color::srgba(0., 0.05, 0.1, 0.95)),
+            GlobalZIndex(10),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                SkillTreeText,
+                Text::new(""),
+                TextFont {
+                    font_size: 18.,
+                    ..Default::default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// 技能树界面销毁系统
+fn skill_tree_cleanup_system(mut commands: Commands, query: Query<Entity, With<SkillTreeScreen>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// 购买系统 - 按1~3购买技能节点（需满足前置且核心足够），按Esc返回主菜单
+fn skill_tree_purchase_system(
+    kb: Res<ButtonInput<KeyCode>>,
+    mut profile: ResMut<Profile>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    for skill in SKILL_TREE {
+        if kb.just_pressed(skill.key) && profile.can_buy_skill(skill) {
+            profile.cores -= skill.cost;
+            profile.skills.push(skill.node);
+            profile.save();
+        }
+    }
+
+    if kb.just_pressed(KeyCode::Escape) {
+        next_state.set(AppState::MainMenu);
+    }
+}
+
+/// 技能树文本系统 - 界面刚创建或档案变化时列出全部节点及其状态
+fn skill_tree_text_system(
+    profile: Res<Profile>,
+    mut query: Query<(&mut Text, Ref<SkillTreeText>)>,
+) {
+    for (mut text, marker) in &mut query {
+        if !profile.is_changed() && !marker.is_added() {
+            continue;
+        }
+
+        let mut lines = vec![
+            "UPGRADE TREE".to_string(),
+            format!("Cores: {}", profile.cores),
+            String::new(),
+        ];
+        for (i, skill) in SKILL_TREE.iter().enumerate() {
+            let status = if profile.has_skill(skill.node) {
+                "[x]".to_string()
+            } else if !skill.requires.is_none_or(|node| profile.has_skill(node)) {
+                "[locked]".to_string()
+            } else {
+                format!("[ ] {} cores", skill.cost)
+            };
+            lines.push(format!("{}: {status}  {}", i + 1, skill.name));
+        }
+        lines.push(String::new());
+        lines.push("Earn 1 core per enemy destroyed".to_string());
+        lines.push("ESC: back".to_string());
+
+        text.0 = lines.join("\n");
+    }
+}
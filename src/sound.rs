@@ -0,0 +1,114 @@
+use crate::registry::{AssetRegistry, Sfx};
+use crate::settings::Settings;
+use crate::{GameSet, WINDOW_SIZE};
+use bevy::audio::{AudioPlayer, PlaybackSettings, SpatialListener, SpatialScale, Volume};
+use bevy::prelude::*;
+
+// 同时播放的音效数量上限（超出时丢弃新的播放请求）
+const SOUND_VOICES_MAX: usize = 16;
+// 收听者两耳之间的距离（像素，两耳位于游戏区域的左右边缘）
+const SOUND_EAR_GAP: f32 = WINDOW_SIZE.0;
+// 声像缩放：把游戏区域的水平坐标缩放到-1~1之间
+const SOUND_SPATIAL_SCALE: f32 = 2. / WINDOW_SIZE.0;
+
+/// 播放音效事件 - 任何系统都可以发送，由音效系统统一取得音效、应用音量和声像后播放，发送方不需要持有资源句柄
+#[derive(Event, Clone, Copy, Debug)]
+pub struct PlaySound {
+    pub sound: Sfx,             // 音效键
+    pub position: Option<Vec3>, // 发声位置（按水平位置分配左右声道，为None时居中播放）
+    pub speed: f32,             // 播放速度（同时改变音调）
+}
+
+impl PlaySound {
+    /// 居中播放的音效
+    pub fn new(sound: Sfx) -> Self {
+        Self {
+            sound,
+            position: None,
+            speed: 1.,
+        }
+    }
+
+    /// 在指定位置发声的音效
+    pub fn at(sound: Sfx, position: Vec3) -> Self {
+        Self {
+            position: Some(position),
+            ..Self::new(sound)
+        }
+    }
+
+    /// 改变播放速度
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+}
+
+/// 音效声道组件 - 对象池中的一个音效播放实体（播放完毕后移除音频组件，留待下次复用）
+#[derive(Component)]
+struct SoundVoice;
+
+/// 音效插件 - 注册播放音效事件，生成声像收听者，每帧播放本帧请求的音效
+pub struct SoundPlugin;
+
+impl Plugin for SoundPlugin {
+    fn build(&self, app: &mut App) {
+        // 注册播放音效事件
+        app.add_event::<PlaySound>()
+            // 在游戏区域中心放置收听者
+            .add_systems(Startup, sound_listener_setup_system)
+            // 每帧播放音效
+            .add_systems(Update, sound_playback_system.in_set(GameSet::Presentation));
+    }
+}
+
+/// 收听者初始化系统
+fn sound_listener_setup_system(mut commands: Commands) {
+    commands.spawn((SpatialListener::new(SOUND_EAR_GAP), Transform::default()));
+}
+
+/// 音效播放系统 - 按音效音量设置播放，优先复用空闲的声道，声道全部占用且达到上限时丢弃请求
+fn sound_playback_system(
+    mut commands: Commands,
+    registry: Res<AssetRegistry>,
+    settings: Res<Settings>,
+    mut events: EventReader<PlaySound>,
+    idle_query: Query<Entity, (With<SoundVoice>, Without<AudioPlayer>)>,
+    voice_query: Query<(), With<SoundVoice>>,
+) {
+    let mut idle = idle_query.iter();
+    let mut voices = voice_query.iter().count();
+
+    for event in events.read() {
+        if settings.sfx_volume <= 0. {
+            continue;
+        }
+        let voice = match idle.next() {
+            Some(voice) => voice,
+            None if voices < SOUND_VOICES_MAX => {
+                voices += 1;
+                commands.spawn((SoundVoice, Transform::default())).id()
+            }
+            None => {
+                debug!(sound = ?event.sound, "音效声道已满，丢弃播放请求");
+                continue;
+            }
+        };
+
+        let mut playback = PlaybackSettings::REMOVE
+            .with_volume(Volume::Linear(settings.sfx_volume))
+            .with_speed(event.speed);
+        let mut transform = Transform::default();
+        if let Some(position) = event.position {
+            // 只按水平位置分配声道：游戏区域左右边缘分别对应左右耳
+            playback = playback.with_spatial(true);
+            playback.spatial_scale = Some(SpatialScale::new_2d(SOUND_SPATIAL_SCALE));
+            transform.translation.x = position.x;
+        }
+        commands.entity(voice).insert((
+            AudioPlayer::new(registry.sound(event.sound)),
+            playback,
+            transform,
+        ));
+    }
+}
@@ -0,0 +1,279 @@
+use crate::components::{Enemy, Laser, Player, Team};
+use crate::net::{NetEvent, NetMessage, NetSession, ReplicaKind, Snapshot};
+use crate::registry::{AssetRegistry, Tex};
+use crate::{AppState, GameSet, GameStats, PlayerState, SPRITE_SCALE};
+use bevy::prelude::*;
+
+// 主机向观战者发送快照的间隔（秒）
+const SNAPSHOT_SECS: f32 = 0.1;
+// 每份快照最多包含的实体数（超出时按玩家、敌人、激光的顺序保留）
+const SNAPSHOT_ENTITIES_MAX: usize = 200;
+// 超过该时间没有收到快照时视为主机不在对局中（秒）
+const SNAPSHOT_TIMEOUT_SECS: f32 = 1.;
+// 自由摄像机的平移速度（像素/秒，按缩放倍数放大）
+const CAMERA_PAN_SPEED: f32 = 500.;
+// 自由摄像机每秒的缩放倍数
+const CAMERA_ZOOM_SPEED: f32 = 1.5;
+// 自由摄像机的缩放范围
+const CAMERA_ZOOM_MIN: f32 = 0.5;
+const CAMERA_ZOOM_MAX: f32 = 3.;
+
+/// 快照计时资源 - 主机每隔SNAPSHOT_SECS向观战者发送一次快照
+#[derive(Resource)]
+struct SnapshotTimer(Timer);
+
+impl Default for SnapshotTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(SNAPSHOT_SECS, TimerMode::Repeating))
+    }
+}
+
+/// 观战画面资源 - 最近一次快照中主机的得分和生命，以及连接状态
+#[derive(Resource, Default)]
+struct SpectatorView {
+    score: u64,         // 主机本局得分
+    lives: u32,         // 主机剩余生命数
+    since: f32,         // 距最近一次快照的时间（秒）
+    received: bool,     // 是否收到过快照（主机不在对局中时为false）
+    disconnected: bool, // 是否已与主机断开
+}
+
+/// 观战界面根节点标记
+#[derive(Component)]
+struct SpectatorScreen;
+
+/// 观战文本标记
+#[derive(Component)]
+struct SpectatorText;
+
+/// 快照重建的实体标记
+#[derive(Component)]
+struct Replica;
+
+/// 观战插件 - 主机对局中定期向观战者发送快照（得分、生命和各实体位置）；观战者只接收快照重建画面、不发送任何输入，
+/// 方向键平移、+/-缩放自由摄像机，Esc离开
+pub struct SpectatorPlugin;
+
+impl Plugin for SpectatorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SnapshotTimer>()
+            .init_resource::<SpectatorView>()
+            // 主机：对局中向观战者发送快照
+            .add_systems(Update, spectator_broadcast_system.in_set(GameSet::Spawn))
+            // 观战者：进入观战时创建界面
+            .add_systems(OnEnter(AppState::Spectating), spectator_setup_system)
+            // 观战者：离开观战时销毁界面、复位摄像机
+            .add_systems(OnExit(AppState::Spectating), spectator_cleanup_system)
+            // 观战者：处理按键、接收快照并刷新界面
+            .add_systems(
+                Update,
+                (
+                    spectator_input_system,
+                    spectator_net_system,
+                    spectator_text_system,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Spectating)),
+            );
+    }
+}
+
+/// 快照发送系统 - 有观战者时每隔SNAPSHOT_SECS把主机的得分、生命和玩家、敌人、激光的位置发给观战者，
+/// 实体数超过上限时优先保留玩家和敌人；消息放入各连接的发送缓冲区，不会因观战者接收慢而卡住对局
+#[allow(clippy::type_complexity)] // 允许复杂的查询类型
+fn spectator_broadcast_system(
+    time: Res<Time>,
+    mut timer: ResMut<SnapshotTimer>,
+    mut session: ResMut<NetSession>,
+    stats: Res<GameStats>,
+    player_state: Res<PlayerState>,
+    query: Query<
+        (&Transform, Has<Player>, Has<Enemy>, Option<&Team>),
+        Or<(With<Player>, With<Enemy>, With<Laser>)>,
+    >,
+) {
+    let NetSession::Host(host) = &mut *session else {
+        return;
+    };
+    if !timer.0.tick(time.delta()).just_finished() || !host.has_spectators() {
+        return;
+    }
+
+    let mut entities: Vec<_> = query
+        .iter()
+        .map(|(tf, player, enemy, team)| {
+            let kind = match (player, enemy, team.copied()) {
+                (true, _, _) => ReplicaKind::Player,
+                (_, true, _) => ReplicaKind::Enemy,
+                (_, _, Some(Team::PLAYER)) => ReplicaKind::PlayerLaser,
+                _ => ReplicaKind::EnemyLaser,
+            };
+            (kind, tf.translation.x, tf.translation.y)
+        })
+        .collect();
+    entities.sort_by_key(|(kind, _, _)| match kind {
+        ReplicaKind::Player => 0,
+        ReplicaKind::Enemy => 1,
+        ReplicaKind::PlayerLaser | ReplicaKind::EnemyLaser => 2,
+    });
+    entities.truncate(SNAPSHOT_ENTITIES_MAX);
+    host.broadcast_spectators(&NetMessage::Snapshot(Snapshot {
+        score: stats.score,
+        lives: player_state.lives,
+        entities,
+    }));
+}
+
+/// 观战界面初始化系统 - 顶部的主机得分、生命和操作提示
+fn spectator_setup_system(mut commands: Commands, mut view: ResMut<SpectatorView>) {
+    *view = SpectatorView::default();
+    commands
+        .spawn((
+            SpectatorScreen,
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                top: Val::Px(8.),
+                justify_content: JustifyContent::Center,
+                ..Default::default()
+            },
+            GlobalZIndex(10),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                SpectatorText,
+                Text::new(""),
+                TextFont {
+                    font_size: 20.,
+                    ..Default::default()
+                },
+                TextColor(Color::WHITE),
+                TextLayout::new_with_justify(JustifyText::Center),
+            ));
+        });
+}
+
+/// 观战界面销毁系统 - 销毁界面和重建的实体，摄像机复位
+fn spectator_cleanup_system(
+    mut commands: Commands,
+    query: Query<Entity, Or<(With<SpectatorScreen>, With<Replica>)>>,
+    mut camera_query: Query<&mut Transform, With<Camera2d>>,
+) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+    if let Ok(mut tf) = camera_query.get_single_mut() {
+        *tf = Transform::default();
+    }
+}
+
+/// 观战按键系统 - 方向键平移摄像机，+/-缩放，Esc断开连接回到主菜单
+fn spectator_input_system(
+    time: Res<Time>,
+    kb: Res<ButtonInput<KeyCode>>,
+    mut session: ResMut<NetSession>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut camera_query: Query<&mut Transform, With<Camera2d>>,
+) {
+    if kb.just_pressed(KeyCode::Escape) {
+        // 丢弃会话即关闭连接
+        *session = NetSession::Offline;
+        next_state.set(AppState::MainMenu);
+        return;
+    }
+
+    let Ok(mut tf) = camera_query.get_single_mut() else {
+        return;
+    };
+    let delta = time.delta_secs();
+    let axis =
+        |negative: KeyCode, positive: KeyCode| match (kb.pressed(negative), kb.pressed(positive)) {
+            (true, false) => -1.,
+            (false, true) => 1.,
+            _ => 0.,
+        };
+    let pan = Vec2::new(
+        axis(KeyCode::ArrowLeft, KeyCode::ArrowRight),
+        axis(KeyCode::ArrowDown, KeyCode::ArrowUp),
+    );
+    let zoom = axis(KeyCode::Equal, KeyCode::Minus);
+    let scale =
+        (tf.scale.x * CAMERA_ZOOM_SPEED.powf(zoom * delta)).clamp(CAMERA_ZOOM_MIN, CAMERA_ZOOM_MAX);
+    tf.scale = Vec3::new(scale, scale, 1.);
+    tf.translation += (pan * CAMERA_PAN_SPEED * scale * delta).extend(0.);
+}
+
+/// 观战网络系统 - 收到快照时按快照重建实体，长时间没有快照时清空画面，与主机断开时显示提示
+fn spectator_net_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut events: EventReader<NetEvent>,
+    registry: Res<AssetRegistry>,
+    mut view: ResMut<SpectatorView>,
+    replicas: Query<Entity, With<Replica>>,
+) {
+    view.since += time.delta_secs();
+    let mut latest = None;
+    for event in events.read() {
+        match event {
+            NetEvent::Received {
+                message: NetMessage::Snapshot(snapshot),
+                ..
+            } => latest = Some(snapshot),
+            NetEvent::Left(_) => view.disconnected = true,
+            _ => {}
+        }
+    }
+
+    let stale = view.since > SNAPSHOT_TIMEOUT_SECS;
+    if latest.is_none() && !stale {
+        return;
+    }
+    for entity in &replicas {
+        commands.entity(entity).despawn();
+    }
+    let Some(snapshot) = latest else {
+        view.received = false;
+        return;
+    };
+
+    view.score = snapshot.score;
+    view.lives = snapshot.lives;
+    view.since = 0.;
+    view.received = true;
+    for &(kind, x, y) in &snapshot.entities {
+        let (tex, z) = match kind {
+            ReplicaKind::Player => (Tex::Player, 10.),
+            ReplicaKind::Enemy => (Tex::Enemy, 10.),
+            ReplicaKind::PlayerLaser => (Tex::PlayerLaser, 1.),
+            ReplicaKind::EnemyLaser => (Tex::EnemyLaser, 1.),
+        };
+        commands.spawn((
+            Replica,
+            registry.sprite(tex),
+            Transform::from_xyz(x, y, z).with_scale(Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.)),
+        ));
+    }
+}
+
+/// 观战文本系统 - 显示主机的得分和生命，主机不在对局中或已断开时显示提示
+fn spectator_text_system(
+    view: Res<SpectatorView>,
+    mut query: Query<&mut Text, With<SpectatorText>>,
+) {
+    let Ok(mut text) = query.get_single_mut() else {
+        return;
+    };
+
+    let status = if view.disconnected {
+        "Host disconnected".to_string()
+    } else if !view.received {
+        "SPECTATING  Waiting for the host's match".to_string()
+    } else {
+        format!("SPECTATING  Score: {}  Lives: {}", view.score, view.lives)
+    };
+    let content = format!("{status}\nARROWS: pan   +/-: zoom   ESC: leave");
+    if text.0 != content {
+        text.0 = content;
+    }
+}
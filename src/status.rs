@@ -0,0 +1,260 @@
+use crate::GameSet;
+use crate::combat::DamageEvent;
+use crate::components::Enemy;
+use crate::faction_time::{Faction, FactionTime};
+use crate::sim::RollbackAppExt;
+use bevy::ecs::system::EntityCommand;
+use bevy::prelude::*;
+
+// 同一种效果最多叠加的层数（层数已满时刷新剩余时间最短的一层）
+const STATUS_MAX_STACKS: usize = 3;
+// 每层燃烧每秒造成的伤害（普通命中为1）
+const BURN_DPS: f32 = 0.5;
+// 每层减速的速度倍率（多层相乘）
+const SLOW_SCALE: f32 = 0.6;
+
+/// 状态效果种类
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatusKind {
+    Burn, // 燃烧：持续造成伤害
+    Slow, // 减速：降低移动速度
+    Stun, // 眩晕：停止移动并无法开火
+}
+
+/// 单层状态效果 - 种类和剩余时间
+#[derive(Clone)]
+struct StatusEffect {
+    kind: StatusKind, // 效果种类
+    timer: Timer,     // 持续时间计时器
+}
+
+/// 状态效果组件 - 实体身上可叠加的限时效果，由状态系统推进计时并结算
+#[derive(Component, Clone, Default)]
+pub struct StatusEffects {
+    effects: Vec<StatusEffect>, // 当前生效的效果（每层一项）
+}
+
+impl StatusEffects {
+    /// 施加一层效果，同种效果的层数已满时刷新剩余时间最短的一层
+    pub fn apply(&mut self, kind: StatusKind, secs: f32) {
+        let timer = Timer::from_seconds(secs, TimerMode::Once);
+        if self.stacks(kind) < STATUS_MAX_STACKS {
+            self.effects.push(StatusEffect { kind, timer });
+            return;
+        }
+        if let Some(effect) = self
+            .effects
+            .iter_mut()
+            .filter(|effect| effect.kind == kind)
+            .min_by(|a, b| a.timer.remaining().cmp(&b.timer.remaining()))
+        {
+            effect.timer = timer;
+        }
+    }
+
+    /// 指定效果当前的层数
+    pub fn stacks(&self, kind: StatusKind) -> usize {
+        self.effects
+            .iter()
+            .filter(|effect| effect.kind == kind)
+            .count()
+    }
+
+    /// 是否处于眩晕状态
+    pub fn stunned(&self) -> bool {
+        self.stacks(StatusKind::Stun) > 0
+    }
+
+    /// 实体当前的速度倍率（没有状态效果组件时为1，眩晕时为0）
+    pub fn speed_scale(status: Option<&StatusEffects>) -> f32 {
+        let Some(status) = status else {
+            return 1.;
+        };
+        if status.stunned() {
+            return 0.;
+        }
+        SLOW_SCALE.powi(status.stacks(StatusKind::Slow) as i32)
+    }
+
+    /// 是否无法开火（没有状态效果组件时为false）
+    pub fn suppressed(status: Option<&StatusEffects>) -> bool {
+        status.is_some_and(StatusEffects::stunned)
+    }
+
+    /// 推进全部效果的计时器并移除到期的效果
+    fn tick(&mut self, delta: std::time::Duration) {
+        for effect in &mut self.effects {
+            effect.timer.tick(delta);
+        }
+        self.effects.retain(|effect| !effect.timer.finished());
+    }
+}
+
+/// 施加效果组件 - 命中时对目标施加的状态效果（由部分武器的弹药携带）
+#[derive(Clone, Copy, Component)]
+pub struct Inflicts {
+    pub kind: StatusKind, // 效果种类
+    pub secs: f32,        // 持续时间（秒）
+}
+
+/// 施加状态效果命令 - 目标没有状态效果组件时先添加
+/// 用法：commands.entity(entity).queue(inflict(kind, secs))
+pub fn inflict(kind: StatusKind, secs: f32) -> impl EntityCommand {
+    move |mut entity: EntityWorldMut| {
+        if let Some(mut status) = entity.get_mut::<StatusEffects>() {
+            status.apply(kind, secs);
+            return;
+        }
+        let mut status = StatusEffects::default();
+        status.apply(kind, secs);
+        entity.insert(status);
+    }
+}
+
+/// 状态效果插件 - 推进状态效果计时并结算燃烧伤害（减速和眩晕由移动、开火系统读取）
+pub struct StatusPlugin;
+
+impl Plugin for StatusPlugin {
+    fn build(&self, app: &mut App) {
+        // 登记参与快照回滚的组件
+        app.rollback_component::<StatusEffects>()
+            .rollback_component::<Inflicts>();
+
+        // 固定步长推进状态效果，燃烧伤害与命中伤害一同结算
+        app.add_systems(
+            FixedUpdate,
+            status_system
+                .in_set(GameSet::Collision)
+                .after(crate::laser_hit_enemy_system),
+        );
+    }
+}
+
+/// 状态效果系统 - 推进计时器，按燃烧层数向敌人发送伤害事件（装甲、核心护盾由伤害系统结算）
+fn status_system(
+    time: Res<Time>,
+    faction_time: Res<FactionTime>,
+    mut query: Query<(Entity, &mut StatusEffects), With<Enemy>>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    for (entity, mut status) in &mut query {
+        // 状态效果按敌方阵营的时间推进（时间减缓期间持续更久）
+        status.tick(faction_time.delta(&time, Faction::Enemy));
+
+        let damage = BURN_DPS
+            * status.stacks(StatusKind::Burn) as f32
+            * faction_time.delta_secs(&time, Faction::Enemy);
+        if damage > 0. {
+            damage_events.send(DamageEvent::new(entity, damage, None));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EnemyCount;
+    use crate::components::{EnemyKind, Health, Team};
+    use crate::enemy::Armored;
+    use crate::testing::{InputScript, headless_app, run_frames};
+    use std::time::Duration;
+
+    // 结算一次伤害事件所需的帧数（留出固定步长追上的余量）
+    const SETTLE_FRAMES: u32 = 3;
+
+    /// 创建带有状态效果插件的测试App
+    fn status_app() -> App {
+        let mut app = headless_app(InputScript::default());
+        app.add_plugins(StatusPlugin);
+        app
+    }
+
+    /// 生成一个带有生命值的敌人（同时计入敌人数量）
+    fn spawn_target(app: &mut App, hp: f32) -> Entity {
+        app.world_mut().resource_mut::<EnemyCount>().0 += 1;
+        app.world_mut()
+            .spawn((
+                Enemy,
+                Team::ENEMY,
+                EnemyKind::Basic,
+                Health::full(hp),
+                Transform::default(),
+            ))
+            .id()
+    }
+
+    fn status(app: &App, entity: Entity) -> &StatusEffects {
+        app.world().get::<StatusEffects>(entity).unwrap()
+    }
+
+    #[test]
+    fn armored_hit_inflicts_status() {
+        let mut app = status_app();
+        let enemy = spawn_target(&mut app, 1.);
+        app.world_mut()
+            .entity_mut(enemy)
+            .insert(Armored { hits: 1. });
+
+        app.world_mut().send_event(DamageEvent {
+            inflicts: Some(Inflicts {
+                kind: StatusKind::Slow,
+                secs: 2.,
+            }),
+            ..DamageEvent::new(enemy, 1., None)
+        });
+        run_frames(&mut app, SETTLE_FRAMES);
+        assert_eq!(status(&app, enemy).stacks(StatusKind::Slow), 1);
+        assert_eq!(
+            StatusEffects::speed_scale(Some(status(&app, enemy))),
+            SLOW_SCALE
+        );
+    }
+
+    #[test]
+    fn stacks_cap_and_refresh_the_shortest() {
+        let mut status = StatusEffects::default();
+        for secs in [1., 2., 3., 4.] {
+            status.apply(StatusKind::Slow, secs);
+        }
+        assert_eq!(status.stacks(StatusKind::Slow), STATUS_MAX_STACKS);
+        assert_eq!(
+            StatusEffects::speed_scale(Some(&status)),
+            SLOW_SCALE.powi(3)
+        );
+
+        // 1秒的一层已刷新为4秒，1.5秒后三层都在
+        status.tick(Duration::from_secs_f32(1.5));
+        assert_eq!(status.stacks(StatusKind::Slow), 3);
+        // 2.5秒后2秒的一层到期
+        status.tick(Duration::from_secs_f32(1.));
+        assert_eq!(status.stacks(StatusKind::Slow), 2);
+    }
+
+    #[test]
+    fn burn_damages_until_expiry() {
+        let mut app = status_app();
+        let enemy = spawn_target(&mut app, 10.);
+        for _ in 0..2 {
+            inflict(StatusKind::Burn, 1.).apply(app.world_mut().entity_mut(enemy));
+        }
+
+        // 两层燃烧持续1秒共造成约1点伤害，到期后不再扣除生命值
+        run_frames(&mut app, 90);
+        let health = app.world().get::<Health>(enemy).unwrap().current;
+        assert!((health - 9.).abs() < 0.05, "health {health}");
+        assert_eq!(status(&app, enemy).stacks(StatusKind::Burn), 0);
+    }
+
+    #[test]
+    fn stun_stops_movement_and_fire_until_expiry() {
+        let mut app = status_app();
+        let enemy = spawn_target(&mut app, 1.);
+        inflict(StatusKind::Stun, 0.5).apply(app.world_mut().entity_mut(enemy));
+        assert!(StatusEffects::suppressed(Some(status(&app, enemy))));
+        assert_eq!(StatusEffects::speed_scale(Some(status(&app, enemy))), 0.);
+
+        run_frames(&mut app, 60);
+        assert!(!StatusEffects::suppressed(Some(status(&app, enemy))));
+        assert_eq!(StatusEffects::speed_scale(Some(status(&app, enemy))), 1.);
+    }
+}
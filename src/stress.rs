@@ -0,0 +1,167 @@
+use crate::components::{Movable, Velocity};
+use crate::pool::{BulletPool, recycle};
+use crate::registry::{AssetRegistry, Tex};
+use crate::{GameSet, SPRITE_SCALE};
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::prelude::*;
+use std::f32::consts::TAU;
+
+// 开关压力测试的按键
+const STRESS_TOGGLE_KEY: KeyCode = KeyCode::F9;
+// 压力测试维持的子弹数量
+const STRESS_BULLETS: usize = 2000;
+// 每个固定步长最多发射的子弹数
+const STRESS_BURST: usize = 40;
+// 螺旋弹幕的旋臂数
+const STRESS_ARMS: usize = 8;
+// 螺旋每发一弹旋转的角度（弧度）
+const STRESS_SPIN: f32 = 0.07;
+// 子弹的飞行速度
+const STRESS_BULLET_SPEED: f32 = 0.5;
+
+/// 压力测试子弹标记 - 只用于渲染测试的弹幕，不参与碰撞
+#[derive(Component)]
+pub struct StressBullet;
+
+/// 压力测试资源 - 是否正在运行以及螺旋弹幕的当前角度
+#[derive(Resource, Default)]
+struct StressTest {
+    active: bool, // 是否正在运行
+    angle: f32,   // 螺旋弹幕的当前角度
+}
+
+/// 性能计数文本标记
+#[derive(Component)]
+struct StressText;
+
+/// 压力测试插件 - 按F9从屏幕中央持续发射螺旋弹幕（维持2000发子弹），并显示帧率、每帧绘制的精灵数和对象池状态
+pub struct StressPlugin;
+
+impl Plugin for StressPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin::default()); // 帧率统计
+        }
+        // 初始化压力测试资源
+        app.init_resource::<StressTest>()
+            // 创建性能计数文本（默认隐藏）
+            .add_systems(Startup, stress_text_setup_system)
+            // 切换压力测试
+            .add_systems(Update, stress_toggle_system)
+            // 固定步长补充弹幕
+            .add_systems(FixedUpdate, stress_spawn_system.in_set(GameSet::Spawn))
+            // 每帧刷新性能计数
+            .add_systems(Update, stress_text_system.in_set(GameSet::Presentation));
+    }
+}
+
+/// 性能计数文本初始化系统 - 左下角显示
+fn stress_text_setup_system(mut commands: Commands) {
+    commands.spawn((
+        StressText,
+        Text::new(""),
+        TextFont {
+            font_size: 14.,
+            ..Default::default()
+        },
+        TextColor(Color::srgb(0.4, 1., 0.4)),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(8.),
+            bottom: Val::Px(8.),
+            ..Default::default()
+        },
+        Visibility::Hidden,
+    ));
+}
+
+/// 压力测试切换系统 - 关闭时回收全部测试子弹
+fn stress_toggle_system(
+    mut commands: Commands,
+    kb: Res<ButtonInput<KeyCode>>,
+    mut stress: ResMut<StressTest>,
+    query: Query<Entity, With<StressBullet>>,
+) {
+    if !kb.just_pressed(STRESS_TOGGLE_KEY) {
+        return;
+    }
+    stress.active = !stress.active;
+    info!(active = stress.active, "压力测试切换");
+    if !stress.active {
+        for entity in &query {
+            commands.entity(entity).queue(recycle);
+        }
+    }
+}
+
+/// 弹幕补充系统 - 从屏幕中央沿多条旋臂发射子弹，直到场上子弹达到目标数量（子弹从对象池复用）
+fn stress_spawn_system(
+    mut commands: Commands,
+    mut stress: ResMut<StressTest>,
+    mut pool: ResMut<BulletPool>,
+    registry: Res<AssetRegistry>,
+    query: Query<(), With<StressBullet>>,
+) {
+    if !stress.active {
+        return;
+    }
+
+    let missing = STRESS_BULLETS.saturating_sub(query.iter().count());
+    for i in 0..missing.min(STRESS_BURST) {
+        let arm = (i % STRESS_ARMS) as f32 / STRESS_ARMS as f32;
+        let direction = Vec2::from_angle(stress.angle + arm * TAU);
+        if i % STRESS_ARMS == STRESS_ARMS - 1 {
+            stress.angle += STRESS_SPIN;
+        }
+        pool.spawn(
+            &mut commands,
+            (
+                registry.sprite(Tex::EnemyLaser),
+                Transform {
+                    translation: Vec3::new(0., 0., 0.),
+                    rotation: Quat::from_rotation_arc_2d(Vec2::NEG_Y, direction),
+                    scale: Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.),
+                },
+                StressBullet,
+                Movable { auto_despawn: true }, // 飞出屏幕后回收到对象池
+                Velocity {
+                    x: direction.x * STRESS_BULLET_SPEED,
+                    y: direction.y * STRESS_BULLET_SPEED,
+                },
+            ),
+        );
+    }
+}
+
+/// 性能计数系统 - 压力测试期间显示帧率、本帧绘制的精灵数、场上测试子弹数和对象池中的空闲实体数
+fn stress_text_system(
+    stress: Res<StressTest>,
+    pool: Res<BulletPool>,
+    diagnostics: Res<DiagnosticsStore>,
+    sprite_query: Query<&ViewVisibility, With<Sprite>>,
+    bullet_query: Query<(), With<StressBullet>>,
+    mut text_query: Query<(&mut Text, &mut Visibility), With<StressText>>,
+) {
+    let Ok((mut text, mut visibility)) = text_query.get_single_mut() else {
+        return;
+    };
+    if !stress.active {
+        visibility.set_if_neq(Visibility::Hidden);
+        return;
+    }
+    visibility.set_if_neq(Visibility::Visible);
+
+    let fps = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|fps| fps.smoothed())
+        .unwrap_or_default();
+    let drawn = sprite_query
+        .iter()
+        .filter(|visibility| visibility.get())
+        .count();
+    text.0 = format!(
+        "STRESS [F9]  FPS {fps:.0}\nSprites drawn {drawn}\nBullets {} / pooled {}",
+        bullet_query.iter().count(),
+        pool.free_count()
+    );
+}
@@ -0,0 +1,88 @@
+use crate::game_mode::{GameMode, GameModePlugin, ModeOutcome, run_mode_system};
+use crate::run::GameOverEvent;
+use crate::sim::RollbackAppExt;
+use crate::wave::Wave;
+use crate::{AppState, GameStats};
+use bevy::prelude::*;
+
+// 生存模式的名称（自定义模式的标识）
+const SURVIVAL_MODE: &str = "Survival";
+// 生存模式中敌人生成频率的额外倍率
+const SURVIVAL_SPAWN_SCALE: f32 = 1.5;
+
+/// 生存记录资源 - 本局开始时的死亡次数和得分
+#[derive(Resource, Clone, Default)]
+struct SurvivalRun {
+    deaths_at_start: u32, // 开始时的累计死亡次数
+    score_at_start: u64,  // 开始时的累计得分（本局得分从这里算起）
+}
+
+/// 生存模式插件 - 只有一条命、没有商店且敌人生成更密集，第一次被击毁时结束本局；
+/// 通过游戏模式接口注册的自定义模式，新增模式可参照该模块
+#[derive(Clone)]
+pub struct SurvivalPlugin;
+
+impl Plugin for SurvivalPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SurvivalRun>()
+            .rollback_resource::<SurvivalRun>();
+    }
+}
+
+impl GameModePlugin for SurvivalPlugin {
+    fn mode(&self) -> GameMode {
+        GameMode::Custom(SURVIVAL_MODE)
+    }
+
+    fn menu_entry(&self) -> Option<(KeyCode, &'static str)> {
+        Some((KeyCode::KeyV, "V: survival (one life)"))
+    }
+
+    /// 记录开始时的死亡次数和得分
+    fn setup(&self, world: &mut World) {
+        run_mode_system(world, survival_start_system);
+    }
+
+    fn spawn_scale(&self) -> f32 {
+        SURVIVAL_SPAWN_SCALE
+    }
+
+    fn shop(&self) -> bool {
+        false
+    }
+
+    /// 第一次被击毁时失败
+    fn outcome(&self, world: &World) -> Option<ModeOutcome> {
+        let deaths_at_start = world.resource::<SurvivalRun>().deaths_at_start;
+        (world.resource::<GameStats>().player_deaths > deaths_at_start)
+            .then_some(ModeOutcome::Defeat)
+    }
+
+    /// 记录本局成绩并返回主菜单
+    fn finish(&self, _outcome: ModeOutcome, world: &mut World) {
+        run_mode_system(world, survival_finish_system);
+    }
+}
+
+/// 开局系统
+fn survival_start_system(stats: Res<GameStats>, mut run: ResMut<SurvivalRun>) {
+    *run = SurvivalRun {
+        deaths_at_start: stats.player_deaths,
+        score_at_start: stats.score,
+    };
+}
+
+/// 结算系统 - 记录本局得分和坚持到的波次，结束本局后返回主菜单
+fn survival_finish_system(
+    stats: Res<GameStats>,
+    run: Res<SurvivalRun>,
+    wave: Res<Wave>,
+    mut game_over: EventWriter<GameOverEvent>,
+) {
+    info!(
+        score = stats.score - run.score_at_start,
+        wave = wave.number,
+        "生存模式结束"
+    );
+    game_over.send(GameOverEvent::to(AppState::MainMenu));
+}
@@ -0,0 +1,128 @@
+use crate::action::{ActionPlugin, ActionSystem};
+use crate::combat::CombatPlugin;
+use crate::config::GameConfig;
+use crate::cutscene::InputLock;
+use crate::enemy::EnemySplitEvent;
+use crate::faction_time::FactionTime;
+use crate::player::PlayerPlugin;
+use crate::pool::BulletPool;
+use crate::profile::Profile;
+use crate::registry::AssetRegistry;
+use crate::settings::SettingsPlugin;
+use crate::shop::RunUpgrades;
+use crate::sim::SimPlugin;
+use crate::sound::SoundPlugin;
+use crate::{
+    AppState, EnemyCount, GameSet, GameStats, WINDOW_SIZE, WinSize, configure_game_sets,
+    movable_system,
+};
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+
+// 测试使用的固定帧时长（60帧/秒）
+pub const FRAME_TIME: f32 = 1. / 60.;
+// 玩家完成首次生成所需的帧数（重生检查每0.5秒运行一次）
+pub const PLAYER_SPAWN_FRAMES: u32 = 40;
+
+/// 脚本按键动作
+#[derive(Clone, Copy)]
+pub enum ScriptedKey {
+    Press(KeyCode),   // 按下按键
+    Release(KeyCode), // 松开按键
+}
+
+/// 脚本输入资源 - 按帧号回放的按键序列，替代真实的键盘输入
+#[derive(Resource, Default)]
+pub struct InputScript {
+    events: Vec<(u32, ScriptedKey)>, // (帧号, 按键动作)
+    frame: u32,                      // 当前帧号
+}
+
+impl InputScript {
+    /// 在指定帧按下按键
+    pub fn press(mut self, frame: u32, key: KeyCode) -> Self {
+        self.events.push((frame, ScriptedKey::Press(key)));
+        self
+    }
+
+    /// 在指定帧松开按键
+    pub fn release(mut self, frame: u32, key: KeyCode) -> Self {
+        self.events.push((frame, ScriptedKey::Release(key)));
+        self
+    }
+
+    /// 在指定帧按下按键，并在下一帧松开
+    pub fn tap(self, frame: u32, key: KeyCode) -> Self {
+        self.press(frame, key).release(frame + 1, key)
+    }
+}
+
+/// 脚本输入系统 - 每帧把当前帧的脚本按键写入键盘输入资源
+fn scripted_input_system(mut script: ResMut<InputScript>, mut kb: ResMut<ButtonInput<KeyCode>>) {
+    // 没有InputPlugin时需要手动清除上一帧的just_pressed/just_released状态
+    kb.clear();
+
+    let frame = script.frame;
+    for &(_, key) in script.events.iter().filter(|(f, _)| *f == frame) {
+        match key {
+            ScriptedKey::Press(key) => kb.press(key),
+            ScriptedKey::Release(key) => kb.release(key),
+        }
+    }
+    script.frame += 1;
+}
+
+/// 创建无窗口的测试App - 包含玩家相关插件，使用固定帧时长和脚本输入
+pub fn headless_app(script: InputScript) -> App {
+    let mut app = App::new();
+    configure_game_sets(&mut app);
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(StatesPlugin)
+        .insert_state(AppState::Playing)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+            FRAME_TIME,
+        )))
+        .init_resource::<ButtonInput<KeyCode>>()
+        .insert_resource(script)
+        .insert_resource(WinSize {
+            w: WINDOW_SIZE.0,
+            h: WINDOW_SIZE.1,
+        })
+        .init_resource::<AssetRegistry>()
+        .init_resource::<BulletPool>()
+        .init_resource::<FactionTime>()
+        .init_resource::<GameConfig>()
+        .init_resource::<GameStats>()
+        .init_resource::<InputLock>()
+        .init_resource::<Profile>()
+        .init_resource::<RunUpgrades>()
+        .insert_resource(EnemyCount(0))
+        .add_event::<EnemySplitEvent>()
+        .add_plugins(CombatPlugin)
+        .add_plugins(SoundPlugin)
+        .add_plugins(SettingsPlugin)
+        .add_plugins(ActionPlugin)
+        .add_plugins(SimPlugin)
+        .add_plugins(PlayerPlugin)
+        .add_systems(PreUpdate, scripted_input_system.before(ActionSystem))
+        .add_systems(FixedUpdate, movable_system.in_set(GameSet::Movement));
+    app
+}
+
+/// 连续运行指定帧数
+pub fn run_frames(app: &mut App, frames: u32) {
+    for _ in 0..frames {
+        app.update();
+    }
+}
+
+/// 查询所有带有指定标记组件的实体位置
+pub fn positions<T: Component>(app: &mut App) -> Vec<Vec3> {
+    let mut query = app.world_mut().query_filtered::<&Transform, With<T>>();
+    query
+        .iter(app.world())
+        .map(|transform| transform.translation)
+        .collect()
+}
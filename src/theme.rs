@@ -0,0 +1,158 @@
+use crate::components::EnemyKind;
+use crate::config::GameConfig;
+use crate::mods::ModList;
+use crate::registry::Tex;
+use crate::settings::Settings;
+use crate::{AppState, WinSize};
+use bevy::audio::{AudioPlayer, PlaybackSettings};
+use bevy::prelude::*;
+use serde::Deserialize;
+
+/// 精灵主题 - 关卡主题选择的敌人贴图变体
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Deserialize)]
+pub enum SpriteTheme {
+    #[default]
+    Standard, // 标准
+    Desert, // 沙漠
+    Ice,    // 冰原
+    Void,   // 虚空
+}
+
+// 各主题下敌人种类对应的纹理（未列出的组合使用标准主题的纹理）
+const ENEMY_SPRITES: [(SpriteTheme, EnemyKind, Tex); 8] = [
+    (SpriteTheme::Standard, EnemyKind::Basic, Tex::Enemy),
+    (SpriteTheme::Standard, EnemyKind::Boss, Tex::Enemy),
+    (SpriteTheme::Desert, EnemyKind::Basic, Tex::EnemyDesert),
+    (SpriteTheme::Desert, EnemyKind::Boss, Tex::EnemyDesert),
+    (SpriteTheme::Ice, EnemyKind::Basic, Tex::EnemyIce),
+    (SpriteTheme::Ice, EnemyKind::Boss, Tex::EnemyIce),
+    (SpriteTheme::Void, EnemyKind::Basic, Tex::EnemyVoid),
+    (SpriteTheme::Void, EnemyKind::Boss, Tex::EnemyVoid),
+];
+
+/// 主题资源 - 按当前关卡主题查找敌人纹理，并记录背景图、敌人着色和背景音乐（载入关卡时切换）
+#[derive(Resource, Default)]
+pub struct ThemeAssets {
+    sprites: SpriteTheme,                   // 当前关卡的精灵主题
+    pub enemy_tint: Color,                  // 当前关卡的敌人着色
+    pub background: Option<Handle<Image>>,  // 当前关卡的背景图
+    pub music: Option<Handle<AudioSource>>, // 当前关卡的背景音乐
+}
+
+impl ThemeAssets {
+    /// 当前主题下指定敌人种类的纹理（无对应变体时使用标准主题）
+    pub fn enemy(&self, kind: EnemyKind) -> Tex {
+        let find = |theme| {
+            ENEMY_SPRITES
+                .iter()
+                .find(|&&(t, k, _)| t == theme && k == kind)
+                .map(|&(_, _, tex)| tex)
+        };
+        find(self.sprites)
+            .or_else(|| find(SpriteTheme::Standard))
+            .unwrap_or(Tex::Enemy)
+    }
+}
+
+/// 关卡背景图标记
+#[derive(Component)]
+struct ThemeBackground;
+
+/// 关卡背景音乐标记（离开游戏时一并停止）
+#[derive(Component)]
+pub struct ThemeMusic;
+
+/// 关卡主题插件 - 载入关卡时按关卡脚本切换敌人精灵组、背景图、敌人配色和背景音乐；低画质下隐藏背景图
+pub struct ThemePlugin;
+
+impl Plugin for ThemePlugin {
+    fn build(&self, app: &mut App) {
+        // 初始化主题资源
+        app.init_resource::<ThemeAssets>()
+            // 进入游戏时载入当前关卡的主题
+            .add_systems(OnEnter(AppState::Playing), theme_load_system)
+            // 离开游戏时停止背景音乐
+            .add_systems(OnExit(AppState::Playing), theme_music_stop_system)
+            // 画质变化时切换背景图的显示
+            .add_systems(
+                Update,
+                theme_background_visibility_system.run_if(resource_changed::<Settings>),
+            );
+    }
+}
+
+/// 主题载入系统 - 切换主题资源中的当前主题，替换背景图并播放背景音乐（模组中的同名文件优先）
+#[allow(clippy::too_many_arguments)] // 载入主题需要同时访问多个资源
+fn theme_load_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mods: Res<ModList>,
+    config: Res<GameConfig>,
+    settings: Res<Settings>,
+    win_size: Res<WinSize>,
+    mut theme_assets: ResMut<ThemeAssets>,
+    query: Query<Entity, Or<(With<ThemeBackground>, With<ThemeMusic>)>>,
+) {
+    let theme = &config.level.theme;
+    theme_assets.sprites = theme.sprites;
+    theme_assets.enemy_tint = theme.enemy_color();
+    theme_assets.background = theme
+        .background
+        .as_ref()
+        .map(|path| asset_server.load(mods.asset_path(path)));
+    theme_assets.music = theme
+        .music
+        .as_ref()
+        .map(|path| asset_server.load(mods.asset_path(path)));
+
+    // 移除上一关的背景和音乐
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+
+    if let Some(background) = &theme_assets.background {
+        commands.spawn((
+            ThemeBackground,
+            Sprite {
+                custom_size: Some(Vec2::new(win_size.w, win_size.h)), // 铺满窗口
+                ..Sprite::from_image(background.clone())
+            },
+            Transform::from_xyz(0., 0., -10.), // Z轴为负，显示在所有游戏实体下方
+            background_visibility(&settings),
+        ));
+    }
+    if let Some(music) = &theme_assets.music {
+        commands.spawn((
+            ThemeMusic,
+            AudioPlayer::new(music.clone()),
+            PlaybackSettings::LOOP,
+        ));
+    }
+}
+
+/// 背景图在当前画质下的显示状态
+fn background_visibility(settings: &Settings) -> Visibility {
+    if settings.graphics.is_low() {
+        Visibility::Hidden
+    } else {
+        Visibility::Inherited
+    }
+}
+
+/// 背景图显示系统 - 低画质下隐藏背景图，切换回高画质时恢复
+fn theme_background_visibility_system(
+    settings: Res<Settings>,
+    mut query: Query<&mut Visibility, With<ThemeBackground>>,
+) {
+    let visibility = background_visibility(&settings);
+    for mut current in &mut query {
+        current.set_if_neq(visibility);
+    }
+}
+
+/// 背景音乐停止系统
+fn theme_music_stop_system(mut commands: Commands, query: Query<Entity, With<ThemeMusic>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,209 @@
+use crate::action::{Action, ActionAdapters, ActionState};
+use crate::settings::Settings;
+use crate::{AppState, GameSet};
+use bevy::input::touch::Touch;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+// 虚拟摇杆和开火按钮与屏幕边缘的距离（像素）
+const TOUCH_MARGIN: f32 = 24.;
+// 开火按钮直径占摇杆直径的比例
+const FIRE_BUTTON_RATIO: f32 = 0.5;
+// 摇杆手柄直径占摇杆直径的比例
+const KNOB_RATIO: f32 = 0.4;
+// 在摇杆半径的这一倍数以内按下即可接管摇杆（手指不必精确按在圆内）
+const STICK_GRAB_RATIO: f32 = 1.5;
+
+/// 触屏控件状态资源 - 接管摇杆的触点和手柄的偏移
+#[derive(Resource, Default)]
+struct TouchControls {
+    stick: Option<u64>, // 接管摇杆的触点（没有时为None）
+    knob: Vec2,         // 手柄相对中心的偏移（屏幕坐标方向，长度不超过1）
+}
+
+/// 触屏控件布局 - 按窗口尺寸和设置计算的摇杆、开火按钮中心与半径（窗口坐标，原点在左上角）
+struct TouchLayout {
+    stick: Vec2,       // 摇杆中心
+    stick_radius: f32, // 摇杆半径
+    fire: Vec2,        // 开火按钮中心
+    fire_radius: f32,  // 开火按钮半径
+}
+
+impl TouchLayout {
+    fn new(window: &Window, settings: &Settings) -> Self {
+        let stick_radius = settings.joystick_size / 2.;
+        let fire_radius = stick_radius * FIRE_BUTTON_RATIO;
+        let (w, h) = (window.width(), window.height());
+        Self {
+            stick: Vec2::new(TOUCH_MARGIN + stick_radius, h - TOUCH_MARGIN - stick_radius),
+            stick_radius,
+            fire: Vec2::new(
+                w - TOUCH_MARGIN - fire_radius,
+                h - TOUCH_MARGIN - fire_radius,
+            ),
+            fire_radius,
+        }
+    }
+}
+
+/// 虚拟摇杆底座标记
+#[derive(Component)]
+struct TouchStick;
+
+/// 虚拟摇杆手柄标记
+#[derive(Component)]
+struct TouchKnob;
+
+/// 开火按钮标记
+#[derive(Component)]
+struct TouchFire;
+
+/// 触屏插件（touch特性）- 游戏中在左下角显示虚拟摇杆、右下角显示开火按钮，触点转换为移动方向和开火动作；
+/// 摇杆大小和死区取自设置
+pub struct TouchPlugin;
+
+impl Plugin for TouchPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TouchControls>()
+            // 创建虚拟摇杆和开火按钮（默认隐藏）
+            .add_systems(Startup, touch_setup_system)
+            // 与键盘、手柄一起转换为动作
+            .add_systems(
+                PreUpdate,
+                touch_action_system
+                    .in_set(ActionAdapters)
+                    .run_if(in_state(AppState::Playing)),
+            )
+            // 刷新控件的显示、大小和手柄位置
+            .add_systems(Update, touch_display_system.in_set(GameSet::Presentation));
+    }
+}
+
+/// 触屏控件初始化系统
+fn touch_setup_system(mut commands: Commands) {
+    commands
+        .spawn((
+            TouchStick,
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(TOUCH_MARGIN),
+                bottom: Val::Px(TOUCH_MARGIN),
+                ..Default::default()
+            },
+            BackgroundColor(Color::srgba(1., 1., 1., 0.15)),
+            BorderRadius::MAX,
+            GlobalZIndex(5),
+            Visibility::Hidden,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TouchKnob,
+                Node {
+                    position_type: PositionType::Absolute,
+                    ..Default::default()
+                },
+                BackgroundColor(Color::srgba(1., 1., 1., 0.4)),
+                BorderRadius::MAX,
+            ));
+        });
+    commands.spawn((
+        TouchFire,
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(TOUCH_MARGIN),
+            bottom: Val::Px(TOUCH_MARGIN),
+            ..Default::default()
+        },
+        BackgroundColor(Color::srgba(1., 0.3, 0.3, 0.3)),
+        BorderRadius::MAX,
+        GlobalZIndex(5),
+        Visibility::Hidden,
+    ));
+}
+
+/// 触屏适配系统 - 在摇杆附近按下的触点接管摇杆直到抬起，偏移超过死区时作为移动方向；按住开火按钮时开火
+fn touch_action_system(
+    touches: Res<Touches>,
+    settings: Res<Settings>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut controls: ResMut<TouchControls>,
+    mut actions: ResMut<ActionState>,
+) {
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let layout = TouchLayout::new(window, &settings);
+
+    if controls
+        .stick
+        .is_some_and(|id| touches.get_pressed(id).is_none())
+    {
+        controls.stick = None;
+    }
+    if controls.stick.is_none() {
+        controls.stick = touches
+            .iter_just_pressed()
+            .find(|touch| {
+                touch.position().distance(layout.stick) <= layout.stick_radius * STICK_GRAB_RATIO
+            })
+            .map(|touch| touch.id());
+    }
+
+    controls.knob = Vec2::ZERO;
+    if let Some(touch) = controls.stick.and_then(|id| touches.get_pressed(id)) {
+        let offset = ((touch.position() - layout.stick) / layout.stick_radius).clamp_length_max(1.);
+        controls.knob = offset;
+        if offset.length() > settings.joystick_dead_zone {
+            // 窗口坐标的Y轴向下，移动方向的Y轴向上
+            actions.push_axis(Vec2::new(offset.x, -offset.y));
+        }
+    }
+
+    let on_fire = |touch: &Touch| {
+        Some(touch.id()) != controls.stick
+            && touch.position().distance(layout.fire) <= layout.fire_radius
+    };
+    if touches.iter().any(on_fire) {
+        actions.hold(Action::Fire);
+    } else if touches.iter_just_released().any(on_fire) {
+        // 同一帧内按下又抬起的轻点
+        actions.tap(Action::Fire);
+    }
+}
+
+/// 触屏显示系统 - 只在游戏中显示控件，按设置调整大小并移动摇杆手柄
+#[allow(clippy::type_complexity)] // 允许复杂的查询类型
+fn touch_display_system(
+    state: Res<State<AppState>>,
+    settings: Res<Settings>,
+    controls: Res<TouchControls>,
+    mut controls_query: Query<
+        (&mut Node, &mut Visibility, Has<TouchFire>),
+        Or<(With<TouchStick>, With<TouchFire>)>,
+    >,
+    mut knob_query: Query<&mut Node, (With<TouchKnob>, Without<TouchStick>, Without<TouchFire>)>,
+) {
+    let visible = *state.get() == AppState::Playing;
+    let size = settings.joystick_size;
+    for (mut node, mut visibility, fire) in &mut controls_query {
+        visibility.set_if_neq(if visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        });
+        let diameter = if fire { size * FIRE_BUTTON_RATIO } else { size };
+        node.width = Val::Px(diameter);
+        node.height = Val::Px(diameter);
+    }
+
+    let Ok(mut node) = knob_query.get_single_mut() else {
+        return;
+    };
+    let knob = size * KNOB_RATIO;
+    let center = (size - knob) / 2.;
+    let offset = controls.knob * size / 2.;
+    node.width = Val::Px(knob);
+    node.height = Val::Px(knob);
+    node.left = Val::Px(center + offset.x);
+    node.top = Val::Px(center + offset.y);
+}
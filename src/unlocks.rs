@@ -0,0 +1,240 @@
+use crate::AppState;
+use crate::profile::Profile;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 战机外观
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ShipSkin {
+    #[default]
+    A, // 初始战机
+    B, // B型战机
+}
+
+impl ShipSkin {
+    pub const ALL: [ShipSkin; 2] = [ShipSkin::A, ShipSkin::B];
+}
+
+/// 激光颜色
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LaserColor {
+    #[default]
+    Default, // 原始颜色
+    Red,
+    Green,
+    Blue,
+}
+
+impl LaserColor {
+    pub const ALL: [LaserColor; 4] = [
+        LaserColor::Default,
+        LaserColor::Red,
+        LaserColor::Green,
+        LaserColor::Blue,
+    ];
+
+    /// 激光精灵的着色
+    pub fn tint(self) -> Color {
+        match self {
+            LaserColor::Default => Color::WHITE,
+            LaserColor::Red => Color::srgb(1., 0.4, 0.4),
+            LaserColor::Green => Color::srgb(0.4, 1., 0.4),
+            LaserColor::Blue => Color::srgb(0.5, 0.6, 1.),
+        }
+    }
+}
+
+/// 开局强化
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StartPowerUp {
+    Shield, // 出生时的无敌时间延长
+}
+
+/// 可解锁的物品
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnlockItem {
+    Ship(ShipSkin),
+    Laser(LaserColor),
+    PowerUp(StartPowerUp),
+}
+
+/// 解锁条目 - 累计得分达到阈值后解锁对应物品
+pub struct Unlock {
+    pub name: &'static str, // 界面显示名称
+    pub score: u64,         // 解锁所需的累计得分
+    pub item: UnlockItem,   // 解锁的物品
+}
+
+/// 解锁表（按所需得分从低到高排列），不在表中的物品默认已解锁
+pub const UNLOCKS: &[Unlock] = &[
+    Unlock {
+        name: "Red laser",
+        score: 1_000,
+        item: UnlockItem::Laser(LaserColor::Red),
+    },
+    Unlock {
+        name: "Ship B",
+        score: 2_500,
+        item: UnlockItem::Ship(ShipSkin::B),
+    },
+    Unlock {
+        name: "Green laser",
+        score: 5_000,
+        item: UnlockItem::Laser(LaserColor::Green),
+    },
+    Unlock {
+        name: "Start shield (4s invincibility)",
+        score: 8_000,
+        item: UnlockItem::PowerUp(StartPowerUp::Shield),
+    },
+    Unlock {
+        name: "Blue laser",
+        score: 12_000,
+        item: UnlockItem::Laser(LaserColor::Blue),
+    },
+];
+
+/// 解锁界面根节点标记
+#[derive(Component)]
+struct UnlocksScreen;
+
+/// 解锁界面文本标记
+#[derive(Component)]
+struct UnlocksText;
+
+/// 解锁插件 - 从主菜单（U键）进入的解锁界面，切换已解锁的装备，离开界面时保存档案
+pub struct UnlocksPlugin;
+
+impl Plugin for UnlocksPlugin {
+    fn build(&self, app: &mut App) {
+        // 进入解锁状态时创建界面
+        app.add_systems(OnEnter(AppState::Unlocks), unlocks_screen_setup_system)
+            // 离开解锁状态时销毁界面并保存装备选择
+            .add_systems(
+                OnExit(AppState::Unlocks),
+                (unlocks_screen_cleanup_system, unlocks_save_system),
+            )
+            // 处理装备切换与返回按键，并刷新界面文本
+            .add_systems(
+                Update,
+                (unlocks_equip_system, unlocks_screen_text_system)
+                    .chain()
+                    .run_if(in_state(AppState::Unlocks)),
+            );
+    }
+}
+
+/// 解锁界面初始化系统 - 生成全屏半透明的解锁列表
+fn unlocks_screen_setup_system(mut commands: Commands) {
+    commands
+        .spawn((
+            UnlocksScreen,
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            BackgroundColor(Color::srgba(0., 0., 0., 0.85)),
+            GlobalZIndex(10),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                UnlocksText,
+                Text::new(""),
+                TextFont {
+                    font_size: 18.,
+                    ..Default::default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+/// 解锁界面销毁系统
+fn unlocks_screen_cleanup_system(
+    mut commands: Commands,
+    query: Query<Entity, With<UnlocksScreen>>,
+) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// 保存系统 - 离开界面时写入装备选择（切换时只修改档案资源）
+fn unlocks_save_system(profile: Res<Profile>) {
+    profile.save();
+}
+
+/// 装备切换系统 - 按S切换战机、按C切换激光颜色（只在已解锁的物品中循环），按U或Esc返回主菜单
+fn unlocks_equip_system(
+    kb: Res<ButtonInput<KeyCode>>,
+    mut profile: ResMut<Profile>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if kb.just_pressed(KeyCode::KeyS) {
+        profile.ship = next_unlocked(&profile, &ShipSkin::ALL, profile.ship, UnlockItem::Ship);
+    }
+    if kb.just_pressed(KeyCode::KeyC) {
+        profile.laser_color = next_unlocked(
+            &profile,
+            &LaserColor::ALL,
+            profile.laser_color,
+            UnlockItem::Laser,
+        );
+    }
+
+    if kb.any_just_pressed([KeyCode::KeyU, KeyCode::Escape]) {
+        next_state.set(AppState::MainMenu);
+    }
+}
+
+/// 在候选列表中找到当前项之后的下一个已解锁项
+fn next_unlocked<T: Copy + PartialEq>(
+    profile: &Profile,
+    all: &[T],
+    current: T,
+    item: fn(T) -> UnlockItem,
+) -> T {
+    let start = all.iter().position(|&x| x == current).unwrap_or(0);
+    (1..=all.len())
+        .map(|offset| all[(start + offset) % all.len()])
+        .find(|&x| profile.is_unlocked(item(x)))
+        .unwrap_or(current)
+}
+
+/// 解锁界面文本系统 - 界面刚创建或档案变化时列出全部解锁条目及其状态
+fn unlocks_screen_text_system(
+    profile: Res<Profile>,
+    mut query: Query<(&mut Text, Ref<UnlocksText>)>,
+) {
+    for (mut text, marker) in &mut query {
+        if !profile.is_changed() && !marker.is_added() {
+            continue;
+        }
+
+        let mut lines = vec![
+            "UNLOCKS".to_string(),
+            format!("Lifetime score: {}", profile.lifetime_score),
+            String::new(),
+        ];
+        for unlock in UNLOCKS {
+            let status = if profile.is_unlocked(unlock.item) {
+                "[x]".to_string()
+            } else {
+                format!("[ ] {} pts", unlock.score)
+            };
+            lines.push(format!("{status}  {}", unlock.name));
+        }
+        lines.push(String::new());
+        lines.push(format!(
+            "Ship: {:?}   Laser: {:?}",
+            profile.ship, profile.laser_color
+        ));
+        lines.push("S: switch ship   C: switch laser   U/ESC: back".to_string());
+
+        text.0 = lines.join("\n");
+    }
+}
@@ -0,0 +1,241 @@
+use crate::sim::RollbackAppExt;
+use crate::{EnemyCount, GameSet};
+use bevy::prelude::*;
+use std::collections::{HashMap, VecDeque};
+
+// 第一波的敌人总数
+const WAVE_BASE_ENEMIES: u32 = 4;
+// 每波额外增加的敌人数
+const WAVE_EXTRA_ENEMIES: u32 = 2;
+// 每隔多少波出现一次首领波次
+const BOSS_WAVE_INTERVAL: u32 = 5;
+// 每波开始前暂停生成的休整时间（秒）
+const WAVE_REST_SECS: f32 = 3.;
+// 单条波次横幅的显示时间（秒）
+const WAVE_BANNER_SECS: f32 = 1.6;
+// 横幅滑入的时间（秒）
+const WAVE_BANNER_SLIDE_SECS: f32 = 0.35;
+// 横幅淡出的时间（秒）
+const WAVE_BANNER_FADE_SECS: f32 = 0.5;
+// 横幅滑入的起始水平偏移
+const WAVE_BANNER_SLIDE_PX: f32 = 400.;
+
+/// 波次阶段
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WavePhase {
+    #[default]
+    Rest, // 休整：波次开始前短暂暂停生成
+    Fighting, // 战斗中：按计划生成敌人
+    Shop,     // 波次间隙：商店开放，暂停生成
+}
+
+/// 波次事件 - 波次开始、首领来袭和波次结束时发送，用于显示横幅等表现
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WaveEvent {
+    Started(u32),      // 波次开始（进入休整）
+    BossIncoming(u32), // 首领波次开始
+    Cleared(u32),      // 本波敌人全部被消灭
+}
+
+impl WaveEvent {
+    /// 横幅文字
+    fn banner(self) -> String {
+        match self {
+            WaveEvent::Started(number) => format!("WAVE {number}"),
+            WaveEvent::BossIncoming(_) => "BOSS INCOMING".to_string(),
+            WaveEvent::Cleared(_) => "WAVE CLEAR".to_string(),
+        }
+    }
+}
+
+/// 波次资源 - 记录当前波次、剩余待生成的敌人和所处阶段
+#[derive(Resource, Clone)]
+pub struct Wave {
+    pub number: u32,      // 当前波次（从1开始）
+    pub remaining: u32,   // 本波剩余待生成的敌人数
+    pub phase: WavePhase, // 当前阶段
+    pub rest: Timer,      // 休整计时器
+    pub scripted: bool,   // 本波由波次脚本控制生成（脚本运行期间本波不会结束）
+}
+
+/// 波次切换记录资源 - 各系统上次处理时的波次和阶段（按系统区分），随快照一起回滚，恢复后不会重复触发波次切换
+#[derive(Resource, Clone, Default)]
+pub struct WaveWatch(HashMap<&'static str, (u32, WavePhase)>);
+
+impl WaveWatch {
+    /// 记录指定系统本次看到的波次和阶段，与上次不同（或第一次检查）时返回true
+    pub fn changed(&mut self, watcher: &'static str, wave: &Wave) -> bool {
+        let current = (wave.number, wave.phase);
+        self.0.insert(watcher, current) != Some(current)
+    }
+}
+
+impl Default for Wave {
+    fn default() -> Self {
+        Self {
+            number: 1,
+            remaining: enemies_for_wave(1),
+            phase: WavePhase::Rest,
+            rest: Timer::from_seconds(WAVE_REST_SECS, TimerMode::Once),
+            scripted: false,
+        }
+    }
+}
+
+impl Wave {
+    /// 是否允许生成敌人
+    pub fn can_spawn(&self) -> bool {
+        self.phase == WavePhase::Fighting && self.remaining > 0
+    }
+
+    /// 是否为首领波次
+    pub fn is_boss_wave(&self) -> bool {
+        self.number % BOSS_WAVE_INTERVAL == 0
+    }
+
+    /// 开始下一波（先进入休整）
+    pub fn start_next(&mut self) {
+        self.number += 1;
+        self.remaining = enemies_for_wave(self.number);
+        self.phase = WavePhase::Rest;
+        self.rest.reset();
+        self.scripted = false;
+    }
+}
+
+/// 计算指定波次的敌人总数
+pub fn enemies_for_wave(number: u32) -> u32 {
+    WAVE_BASE_ENEMIES + WAVE_EXTRA_ENEMIES * (number - 1)
+}
+
+/// 波次横幅组件 - 依次播放排队的横幅文字（滑入后淡出），其他模块可直接向队列加入文字
+#[derive(Component)]
+pub struct WaveBanner {
+    pub queue: VecDeque<String>, // 等待显示的横幅
+    timer: Timer,                // 当前横幅的显示计时器
+}
+
+/// 波次插件 - 管理波次推进，并在屏幕中央显示波次横幅
+pub struct WavePlugin;
+
+impl Plugin for WavePlugin {
+    fn build(&self, app: &mut App) {
+        // 初始化波次资源
+        app.init_resource::<Wave>()
+            .init_resource::<WaveWatch>()
+            .rollback_resource::<Wave>()
+            .rollback_resource::<WaveWatch>()
+            .add_event::<WaveEvent>() // 注册波次事件
+            // 创建波次横幅（默认隐藏）
+            .add_systems(Startup, wave_banner_setup_system)
+            // 敌人生成与销毁处理完后检查本波是否结束
+            .add_systems(
+                FixedUpdate,
+                wave_progress_system
+                    .in_set(GameSet::Spawn)
+                    .after(crate::combat::death_system)
+                    .after(crate::enemy::enemy_spawner_system),
+            )
+            // 收到波次事件时播放横幅动画
+            .add_systems(Update, wave_banner_system.in_set(GameSet::Presentation));
+    }
+}
+
+/// 波次推进系统 - 休整结束后开始生成，本波敌人全部生成且被消灭后进入商店阶段，并发送对应的波次事件
+fn wave_progress_system(
+    time: Res<Time>,
+    mut wave: ResMut<Wave>,
+    enemy_count: Res<EnemyCount>,
+    mut events: EventWriter<WaveEvent>,
+    mut watch: ResMut<WaveWatch>,
+) {
+    // 进入新的休整阶段时宣告波次（商店等外部模块也会开始下一波）
+    if watch.changed("wave_progress", &wave) && wave.phase == WavePhase::Rest {
+        events.send(WaveEvent::Started(wave.number));
+        if wave.is_boss_wave() {
+            events.send(WaveEvent::BossIncoming(wave.number));
+        }
+    }
+
+    match wave.phase {
+        WavePhase::Rest => {
+            if wave.rest.tick(time.delta()).finished() {
+                wave.phase = WavePhase::Fighting;
+            }
+        }
+        WavePhase::Fighting if wave.remaining == 0 && !wave.scripted && enemy_count.0 == 0 => {
+            wave.phase = WavePhase::Shop;
+            events.send(WaveEvent::Cleared(wave.number));
+        }
+        _ => {}
+    }
+}
+
+/// 波次横幅初始化系统
+fn wave_banner_setup_system(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                top: Val::Percent(40.),
+                justify_content: JustifyContent::Center,
+                ..Default::default()
+            },
+            GlobalZIndex(5),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                WaveBanner {
+                    queue: VecDeque::new(),
+                    timer: Timer::from_seconds(WAVE_BANNER_SECS, TimerMode::Once),
+                },
+                Text::new(""),
+                TextFont {
+                    font_size: 48.,
+                    ..Default::default()
+                },
+                TextColor(Color::WHITE),
+                Visibility::Hidden,
+            ));
+        });
+}
+
+/// 波次横幅系统 - 把波次事件加入队列，逐条播放：从左侧滑入，停留后淡出
+fn wave_banner_system(
+    time: Res<Time>,
+    mut events: EventReader<WaveEvent>,
+    mut query: Query<(
+        &mut WaveBanner,
+        &mut Text,
+        &mut TextColor,
+        &mut Node,
+        &mut Visibility,
+    )>,
+) {
+    let Ok((mut banner, mut text, mut color, mut node, mut visibility)) = query.get_single_mut()
+    else {
+        return;
+    };
+    banner
+        .queue
+        .extend(events.read().map(|event| event.banner()));
+
+    // 当前横幅播放完毕后切换到下一条
+    if *visibility == Visibility::Hidden || banner.timer.tick(time.delta()).finished() {
+        let Some(next) = banner.queue.pop_front() else {
+            *visibility = Visibility::Hidden;
+            return;
+        };
+        text.0 = next;
+        banner.timer.reset();
+        *visibility = Visibility::Visible;
+    }
+
+    // 滑入（缓出曲线）与淡出
+    let elapsed = banner.timer.elapsed_secs();
+    let slide = (elapsed / WAVE_BANNER_SLIDE_SECS).min(1.);
+    node.left = Val::Px(-WAVE_BANNER_SLIDE_PX * (1. - slide).powi(3));
+    let fade = (banner.timer.remaining_secs() / WAVE_BANNER_FADE_SECS).min(1.);
+    color.0 = Color::WHITE.with_alpha(fade.min(slide));
+}
@@ -0,0 +1,383 @@
+use crate::enemy::{EnemyRoster, SpawnEnemyEvent, SpawnKind, Upgrades};
+use crate::game_mode::{GameMode, GameModes};
+use crate::mods::ModList;
+use crate::registry::asset_path;
+use crate::theme::ThemeMusic;
+use crate::wave::{Wave, WaveBanner, WavePhase};
+use crate::{AppState, GameSet};
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, AssetPath, LoadContext, LoadedFolder};
+use bevy::prelude::*;
+use mlua::{HookTriggers, Lua, LuaOptions, StdLib, Thread, ThreadStatus, VmState};
+
+// 波次脚本所在目录（相对assets目录）
+const WAVES_DIR: &str = "waves";
+// 波次脚本文件的扩展名
+const WAVE_SCRIPT_EXTENSION: &str = "lua";
+const WAVE_SCRIPT_EXTENSIONS: &[&str] = &[WAVE_SCRIPT_EXTENSION];
+// 每次恢复脚本可执行的指令数上限（超过时视为死循环并中止脚本）
+const WAVE_SCRIPT_INSTRUCTIONS: u32 = 1_000_000;
+// 指令计数的检查间隔
+const WAVE_SCRIPT_HOOK_INTERVAL: u32 = 1000;
+// 单次spawn调用最多生成的敌人数
+const WAVE_SCRIPT_MAX_SPAWN: u32 = 50;
+// wait由脚本自身实现：让出协程并把等待秒数交给宿主
+const WAVE_SCRIPT_PRELUDE: &str = "function wait(secs) coroutine.yield(secs or 0) end";
+
+/// 波次脚本 - .lua文件的源码
+#[derive(Asset, TypePath)]
+pub struct WaveScript(String);
+
+/// 波次脚本加载器 - 读取.lua文件，编译在波次开始时进行
+#[derive(Default)]
+pub struct WaveScriptLoader;
+
+impl AssetLoader for WaveScriptLoader {
+    type Asset = WaveScript;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<WaveScript, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(WaveScript(String::from_utf8(bytes)?))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        WAVE_SCRIPT_EXTENSIONS
+    }
+}
+
+/// 脚本调用 - 脚本中的spawn/banner/music调用，先记入队列，恢复结束后由宿主执行
+#[derive(Debug)]
+enum WaveCall {
+    Spawn(String, u32),    // 生成敌人（敌人定义名称或carrier，数量）
+    Banner(String),        // 显示横幅
+    Music(Option<String>), // 切换背景音乐（相对assets目录，为None时停止）
+}
+
+/// 指令计数 - 本次恢复已执行的指令数（由钩子累加）
+struct InstructionCount(u32);
+
+/// 波次脚本来源资源 - 持有assets/waves目录和模组中脚本的句柄，保持这些脚本处于加载状态
+#[derive(Resource)]
+struct WaveScriptSources {
+    folder: Handle<LoadedFolder>,  // assets/waves目录
+    mods: Vec<Handle<WaveScript>>, // 模组包的waves目录中的脚本（按加载顺序）
+}
+
+impl WaveScriptSources {
+    /// 按文件名查找已加载的脚本（模组中的同名脚本优先）
+    fn find<'a>(
+        &self,
+        file_name: &str,
+        folders: &Assets<LoadedFolder>,
+        scripts: &'a Assets<WaveScript>,
+    ) -> Option<&'a WaveScript> {
+        let from_mods = self
+            .mods
+            .iter()
+            .rev()
+            .find(|handle| has_file_name(handle.path(), file_name));
+        if let Some(handle) = from_mods {
+            return scripts.get(handle);
+        }
+        let folder = folders.get(&self.folder)?;
+        let handle = folder
+            .handles
+            .iter()
+            .find(|handle| has_file_name(handle.path(), file_name))?;
+        scripts.get(handle.id().try_typed::<WaveScript>().ok()?)
+    }
+}
+
+/// 资源路径的文件名是否为指定名称
+fn has_file_name(path: Option<&AssetPath>, file_name: &str) -> bool {
+    path.and_then(|path| path.path().file_name())
+        .is_some_and(|name| name == file_name)
+}
+
+/// 波次脚本运行器 - Lua解释器和正在运行的脚本协程
+struct WaveScriptRunner {
+    lua: Lua,               // 解释器（只开放协程、数学、字符串和表标准库）
+    wave: u32,              // 最近一次检查过脚本的波次
+    thread: Option<Thread>, // 正在运行的脚本
+    name: String,           // 正在运行的脚本文件名（日志中显示）
+    wait: f32,              // 距下次恢复脚本的时间（秒）
+}
+
+impl WaveScriptRunner {
+    /// 创建解释器并登记向脚本开放的函数：
+    /// spawn(name, count) 生成敌人，wait(secs) 等待，banner(text) 显示横幅，music(path) 切换背景音乐
+    fn new() -> mlua::Result<Self> {
+        let lua = Lua::new_with(
+            StdLib::COROUTINE | StdLib::MATH | StdLib::STRING | StdLib::TABLE,
+            LuaOptions::default(),
+        )?;
+        lua.set_app_data(Vec::<WaveCall>::new());
+        lua.set_app_data(InstructionCount(0));
+        lua.set_hook(
+            HookTriggers::new().every_nth_instruction(WAVE_SCRIPT_HOOK_INTERVAL),
+            |lua, _debug| {
+                let mut count = lua
+                    .app_data_mut::<InstructionCount>()
+                    .ok_or_else(|| mlua::Error::runtime("缺少指令计数"))?;
+                count.0 += WAVE_SCRIPT_HOOK_INTERVAL;
+                if count.0 > WAVE_SCRIPT_INSTRUCTIONS {
+                    return Err(mlua::Error::runtime("执行的指令过多，可能是缺少wait的循环"));
+                }
+                Ok(VmState::Continue)
+            },
+        );
+
+        let globals = lua.globals();
+        globals.set(
+            "spawn",
+            lua.create_function(|lua, (name, count): (String, Option<u32>)| {
+                let count = count.unwrap_or(1).min(WAVE_SCRIPT_MAX_SPAWN);
+                push_call(lua, WaveCall::Spawn(name, count));
+                Ok(())
+            })?,
+        )?;
+        globals.set(
+            "banner",
+            lua.create_function(|lua, text: String| {
+                push_call(lua, WaveCall::Banner(text));
+                Ok(())
+            })?,
+        )?;
+        globals.set(
+            "music",
+            lua.create_function(|lua, path: Option<String>| {
+                push_call(lua, WaveCall::Music(path));
+                Ok(())
+            })?,
+        )?;
+        lua.load(WAVE_SCRIPT_PRELUDE).exec()?;
+
+        Ok(Self {
+            lua,
+            wave: 0,
+            thread: None,
+            name: String::new(),
+            wait: 0.,
+        })
+    }
+
+    /// 以协程启动脚本（脚本的顶层代码即为协程主体）
+    fn start(&mut self, name: String, script: &WaveScript) -> mlua::Result<()> {
+        let function = self
+            .lua
+            .load(script.0.as_str())
+            .set_name(name.as_str())
+            .into_function()?;
+        self.thread = Some(self.lua.create_thread(function)?);
+        self.name = name;
+        self.wait = 0.;
+        Ok(())
+    }
+
+    /// 恢复脚本直到下一次wait或结束，返回脚本是否仍在运行
+    fn resume(&mut self) -> mlua::Result<bool> {
+        let Some(thread) = &self.thread else {
+            return Ok(false);
+        };
+        if let Some(mut count) = self.lua.app_data_mut::<InstructionCount>() {
+            count.0 = 0;
+        }
+        let wait: Option<f32> = thread.resume(())?;
+        if thread.status() != ThreadStatus::Resumable {
+            self.thread = None;
+            return Ok(false);
+        }
+        self.wait = wait.filter(|secs| secs.is_finite()).unwrap_or(0.).max(0.);
+        Ok(true)
+    }
+
+    /// 取出脚本本次记入的调用
+    fn take_calls(&self) -> Vec<WaveCall> {
+        self.lua
+            .app_data_mut::<Vec<WaveCall>>()
+            .map(|mut calls| std::mem::take(&mut *calls))
+            .unwrap_or_default()
+    }
+
+    /// 停止正在运行的脚本（丢弃未执行的调用）
+    fn stop(&mut self) {
+        self.thread = None;
+        self.take_calls();
+    }
+}
+
+/// 把调用记入队列
+fn push_call(lua: &Lua, call: WaveCall) {
+    if let Some(mut calls) = lua.app_data_mut::<Vec<WaveCall>>() {
+        calls.push(call);
+    }
+}
+
+/// 波次脚本插件 - 启用lua特性时，波次开始战斗时若有waves/wave_NN.lua（NN为两位波次号，模组中的同名文件优先），
+/// 本波改由脚本生成敌人：脚本可调用spawn、wait、banner和music，运行结束且敌人被消灭后本波结束；
+/// 脚本出错时记录警告并结束脚本，之后按已生成的敌人结束本波
+pub struct WaveScriptPlugin;
+
+impl Plugin for WaveScriptPlugin {
+    fn build(&self, app: &mut App) {
+        let runner = match WaveScriptRunner::new() {
+            Ok(runner) => runner,
+            Err(e) => {
+                error!("创建Lua解释器失败，波次脚本不可用: {e}");
+                return;
+            }
+        };
+        // 注册波次脚本资源类型和加载器，插入脚本运行器（解释器只在主线程使用）
+        app.init_asset::<WaveScript>()
+            .init_asset_loader::<WaveScriptLoader>()
+            .insert_non_send_resource(runner)
+            // 加载脚本目录
+            .add_systems(Startup, wave_script_load_system)
+            // 开始游戏时停止上一局的脚本
+            .add_systems(OnEnter(AppState::Playing), wave_script_reset_system)
+            // 在生成敌人之前启动、推进脚本并执行脚本的调用
+            .add_systems(
+                FixedUpdate,
+                (wave_script_run_system, wave_script_call_system)
+                    .chain()
+                    .before(GameSet::Spawn)
+                    .run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+/// 脚本加载系统
+fn wave_script_load_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mods: Res<ModList>,
+) {
+    let folder = asset_server.load_folder(asset_path(WAVES_DIR));
+    let mods = mods
+        .files_in(WAVES_DIR, WAVE_SCRIPT_EXTENSION)
+        .into_iter()
+        .map(|(_, path)| asset_server.load(path))
+        .collect();
+    commands.insert_resource(WaveScriptSources { folder, mods });
+}
+
+/// 脚本重置系统
+fn wave_script_reset_system(mut runner: NonSendMut<WaveScriptRunner>) {
+    runner.stop();
+    runner.wave = 0;
+}
+
+/// 脚本运行系统 - 每波进入战斗时查找该波的脚本并启动（本波不再自动生成），之后按wait的秒数恢复脚本，
+/// 脚本结束或出错时交还本波的结束判定
+fn wave_script_run_system(
+    time: Res<Time>,
+    mode: Res<GameMode>,
+    modes: Res<GameModes>,
+    sources: Res<WaveScriptSources>,
+    folders: Res<Assets<LoadedFolder>>,
+    scripts: Res<Assets<WaveScript>>,
+    mut wave: ResMut<Wave>,
+    mut runner: NonSendMut<WaveScriptRunner>,
+) {
+    if !modes.get(*mode).auto_spawn() {
+        return;
+    }
+    // 新的一波进入战斗时启动脚本
+    if wave.phase == WavePhase::Fighting && runner.wave != wave.number {
+        runner.wave = wave.number;
+        runner.stop();
+        let name = format!("wave_{:02}.{WAVE_SCRIPT_EXTENSION}", wave.number);
+        if let Some(script) = sources.find(&name, &folders, &scripts) {
+            match runner.start(name.clone(), script) {
+                Ok(()) => {
+                    info!(wave = wave.number, script = %name, "启动波次脚本");
+                    wave.remaining = 0;
+                    wave.scripted = true;
+                }
+                Err(e) => warn!(script = %name, "波次脚本编译失败，按默认方式生成: {e}"),
+            }
+        }
+    }
+    if runner.thread.is_none() {
+        return;
+    }
+
+    runner.wait -= time.delta_secs();
+    if runner.wait > 0. {
+        return;
+    }
+    match runner.resume() {
+        Ok(true) => {}
+        Ok(false) => {
+            debug!(script = %runner.name, "波次脚本运行结束");
+            wave.scripted = false;
+        }
+        Err(e) => {
+            warn!(script = %runner.name, "波次脚本出错，停止脚本: {e}");
+            runner.thread = None;
+            wave.scripted = false;
+        }
+    }
+}
+
+/// 脚本调用系统 - 执行脚本记入的调用：按定义名称（不区分大小写）或carrier从编队生成敌人，
+/// 向波次横幅加入文字，替换关卡背景音乐
+#[allow(clippy::too_many_arguments)] // 执行脚本调用需要同时访问多个资源
+fn wave_script_call_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mods: Res<ModList>,
+    roster: Res<EnemyRoster>,
+    runner: NonSend<WaveScriptRunner>,
+    mut banner_query: Query<&mut WaveBanner>,
+    music_query: Query<Entity, With<ThemeMusic>>,
+    mut spawn_events: EventWriter<SpawnEnemyEvent>,
+) {
+    for call in runner.take_calls() {
+        match call {
+            WaveCall::Spawn(name, count) => {
+                let carrier = name.eq_ignore_ascii_case("carrier");
+                let def = roster
+                    .0
+                    .iter()
+                    .find(|def| def.name.eq_ignore_ascii_case(&name));
+                if !carrier && def.is_none() {
+                    warn!(script = %runner.name, enemy = %name, "波次脚本生成了未知的敌人");
+                    continue;
+                }
+                let kind = match def {
+                    Some(def) => SpawnKind::Enemy(def.clone(), Upgrades::None),
+                    None => SpawnKind::Carrier,
+                };
+                for _ in 0..count {
+                    spawn_events.send(SpawnEnemyEvent::new(kind.clone()));
+                }
+            }
+            WaveCall::Banner(text) => {
+                if let Ok(mut banner) = banner_query.get_single_mut() {
+                    banner.queue.push_back(text);
+                }
+            }
+            WaveCall::Music(path) => {
+                for entity in &music_query {
+                    commands.entity(entity).despawn();
+                }
+                if let Some(path) = path {
+                    commands.spawn((
+                        ThemeMusic,
+                        AudioPlayer::new(asset_server.load(mods.asset_path(&path))),
+                        PlaybackSettings::LOOP,
+                    ));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,338 @@
+use crate::action::Action;
+use crate::components::{DamageType, Enemy, FireCooldown, Laser, Player, Velocity};
+use crate::shop::RunUpgrades;
+use crate::sim::RollbackAppExt;
+use crate::sim::TickInput;
+use crate::status::{Inflicts, StatusKind};
+use crate::wave::{Wave, WavePhase};
+use crate::{GameSet, movable_system};
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::time::Duration;
+
+// 散射两侧激光的水平速度
+const SPREAD_SIDE_SPEED: f32 = 0.3;
+// 光束激光的飞行速度
+const BEAM_SPEED: f32 = 2.;
+// 导弹的飞行速度
+const MISSILE_SPEED: f32 = 0.8;
+// 导弹的转向速率（弧度/秒）
+const MISSILE_TURN_RATE: f32 = 4.;
+// 光束命中时施加的减速时长（秒）
+const BEAM_SLOW_SECS: f32 = 1.5;
+// 导弹和鱼雷命中时施加的燃烧时长（秒）
+pub const BURN_SECS: f32 = 3.;
+// 重型鱼雷的冷却（秒）
+const TORPEDO_COOLDOWN: f32 = 3.;
+// 每条命携带的重型鱼雷数量
+pub const TORPEDO_AMMO: u32 = 5;
+// 重型鱼雷的飞行速度
+pub const TORPEDO_SPEED: f32 = 0.6;
+// 重型鱼雷的爆炸半径
+pub const TORPEDO_BLAST_RADIUS: f32 = 120.;
+
+/// 武器种类
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Weapon {
+    Standard, // 标准：两侧各一束激光
+    Spread,   // 散射：三束扇形激光，冷却较长
+    Beam,     // 光束：中央高速连射的细激光
+    Missiles, // 导弹：追踪最近敌人的导弹，冷却最长
+}
+
+/// 单发弹道 - 相对玩家的发射偏移和初速度
+pub struct Shot {
+    pub offset: Vec2,   // 发射位置偏移
+    pub velocity: Vec2, // 初速度（速度单位）
+    pub homing: bool,   // 是否追踪敌人
+}
+
+impl Weapon {
+    pub const ALL: [Weapon; 4] = [
+        Weapon::Standard,
+        Weapon::Spread,
+        Weapon::Beam,
+        Weapon::Missiles,
+    ];
+
+    /// 切换槽位（1~4键）
+    pub fn slot(self) -> u8 {
+        match self {
+            Weapon::Standard => 1,
+            Weapon::Spread => 2,
+            Weapon::Beam => 3,
+            Weapon::Missiles => 4,
+        }
+    }
+
+    /// HUD显示名称
+    pub fn name(self) -> &'static str {
+        match self {
+            Weapon::Standard => "Standard",
+            Weapon::Spread => "Spread",
+            Weapon::Beam => "Beam",
+            Weapon::Missiles => "Missiles",
+        }
+    }
+
+    /// 弹药上限（标准武器弹药无限，返回None）
+    pub fn max_ammo(self) -> Option<u32> {
+        match self {
+            Weapon::Standard => None,
+            Weapon::Spread => Some(60),
+            Weapon::Beam => Some(150),
+            Weapon::Missiles => Some(20),
+        }
+    }
+
+    /// 发射弹药的伤害类型（不同武器克制不同抗性的敌人）
+    pub fn damage_type(self) -> DamageType {
+        match self {
+            Weapon::Standard => DamageType::Energy,
+            Weapon::Spread => DamageType::Kinetic,
+            Weapon::Beam => DamageType::Energy,
+            Weapon::Missiles => DamageType::Explosive,
+        }
+    }
+
+    /// 弹药命中时施加的状态效果（光束减速、导弹燃烧）
+    pub fn inflicts(self) -> Option<Inflicts> {
+        match self {
+            Weapon::Standard | Weapon::Spread => None,
+            Weapon::Beam => Some(Inflicts {
+                kind: StatusKind::Slow,
+                secs: BEAM_SLOW_SECS,
+            }),
+            Weapon::Missiles => Some(Inflicts {
+                kind: StatusKind::Burn,
+                secs: BURN_SECS,
+            }),
+        }
+    }
+
+    /// 开火冷却相对基础冷却的倍率
+    fn cooldown_scale(self) -> f32 {
+        match self {
+            Weapon::Standard => 1.,
+            Weapon::Spread => 1.5,
+            Weapon::Beam => 0.35,
+            Weapon::Missiles => 2.5,
+        }
+    }
+
+    /// 当前武器的开火冷却（秒，含商店强化）
+    pub fn cooldown(self, upgrades: &RunUpgrades) -> f32 {
+        upgrades.fire_cooldown() * self.cooldown_scale()
+    }
+
+    /// 一次开火发射的全部弹道，side_offset为玩家两侧炮口的水平偏移
+    pub fn shots(self, side_offset: f32) -> Vec<Shot> {
+        let shot = |x: f32, velocity: Vec2, homing: bool| Shot {
+            offset: Vec2::new(x, 15.),
+            velocity,
+            homing,
+        };
+        match self {
+            Weapon::Standard => vec![
+                shot(side_offset, Vec2::Y, false),
+                shot(-side_offset, Vec2::Y, false),
+            ],
+            Weapon::Spread => vec![
+                shot(0., Vec2::Y, false),
+                shot(side_offset, Vec2::new(SPREAD_SIDE_SPEED, 1.), false),
+                shot(-side_offset, Vec2::new(-SPREAD_SIDE_SPEED, 1.), false),
+            ],
+            Weapon::Beam => vec![shot(0., Vec2::Y * BEAM_SPEED, false)],
+            Weapon::Missiles => vec![
+                shot(side_offset, Vec2::Y * MISSILE_SPEED, true),
+                shot(-side_offset, Vec2::Y * MISSILE_SPEED, true),
+            ],
+        }
+    }
+}
+
+/// 武器配置组件 - 玩家携带的武器和当前使用的武器
+#[derive(Component, Clone)]
+pub struct Loadout {
+    pub weapons: Vec<Weapon>, // 携带的武器
+    pub active: usize,        // 当前武器的下标
+}
+
+impl Default for Loadout {
+    fn default() -> Self {
+        Self {
+            weapons: Weapon::ALL.to_vec(),
+            active: 0,
+        }
+    }
+}
+
+impl Loadout {
+    /// 当前使用的武器
+    pub fn current(&self) -> Weapon {
+        self.weapons[self.active]
+    }
+}
+
+/// 弹药组件 - 非标准武器的剩余弹药，每次开火消耗一发，拾取弹药补给后补充
+#[derive(Component, Clone)]
+pub struct Ammo(HashMap<Weapon, u32>);
+
+impl Ammo {
+    /// 为配置中的每把有限弹药武器装满弹药
+    pub fn full(loadout: &Loadout) -> Self {
+        Self(
+            loadout
+                .weapons
+                .iter()
+                .filter_map(|&weapon| weapon.max_ammo().map(|max| (weapon, max)))
+                .collect(),
+        )
+    }
+
+    /// 武器的剩余弹药（弹药无限的武器返回None）
+    pub fn get(&self, weapon: Weapon) -> Option<u32> {
+        self.0.get(&weapon).copied()
+    }
+
+    /// 尝试消耗一发弹药，弹药耗尽时返回false
+    pub fn try_use(&mut self, weapon: Weapon) -> bool {
+        match self.0.get_mut(&weapon) {
+            None => true,
+            Some(0) => false,
+            Some(rounds) => {
+                *rounds -= 1;
+                true
+            }
+        }
+    }
+
+    /// 为每把武器补充上限的一半弹药（不超过上限）
+    pub fn refill(&mut self) {
+        for (weapon, rounds) in &mut self.0 {
+            if let Some(max) = weapon.max_ammo() {
+                *rounds = (*rounds + max / 2).min(max);
+            }
+        }
+    }
+}
+
+/// 副武器种类（重型武器）
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HeavyWeapon {
+    Torpedo, // 重型鱼雷：追踪最近的敌人，命中后波及爆炸半径内的全部敌人
+}
+
+/// 副武器组件 - 独立于主武器的冷却和弹药
+#[derive(Component, Clone)]
+pub struct SecondaryWeapon {
+    pub kind: HeavyWeapon, // 副武器种类
+    pub cooldown: Timer,   // 副武器开火冷却
+    pub ammo: u32,         // 剩余弹药
+}
+
+impl Default for SecondaryWeapon {
+    fn default() -> Self {
+        // 冷却从结束状态开始，生成后可立即发射
+        let mut cooldown = Timer::from_seconds(TORPEDO_COOLDOWN, TimerMode::Once);
+        cooldown.tick(cooldown.duration());
+        Self {
+            kind: HeavyWeapon::Torpedo,
+            cooldown,
+            ammo: TORPEDO_AMMO,
+        }
+    }
+}
+
+/// 爆炸弹头组件 - 命中敌人时波及半径内的其他敌人
+#[derive(Component, Clone)]
+pub struct Blast {
+    pub radius: f32, // 爆炸半径
+}
+
+/// 追踪组件 - 导弹每帧转向最近的敌人
+#[derive(Component, Clone)]
+pub struct Homing;
+
+/// 武器插件 - 处理武器切换和导弹追踪
+pub struct WeaponPlugin;
+
+impl Plugin for WeaponPlugin {
+    fn build(&self, app: &mut App) {
+        // 登记参与快照回滚的组件
+        app.rollback_component::<Loadout>()
+            .rollback_component::<Ammo>()
+            .rollback_component::<SecondaryWeapon>()
+            .rollback_component::<Blast>()
+            .rollback_component::<Homing>();
+
+        // 每一步读取数字键切换武器
+        app.add_systems(FixedUpdate, weapon_switch_system.in_set(GameSet::Input))
+            // 在通用移动之前调整导弹速度方向
+            .add_systems(
+                FixedUpdate,
+                homing_system
+                    .before(movable_system)
+                    .in_set(GameSet::Movement),
+            );
+    }
+}
+
+/// 武器切换系统 - 按1~4切换到携带的对应武器（商店开放时数字键用于购买）
+fn weapon_switch_system(
+    input: Res<TickInput>,
+    wave: Res<Wave>,
+    upgrades: Res<RunUpgrades>,
+    mut query: Query<(&mut Loadout, &mut FireCooldown), With<Player>>,
+) {
+    if wave.phase == WavePhase::Shop {
+        return;
+    }
+    let Ok((mut loadout, mut cooldown)) = query.get_single_mut() else {
+        return;
+    };
+
+    let selected = loadout
+        .weapons
+        .iter()
+        .position(|weapon| input.just_pressed(Action::Slot(weapon.slot())));
+    if let Some(index) = selected.filter(|&index| index != loadout.active) {
+        loadout.active = index;
+        cooldown.0.set_duration(Duration::from_secs_f32(
+            loadout.current().cooldown(&upgrades),
+        ));
+    }
+}
+
+/// 导弹追踪系统 - 以有限的转向速率把导弹速度转向最近的敌人，并让导弹朝向飞行方向
+fn homing_system(
+    time: Res<Time>,
+    enemy_query: Query<&Transform, (With<Enemy>, Without<Laser>)>,
+    mut missile_query: Query<(&mut Velocity, &mut Transform), (With<Laser>, With<Homing>)>,
+) {
+    let max_turn = MISSILE_TURN_RATE * time.delta_secs();
+
+    for (mut velocity, mut missile_tf) in &mut missile_query {
+        let position = missile_tf.translation.truncate();
+        let target = enemy_query
+            .iter()
+            .map(|enemy_tf| enemy_tf.translation.truncate())
+            .min_by(|a, b| a.distance(position).total_cmp(&b.distance(position)));
+        let Some(target) = target else {
+            continue;
+        };
+
+        let current = Vec2::new(velocity.x, velocity.y);
+        let desired = (target - position).normalize_or_zero();
+        if desired == Vec2::ZERO {
+            continue;
+        }
+
+        // 限制每帧的转向角度
+        let angle = current.angle_to(desired).clamp(-max_turn, max_turn);
+        let turned = Vec2::from_angle(angle).rotate(current);
+        velocity.x = turned.x;
+        velocity.y = turned.y;
+        missile_tf.rotation = Quat::from_rotation_arc_2d(Vec2::Y, turned.normalize_or_zero());
+    }
+}
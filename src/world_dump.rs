@@ -0,0 +1,152 @@
+use crate::AppState;
+use crate::components::{Health, Velocity};
+use bevy::ecs::world::EntityRef;
+use bevy::input::common_conditions::input_just_pressed;
+use bevy::prelude::*;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// 导出世界快照的按键
+const WORLD_DUMP_KEY: KeyCode = KeyCode::F4;
+// 快照文件所在目录
+const DUMP_DIR: &str = "dumps";
+
+/// 世界快照插件 - 按F4把全部实体及其位置、速度、生命值和组件列表写入dumps目录下的JSON文件，
+/// 用于排查“敌人卡在屏幕外”之类的问题
+pub struct WorldDumpPlugin;
+
+impl Plugin for WorldDumpPlugin {
+    fn build(&self, app: &mut App) {
+        // 按下导出键时写入快照
+        app.add_systems(
+            Update,
+            world_dump_system.run_if(input_just_pressed(WORLD_DUMP_KEY)),
+        );
+    }
+}
+
+/// 世界快照系统 - 以只读方式遍历整个世界，写入dumps/world-<Unix时间>.json
+fn world_dump_system(world: &World) {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    let path = PathBuf::from(DUMP_DIR).join(format!("world-{secs}.json"));
+    let json = world_json(world);
+    let result = fs::create_dir_all(DUMP_DIR).and_then(|()| fs::write(&path, json));
+    match result {
+        Ok(()) => info!(
+            path = %path.display(),
+            entities = world.entities().len(),
+            "导出世界快照"
+        ),
+        Err(e) => warn!("导出世界快照失败: {e}"),
+    }
+}
+
+/// 把整个世界序列化为JSON：游戏状态、游戏时间和每个实体的关键组件
+fn world_json(world: &World) -> String {
+    let state = world
+        .get_resource::<State<AppState>>()
+        .map(|state| format!("{:?}", state.get()))
+        .unwrap_or_default();
+    let elapsed = world
+        .get_resource::<Time>()
+        .map(Time::elapsed_secs)
+        .unwrap_or_default();
+
+    let entities: Vec<String> = world
+        .iter_entities()
+        .map(|entity| entity_json(world, entity))
+        .collect();
+    format!(
+        "{{\n  \"state\": {},\n  \"elapsed_secs\": {},\n  \"entities\": [\n    {}\n  ]\n}}\n",
+        json_string(&state),
+        json_number(elapsed),
+        entities.join(",\n    ")
+    )
+}
+
+/// 单个实体的JSON对象（没有的组件省略对应字段）
+fn entity_json(world: &World, entity: EntityRef) -> String {
+    let mut json = format!("{{\"id\": \"{}\"", entity.id());
+    if let Some(name) = entity.get::<Name>() {
+        let _ = write!(json, ", \"name\": {}", json_string(name.as_str()));
+    }
+    if let Some(transform) = entity.get::<Transform>() {
+        let t = transform.translation;
+        let _ = write!(
+            json,
+            ", \"position\": [{}, {}, {}]",
+            json_number(t.x),
+            json_number(t.y),
+            json_number(t.z)
+        );
+    }
+    if let Some(velocity) = entity.get::<Velocity>() {
+        let _ = write!(
+            json,
+            ", \"velocity\": [{}, {}]",
+            json_number(velocity.x),
+            json_number(velocity.y)
+        );
+    }
+    if let Some(health) = entity.get::<Health>() {
+        let _ = write!(
+            json,
+            ", \"health\": {{\"current\": {}, \"max\": {}}}",
+            json_number(health.current),
+            json_number(health.max)
+        );
+    }
+    if let Some(visibility) = entity.get::<Visibility>() {
+        let _ = write!(json, ", \"visibility\": \"{visibility:?}\"");
+    }
+
+    // 组件列表只保留类型名的最后一段（泛型参数保留原样）
+    let components: Vec<String> = entity
+        .archetype()
+        .components()
+        .filter_map(|id| world.components().get_info(id))
+        .map(|info| json_string(short_type_name(info.name())))
+        .collect();
+    let _ = write!(json, ", \"components\": [{}]}}", components.join(", "));
+    json
+}
+
+/// 去掉类型名中泛型参数之前的模块路径
+fn short_type_name(name: &str) -> &str {
+    let head = name.split('<').next().unwrap_or(name);
+    let start = head.rfind("::").map_or(0, |i| i + 2);
+    &name[start..]
+}
+
+/// 转为JSON数字（NaN和无穷大在JSON中无法表示，写为null，出问题的实体正需要看到这一点）
+fn json_number(value: f32) -> String {
+    if value.is_finite() {
+        value.to_string()
+    } else {
+        "null".to_string()
+    }
+}
+
+/// 转义为JSON字符串
+fn json_string(text: &str) -> String {
+    let mut json = String::with_capacity(text.len() + 2);
+    json.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}